@@ -3,23 +3,29 @@ mod path;
 mod real;
 mod result;
 
+use base64::Engine as _;
+use base64::prelude::BASE64_STANDARD;
 use fathom_capability_domain::CapabilityActionResult;
 use serde::Deserialize;
 use serde_json::{Value, json};
 
 use self::error::FsError;
-use self::path::{ParsedPath, parse_path, resolve_base_path};
-use self::real::{GlobOptions, ListOptions, ReadOptions, SearchOptions};
+use self::path::{ParsedPath, parse_path, redact_base_path, resolve_base_path};
+use self::real::{ByteRange, GlobOptions, ListOptions, ReadOptions, SearchOptions, UsageOptions};
 
-const LIST_DEFAULT_MAX_ENTRIES: usize = 200;
-const LIST_MAX_ENTRIES_CAP: usize = 5_000;
+pub(crate) const LIST_DEFAULT_MAX_ENTRIES: usize = 200;
+pub(crate) const LIST_MAX_ENTRIES_CAP: usize = 5_000;
 const READ_DEFAULT_OFFSET_LINE: usize = 1;
 const READ_DEFAULT_LIMIT_LINES: usize = 200;
 const READ_MAX_LIMIT_LINES: usize = 2_000;
+pub(crate) const READ_DEFAULT_LENGTH_BYTES: usize = 64 * 1024;
+const READ_MAX_LENGTH_BYTES: usize = 1024 * 1024;
 const GLOB_DEFAULT_MAX_RESULTS: usize = 500;
 const GLOB_MAX_RESULTS_CAP: usize = 5_000;
 const SEARCH_DEFAULT_MAX_RESULTS: usize = 200;
 const SEARCH_MAX_RESULTS_CAP: usize = 10_000;
+const USAGE_DEFAULT_MAX_ENTRIES: usize = 10_000;
+const USAGE_MAX_ENTRIES_CAP: usize = 100_000;
 
 #[derive(Debug, Clone, Copy, Deserialize)]
 #[serde(rename_all = "lowercase")]
@@ -28,6 +34,13 @@ enum ReplaceMode {
     All,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum ReadEncoding {
+    Utf8,
+    Base64,
+}
+
 #[derive(Debug, Deserialize)]
 #[serde(deny_unknown_fields)]
 struct ListArgs {
@@ -35,6 +48,7 @@ struct ListArgs {
     recursive: Option<bool>,
     max_entries: Option<u64>,
     include_hidden: Option<bool>,
+    pattern: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -43,13 +57,23 @@ struct ReadArgs {
     path: String,
     offset_line: Option<u64>,
     limit_lines: Option<u64>,
+    offset: Option<u64>,
+    length: Option<u64>,
+    encoding: Option<ReadEncoding>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ReadJsonArgs {
+    path: String,
 }
 
 #[derive(Debug, Deserialize)]
 #[serde(deny_unknown_fields)]
 struct WriteArgs {
     path: String,
-    content: String,
+    content: Option<String>,
+    content_base64: Option<String>,
     allow_override: bool,
     create_parents: Option<bool>,
 }
@@ -64,6 +88,21 @@ struct ReplaceArgs {
     expected_replacements: Option<u64>,
 }
 
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct DeleteArgs {
+    path: String,
+    recursive: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct MoveArgs {
+    src: String,
+    dst: String,
+    allow_override: bool,
+}
+
 #[derive(Debug, Deserialize)]
 #[serde(deny_unknown_fields)]
 struct GlobArgs {
@@ -83,6 +122,20 @@ struct SearchArgs {
     case_sensitive: Option<bool>,
 }
 
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct UsageArgs {
+    path: String,
+    max_entries: Option<u64>,
+    include_hidden: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct StatArgs {
+    path: String,
+}
+
 #[derive(Debug, Deserialize)]
 #[serde(deny_unknown_fields)]
 struct GetBasePathArgs {}
@@ -96,10 +149,15 @@ pub fn execute_action(
         "get_base_path" => Some(execute_get_base_path(args_json, capability_domain_state)),
         "list" => Some(execute_list(args_json, capability_domain_state)),
         "read" => Some(execute_read(args_json, capability_domain_state)),
+        "read_json" => Some(execute_read_json(args_json, capability_domain_state)),
         "write" => Some(execute_write(args_json, capability_domain_state)),
         "replace" => Some(execute_replace(args_json, capability_domain_state)),
+        "delete" => Some(execute_delete(args_json, capability_domain_state)),
+        "move" => Some(execute_move(args_json, capability_domain_state)),
         "glob" => Some(execute_glob(args_json, capability_domain_state)),
         "search" => Some(execute_search(args_json, capability_domain_state)),
+        "usage" => Some(execute_usage(args_json, capability_domain_state)),
+        "stat" => Some(execute_stat(args_json, capability_domain_state)),
         _ => None,
     }
 }
@@ -122,7 +180,12 @@ fn execute_get_base_path(
                 "source": "filesystem_env_state"
             }),
         ),
-        Err(error) => result::failure("get_base_path", Some("."), &error, Some("filesystem")),
+        Err(error) => result::failure(
+            "get_base_path",
+            Some("."),
+            &redact_base_path(error, capability_domain_state),
+            Some("filesystem"),
+        ),
     }
 }
 
@@ -135,7 +198,7 @@ fn execute_list(args_json: &str, capability_domain_state: &Value) -> CapabilityA
         Ok(parsed) => parsed,
         Err(error) => return result::failure("list", Some(&args.path), &error, None),
     };
-    let options = match parse_list_options(args) {
+    let options = match parse_list_options(args, capability_domain_state) {
         Ok(options) => options,
         Err(error) => {
             return result::failure(
@@ -174,6 +237,19 @@ fn execute_read(args_json: &str, capability_domain_state: &Value) -> CapabilityA
     execute_read_on_path(parsed, options, capability_domain_state)
 }
 
+fn execute_read_json(args_json: &str, capability_domain_state: &Value) -> CapabilityActionResult {
+    let args = match parse_args::<ReadJsonArgs>(args_json, "filesystem__read_json") {
+        Ok(args) => args,
+        Err(error) => return result::failure("read_json", None, &error, None),
+    };
+    let parsed = match parse_path(&args.path) {
+        Ok(parsed) => parsed,
+        Err(error) => return result::failure("read_json", Some(&args.path), &error, None),
+    };
+
+    execute_read_json_on_path(parsed, capability_domain_state)
+}
+
 fn execute_write(args_json: &str, capability_domain_state: &Value) -> CapabilityActionResult {
     let args = match parse_args::<WriteArgs>(args_json, "filesystem__write") {
         Ok(args) => args,
@@ -183,10 +259,21 @@ fn execute_write(args_json: &str, capability_domain_state: &Value) -> Capability
         Ok(parsed) => parsed,
         Err(error) => return result::failure("write", Some(&args.path), &error, None),
     };
+    let content = match parse_write_content(args.content, args.content_base64) {
+        Ok(content) => content,
+        Err(error) => {
+            return result::failure(
+                "write",
+                Some(parsed.normalized_path()),
+                &error,
+                Some("filesystem"),
+            );
+        }
+    };
 
     execute_write_on_path(
         parsed,
-        &args.content,
+        &content,
         args.allow_override,
         args.create_parents.unwrap_or(true),
         capability_domain_state,
@@ -230,6 +317,40 @@ fn execute_replace(args_json: &str, capability_domain_state: &Value) -> Capabili
     )
 }
 
+fn execute_delete(args_json: &str, capability_domain_state: &Value) -> CapabilityActionResult {
+    let args = match parse_args::<DeleteArgs>(args_json, "filesystem__delete") {
+        Ok(args) => args,
+        Err(error) => return result::failure("delete", None, &error, None),
+    };
+    let parsed = match parse_path(&args.path) {
+        Ok(parsed) => parsed,
+        Err(error) => return result::failure("delete", Some(&args.path), &error, None),
+    };
+
+    execute_delete_on_path(
+        parsed,
+        args.recursive.unwrap_or(false),
+        capability_domain_state,
+    )
+}
+
+fn execute_move(args_json: &str, capability_domain_state: &Value) -> CapabilityActionResult {
+    let args = match parse_args::<MoveArgs>(args_json, "filesystem__move") {
+        Ok(args) => args,
+        Err(error) => return result::failure("move", None, &error, None),
+    };
+    let src = match parse_path(&args.src) {
+        Ok(parsed) => parsed,
+        Err(error) => return result::failure("move", Some(&args.src), &error, None),
+    };
+    let dst = match parse_path(&args.dst) {
+        Ok(parsed) => parsed,
+        Err(error) => return result::failure("move", Some(&args.dst), &error, None),
+    };
+
+    execute_move_on_paths(src, dst, args.allow_override, capability_domain_state)
+}
+
 fn execute_glob(args_json: &str, capability_domain_state: &Value) -> CapabilityActionResult {
     let args = match parse_args::<GlobArgs>(args_json, "filesystem__glob") {
         Ok(args) => args,
@@ -280,6 +401,43 @@ fn execute_search(args_json: &str, capability_domain_state: &Value) -> Capabilit
     execute_search_on_path(parsed, &args.pattern, options, capability_domain_state)
 }
 
+fn execute_usage(args_json: &str, capability_domain_state: &Value) -> CapabilityActionResult {
+    let args = match parse_args::<UsageArgs>(args_json, "filesystem__usage") {
+        Ok(args) => args,
+        Err(error) => return result::failure("usage", None, &error, None),
+    };
+    let parsed = match parse_path(&args.path) {
+        Ok(parsed) => parsed,
+        Err(error) => return result::failure("usage", Some(&args.path), &error, None),
+    };
+    let options = match parse_usage_options(args.max_entries, args.include_hidden) {
+        Ok(options) => options,
+        Err(error) => {
+            return result::failure(
+                "usage",
+                Some(parsed.normalized_path()),
+                &error,
+                Some("filesystem"),
+            );
+        }
+    };
+
+    execute_usage_on_path(parsed, options, capability_domain_state)
+}
+
+fn execute_stat(args_json: &str, capability_domain_state: &Value) -> CapabilityActionResult {
+    let args = match parse_args::<StatArgs>(args_json, "filesystem__stat") {
+        Ok(args) => args,
+        Err(error) => return result::failure("stat", None, &error, None),
+    };
+    let parsed = match parse_path(&args.path) {
+        Ok(parsed) => parsed,
+        Err(error) => return result::failure("stat", Some(&args.path), &error, None),
+    };
+
+    execute_stat_on_path(parsed, capability_domain_state)
+}
+
 fn execute_list_on_path(
     path: ParsedPath,
     options: ListOptions,
@@ -290,7 +448,12 @@ fn execute_list_on_path(
 
     match real::list(&path, options, capability_domain_state) {
         Ok(data) => result::success("list", &normalized_path, target, data),
-        Err(error) => result::failure("list", Some(&normalized_path), &error, Some(target)),
+        Err(error) => result::failure(
+            "list",
+            Some(&normalized_path),
+            &scoped_error(error, &normalized_path, capability_domain_state),
+            Some(target),
+        ),
     }
 }
 
@@ -304,13 +467,36 @@ fn execute_read_on_path(
 
     match real::read(&path, options, capability_domain_state) {
         Ok(data) => result::success("read", &normalized_path, target, data),
-        Err(error) => result::failure("read", Some(&normalized_path), &error, Some(target)),
+        Err(error) => result::failure(
+            "read",
+            Some(&normalized_path),
+            &scoped_error(error, &normalized_path, capability_domain_state),
+            Some(target),
+        ),
+    }
+}
+
+fn execute_read_json_on_path(
+    path: ParsedPath,
+    capability_domain_state: &Value,
+) -> CapabilityActionResult {
+    let target = path.target_label();
+    let normalized_path = path.normalized_path().to_string();
+
+    match real::read_json(&path, capability_domain_state) {
+        Ok(data) => result::success("read_json", &normalized_path, target, data),
+        Err(error) => result::failure(
+            "read_json",
+            Some(&normalized_path),
+            &scoped_error(error, &normalized_path, capability_domain_state),
+            Some(target),
+        ),
     }
 }
 
 fn execute_write_on_path(
     path: ParsedPath,
-    content: &str,
+    content: &[u8],
     allow_override: bool,
     create_parents: bool,
     capability_domain_state: &Value,
@@ -326,7 +512,12 @@ fn execute_write_on_path(
         capability_domain_state,
     ) {
         Ok(data) => result::success("write", &normalized_path, target, data),
-        Err(error) => result::failure("write", Some(&normalized_path), &error, Some(target)),
+        Err(error) => result::failure(
+            "write",
+            Some(&normalized_path),
+            &scoped_error(error, &normalized_path, capability_domain_state),
+            Some(target),
+        ),
     }
 }
 
@@ -350,7 +541,51 @@ fn execute_replace_on_path(
         capability_domain_state,
     ) {
         Ok(data) => result::success("replace", &normalized_path, target, data),
-        Err(error) => result::failure("replace", Some(&normalized_path), &error, Some(target)),
+        Err(error) => result::failure(
+            "replace",
+            Some(&normalized_path),
+            &scoped_error(error, &normalized_path, capability_domain_state),
+            Some(target),
+        ),
+    }
+}
+
+fn execute_delete_on_path(
+    path: ParsedPath,
+    recursive: bool,
+    capability_domain_state: &Value,
+) -> CapabilityActionResult {
+    let target = path.target_label();
+    let normalized_path = path.normalized_path().to_string();
+
+    match real::delete(&path, recursive, capability_domain_state) {
+        Ok(data) => result::success("delete", &normalized_path, target, data),
+        Err(error) => result::failure(
+            "delete",
+            Some(&normalized_path),
+            &scoped_error(error, &normalized_path, capability_domain_state),
+            Some(target),
+        ),
+    }
+}
+
+fn execute_move_on_paths(
+    src: ParsedPath,
+    dst: ParsedPath,
+    allow_override: bool,
+    capability_domain_state: &Value,
+) -> CapabilityActionResult {
+    let target = src.target_label();
+    let normalized_path = src.normalized_path().to_string();
+
+    match real::move_path(&src, &dst, allow_override, capability_domain_state) {
+        Ok(data) => result::success("move", &normalized_path, target, data),
+        Err(error) => result::failure(
+            "move",
+            Some(&normalized_path),
+            &scoped_error(error, &normalized_path, capability_domain_state),
+            Some(target),
+        ),
     }
 }
 
@@ -365,7 +600,12 @@ fn execute_glob_on_path(
 
     match real::glob(&path, pattern, options, capability_domain_state) {
         Ok(data) => result::success("glob", &normalized_path, target, data),
-        Err(error) => result::failure("glob", Some(&normalized_path), &error, Some(target)),
+        Err(error) => result::failure(
+            "glob",
+            Some(&normalized_path),
+            &scoped_error(error, &normalized_path, capability_domain_state),
+            Some(target),
+        ),
     }
 }
 
@@ -380,11 +620,66 @@ fn execute_search_on_path(
 
     match real::search(&path, pattern, options, capability_domain_state) {
         Ok(data) => result::success("search", &normalized_path, target, data),
-        Err(error) => result::failure("search", Some(&normalized_path), &error, Some(target)),
+        Err(error) => result::failure(
+            "search",
+            Some(&normalized_path),
+            &scoped_error(error, &normalized_path, capability_domain_state),
+            Some(target),
+        ),
+    }
+}
+
+fn execute_usage_on_path(
+    path: ParsedPath,
+    options: UsageOptions,
+    capability_domain_state: &Value,
+) -> CapabilityActionResult {
+    let target = path.target_label();
+    let normalized_path = path.normalized_path().to_string();
+
+    match real::usage(&path, options, capability_domain_state) {
+        Ok(data) => result::success("usage", &normalized_path, target, data),
+        Err(error) => result::failure(
+            "usage",
+            Some(&normalized_path),
+            &scoped_error(error, &normalized_path, capability_domain_state),
+            Some(target),
+        ),
+    }
+}
+
+fn execute_stat_on_path(
+    path: ParsedPath,
+    capability_domain_state: &Value,
+) -> CapabilityActionResult {
+    let target = path.target_label();
+    let normalized_path = path.normalized_path().to_string();
+
+    match real::stat(&path, capability_domain_state) {
+        Ok(data) => result::success("stat", &normalized_path, target, data),
+        Err(error) => result::failure(
+            "stat",
+            Some(&normalized_path),
+            &scoped_error(error, &normalized_path, capability_domain_state),
+            Some(target),
+        ),
     }
 }
 
-fn parse_list_options(args: ListArgs) -> Result<ListOptions, FsError> {
+fn scoped_error(error: FsError, normalized_path: &str, capability_domain_state: &Value) -> FsError {
+    redact_base_path(error, capability_domain_state).with_path_context(normalized_path)
+}
+
+fn parse_list_options(
+    args: ListArgs,
+    capability_domain_state: &Value,
+) -> Result<ListOptions, FsError> {
+    let default_max_entries = capability_domain_state
+        .get("list_max_entries")
+        .and_then(Value::as_u64)
+        .and_then(|value| usize::try_from(value).ok())
+        .map(|value| value.clamp(1, LIST_MAX_ENTRIES_CAP))
+        .unwrap_or(LIST_DEFAULT_MAX_ENTRIES);
     let max_entries = parse_optional_usize(
         args.max_entries,
         "filesystem__list",
@@ -392,16 +687,18 @@ fn parse_list_options(args: ListArgs) -> Result<ListOptions, FsError> {
         1,
         LIST_MAX_ENTRIES_CAP,
     )?
-    .unwrap_or(LIST_DEFAULT_MAX_ENTRIES);
+    .unwrap_or(default_max_entries);
 
     Ok(ListOptions {
         recursive: args.recursive.unwrap_or(false),
         max_entries,
         include_hidden: args.include_hidden.unwrap_or(false),
+        pattern: args.pattern,
     })
 }
 
 fn parse_read_options(args: ReadArgs) -> Result<ReadOptions, FsError> {
+    let byte_range = parse_read_byte_range(&args)?;
     let offset_line = parse_optional_usize(
         args.offset_line,
         "filesystem__read",
@@ -423,9 +720,52 @@ fn parse_read_options(args: ReadArgs) -> Result<ReadOptions, FsError> {
     Ok(ReadOptions {
         offset_line,
         limit_lines,
+        byte_range,
+        encoding: args.encoding,
     })
 }
 
+fn parse_read_byte_range(args: &ReadArgs) -> Result<Option<ByteRange>, FsError> {
+    if args.offset.is_none() && args.length.is_none() {
+        return Ok(None);
+    }
+    if args.offset_line.is_some() || args.limit_lines.is_some() {
+        return Err(FsError::invalid_args(
+            "`filesystem__read` accepts either `offset`/`length` or `offset_line`/`limit_lines`, not both",
+        ));
+    }
+
+    let offset = parse_optional_usize(args.offset, "filesystem__read", "offset", 0, usize::MAX)?
+        .unwrap_or(0);
+    let length = parse_optional_usize(
+        args.length,
+        "filesystem__read",
+        "length",
+        1,
+        READ_MAX_LENGTH_BYTES,
+    )?
+    .unwrap_or(READ_DEFAULT_LENGTH_BYTES);
+
+    Ok(Some(ByteRange { offset, length }))
+}
+
+fn parse_write_content(
+    content: Option<String>,
+    content_base64: Option<String>,
+) -> Result<Vec<u8>, FsError> {
+    match (content, content_base64) {
+        (Some(content), None) => Ok(content.into_bytes()),
+        (None, Some(content_base64)) => BASE64_STANDARD.decode(content_base64).map_err(|error| {
+            FsError::invalid_args(format!(
+                "`filesystem__write.content_base64` is not valid base64: {error}"
+            ))
+        }),
+        _ => Err(FsError::invalid_args(
+            "`filesystem__write` requires exactly one of `content` or `content_base64`",
+        )),
+    }
+}
+
 fn parse_glob_options(
     max_results: Option<u64>,
     include_hidden: Option<bool>,
@@ -466,6 +806,25 @@ fn parse_search_options(
     })
 }
 
+fn parse_usage_options(
+    max_entries: Option<u64>,
+    include_hidden: Option<bool>,
+) -> Result<UsageOptions, FsError> {
+    let max_entries = parse_optional_usize(
+        max_entries,
+        "filesystem__usage",
+        "max_entries",
+        1,
+        USAGE_MAX_ENTRIES_CAP,
+    )?
+    .unwrap_or(USAGE_DEFAULT_MAX_ENTRIES);
+
+    Ok(UsageOptions {
+        max_entries,
+        include_hidden: include_hidden.unwrap_or(false),
+    })
+}
+
 fn parse_optional_usize(
     value: Option<u64>,
     action_id: &str,
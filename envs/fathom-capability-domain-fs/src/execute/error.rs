@@ -20,6 +20,10 @@ impl FsError {
         Self::new("invalid_path", message)
     }
 
+    pub(crate) fn workspace_missing(message: impl Into<String>) -> Self {
+        Self::new("workspace_missing", message)
+    }
+
     pub(crate) fn not_found(message: impl Into<String>) -> Self {
         Self::new("not_found", message)
     }
@@ -48,6 +52,16 @@ impl FsError {
         Self::new("io_error", message)
     }
 
+    pub(crate) fn with_path_context(self, normalized_path: &str) -> Self {
+        if self.message.contains(&format!("`{normalized_path}`")) {
+            return self;
+        }
+        Self {
+            code: self.code,
+            message: format!("`{normalized_path}`: {}", self.message),
+        }
+    }
+
     pub(crate) fn code(&self) -> &'static str {
         self.code
     }
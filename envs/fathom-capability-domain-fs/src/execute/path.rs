@@ -48,48 +48,124 @@ pub(crate) fn resolve_target_path(
     let base_path = resolve_base_path(capability_domain_state)?;
     let target = base_path.join(rel_path);
     ensure_path_stays_within_base(&base_path, &target)?;
+    if is_protected_path(capability_domain_state, &target) {
+        return Err(FsError::permission_denied(
+            "path is protected by the filesystem access policy",
+        ));
+    }
     Ok((base_path, target))
 }
 
+pub(crate) fn is_protected_path(capability_domain_state: &Value, target: &Path) -> bool {
+    let Some(target) = resolve_existing_prefix(target) else {
+        return false;
+    };
+    protected_paths(capability_domain_state)
+        .iter()
+        .any(|protected| target.starts_with(protected))
+}
+
+pub(crate) fn contains_protected_path(capability_domain_state: &Value, directory: &Path) -> bool {
+    let Some(directory) = resolve_existing_prefix(directory) else {
+        return false;
+    };
+    protected_paths(capability_domain_state)
+        .iter()
+        .any(|protected| protected.starts_with(&directory))
+}
+
+fn protected_paths(capability_domain_state: &Value) -> Vec<PathBuf> {
+    capability_domain_state
+        .get("protected_paths")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str)
+        .map(str::trim)
+        .filter(|raw| !raw.is_empty())
+        .filter_map(|raw| absolute_base_path(raw).ok())
+        .filter_map(|path| resolve_existing_prefix(&path))
+        .collect()
+}
+
+fn resolve_existing_prefix(path: &Path) -> Option<PathBuf> {
+    let mut probe = path.to_path_buf();
+    let mut missing = Vec::new();
+    while !probe.exists() {
+        missing.push(probe.file_name()?.to_os_string());
+        probe.pop();
+    }
+
+    let mut resolved = fs::canonicalize(&probe).ok()?;
+    resolved.extend(missing.iter().rev());
+    Some(resolved)
+}
+
 pub(crate) fn resolve_base_path(capability_domain_state: &Value) -> Result<PathBuf, FsError> {
-    let raw_base = capability_domain_state
-        .as_object()
-        .and_then(|state| state.get("base_path"))
-        .and_then(Value::as_str)
-        .unwrap_or(".");
-    let raw_base = raw_base.trim();
+    let raw_base = raw_base_path(capability_domain_state);
     if raw_base.is_empty() {
         return Err(FsError::invalid_path(
             "filesystem capability-domain base_path must be a non-empty string",
         ));
     }
 
-    let base_path = PathBuf::from(raw_base);
-    let base_path = if base_path.is_absolute() {
-        base_path
-    } else {
-        std::env::current_dir()
-            .map_err(|error| FsError::io_error(format!("failed to resolve current dir: {error}")))?
-            .join(base_path)
-    };
-
+    let base_path = absolute_base_path(raw_base)?;
     let canonical_base = fs::canonicalize(&base_path).map_err(|error| {
-        FsError::invalid_path(format!(
-            "filesystem base path `{}` cannot be resolved: {error}",
-            base_path.display()
-        ))
+        if error.kind() == std::io::ErrorKind::NotFound {
+            FsError::workspace_missing("filesystem workspace root no longer exists")
+        } else {
+            FsError::invalid_path(format!("filesystem base path cannot be resolved: {error}"))
+        }
     })?;
     let metadata = fs::metadata(&canonical_base).map_err(map_io_error)?;
     if !metadata.is_dir() {
-        return Err(FsError::invalid_path(format!(
-            "filesystem base path `{}` is not a directory",
-            canonical_base.display()
-        )));
+        return Err(FsError::invalid_path(
+            "filesystem base path is not a directory",
+        ));
     }
 
     Ok(canonical_base)
 }
 
+pub(crate) fn redact_base_path(error: FsError, capability_domain_state: &Value) -> FsError {
+    let raw_base = raw_base_path(capability_domain_state);
+    let Ok(base_path) = absolute_base_path(raw_base) else {
+        return error;
+    };
+
+    let mut host_paths = vec![base_path.display().to_string()];
+    if let Ok(canonical_base) = fs::canonicalize(&base_path) {
+        host_paths.push(canonical_base.display().to_string());
+    }
+    host_paths.retain(|host_path| host_path.len() > 1);
+    host_paths.sort_by_key(|host_path| std::cmp::Reverse(host_path.len()));
+
+    let mut message = error.message().to_string();
+    for host_path in &host_paths {
+        message = message.replace(host_path.as_str(), ".");
+    }
+    FsError::new(error.code(), message)
+}
+
+fn raw_base_path(capability_domain_state: &Value) -> &str {
+    capability_domain_state
+        .as_object()
+        .and_then(|state| state.get("base_path"))
+        .and_then(Value::as_str)
+        .unwrap_or(".")
+        .trim()
+}
+
+fn absolute_base_path(raw_base: &str) -> Result<PathBuf, FsError> {
+    let base_path = PathBuf::from(raw_base);
+    if base_path.is_absolute() {
+        return Ok(base_path);
+    }
+    Ok(std::env::current_dir()
+        .map_err(|error| FsError::io_error(format!("failed to resolve current dir: {error}")))?
+        .join(base_path))
+}
+
 fn ensure_path_stays_within_base(base_path: &Path, target: &Path) -> Result<(), FsError> {
     let mut probe = target.to_path_buf();
     while !probe.exists() {
@@ -1,28 +1,42 @@
 mod common;
+mod delete;
 mod glob;
 mod list;
+mod move_path;
 mod read;
+mod read_json;
 mod replace;
 mod search;
+mod stat;
+mod usage;
 mod write;
 
 use serde_json::Value;
 
-use super::ReplaceMode;
 use super::error::FsError;
 use super::path::ParsedPath;
+use super::{ReadEncoding, ReplaceMode};
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 pub(crate) struct ListOptions {
     pub(crate) recursive: bool,
     pub(crate) max_entries: usize,
     pub(crate) include_hidden: bool,
+    pub(crate) pattern: Option<String>,
 }
 
 #[derive(Debug, Clone, Copy)]
 pub(crate) struct ReadOptions {
     pub(crate) offset_line: usize,
     pub(crate) limit_lines: usize,
+    pub(crate) byte_range: Option<ByteRange>,
+    pub(crate) encoding: Option<ReadEncoding>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct ByteRange {
+    pub(crate) offset: usize,
+    pub(crate) length: usize,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -38,6 +52,12 @@ pub(crate) struct SearchOptions {
     pub(crate) case_sensitive: bool,
 }
 
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct UsageOptions {
+    pub(crate) max_entries: usize,
+    pub(crate) include_hidden: bool,
+}
+
 pub(crate) fn list(
     path: &ParsedPath,
     options: ListOptions,
@@ -54,9 +74,16 @@ pub(crate) fn read(
     read::read(path, options, capability_domain_state)
 }
 
+pub(crate) fn read_json(
+    path: &ParsedPath,
+    capability_domain_state: &Value,
+) -> Result<Value, FsError> {
+    read_json::read_json(path, capability_domain_state)
+}
+
 pub(crate) fn write(
     path: &ParsedPath,
-    content: &str,
+    content: &[u8],
     allow_override: bool,
     create_parents: bool,
     capability_domain_state: &Value,
@@ -88,6 +115,23 @@ pub(crate) fn replace(
     )
 }
 
+pub(crate) fn delete(
+    path: &ParsedPath,
+    recursive: bool,
+    capability_domain_state: &Value,
+) -> Result<Value, FsError> {
+    delete::delete(path, recursive, capability_domain_state)
+}
+
+pub(crate) fn move_path(
+    src: &ParsedPath,
+    dst: &ParsedPath,
+    allow_override: bool,
+    capability_domain_state: &Value,
+) -> Result<Value, FsError> {
+    move_path::move_path(src, dst, allow_override, capability_domain_state)
+}
+
 pub(crate) fn glob(
     path: &ParsedPath,
     pattern: &str,
@@ -105,3 +149,15 @@ pub(crate) fn search(
 ) -> Result<Value, FsError> {
     search::search(path, pattern, options, capability_domain_state)
 }
+
+pub(crate) fn usage(
+    path: &ParsedPath,
+    options: UsageOptions,
+    capability_domain_state: &Value,
+) -> Result<Value, FsError> {
+    usage::usage(path, options, capability_domain_state)
+}
+
+pub(crate) fn stat(path: &ParsedPath, capability_domain_state: &Value) -> Result<Value, FsError> {
+    stat::stat(path, capability_domain_state)
+}
@@ -3,7 +3,11 @@ use std::fs;
 use std::io;
 use std::path::Path;
 
+use serde_json::Value;
+
 use super::super::error::FsError;
+use super::super::path::ParsedPath;
+use crate::write_policy::normalize_extension;
 
 pub(crate) fn map_io_error(error: io::Error) -> FsError {
     match error.kind() {
@@ -37,3 +41,55 @@ pub(crate) fn read_utf8_file(path: &Path, normalized_path: &str) -> Result<Strin
         ))
     })
 }
+
+pub(crate) fn ensure_write_extension_allowed(
+    path: &ParsedPath,
+    capability_domain_state: &Value,
+) -> Result<(), FsError> {
+    let Some(extension) = path
+        .rel_path
+        .extension()
+        .and_then(|extension| normalize_extension(&extension.to_string_lossy()))
+    else {
+        return Ok(());
+    };
+
+    let blocked = capability_domain_state
+        .get("blocked_write_extensions")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str)
+        .filter_map(normalize_extension)
+        .any(|blocked| blocked == extension);
+    if blocked {
+        return Err(FsError::permission_denied(format!(
+            "writing `.{extension}` files is blocked by the filesystem write policy"
+        )));
+    }
+    Ok(())
+}
+
+pub(crate) fn ensure_line_length_allowed(
+    content: &str,
+    capability_domain_state: &Value,
+) -> Result<(), FsError> {
+    let Some(max_line_length) = capability_domain_state
+        .get("max_write_line_length")
+        .and_then(Value::as_u64)
+        .filter(|value| *value > 0)
+    else {
+        return Ok(());
+    };
+
+    for (index, line) in content.lines().enumerate() {
+        let line_length = line.chars().count() as u64;
+        if line_length > max_line_length {
+            return Err(FsError::invalid_args(format!(
+                "line {} is {line_length} characters long; the filesystem write policy allows at most {max_line_length}",
+                index + 1
+            )));
+        }
+    }
+    Ok(())
+}
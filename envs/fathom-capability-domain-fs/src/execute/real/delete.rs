@@ -0,0 +1,53 @@
+use std::fs;
+
+use serde_json::{Value, json};
+
+use super::super::error::FsError;
+use super::super::path::{ParsedPath, contains_protected_path, resolve_target_path};
+use super::common::{ensure_write_extension_allowed, map_io_error};
+
+pub(crate) fn delete(
+    path: &ParsedPath,
+    recursive: bool,
+    capability_domain_state: &Value,
+) -> Result<Value, FsError> {
+    if path.normalized_path() == "." {
+        return Err(FsError::permission_denied(
+            "the filesystem base path itself cannot be deleted",
+        ));
+    }
+    ensure_write_extension_allowed(path, capability_domain_state)?;
+    let (_base_path, target) = resolve_target_path(capability_domain_state, &path.rel_path)?;
+
+    let metadata = fs::symlink_metadata(&target).map_err(map_io_error)?;
+    if metadata.is_dir() {
+        if contains_protected_path(capability_domain_state, &target) {
+            return Err(FsError::permission_denied(format!(
+                "`{}` contains a path protected by the filesystem access policy",
+                path.normalized_path()
+            )));
+        }
+        if recursive {
+            fs::remove_dir_all(&target).map_err(map_io_error)?;
+        } else {
+            fs::remove_dir(&target).map_err(|error| {
+                if error.kind() == std::io::ErrorKind::DirectoryNotEmpty {
+                    FsError::invalid_args(format!(
+                        "`{}` is not empty; set `recursive` to delete it with its contents",
+                        path.normalized_path()
+                    ))
+                } else {
+                    map_io_error(error)
+                }
+            })?;
+        }
+    } else {
+        fs::remove_file(&target).map_err(map_io_error)?;
+    }
+
+    Ok(json!({
+        "deleted": true,
+        "kind": if metadata.is_dir() { "dir" } else { "file" },
+        "recursive": recursive,
+    }))
+}
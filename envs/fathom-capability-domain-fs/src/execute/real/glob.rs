@@ -5,7 +5,7 @@ use glob::Pattern;
 use serde_json::{Value, json};
 
 use super::super::error::FsError;
-use super::super::path::{ParsedPath, resolve_target_path};
+use super::super::path::{ParsedPath, is_protected_path, resolve_target_path};
 use super::GlobOptions;
 use super::common::{is_hidden_name, map_io_error, path_for_output};
 
@@ -37,7 +37,12 @@ pub(crate) fn glob(
 
     let mut candidates = Vec::new();
     if target_is_dir {
-        collect_files_recursive(&target, options.include_hidden, &mut candidates)?;
+        collect_files_recursive(
+            &target,
+            options.include_hidden,
+            capability_domain_state,
+            &mut candidates,
+        )?;
     } else {
         candidates.push(target.clone());
     }
@@ -81,6 +86,7 @@ pub(crate) fn glob(
 fn collect_files_recursive(
     directory: &Path,
     include_hidden: bool,
+    capability_domain_state: &Value,
     out: &mut Vec<PathBuf>,
 ) -> Result<(), FsError> {
     let mut children = fs::read_dir(directory)
@@ -93,10 +99,13 @@ fn collect_files_recursive(
         if !include_hidden && is_hidden_name(&child.file_name()) {
             continue;
         }
-        let entry_type = child.file_type().map_err(map_io_error)?;
         let entry_path = child.path();
+        if is_protected_path(capability_domain_state, &entry_path) {
+            continue;
+        }
+        let entry_type = child.file_type().map_err(map_io_error)?;
         if entry_type.is_dir() {
-            collect_files_recursive(&entry_path, include_hidden, out)?;
+            collect_files_recursive(&entry_path, include_hidden, capability_domain_state, out)?;
         } else if entry_type.is_file() {
             out.push(entry_path);
         }
@@ -1,10 +1,11 @@
 use std::fs;
 use std::path::Path;
 
+use glob::Pattern;
 use serde_json::{Value, json};
 
 use super::super::error::FsError;
-use super::super::path::{ParsedPath, resolve_target_path};
+use super::super::path::{ParsedPath, is_protected_path, resolve_target_path};
 use super::ListOptions;
 use super::common::{is_hidden_name, map_io_error, path_for_output};
 
@@ -13,6 +14,11 @@ pub(crate) fn list(
     options: ListOptions,
     capability_domain_state: &Value,
 ) -> Result<Value, FsError> {
+    let pattern = options
+        .pattern
+        .as_deref()
+        .map(compile_name_pattern)
+        .transpose()?;
     let (base_path, target) = resolve_target_path(capability_domain_state, &path.rel_path)?;
     let metadata = fs::metadata(&target).map_err(map_io_error)?;
     if !metadata.is_dir() {
@@ -22,30 +28,26 @@ pub(crate) fn list(
         )));
     }
 
-    let mut entries = Vec::new();
-    collect_dir_entries(
-        &base_path,
-        &target,
-        options.recursive,
-        options.include_hidden,
-        &mut entries,
-    )?;
+    let mut walk = ListWalk {
+        base_path: &base_path,
+        capability_domain_state,
+        recursive: options.recursive,
+        include_hidden: options.include_hidden,
+        pattern: pattern.as_ref(),
+        entry_limit: options.max_entries.saturating_add(1),
+        entries: Vec::new(),
+        skipped_non_utf8: Vec::new(),
+    };
+    collect_dir_entries(&mut walk, &target)?;
+    let ListWalk {
+        mut entries,
+        mut skipped_non_utf8,
+        ..
+    } = walk;
+    skipped_non_utf8.sort();
 
-    entries.sort_by(|a, b| {
-        let a = a
-            .get("path")
-            .and_then(Value::as_str)
-            .unwrap_or_default()
-            .to_string();
-        let b = b
-            .get("path")
-            .and_then(Value::as_str)
-            .unwrap_or_default()
-            .to_string();
-        a.cmp(&b)
-    });
-
-    let truncated = entries.len() > options.max_entries;
+    let total_entries = entries.len();
+    let truncated = total_entries > options.max_entries;
     if truncated {
         entries.truncate(options.max_entries);
     }
@@ -53,17 +55,35 @@ pub(crate) fn list(
     Ok(json!({
         "entries": entries,
         "truncated": truncated,
+        "total_entries": total_entries,
         "next_cursor": Value::Null,
+        "skipped_non_utf8": skipped_non_utf8,
     }))
 }
 
-fn collect_dir_entries(
-    base_path: &Path,
-    directory: &Path,
+fn compile_name_pattern(raw: &str) -> Result<Pattern, FsError> {
+    let value = raw.trim();
+    if value.is_empty() {
+        return Err(FsError::invalid_args(
+            "filesystem__list.pattern must be a non-empty string",
+        ));
+    }
+    Pattern::new(value)
+        .map_err(|error| FsError::invalid_args(format!("invalid glob pattern `{value}`: {error}")))
+}
+
+struct ListWalk<'a> {
+    base_path: &'a Path,
+    capability_domain_state: &'a Value,
     recursive: bool,
     include_hidden: bool,
-    entries: &mut Vec<Value>,
-) -> Result<(), FsError> {
+    pattern: Option<&'a Pattern>,
+    entry_limit: usize,
+    entries: Vec<Value>,
+    skipped_non_utf8: Vec<String>,
+}
+
+fn collect_dir_entries(walk: &mut ListWalk<'_>, directory: &Path) -> Result<(), FsError> {
     let mut children = fs::read_dir(directory)
         .map_err(map_io_error)?
         .collect::<Result<Vec<_>, _>>()
@@ -71,11 +91,25 @@ fn collect_dir_entries(
     children.sort_by_key(|entry| entry.path());
 
     for child in children {
-        if !include_hidden && is_hidden_name(&child.file_name()) {
+        if walk.entries.len() >= walk.entry_limit {
+            return Ok(());
+        }
+        if !walk.include_hidden && is_hidden_name(&child.file_name()) {
             continue;
         }
 
         let entry_path = child.path();
+        if is_protected_path(walk.capability_domain_state, &entry_path) {
+            continue;
+        }
+        let rel_path = entry_path
+            .strip_prefix(walk.base_path)
+            .map_err(|_| FsError::permission_denied("path escaped filesystem base path"))?;
+        let Some(name) = child.file_name().to_str().map(str::to_string) else {
+            walk.skipped_non_utf8.push(path_for_output(rel_path));
+            continue;
+        };
+
         let entry_type = child.file_type().map_err(map_io_error)?;
         let kind = if entry_type.is_dir() {
             "dir"
@@ -85,23 +119,22 @@ fn collect_dir_entries(
             "other"
         };
 
-        let rel_path = entry_path
-            .strip_prefix(base_path)
-            .map_err(|_| FsError::permission_denied("path escaped filesystem base path"))?;
-        let rel_string = path_for_output(rel_path);
-        let mut entry_json = json!({
-            "path": rel_string,
-            "name": child.file_name().to_string_lossy().to_string(),
-            "kind": kind,
-        });
-        if entry_type.is_file() {
-            let size = child.metadata().map_err(map_io_error)?.len();
-            entry_json["size"] = json!(size);
+        if walk.pattern.is_none_or(|pattern| pattern.matches(&name)) {
+            let rel_string = path_for_output(rel_path);
+            let mut entry_json = json!({
+                "path": rel_string,
+                "name": name,
+                "kind": kind,
+            });
+            if entry_type.is_file() {
+                let size = child.metadata().map_err(map_io_error)?.len();
+                entry_json["size"] = json!(size);
+            }
+            walk.entries.push(entry_json);
         }
-        entries.push(entry_json);
 
-        if recursive && entry_type.is_dir() {
-            collect_dir_entries(base_path, &entry_path, recursive, include_hidden, entries)?;
+        if walk.recursive && entry_type.is_dir() {
+            collect_dir_entries(walk, &entry_path)?;
         }
     }
 
@@ -0,0 +1,109 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use serde_json::{Value, json};
+
+use super::super::error::FsError;
+use super::super::path::{ParsedPath, contains_protected_path, resolve_target_path};
+use super::common::{ensure_write_extension_allowed, map_io_error};
+
+pub(crate) fn move_path(
+    src: &ParsedPath,
+    dst: &ParsedPath,
+    allow_override: bool,
+    capability_domain_state: &Value,
+) -> Result<Value, FsError> {
+    if src.normalized_path() == "." || dst.normalized_path() == "." {
+        return Err(FsError::permission_denied(
+            "the filesystem base path itself cannot be moved or replaced",
+        ));
+    }
+    ensure_write_extension_allowed(src, capability_domain_state)?;
+    ensure_write_extension_allowed(dst, capability_domain_state)?;
+    let (_base_path, source) = resolve_target_path(capability_domain_state, &src.rel_path)?;
+    let (_base_path, target) = resolve_target_path(capability_domain_state, &dst.rel_path)?;
+
+    let metadata = fs::symlink_metadata(&source).map_err(map_io_error)?;
+    if metadata.is_dir() && contains_protected_path(capability_domain_state, &source) {
+        return Err(FsError::permission_denied(format!(
+            "`{}` contains a path protected by the filesystem access policy",
+            src.normalized_path()
+        )));
+    }
+    let bytes = (!metadata.is_dir()).then_some(metadata.len());
+    if src.rel_path == dst.rel_path {
+        return Ok(move_payload(src, dst, bytes, false));
+    }
+    if metadata.is_dir() && dst.rel_path.starts_with(&src.rel_path) {
+        return Err(FsError::invalid_args(format!(
+            "cannot move `{}` into itself",
+            src.normalized_path()
+        )));
+    }
+
+    let overwritten = match fs::symlink_metadata(&target) {
+        Ok(existing) => {
+            if existing.is_dir() || metadata.is_dir() {
+                return Err(FsError::already_exists(format!(
+                    "`{}` already exists and cannot be replaced by a move",
+                    dst.normalized_path()
+                )));
+            }
+            if !allow_override {
+                return Err(FsError::already_exists(format!(
+                    "`{}` already exists",
+                    dst.normalized_path()
+                )));
+            }
+            true
+        }
+        Err(error) if error.kind() == io::ErrorKind::NotFound => false,
+        Err(error) => return Err(map_io_error(error)),
+    };
+
+    match target.parent().map(fs::metadata) {
+        Some(Ok(parent)) if parent.is_dir() => {}
+        Some(Ok(_)) => {
+            return Err(FsError::not_directory(format!(
+                "parent path for `{}` is not a directory",
+                dst.normalized_path()
+            )));
+        }
+        _ => {
+            return Err(FsError::not_found(format!(
+                "parent directory for `{}` does not exist",
+                dst.normalized_path()
+            )));
+        }
+    }
+
+    match fs::rename(&source, &target) {
+        Ok(()) => {}
+        Err(error) if error.kind() == io::ErrorKind::CrossesDevices && metadata.is_file() => {
+            copy_then_remove(&source, &target)?;
+        }
+        Err(error) => return Err(map_io_error(error)),
+    }
+
+    Ok(move_payload(src, dst, bytes, overwritten))
+}
+
+fn copy_then_remove(source: &Path, target: &Path) -> Result<(), FsError> {
+    fs::copy(source, target).map_err(map_io_error)?;
+    fs::remove_file(source).map_err(map_io_error)
+}
+
+fn move_payload(
+    src: &ParsedPath,
+    dst: &ParsedPath,
+    bytes: Option<u64>,
+    overwritten: bool,
+) -> Value {
+    json!({
+        "src": src.normalized_path(),
+        "dst": dst.normalized_path(),
+        "bytes": bytes,
+        "overwritten": overwritten,
+    })
+}
@@ -1,12 +1,19 @@
 use std::cmp::min;
 use std::fs;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::Path;
 
+use base64::Engine as _;
+use base64::prelude::BASE64_STANDARD;
 use serde_json::{Value, json};
 
+use super::super::READ_DEFAULT_LENGTH_BYTES;
 use super::super::error::FsError;
 use super::super::path::{ParsedPath, resolve_target_path};
-use super::ReadOptions;
 use super::common::{map_io_error, read_utf8_file};
+use super::{ByteRange, ReadEncoding, ReadOptions};
+
+const UTF8_BOUNDARY_SLACK_BYTES: usize = 8;
 
 pub(crate) fn read(
     path: &ParsedPath,
@@ -22,7 +29,36 @@ pub(crate) fn read(
         )));
     }
 
-    let text = read_utf8_file(&target, path.normalized_path())?;
+    let byte_range = match (options.encoding, options.byte_range) {
+        (_, Some(range)) => Some(range),
+        (Some(ReadEncoding::Base64), None) => Some(default_byte_range()),
+        _ => None,
+    };
+    if let Some(range) = byte_range {
+        let total_bytes = usize::try_from(metadata.len()).unwrap_or(usize::MAX);
+        return read_byte_range(
+            &target,
+            path.normalized_path(),
+            range,
+            total_bytes,
+            options.encoding,
+        );
+    }
+
+    let text = match options.encoding {
+        Some(ReadEncoding::Utf8) => read_utf8_file(&target, path.normalized_path())?,
+        _ => {
+            let bytes = fs::read(&target).map_err(map_io_error)?;
+            match String::from_utf8(bytes) {
+                Ok(text) => text,
+                Err(error) => {
+                    let bytes = error.as_bytes();
+                    let returned = &bytes[..min(bytes.len(), READ_DEFAULT_LENGTH_BYTES)];
+                    return Ok(base64_payload(returned, 0, bytes.len()));
+                }
+            }
+        }
+    };
     let lines: Vec<&str> = text.lines().collect();
     let total_lines = lines.len();
     let start_index = options.offset_line.saturating_sub(1);
@@ -44,5 +80,98 @@ pub(crate) fn read(
         "total_lines": total_lines,
         "truncated": start_index.saturating_add(returned_lines) < total_lines,
         "bytes": text.len(),
+        "encoding": "utf8",
     }))
 }
+
+fn default_byte_range() -> ByteRange {
+    ByteRange {
+        offset: 0,
+        length: READ_DEFAULT_LENGTH_BYTES,
+    }
+}
+
+fn read_byte_range(
+    target: &Path,
+    normalized_path: &str,
+    range: ByteRange,
+    total_bytes: usize,
+    encoding: Option<ReadEncoding>,
+) -> Result<Value, FsError> {
+    let offset = min(range.offset, total_bytes);
+    let window = read_window(
+        target,
+        offset,
+        range.length.saturating_add(UTF8_BOUNDARY_SLACK_BYTES),
+    )?;
+    let base64_window = || {
+        let returned = &window[..min(window.len(), range.length)];
+        base64_payload(returned, offset, total_bytes)
+    };
+
+    if encoding == Some(ReadEncoding::Base64) {
+        return Ok(base64_window());
+    }
+    match utf8_window(&window, range.length) {
+        Some((start, text)) => Ok(utf8_range_payload(text, offset + start, total_bytes)),
+        None if encoding == Some(ReadEncoding::Utf8) => Err(FsError::invalid_encoding(format!(
+            "`{normalized_path}` is not valid UTF-8 text at byte offset {offset}"
+        ))),
+        None => Ok(base64_window()),
+    }
+}
+
+fn read_window(target: &Path, offset: usize, max_bytes: usize) -> Result<Vec<u8>, FsError> {
+    let mut file = fs::File::open(target).map_err(map_io_error)?;
+    file.seek(SeekFrom::Start(offset as u64))
+        .map_err(map_io_error)?;
+    let mut window = Vec::new();
+    file.take(max_bytes as u64)
+        .read_to_end(&mut window)
+        .map_err(map_io_error)?;
+    Ok(window)
+}
+
+fn utf8_window(window: &[u8], length: usize) -> Option<(usize, &str)> {
+    let is_boundary = |index: usize| index >= window.len() || (window[index] as i8) >= -0x40;
+    let mut start = 0;
+    while !is_boundary(start) {
+        start += 1;
+    }
+    let mut end = min(start.saturating_add(length), window.len());
+    while !is_boundary(end) {
+        end -= 1;
+    }
+    if end == start && start < window.len() {
+        end = start + 1;
+        while !is_boundary(end) {
+            end += 1;
+        }
+    }
+    std::str::from_utf8(&window[start..end])
+        .ok()
+        .map(|text| (start, text))
+}
+
+fn utf8_range_payload(text: &str, offset: usize, total_bytes: usize) -> Value {
+    json!({
+        "content": text,
+        "offset": offset,
+        "returned_bytes": text.len(),
+        "total_bytes": total_bytes,
+        "truncated": offset + text.len() < total_bytes,
+        "encoding": "utf8",
+    })
+}
+
+fn base64_payload(bytes: &[u8], offset: usize, total_bytes: usize) -> Value {
+    json!({
+        "content": null,
+        "content_base64": BASE64_STANDARD.encode(bytes),
+        "offset": offset,
+        "returned_bytes": bytes.len(),
+        "total_bytes": total_bytes,
+        "truncated": offset + bytes.len() < total_bytes,
+        "encoding": "base64",
+    })
+}
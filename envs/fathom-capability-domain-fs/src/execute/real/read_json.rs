@@ -0,0 +1,35 @@
+use std::fs;
+
+use serde_json::{Value, json};
+
+use super::super::error::FsError;
+use super::super::path::{ParsedPath, resolve_target_path};
+use super::common::{map_io_error, read_utf8_file};
+
+pub(crate) fn read_json(
+    path: &ParsedPath,
+    capability_domain_state: &Value,
+) -> Result<Value, FsError> {
+    let (_base_path, target) = resolve_target_path(capability_domain_state, &path.rel_path)?;
+    let metadata = fs::metadata(&target).map_err(map_io_error)?;
+    if !metadata.is_file() {
+        return Err(FsError::not_file(format!(
+            "`{}` is not a file",
+            path.normalized_path()
+        )));
+    }
+
+    let text = read_utf8_file(&target, path.normalized_path())?;
+    let value = serde_json::from_str::<Value>(&text).map_err(|error| {
+        FsError::invalid_args(format!(
+            "`{}` is not valid JSON: {error}",
+            path.normalized_path()
+        ))
+    })?;
+
+    Ok(json!({
+        "value": value,
+        "content": text,
+        "bytes": text.len(),
+    }))
+}
@@ -5,7 +5,7 @@ use serde_json::{Value, json};
 use super::super::ReplaceMode;
 use super::super::error::FsError;
 use super::super::path::{ParsedPath, resolve_target_path};
-use super::common::{map_io_error, read_utf8_file};
+use super::common::{ensure_write_extension_allowed, map_io_error, read_utf8_file};
 
 pub(crate) fn replace(
     path: &ParsedPath,
@@ -18,6 +18,7 @@ pub(crate) fn replace(
     if old.is_empty() {
         return Err(FsError::invalid_args("replace.old must be non-empty"));
     }
+    ensure_write_extension_allowed(path, capability_domain_state)?;
 
     let (_base_path, target) = resolve_target_path(capability_domain_state, &path.rel_path)?;
     let metadata = fs::metadata(&target).map_err(map_io_error)?;
@@ -6,7 +6,7 @@ use regex::RegexBuilder;
 use serde_json::{Value, json};
 
 use super::super::error::FsError;
-use super::super::path::{ParsedPath, resolve_target_path};
+use super::super::path::{ParsedPath, is_protected_path, resolve_target_path};
 use super::SearchOptions;
 use super::common::{is_hidden_name, map_io_error, path_for_output, read_utf8_file};
 
@@ -50,6 +50,9 @@ pub(crate) fn search(
     let mut truncated = false;
 
     for candidate in candidates {
+        if is_protected_path(capability_domain_state, &candidate) {
+            continue;
+        }
         let rel_base = candidate
             .strip_prefix(&base_path)
             .map_err(|_| FsError::permission_denied("path escaped filesystem base path"))?;
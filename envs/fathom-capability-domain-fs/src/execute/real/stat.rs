@@ -0,0 +1,40 @@
+use std::fs;
+use std::io::ErrorKind;
+use std::time::UNIX_EPOCH;
+
+use serde_json::{Value, json};
+
+use super::super::error::FsError;
+use super::super::path::{ParsedPath, resolve_target_path};
+use super::common::map_io_error;
+
+pub(crate) fn stat(path: &ParsedPath, capability_domain_state: &Value) -> Result<Value, FsError> {
+    let (_base_path, target) = resolve_target_path(capability_domain_state, &path.rel_path)?;
+    let metadata = match fs::metadata(&target) {
+        Ok(metadata) => metadata,
+        Err(error) if error.kind() == ErrorKind::NotFound => {
+            return Ok(json!({ "exists": false }));
+        }
+        Err(error) => return Err(map_io_error(error)),
+    };
+
+    let kind = if metadata.is_file() {
+        "file"
+    } else if metadata.is_dir() {
+        "dir"
+    } else {
+        "other"
+    };
+    let modified_unix_ms = metadata
+        .modified()
+        .ok()
+        .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+        .map(|elapsed| elapsed.as_millis().min(i64::MAX as u128) as i64);
+
+    Ok(json!({
+        "exists": true,
+        "kind": kind,
+        "size_bytes": metadata.len(),
+        "modified_unix_ms": modified_unix_ms,
+    }))
+}
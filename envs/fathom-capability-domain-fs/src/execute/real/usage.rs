@@ -0,0 +1,89 @@
+use std::fs;
+use std::path::Path;
+
+use serde_json::{Value, json};
+
+use super::super::error::FsError;
+use super::super::path::{ParsedPath, is_protected_path, resolve_target_path};
+use super::UsageOptions;
+use super::common::{is_hidden_name, map_io_error};
+
+#[derive(Debug, Default)]
+struct UsageTotals {
+    file_count: u64,
+    directory_count: u64,
+    total_bytes: u64,
+    visited_entries: usize,
+    truncated: bool,
+}
+
+pub(crate) fn usage(
+    path: &ParsedPath,
+    options: UsageOptions,
+    capability_domain_state: &Value,
+) -> Result<Value, FsError> {
+    let (_, target) = resolve_target_path(capability_domain_state, &path.rel_path)?;
+    let metadata = fs::symlink_metadata(&target).map_err(map_io_error)?;
+
+    let mut totals = UsageTotals::default();
+    if metadata.is_dir() {
+        collect_usage(&target, options, capability_domain_state, &mut totals)?;
+    } else if metadata.is_file() {
+        totals.file_count = 1;
+        totals.total_bytes = metadata.len();
+    } else {
+        return Err(FsError::not_file(format!(
+            "`{}` is neither a file nor a directory",
+            path.normalized_path()
+        )));
+    }
+
+    Ok(json!({
+        "file_count": totals.file_count,
+        "directory_count": totals.directory_count,
+        "total_bytes": totals.total_bytes,
+        "truncated": totals.truncated,
+        "max_entries": options.max_entries,
+    }))
+}
+
+fn collect_usage(
+    directory: &Path,
+    options: UsageOptions,
+    capability_domain_state: &Value,
+    totals: &mut UsageTotals,
+) -> Result<(), FsError> {
+    let mut children = fs::read_dir(directory)
+        .map_err(map_io_error)?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(map_io_error)?;
+    children.sort_by_key(|entry| entry.path());
+
+    for child in children {
+        if !options.include_hidden && is_hidden_name(&child.file_name()) {
+            continue;
+        }
+        if is_protected_path(capability_domain_state, &child.path()) {
+            continue;
+        }
+        if totals.visited_entries >= options.max_entries {
+            totals.truncated = true;
+            return Ok(());
+        }
+        totals.visited_entries += 1;
+
+        let entry_type = child.file_type().map_err(map_io_error)?;
+        if entry_type.is_dir() {
+            totals.directory_count += 1;
+            collect_usage(&child.path(), options, capability_domain_state, totals)?;
+            if totals.truncated {
+                return Ok(());
+            }
+        } else if entry_type.is_file() {
+            totals.file_count += 1;
+            totals.total_bytes += child.metadata().map_err(map_io_error)?.len();
+        }
+    }
+
+    Ok(())
+}
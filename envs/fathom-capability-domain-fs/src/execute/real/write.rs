@@ -4,15 +4,19 @@ use serde_json::{Value, json};
 
 use super::super::error::FsError;
 use super::super::path::{ParsedPath, resolve_target_path};
-use super::common::map_io_error;
+use super::common::{ensure_line_length_allowed, ensure_write_extension_allowed, map_io_error};
 
 pub(crate) fn write(
     path: &ParsedPath,
-    content: &str,
+    content: &[u8],
     allow_override: bool,
     create_parents: bool,
     capability_domain_state: &Value,
 ) -> Result<Value, FsError> {
+    ensure_write_extension_allowed(path, capability_domain_state)?;
+    if let Ok(text) = std::str::from_utf8(content) {
+        ensure_line_length_allowed(text, capability_domain_state)?;
+    }
     let (_base_path, target) = resolve_target_path(capability_domain_state, &path.rel_path)?;
 
     let existed = target.exists();
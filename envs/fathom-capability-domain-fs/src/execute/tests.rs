@@ -1,3 +1,5 @@
+use base64::Engine as _;
+use base64::prelude::BASE64_STANDARD;
 use fathom_capability_domain::{ActionError, CapabilityActionResult};
 use serde_json::{Value, json};
 
@@ -161,14 +163,101 @@ fn fs_env_read_supports_line_windowing() {
 }
 
 #[test]
-fn fs_env_read_rejects_non_utf8_file() {
+fn fs_env_read_returns_utf8_safe_byte_ranges() {
+    let root = unique_temp_dir("fathom-fs-read-range");
+    std::fs::create_dir_all(&root).expect("create temp root");
+    std::fs::write(root.join("note.txt"), "héllo wörld").expect("write file");
+    let state = json!({ "base_path": root.display().to_string() });
+
+    let slice = execute_action(
+        "read",
+        r#"{"path":"note.txt","offset":2,"length":6}"#,
+        &state,
+    )
+    .expect("filesystem__read should dispatch");
+    let payload = outcome_payload(&slice);
+    assert_eq!(payload["data"]["content"], json!("llo w"));
+    assert_eq!(payload["data"]["offset"], json!(3));
+    assert_eq!(payload["data"]["returned_bytes"], json!(5));
+    assert_eq!(payload["data"]["total_bytes"], json!(13));
+    assert_eq!(payload["data"]["truncated"], json!(true));
+
+    let past_eof = execute_action("read", r#"{"path":"note.txt","offset":100}"#, &state)
+        .expect("filesystem__read should dispatch");
+    assert!(past_eof.outcome.is_ok());
+    let payload = outcome_payload(&past_eof);
+    assert_eq!(payload["data"]["content"], json!(""));
+    assert_eq!(payload["data"]["offset"], json!(13));
+    assert_eq!(payload["data"]["returned_bytes"], json!(0));
+    assert_eq!(payload["data"]["truncated"], json!(false));
+
+    let mixed = execute_action(
+        "read",
+        r#"{"path":"note.txt","offset":0,"limit_lines":1}"#,
+        &state,
+    )
+    .expect("filesystem__read should dispatch");
+    assert_eq!(outcome_payload(&mixed)["error_code"], json!("invalid_args"));
+
+    let _ = std::fs::remove_dir_all(&root);
+}
+
+#[test]
+fn fs_env_byte_range_reads_decode_only_the_requested_window() {
+    let root = unique_temp_dir("fathom-fs-read-window");
+    std::fs::create_dir_all(&root).expect("create temp root");
+    let mut bytes = b"hello world".to_vec();
+    bytes.extend([0xffu8; 1000]);
+    std::fs::write(root.join("mixed.dat"), &bytes).expect("write mixed file");
+    let state = json!({ "base_path": root.display().to_string() });
+
+    let text = execute_action(
+        "read",
+        r#"{"path":"mixed.dat","offset":0,"length":5}"#,
+        &state,
+    )
+    .expect("filesystem__read should dispatch");
+    let payload = outcome_payload(&text);
+    assert_eq!(payload["data"]["encoding"], json!("utf8"));
+    assert_eq!(payload["data"]["content"], json!("hello"));
+    assert_eq!(payload["data"]["total_bytes"], json!(1011));
+    assert_eq!(payload["data"]["truncated"], json!(true));
+
+    let binary = execute_action(
+        "read",
+        r#"{"path":"mixed.dat","offset":11,"length":4}"#,
+        &state,
+    )
+    .expect("filesystem__read should dispatch");
+    let payload = outcome_payload(&binary);
+    assert_eq!(payload["data"]["encoding"], json!("base64"));
+    assert_eq!(payload["data"]["content_base64"], json!("/////w=="));
+    assert_eq!(payload["data"]["offset"], json!(11));
+    assert_eq!(payload["data"]["returned_bytes"], json!(4));
+
+    let strict = execute_action(
+        "read",
+        r#"{"path":"mixed.dat","offset":11,"length":4,"encoding":"utf8"}"#,
+        &state,
+    )
+    .expect("filesystem__read should dispatch");
+    assert_eq!(
+        outcome_payload(&strict)["error_code"],
+        json!("invalid_encoding")
+    );
+
+    let _ = std::fs::remove_dir_all(&root);
+}
+
+#[test]
+fn fs_env_read_with_utf8_encoding_rejects_non_utf8_file() {
     let root = unique_temp_dir("fathom-fs-read-non-utf8");
     std::fs::create_dir_all(&root).expect("create temp root");
     std::fs::write(root.join("bin.dat"), [0xffu8, 0xfdu8]).expect("write non utf8");
 
     let outcome = execute_action(
         "read",
-        r#"{"path":"bin.dat"}"#,
+        r#"{"path":"bin.dat","encoding":"utf8"}"#,
         &json!({ "base_path": root.display().to_string() }),
     )
     .expect("filesystem__read should dispatch");
@@ -179,6 +268,316 @@ fn fs_env_read_rejects_non_utf8_file() {
     let _ = std::fs::remove_dir_all(&root);
 }
 
+#[test]
+fn fs_env_binary_content_round_trips_through_base64() {
+    let root = unique_temp_dir("fathom-fs-base64");
+    std::fs::create_dir_all(&root).expect("create temp root");
+    let state = json!({ "base_path": root.display().to_string() });
+    let blob = (0..=255u8).rev().collect::<Vec<_>>();
+    let encoded = BASE64_STANDARD.encode(&blob);
+
+    let write_args = json!({
+        "path": "blob.bin",
+        "content_base64": encoded,
+        "allow_override": false
+    })
+    .to_string();
+    let written = execute_action("write", &write_args, &state).expect("fs_write should dispatch");
+    assert_eq!(
+        outcome_payload(&written)["data"]["bytes_written"],
+        json!(256)
+    );
+    assert_eq!(
+        std::fs::read(root.join("blob.bin")).expect("read blob"),
+        blob
+    );
+
+    let read =
+        execute_action("read", r#"{"path":"blob.bin"}"#, &state).expect("fs_read should dispatch");
+    let payload = outcome_payload(&read);
+    assert_eq!(payload["data"]["encoding"], json!("base64"));
+    assert_eq!(payload["data"]["content"], Value::Null);
+    assert_eq!(payload["data"]["total_bytes"], json!(256));
+    assert_eq!(payload["data"]["truncated"], json!(false));
+    let decoded = BASE64_STANDARD
+        .decode(
+            payload["data"]["content_base64"]
+                .as_str()
+                .expect("base64 content"),
+        )
+        .expect("valid base64");
+    assert_eq!(decoded, blob);
+
+    std::fs::write(root.join("note.txt"), "hello").expect("write text");
+    let forced = execute_action("read", r#"{"path":"note.txt","encoding":"base64"}"#, &state)
+        .expect("fs_read should dispatch");
+    assert_eq!(
+        outcome_payload(&forced)["data"]["content_base64"],
+        json!("aGVsbG8=")
+    );
+
+    let both = json!({
+        "path": "both.bin",
+        "content": "x",
+        "content_base64": "eA==",
+        "allow_override": false
+    })
+    .to_string();
+    let rejected = execute_action("write", &both, &state).expect("fs_write should dispatch");
+    assert_eq!(
+        outcome_payload(&rejected)["error_code"],
+        json!("invalid_args")
+    );
+    assert!(!root.join("both.bin").exists());
+
+    let _ = std::fs::remove_dir_all(&root);
+}
+
+#[test]
+fn fs_env_binary_reads_are_capped_without_a_byte_range() {
+    let root = unique_temp_dir("fathom-fs-base64-cap");
+    std::fs::create_dir_all(&root).expect("create temp root");
+    let state = json!({ "base_path": root.display().to_string() });
+    let blob = (0..200 * 1024)
+        .map(|index| if index % 2 == 0 { 0xff } else { index as u8 })
+        .collect::<Vec<u8>>();
+    std::fs::write(root.join("large.bin"), &blob).expect("write large blob");
+
+    let read =
+        execute_action("read", r#"{"path":"large.bin"}"#, &state).expect("fs_read should dispatch");
+    let payload = outcome_payload(&read);
+    assert_eq!(payload["data"]["encoding"], json!("base64"));
+    assert_eq!(payload["data"]["offset"], json!(0));
+    assert_eq!(payload["data"]["returned_bytes"], json!(64 * 1024));
+    assert_eq!(payload["data"]["total_bytes"], json!(200 * 1024));
+    assert_eq!(payload["data"]["truncated"], json!(true));
+    let decoded = BASE64_STANDARD
+        .decode(
+            payload["data"]["content_base64"]
+                .as_str()
+                .expect("base64 content"),
+        )
+        .expect("valid base64");
+    assert_eq!(decoded, blob[..64 * 1024]);
+
+    let tail = execute_action(
+        "read",
+        r#"{"path":"large.bin","encoding":"base64","offset":196608}"#,
+        &state,
+    )
+    .expect("fs_read should dispatch");
+    let payload = outcome_payload(&tail);
+    assert_eq!(payload["data"]["returned_bytes"], json!(8 * 1024));
+    assert_eq!(payload["data"]["truncated"], json!(false));
+
+    let _ = std::fs::remove_dir_all(&root);
+}
+
+#[test]
+fn fs_env_read_json_returns_parsed_value_and_raw_text() {
+    let root = unique_temp_dir("fathom-fs-read-json");
+    std::fs::create_dir_all(&root).expect("create temp root");
+    let raw = "{\n  \"name\": \"fathom\",\n  \"features\": [\"fs\", \"shell\"]\n}\n";
+    std::fs::write(root.join("config.json"), raw).expect("write config");
+
+    let outcome = execute_action(
+        "read_json",
+        r#"{"path":"config.json"}"#,
+        &json!({ "base_path": root.display().to_string() }),
+    )
+    .expect("filesystem__read_json should dispatch");
+    assert!(outcome.outcome.is_ok());
+    let payload = outcome_payload(&outcome);
+    assert_eq!(payload["op"], json!("read_json"));
+    assert_eq!(
+        payload["data"]["value"],
+        json!({ "name": "fathom", "features": ["fs", "shell"] })
+    );
+    assert_eq!(payload["data"]["content"], json!(raw));
+    assert_eq!(payload["data"]["bytes"], json!(raw.len()));
+
+    let _ = std::fs::remove_dir_all(&root);
+}
+
+#[test]
+fn fs_env_read_json_rejects_invalid_json() {
+    let root = unique_temp_dir("fathom-fs-read-json-invalid");
+    std::fs::create_dir_all(&root).expect("create temp root");
+    std::fs::write(root.join("broken.json"), "{\"name\": \"fathom\",}\n").expect("write broken");
+
+    let outcome = execute_action(
+        "read_json",
+        r#"{"path":"broken.json"}"#,
+        &json!({ "base_path": root.display().to_string() }),
+    )
+    .expect("filesystem__read_json should dispatch");
+    assert!(matches!(outcome.outcome, Err(ActionError::InputError(_))));
+    let payload = outcome_payload(&outcome);
+    assert_eq!(payload["error_code"], json!("invalid_args"));
+    let message = payload["message"].as_str().unwrap_or_default();
+    assert!(message.contains("`broken.json` is not valid JSON"));
+    assert!(!message.contains(&root.display().to_string()));
+
+    let _ = std::fs::remove_dir_all(&root);
+}
+
+#[test]
+fn fs_env_list_truncates_sorted_entries_at_max_entries() {
+    let root = unique_temp_dir("fathom-fs-list-cap");
+    std::fs::create_dir_all(&root).expect("create temp root");
+    for index in (0..12).rev() {
+        std::fs::write(root.join(format!("file-{index:02}.txt")), "x").expect("write file");
+    }
+    let state = json!({
+        "base_path": root.display().to_string(),
+        "list_max_entries": 5
+    });
+
+    let capped = execute_action("list", r#"{"path":"."}"#, &state)
+        .expect("filesystem__list should dispatch");
+    let payload = outcome_payload(&capped);
+    let names = payload["data"]["entries"]
+        .as_array()
+        .expect("entries array")
+        .iter()
+        .map(|entry| entry["name"].as_str().unwrap_or_default().to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        names,
+        vec![
+            "file-00.txt",
+            "file-01.txt",
+            "file-02.txt",
+            "file-03.txt",
+            "file-04.txt"
+        ]
+    );
+    assert_eq!(payload["data"]["truncated"], json!(true));
+    assert_eq!(payload["data"]["total_entries"], json!(6));
+
+    let overridden = execute_action("list", r#"{"path":".","max_entries":20}"#, &state)
+        .expect("filesystem__list should dispatch");
+    let payload = outcome_payload(&overridden);
+    assert_eq!(
+        payload["data"]["entries"].as_array().map(Vec::len),
+        Some(12)
+    );
+    assert_eq!(payload["data"]["truncated"], json!(false));
+    assert_eq!(payload["data"]["total_entries"], json!(12));
+
+    let _ = std::fs::remove_dir_all(&root);
+}
+
+#[test]
+fn fs_env_list_recursive_walks_nested_directories() {
+    let root = unique_temp_dir("fathom-fs-list-recursive");
+    let outside = unique_temp_dir("fathom-fs-list-outside");
+    std::fs::create_dir_all(root.join("src/nested/deeper")).expect("create temp tree");
+    std::fs::create_dir_all(&outside).expect("create outside dir");
+    std::fs::write(root.join("top.txt"), "x").expect("write top.txt");
+    std::fs::write(root.join("src/a.rs"), "x").expect("write a.rs");
+    std::fs::write(root.join("src/nested/deeper/b.rs"), "xy").expect("write b.rs");
+    std::fs::write(outside.join("secret.txt"), "x").expect("write secret.txt");
+    #[cfg(unix)]
+    std::os::unix::fs::symlink(&outside, root.join("src/link")).expect("symlink outside");
+    let state = json!({ "base_path": root.display().to_string() });
+
+    let entry_paths = |payload: &Value| {
+        payload["data"]["entries"]
+            .as_array()
+            .expect("entries array")
+            .iter()
+            .map(|entry| entry["path"].as_str().unwrap_or_default().to_string())
+            .collect::<Vec<_>>()
+    };
+
+    let flat = execute_action("list", r#"{"path":"src"}"#, &state)
+        .expect("filesystem__list should dispatch");
+    assert!(!entry_paths(&outcome_payload(&flat)).contains(&"src/nested/deeper/b.rs".to_string()));
+
+    let recursive = execute_action("list", r#"{"path":"src","recursive":true}"#, &state)
+        .expect("filesystem__list should dispatch");
+    let payload = outcome_payload(&recursive);
+    let paths = entry_paths(&payload);
+    for expected in [
+        "src/a.rs",
+        "src/nested",
+        "src/nested/deeper",
+        "src/nested/deeper/b.rs",
+    ] {
+        assert!(paths.contains(&expected.to_string()), "{expected}");
+    }
+    assert!(!paths.contains(&"top.txt".to_string()));
+    assert!(paths.iter().all(|path| !path.contains("secret.txt")));
+    assert_eq!(payload["data"]["truncated"], json!(false));
+
+    let capped = execute_action(
+        "list",
+        r#"{"path":".","recursive":true,"max_entries":2}"#,
+        &state,
+    )
+    .expect("filesystem__list should dispatch");
+    let payload = outcome_payload(&capped);
+    assert_eq!(entry_paths(&payload), vec!["src", "src/a.rs"]);
+    assert_eq!(payload["data"]["truncated"], json!(true));
+    assert_eq!(payload["data"]["total_entries"], json!(3));
+
+    let _ = std::fs::remove_dir_all(&root);
+    let _ = std::fs::remove_dir_all(&outside);
+}
+
+#[test]
+fn fs_env_list_filters_entries_by_name_pattern() {
+    let root = unique_temp_dir("fathom-fs-list-pattern");
+    std::fs::create_dir_all(root.join("docs")).expect("create docs dir");
+    std::fs::write(root.join("a.txt"), "x").expect("write a.txt");
+    std::fs::write(root.join("b.rs"), "x").expect("write b.rs");
+    std::fs::write(root.join("docs/c.txt"), "x").expect("write c.txt");
+    std::fs::write(root.join("docs/d.md"), "x").expect("write d.md");
+    let state = json!({ "base_path": root.display().to_string() });
+
+    let entry_paths = |payload: &Value| {
+        payload["data"]["entries"]
+            .as_array()
+            .expect("entries array")
+            .iter()
+            .map(|entry| entry["path"].as_str().unwrap_or_default().to_string())
+            .collect::<Vec<_>>()
+    };
+
+    let flat = execute_action("list", r#"{"path":".","pattern":"*.txt"}"#, &state)
+        .expect("filesystem__list should dispatch");
+    assert_eq!(entry_paths(&outcome_payload(&flat)), vec!["a.txt"]);
+
+    let recursive = execute_action(
+        "list",
+        r#"{"path":".","pattern":"*.txt","recursive":true}"#,
+        &state,
+    )
+    .expect("filesystem__list should dispatch");
+    let payload = outcome_payload(&recursive);
+    assert_eq!(entry_paths(&payload), vec!["a.txt", "docs/c.txt"]);
+    assert_eq!(payload["data"]["total_entries"], json!(2));
+
+    let classes = execute_action("list", r#"{"path":".","pattern":"[ab].?s"}"#, &state)
+        .expect("filesystem__list should dispatch");
+    assert_eq!(entry_paths(&outcome_payload(&classes)), vec!["b.rs"]);
+
+    let malformed = execute_action("list", r#"{"path":".","pattern":"[a"}"#, &state)
+        .expect("filesystem__list should dispatch");
+    assert!(malformed.outcome.is_err());
+    let payload = outcome_payload(&malformed);
+    assert_eq!(payload["error_code"], json!("invalid_args"));
+    assert!(
+        payload["message"]
+            .as_str()
+            .unwrap_or_default()
+            .contains("invalid glob pattern `[a`")
+    );
+
+    let _ = std::fs::remove_dir_all(&root);
+}
+
 #[test]
 fn fs_env_glob_returns_matching_files() {
     let root = unique_temp_dir("fathom-fs-glob");
@@ -269,6 +668,529 @@ fn fs_env_write_respects_create_parents_flag() {
     let _ = std::fs::remove_dir_all(&root);
 }
 
+#[test]
+fn fs_env_not_found_error_reports_relative_path_without_base_path() {
+    let root = unique_temp_dir("fathom-fs-not-found-mask");
+    std::fs::create_dir_all(&root).expect("create temp root");
+    let canonical_root =
+        std::fs::canonicalize(&root).expect("base path should canonicalize for comparison");
+
+    let outcome = execute_action(
+        "read",
+        r#"{"path":"notes/missing.txt"}"#,
+        &json!({ "base_path": root.display().to_string() }),
+    )
+    .expect("filesystem__read should dispatch");
+    assert!(outcome.outcome.is_err());
+    let payload = outcome_payload(&outcome);
+    let message = payload["message"].as_str().unwrap_or_default();
+    assert_eq!(payload["error_code"], json!("not_found"));
+    assert!(message.contains("`notes/missing.txt`"));
+    assert!(!message.contains(&root.display().to_string()));
+    assert!(!message.contains(&canonical_root.display().to_string()));
+
+    let _ = std::fs::remove_dir_all(&root);
+}
+
+#[test]
+fn fs_env_write_blocks_denied_extensions() {
+    let root = unique_temp_dir("fathom-fs-write-denylist");
+    std::fs::create_dir_all(&root).expect("create temp root");
+    let state = json!({
+        "base_path": root.display().to_string(),
+        "blocked_write_extensions": ["sh", ".EXE"]
+    });
+
+    let blocked = execute_action(
+        "write",
+        r#"{"path":"scripts/run.SH","content":"echo hi","allow_override":false}"#,
+        &state,
+    )
+    .expect("filesystem__write should dispatch");
+    assert!(blocked.outcome.is_err());
+    let payload = outcome_payload(&blocked);
+    assert_eq!(payload["error_code"], json!("permission_denied"));
+    assert!(!root.join("scripts/run.SH").exists());
+
+    let allowed = execute_action(
+        "write",
+        r#"{"path":"notes.txt","content":"hello","allow_override":false}"#,
+        &state,
+    )
+    .expect("filesystem__write should dispatch");
+    assert!(allowed.outcome.is_ok());
+    assert_eq!(
+        std::fs::read_to_string(root.join("notes.txt")).expect("read written file"),
+        "hello"
+    );
+
+    let _ = std::fs::remove_dir_all(&root);
+}
+
+#[test]
+fn fs_env_replace_blocks_denied_extensions() {
+    let root = unique_temp_dir("fathom-fs-replace-denylist");
+    std::fs::create_dir_all(root.join("scripts")).expect("create scripts dir");
+    std::fs::write(root.join("scripts/run.sh"), "echo hi").expect("write script");
+    let state = json!({
+        "base_path": root.display().to_string(),
+        "blocked_write_extensions": ["sh"]
+    });
+
+    let blocked = execute_action(
+        "replace",
+        r#"{"path":"scripts/run.sh","old":"hi","new":"bye","mode":"all"}"#,
+        &state,
+    )
+    .expect("filesystem__replace should dispatch");
+    assert!(blocked.outcome.is_err());
+    let payload = outcome_payload(&blocked);
+    assert_eq!(payload["error_code"], json!("permission_denied"));
+    assert_eq!(
+        std::fs::read_to_string(root.join("scripts/run.sh")).expect("read script"),
+        "echo hi"
+    );
+
+    let _ = std::fs::remove_dir_all(&root);
+}
+
+#[test]
+fn fs_env_reports_workspace_missing_after_root_is_deleted() {
+    let root = unique_temp_dir("fathom-fs-workspace-missing");
+    std::fs::create_dir_all(&root).expect("create temp root");
+    let state = json!({ "base_path": root.display().to_string() });
+
+    let before = execute_action("list", r#"{"path":"."}"#, &state)
+        .expect("filesystem__list should dispatch");
+    assert!(before.outcome.is_ok());
+
+    std::fs::remove_dir_all(&root).expect("remove temp root");
+
+    for (action, args) in [
+        ("list", r#"{"path":"."}"#),
+        ("read", r#"{"path":"notes.txt"}"#),
+        (
+            "write",
+            r#"{"path":"notes.txt","content":"hello","allow_override":false}"#,
+        ),
+        ("get_base_path", "{}"),
+    ] {
+        let outcome = execute_action(action, args, &state).expect("action should dispatch");
+        assert!(outcome.outcome.is_err());
+        let payload = outcome_payload(&outcome);
+        assert_eq!(
+            payload["error_code"],
+            json!("workspace_missing"),
+            "{action}"
+        );
+        let message = payload["message"].as_str().unwrap_or_default();
+        assert!(!message.contains(&root.display().to_string()));
+    }
+    assert!(!root.exists());
+}
+
+#[test]
+fn fs_env_usage_reports_file_count_and_bytes_for_a_known_tree() {
+    let root = unique_temp_dir("fathom-fs-usage");
+    std::fs::create_dir_all(root.join("nested/deeper")).expect("create temp tree");
+    std::fs::write(root.join("a.txt"), "hello").expect("write a.txt");
+    std::fs::write(root.join("nested/b.txt"), "0123456789").expect("write b.txt");
+    std::fs::write(root.join("nested/deeper/c.bin"), [0_u8; 7]).expect("write c.bin");
+    std::fs::write(root.join(".hidden"), "secret").expect("write .hidden");
+    let state = json!({ "base_path": root.display().to_string() });
+
+    let outcome =
+        execute_action("usage", r#"{"path":"."}"#, &state).expect("fs_usage should dispatch");
+    let payload = outcome_payload(&outcome);
+    assert_eq!(payload["data"]["file_count"], json!(3));
+    assert_eq!(payload["data"]["directory_count"], json!(2));
+    assert_eq!(payload["data"]["total_bytes"], json!(22));
+    assert_eq!(payload["data"]["truncated"], json!(false));
+
+    let with_hidden = execute_action("usage", r#"{"path":".","include_hidden":true}"#, &state)
+        .expect("fs_usage should dispatch");
+    let payload = outcome_payload(&with_hidden);
+    assert_eq!(payload["data"]["file_count"], json!(4));
+    assert_eq!(payload["data"]["total_bytes"], json!(28));
+
+    let capped = execute_action("usage", r#"{"path":".","max_entries":2}"#, &state)
+        .expect("fs_usage should dispatch");
+    let payload = outcome_payload(&capped);
+    assert_eq!(payload["data"]["truncated"], json!(true));
+
+    let escaped =
+        execute_action("usage", r#"{"path":"../"}"#, &state).expect("fs_usage should dispatch");
+    assert!(escaped.outcome.is_err());
+
+    let _ = std::fs::remove_dir_all(&root);
+}
+
+#[test]
+fn fs_env_stat_reports_kind_size_and_missing_paths() {
+    let root = unique_temp_dir("fathom-fs-stat");
+    std::fs::create_dir_all(root.join("nested")).expect("create temp tree");
+    std::fs::write(root.join("a.txt"), "hello").expect("write a.txt");
+    let state = json!({ "base_path": root.display().to_string() });
+
+    let file =
+        execute_action("stat", r#"{"path":"a.txt"}"#, &state).expect("fs_stat should dispatch");
+    let payload = outcome_payload(&file);
+    assert_eq!(payload["data"]["exists"], json!(true));
+    assert_eq!(payload["data"]["kind"], json!("file"));
+    assert_eq!(payload["data"]["size_bytes"], json!(5));
+    assert!(
+        payload["data"]["modified_unix_ms"]
+            .as_i64()
+            .unwrap_or_default()
+            > 0
+    );
+
+    let dir =
+        execute_action("stat", r#"{"path":"nested"}"#, &state).expect("fs_stat should dispatch");
+    let payload = outcome_payload(&dir);
+    assert_eq!(payload["data"]["exists"], json!(true));
+    assert_eq!(payload["data"]["kind"], json!("dir"));
+
+    let missing = execute_action("stat", r#"{"path":"missing.txt"}"#, &state)
+        .expect("fs_stat should dispatch");
+    assert!(missing.outcome.is_ok());
+    assert_eq!(
+        outcome_payload(&missing)["data"],
+        json!({ "exists": false })
+    );
+
+    let escaped =
+        execute_action("stat", r#"{"path":"../"}"#, &state).expect("fs_stat should dispatch");
+    assert!(escaped.outcome.is_err());
+
+    let _ = std::fs::remove_dir_all(&root);
+}
+
+#[test]
+fn fs_env_delete_removes_files_and_directories_recursively() {
+    let root = unique_temp_dir("fathom-fs-delete");
+    std::fs::create_dir_all(root.join("build/cache")).expect("create temp tree");
+    std::fs::write(root.join("notes.txt"), "hello").expect("write notes.txt");
+    std::fs::write(root.join("build/cache/blob"), "data").expect("write blob");
+    let state = json!({ "base_path": root.display().to_string() });
+
+    let file = execute_action("delete", r#"{"path":"notes.txt"}"#, &state)
+        .expect("fs_delete should dispatch");
+    assert!(file.outcome.is_ok());
+    assert!(!root.join("notes.txt").exists());
+
+    let missing = execute_action("delete", r#"{"path":"notes.txt"}"#, &state)
+        .expect("fs_delete should dispatch");
+    assert!(missing.outcome.is_err());
+    assert_eq!(outcome_payload(&missing)["error_code"], json!("not_found"));
+    assert!(
+        outcome_payload(&missing)["message"]
+            .as_str()
+            .unwrap_or_default()
+            .contains("notes.txt")
+    );
+
+    let non_recursive =
+        execute_action("delete", r#"{"path":"build"}"#, &state).expect("fs_delete should dispatch");
+    assert!(non_recursive.outcome.is_err());
+    assert!(root.join("build/cache/blob").exists());
+
+    let recursive = execute_action("delete", r#"{"path":"build","recursive":true}"#, &state)
+        .expect("fs_delete should dispatch");
+    let payload = outcome_payload(&recursive);
+    assert_eq!(payload["data"]["kind"], json!("dir"));
+    assert!(!root.join("build").exists());
+    assert!(root.exists());
+
+    for path in [".", "../outside", "notes/../../outside"] {
+        let escaped = execute_action(
+            "delete",
+            &json!({ "path": path, "recursive": true }).to_string(),
+            &state,
+        )
+        .expect("fs_delete should dispatch");
+        assert!(escaped.outcome.is_err(), "{path}");
+        assert_eq!(
+            outcome_payload(&escaped)["error_code"],
+            json!("permission_denied"),
+            "{path}"
+        );
+    }
+    assert!(root.exists());
+
+    let _ = std::fs::remove_dir_all(&root);
+}
+
+#[test]
+fn fs_env_refuses_protected_paths_but_allows_siblings() {
+    let root = unique_temp_dir("fathom-fs-protected");
+    std::fs::create_dir_all(root.join("var/state")).expect("create state dir");
+    std::fs::write(root.join("var/state/session-1.jsonl"), "secret-token").expect("write state");
+    std::fs::write(root.join("notes.txt"), "secret-token").expect("write notes.txt");
+    let state = json!({
+        "base_path": root.display().to_string(),
+        "protected_paths": [root.join("var/state").display().to_string()],
+    });
+
+    let sibling =
+        execute_action("read", r#"{"path":"notes.txt"}"#, &state).expect("fs_read should dispatch");
+    assert!(sibling.outcome.is_ok());
+
+    for (action, args) in [
+        ("read", json!({ "path": "var/state/session-1.jsonl" })),
+        (
+            "read",
+            json!({ "path": "notes/../var/state/session-1.jsonl" }),
+        ),
+        ("list", json!({ "path": "var/state" })),
+        (
+            "write",
+            json!({ "path": "var/state/session-2.jsonl", "content": "x", "allow_override": false }),
+        ),
+        ("delete", json!({ "path": "var", "recursive": true })),
+    ] {
+        let result =
+            execute_action(action, &args.to_string(), &state).expect("fs action should dispatch");
+        assert!(result.outcome.is_err(), "{action} {args}");
+        assert_eq!(
+            outcome_payload(&result)["error_code"],
+            json!("permission_denied"),
+            "{action} {args}"
+        );
+    }
+    assert!(!root.join("var/state/session-2.jsonl").exists());
+
+    let search = execute_action("search", r#"{"pattern":"secret-token","path":"."}"#, &state)
+        .expect("fs_search should dispatch");
+    let payload = outcome_payload(&search);
+    let matches = payload["data"]["matches"]
+        .as_array()
+        .expect("matches array");
+    assert_eq!(matches.len(), 1);
+    assert_eq!(matches[0]["path"], json!("notes.txt"));
+
+    let _ = std::fs::remove_dir_all(&root);
+}
+
+#[test]
+fn fs_env_walks_leave_out_protected_paths() {
+    let root = unique_temp_dir("fathom-fs-walk-protected");
+    std::fs::create_dir_all(root.join("var/state")).expect("create state dir");
+    std::fs::write(root.join("var/state/session-1.jsonl"), "secret-token").expect("write state");
+    std::fs::write(root.join("var/audit.log"), "audit").expect("write audit log");
+    std::fs::write(root.join("var/notes.txt"), "notes").expect("write notes");
+    let state = json!({
+        "base_path": root.display().to_string(),
+        "protected_paths": [
+            root.join("var/state").display().to_string(),
+            root.join("var/audit.log").display().to_string(),
+        ],
+    });
+
+    let list = execute_action("list", r#"{"path":".","recursive":true}"#, &state)
+        .expect("fs_list should dispatch");
+    let listed = outcome_payload(&list)["data"]["entries"]
+        .as_array()
+        .expect("entries array")
+        .iter()
+        .filter_map(|entry| entry["path"].as_str().map(str::to_string))
+        .collect::<Vec<_>>();
+    assert_eq!(listed, vec!["var", "var/notes.txt"]);
+
+    let glob = execute_action("glob", r#"{"pattern":"**/*","path":"."}"#, &state)
+        .expect("fs_glob should dispatch");
+    assert_eq!(
+        outcome_payload(&glob)["data"]["matches"],
+        json!(["var/notes.txt"])
+    );
+
+    let usage =
+        execute_action("usage", r#"{"path":"."}"#, &state).expect("fs_usage should dispatch");
+    let payload = outcome_payload(&usage);
+    assert_eq!(payload["data"]["file_count"], json!(1));
+    assert_eq!(payload["data"]["directory_count"], json!(1));
+    assert_eq!(payload["data"]["total_bytes"], json!(5));
+
+    let _ = std::fs::remove_dir_all(&root);
+}
+
+#[test]
+fn fs_env_move_refuses_directories_that_contain_protected_paths() {
+    let root = unique_temp_dir("fathom-fs-move-protected");
+    std::fs::create_dir_all(root.join("var/state")).expect("create state dir");
+    std::fs::write(root.join("var/state/session-1.jsonl"), "secret-token").expect("write state");
+    let state = json!({
+        "base_path": root.display().to_string(),
+        "protected_paths": [root.join("var/state").display().to_string()],
+    });
+
+    let moved = execute_action(
+        "move",
+        r#"{"src":"var","dst":"exposed","allow_override":false}"#,
+        &state,
+    )
+    .expect("fs_move should dispatch");
+    assert_eq!(
+        outcome_payload(&moved)["error_code"],
+        json!("permission_denied")
+    );
+    assert!(root.join("var/state/session-1.jsonl").exists());
+    assert!(!root.join("exposed").exists());
+
+    let _ = std::fs::remove_dir_all(&root);
+}
+
+#[test]
+fn fs_env_move_renames_across_directories_and_rejects_escapes() {
+    let root = unique_temp_dir("fathom-fs-move");
+    std::fs::create_dir_all(root.join("drafts")).expect("create drafts");
+    std::fs::create_dir_all(root.join("published")).expect("create published");
+    std::fs::write(root.join("drafts/post.md"), "hello").expect("write post");
+    std::fs::write(root.join("published/existing.md"), "old").expect("write existing");
+    let state = json!({ "base_path": root.display().to_string() });
+
+    let moved = execute_action(
+        "move",
+        r#"{"src":"drafts/post.md","dst":"published/post.md","allow_override":false}"#,
+        &state,
+    )
+    .expect("fs_move should dispatch");
+    let payload = outcome_payload(&moved);
+    assert_eq!(payload["data"]["src"], json!("drafts/post.md"));
+    assert_eq!(payload["data"]["dst"], json!("published/post.md"));
+    assert_eq!(payload["data"]["bytes"], json!(5));
+    assert!(!root.join("drafts/post.md").exists());
+    assert_eq!(
+        std::fs::read_to_string(root.join("published/post.md")).expect("moved file"),
+        "hello"
+    );
+
+    let same = execute_action(
+        "move",
+        r#"{"src":"published/post.md","dst":"./published/post.md","allow_override":false}"#,
+        &state,
+    )
+    .expect("fs_move should dispatch");
+    assert!(same.outcome.is_ok());
+    assert!(root.join("published/post.md").exists());
+
+    let blocked = execute_action(
+        "move",
+        r#"{"src":"published/post.md","dst":"published/existing.md","allow_override":false}"#,
+        &state,
+    )
+    .expect("fs_move should dispatch");
+    assert_eq!(
+        outcome_payload(&blocked)["error_code"],
+        json!("already_exists")
+    );
+    assert_eq!(
+        std::fs::read_to_string(root.join("published/existing.md")).expect("existing"),
+        "old"
+    );
+
+    for (src, dst) in [
+        ("published/post.md", "../post.md"),
+        ("../outside.md", "published/outside.md"),
+        (".", "renamed"),
+    ] {
+        let escaped = execute_action(
+            "move",
+            &json!({ "src": src, "dst": dst, "allow_override": true }).to_string(),
+            &state,
+        )
+        .expect("fs_move should dispatch");
+        assert_eq!(
+            outcome_payload(&escaped)["error_code"],
+            json!("permission_denied"),
+            "{src} -> {dst}"
+        );
+    }
+    assert!(root.join("published/post.md").exists());
+
+    let _ = std::fs::remove_dir_all(&root);
+}
+
+#[test]
+fn fs_env_write_rejects_over_long_lines_only_when_enabled() {
+    let root = unique_temp_dir("fathom-fs-line-length");
+    std::fs::create_dir_all(&root).expect("create temp root");
+    let runaway = "x".repeat(200);
+    let args =
+        json!({ "path": "runaway.txt", "content": runaway, "allow_override": true }).to_string();
+
+    let limited = json!({ "base_path": root.display().to_string(), "max_write_line_length": 120 });
+    let rejected = execute_action("write", &args, &limited).expect("fs_write should dispatch");
+    let payload = outcome_payload(&rejected);
+    assert_eq!(payload["error_code"], json!("invalid_args"));
+    assert!(
+        payload["message"]
+            .as_str()
+            .is_some_and(|message| message.contains("line 1 is 200 characters"))
+    );
+    assert!(!root.join("runaway.txt").exists());
+
+    let short_lines = json!({
+        "path": "ok.txt",
+        "content": format!("{}\n{}", "y".repeat(120), "z".repeat(10)),
+        "allow_override": true
+    })
+    .to_string();
+    let accepted =
+        execute_action("write", &short_lines, &limited).expect("fs_write should dispatch");
+    assert!(accepted.outcome.is_ok());
+
+    let unlimited = json!({ "base_path": root.display().to_string() });
+    let written = execute_action("write", &args, &unlimited).expect("fs_write should dispatch");
+    assert!(written.outcome.is_ok());
+    assert!(root.join("runaway.txt").exists());
+
+    let _ = std::fs::remove_dir_all(&root);
+}
+
+#[cfg(unix)]
+#[test]
+fn fs_env_list_skips_non_utf8_names_with_a_diagnostic() {
+    use std::os::unix::ffi::OsStrExt;
+
+    let root = unique_temp_dir("fathom-fs-list-non-utf8");
+    std::fs::create_dir_all(&root).expect("create temp root");
+    let raw_name = std::ffi::OsStr::from_bytes(b"bad\xffname.txt");
+    if std::fs::write(root.join(raw_name), "opaque").is_err() {
+        let _ = std::fs::remove_dir_all(&root);
+        return;
+    }
+    std::fs::write(root.join("good.txt"), "fine").expect("write utf8 file");
+
+    let state = json!({ "base_path": root.display().to_string() });
+    let listed =
+        execute_action("list", r#"{"path":"."}"#, &state).expect("fs_list should dispatch");
+    let payload = outcome_payload(&listed);
+    let names = payload["data"]["entries"]
+        .as_array()
+        .expect("entries array")
+        .iter()
+        .filter_map(|entry| entry["name"].as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(names, vec!["good.txt"]);
+    assert_eq!(payload["data"]["total_entries"], json!(1));
+    assert_eq!(
+        payload["data"]["skipped_non_utf8"],
+        json!(["bad\u{fffd}name.txt"])
+    );
+
+    for entry in payload["data"]["entries"]
+        .as_array()
+        .expect("entries array")
+    {
+        let args = json!({ "path": entry["path"] }).to_string();
+        let read = execute_action("read", &args, &state).expect("fs_read should dispatch");
+        assert!(read.outcome.is_ok());
+    }
+
+    let _ = std::fs::remove_dir_all(&root);
+}
+
 fn unique_temp_dir(prefix: &str) -> std::path::PathBuf {
     let nanos = std::time::SystemTime::now()
         .duration_since(std::time::UNIX_EPOCH)
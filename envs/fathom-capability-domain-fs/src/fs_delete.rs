@@ -0,0 +1,21 @@
+use fathom_capability_domain::{CapabilityActionDefinition, CapabilityActionKey};
+use serde_json::json;
+
+pub(crate) const FS_DELETE_ACTION_KEY: CapabilityActionKey = CapabilityActionKey(9);
+
+pub(crate) fn definition() -> CapabilityActionDefinition {
+    CapabilityActionDefinition {
+        key: FS_DELETE_ACTION_KEY,
+        action_name: "delete",
+        description: "Delete a file or directory at a non-empty relative path under the current base path. Directories must be empty unless `recursive` is true; the base path itself cannot be deleted.",
+        input_schema: json!({
+            "type": "object",
+            "properties": {
+                "path": { "type": "string" },
+                "recursive": { "type": "boolean" }
+            },
+            "required": ["path"],
+            "additionalProperties": false
+        }),
+    }
+}
@@ -0,0 +1,22 @@
+use fathom_capability_domain::{CapabilityActionDefinition, CapabilityActionKey};
+use serde_json::json;
+
+pub(crate) const FS_MOVE_ACTION_KEY: CapabilityActionKey = CapabilityActionKey(10);
+
+pub(crate) fn definition() -> CapabilityActionDefinition {
+    CapabilityActionDefinition {
+        key: FS_MOVE_ACTION_KEY,
+        action_name: "move",
+        description: "Rename or move a file or directory from `src` to `dst`, both relative to the current base path. The destination parent must exist; `allow_override` controls whether an existing destination file may be replaced.",
+        input_schema: json!({
+            "type": "object",
+            "properties": {
+                "src": { "type": "string" },
+                "dst": { "type": "string" },
+                "allow_override": { "type": "boolean" }
+            },
+            "required": ["src", "dst", "allow_override"],
+            "additionalProperties": false
+        }),
+    }
+}
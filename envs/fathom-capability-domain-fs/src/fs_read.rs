@@ -7,13 +7,16 @@ pub(crate) fn definition() -> CapabilityActionDefinition {
     CapabilityActionDefinition {
         key: FS_READ_ACTION_KEY,
         action_name: "read",
-        description: "Read UTF-8 text from a relative file path under the current base path. Supports line-windowed reads for large files.",
+        description: "Read a file from a relative path under the current base path. UTF-8 text supports line-windowed reads, or byte-range reads with `offset` and `length` (snapped to UTF-8 character boundaries, clamped at end of file); files that are not valid UTF-8 (or any file when `encoding` is `base64`) are returned as `content_base64`, at most 64 KiB per call by default, with `truncated` and `total_bytes` reporting what is left; page through the rest with `offset` and `length`. Set `encoding` to `utf8` to fail on non-UTF-8 files instead.",
         input_schema: json!({
             "type": "object",
             "properties": {
                 "path": { "type": "string" },
                 "offset_line": { "type": "integer", "minimum": 1 },
-                "limit_lines": { "type": "integer", "minimum": 1 }
+                "limit_lines": { "type": "integer", "minimum": 1 },
+                "offset": { "type": "integer", "minimum": 0 },
+                "length": { "type": "integer", "minimum": 1 },
+                "encoding": { "type": "string", "enum": ["utf8", "base64"] }
             },
             "required": ["path"],
             "additionalProperties": false
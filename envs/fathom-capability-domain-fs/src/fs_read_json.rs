@@ -0,0 +1,20 @@
+use fathom_capability_domain::{CapabilityActionDefinition, CapabilityActionKey};
+use serde_json::json;
+
+pub(crate) const FS_READ_JSON_ACTION_KEY: CapabilityActionKey = CapabilityActionKey(7);
+
+pub(crate) fn definition() -> CapabilityActionDefinition {
+    CapabilityActionDefinition {
+        key: FS_READ_JSON_ACTION_KEY,
+        action_name: "read_json",
+        description: "Read a UTF-8 JSON file from a relative path under the current base path. Returns the parsed JSON value together with the raw text, or `invalid_args` when the file is not valid JSON.",
+        input_schema: json!({
+            "type": "object",
+            "properties": {
+                "path": { "type": "string" }
+            },
+            "required": ["path"],
+            "additionalProperties": false
+        }),
+    }
+}
@@ -0,0 +1,20 @@
+use fathom_capability_domain::{CapabilityActionDefinition, CapabilityActionKey};
+use serde_json::json;
+
+pub(crate) const FS_STAT_ACTION_KEY: CapabilityActionKey = CapabilityActionKey(11);
+
+pub(crate) fn definition() -> CapabilityActionDefinition {
+    CapabilityActionDefinition {
+        key: FS_STAT_ACTION_KEY,
+        action_name: "stat",
+        description: "Report metadata for a relative path under the current base path without reading its content: whether it exists, its kind (`file`, `dir`, or `other`), its size in bytes, and its modification time. A missing path succeeds with `exists: false`.",
+        input_schema: json!({
+            "type": "object",
+            "properties": {
+                "path": { "type": "string" }
+            },
+            "required": ["path"],
+            "additionalProperties": false
+        }),
+    }
+}
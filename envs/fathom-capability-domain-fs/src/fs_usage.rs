@@ -0,0 +1,22 @@
+use fathom_capability_domain::{CapabilityActionDefinition, CapabilityActionKey};
+use serde_json::json;
+
+pub(crate) const FS_USAGE_ACTION_KEY: CapabilityActionKey = CapabilityActionKey(8);
+
+pub(crate) fn definition() -> CapabilityActionDefinition {
+    CapabilityActionDefinition {
+        key: FS_USAGE_ACTION_KEY,
+        action_name: "usage",
+        description: "Report the total file count and byte size under a relative path in the current base path; use `.` for the whole workspace. Symlinks are not followed and the walk stops after a bounded number of entries.",
+        input_schema: json!({
+            "type": "object",
+            "properties": {
+                "path": { "type": "string" },
+                "max_entries": { "type": "integer", "minimum": 1 },
+                "include_hidden": { "type": "boolean" }
+            },
+            "required": ["path"],
+            "additionalProperties": false
+        }),
+    }
+}
@@ -7,16 +7,17 @@ pub(crate) fn definition() -> CapabilityActionDefinition {
     CapabilityActionDefinition {
         key: FS_WRITE_ACTION_KEY,
         action_name: "write",
-        description: "Create or overwrite a UTF-8 text file at a relative path under the current base path. `allow_override` controls whether an existing file may be replaced.",
+        description: "Create or overwrite a file at a relative path under the current base path. Pass UTF-8 text as `content`, or binary data as `content_base64`; exactly one is required. `allow_override` controls whether an existing file may be replaced.",
         input_schema: json!({
             "type": "object",
             "properties": {
                 "path": { "type": "string" },
                 "content": { "type": "string" },
+                "content_base64": { "type": "string" },
                 "allow_override": { "type": "boolean" },
                 "create_parents": { "type": "boolean" }
             },
-            "required": ["path", "content", "allow_override"],
+            "required": ["path", "allow_override"],
             "additionalProperties": false
         }),
     }
@@ -1,11 +1,19 @@
 mod execute;
+mod fs_delete;
 mod fs_get_base_path;
 mod fs_glob;
 mod fs_list;
+mod fs_move;
 mod fs_read;
+mod fs_read_json;
 mod fs_replace;
 mod fs_search;
+mod fs_stat;
+mod fs_usage;
 mod fs_write;
+mod list_limit;
+mod protected_paths;
+mod write_policy;
 
 use std::path::PathBuf;
 use std::time::Instant;
@@ -19,14 +27,47 @@ use serde_json::{Value, json};
 
 pub const FILESYSTEM_CAPABILITY_DOMAIN_ID: &str = "filesystem";
 pub use execute::execute_action;
+pub use list_limit::ListMaxEntries;
+pub use protected_paths::ProtectedPaths;
+pub use write_policy::WriteExtensionDenylist;
 
 pub struct FilesystemDomainFactory {
     base_path: PathBuf,
+    write_extension_denylist: WriteExtensionDenylist,
+    protected_paths: ProtectedPaths,
+    list_max_entries: ListMaxEntries,
+    max_write_line_length: Option<usize>,
 }
 
 impl FilesystemDomainFactory {
     pub fn new(base_path: PathBuf) -> Self {
-        Self { base_path }
+        Self {
+            base_path,
+            write_extension_denylist: WriteExtensionDenylist::default(),
+            protected_paths: ProtectedPaths::default(),
+            list_max_entries: ListMaxEntries::default(),
+            max_write_line_length: None,
+        }
+    }
+
+    pub fn with_write_extension_denylist(mut self, denylist: WriteExtensionDenylist) -> Self {
+        self.write_extension_denylist = denylist;
+        self
+    }
+
+    pub fn with_protected_paths(mut self, protected_paths: ProtectedPaths) -> Self {
+        self.protected_paths = protected_paths;
+        self
+    }
+
+    pub fn with_list_max_entries(mut self, list_max_entries: ListMaxEntries) -> Self {
+        self.list_max_entries = list_max_entries;
+        self
+    }
+
+    pub fn with_max_write_line_length(mut self, max_line_length: Option<usize>) -> Self {
+        self.max_write_line_length = max_line_length.filter(|value| *value > 0);
+        self
     }
 }
 
@@ -35,7 +76,7 @@ impl DomainFactory for FilesystemDomainFactory {
         CapabilityDomainSpec {
             id: FILESYSTEM_CAPABILITY_DOMAIN_ID,
             name: "Filesystem",
-            description: "Workspace-scoped filesystem capability domain rooted at a base path. Operates on non-empty relative paths under `base_path`; `read`, `replace`, and `search` work on UTF-8 text content.",
+            description: "Workspace-scoped filesystem capability domain rooted at a base path. Operates on non-empty relative paths under `base_path`; `read`, `read_json`, `replace`, and `search` work on UTF-8 text content.",
             schema_version: 1,
         }
     }
@@ -45,10 +86,15 @@ impl DomainFactory for FilesystemDomainFactory {
             fs_get_base_path::definition(),
             fs_list::definition(),
             fs_read::definition(),
+            fs_read_json::definition(),
             fs_write::definition(),
             fs_replace::definition(),
+            fs_delete::definition(),
+            fs_move::definition(),
             fs_glob::definition(),
             fs_search::definition(),
+            fs_usage::definition(),
+            fs_stat::definition(),
         ]
     }
 
@@ -56,7 +102,13 @@ impl DomainFactory for FilesystemDomainFactory {
         &self,
         _session_context: CapabilityDomainSessionContext,
     ) -> Box<dyn DomainInstance> {
-        Box::new(FilesystemDomainInstance::new(self.base_path.clone()))
+        Box::new(FilesystemDomainInstance::new(
+            self.base_path.clone(),
+            self.write_extension_denylist.clone(),
+            self.protected_paths.clone(),
+            self.list_max_entries.clone(),
+            self.max_write_line_length,
+        ))
     }
 
     fn recipes(&self) -> Vec<CapabilityDomainRecipe> {
@@ -67,8 +119,11 @@ impl DomainFactory for FilesystemDomainFactory {
                     "Use `filesystem__get_base_path` when you need to inspect the current filesystem root for this domain.".to_string(),
                     "Do not use empty path values; use path '.' to target the root directory.".to_string(),
                     "Use `filesystem__list` with `path: \".\"` or a relative directory to discover entries under the current base path.".to_string(),
+                    "Use `filesystem__stat` to check whether a path exists, its kind, and its size before reading or overwriting it.".to_string(),
                     "Use `filesystem__read` on a specific relative file path once you know the target.".to_string(),
-                    "For large files, set `offset_line` and `limit_lines` to inspect only the relevant window.".to_string(),
+                    "For large files, set `offset_line` and `limit_lines` to inspect only the relevant window, or `offset` and `length` to page through it by bytes.".to_string(),
+                    "Use `filesystem__read_json` for JSON files when you need a confirmed-valid parsed value instead of raw text.".to_string(),
+                    "Use `filesystem__usage` with `path: \".\"` to check how many files and bytes the workspace holds.".to_string(),
                     "If a text action returns `invalid_encoding`, treat the target as non-UTF-8 content and stop using text-only actions on it.".to_string(),
                 ],
             },
@@ -99,22 +154,65 @@ impl DomainFactory for FilesystemDomainFactory {
                     "Use `filesystem__read` after writing when the final content must be verified.".to_string(),
                 ],
             },
+            CapabilityDomainRecipe {
+                title: "Rename or move files".to_string(),
+                steps: vec![
+                    "Call `filesystem__move` with the current path in `src` and the new path in `dst`.".to_string(),
+                    "Create the destination directory first; `move` does not create parents.".to_string(),
+                    "Set `allow_override` to true only when an existing destination file should be replaced.".to_string(),
+                ],
+            },
+            CapabilityDomainRecipe {
+                title: "Remove files or directories".to_string(),
+                steps: vec![
+                    "Use `filesystem__list` first to confirm the exact relative path to remove.".to_string(),
+                    "Call `filesystem__delete` on a file or an empty directory.".to_string(),
+                    "Set `recursive` to true only when a directory and everything under it should be removed.".to_string(),
+                ],
+            },
         ]
     }
 }
 
 struct FilesystemDomainInstance {
-    state: Value,
+    base_path: PathBuf,
+    write_extension_denylist: WriteExtensionDenylist,
+    protected_paths: ProtectedPaths,
+    list_max_entries: ListMaxEntries,
+    max_write_line_length: Option<usize>,
 }
 
 impl FilesystemDomainInstance {
-    fn new(base_path: PathBuf) -> Self {
+    fn new(
+        base_path: PathBuf,
+        write_extension_denylist: WriteExtensionDenylist,
+        protected_paths: ProtectedPaths,
+        list_max_entries: ListMaxEntries,
+        max_write_line_length: Option<usize>,
+    ) -> Self {
         Self {
-            state: json!({
-                "base_path": base_path.to_string_lossy().to_string(),
-            }),
+            base_path,
+            write_extension_denylist,
+            protected_paths,
+            list_max_entries,
+            max_write_line_length,
         }
     }
+
+    fn state(&self) -> Value {
+        json!({
+            "base_path": self.base_path.to_string_lossy().to_string(),
+            "blocked_write_extensions": self.write_extension_denylist.extensions(),
+            "protected_paths": self
+                .protected_paths
+                .paths()
+                .iter()
+                .map(|path| path.to_string_lossy().to_string())
+                .collect::<Vec<_>>(),
+            "list_max_entries": self.list_max_entries.get(),
+            "max_write_line_length": self.max_write_line_length,
+        })
+    }
 }
 
 impl DomainInstance for FilesystemDomainInstance {
@@ -123,9 +221,10 @@ impl DomainInstance for FilesystemDomainInstance {
         submissions: Vec<CapabilityActionSubmission>,
     ) -> DomainInstanceFuture<'a> {
         Box::pin(async move {
+            let state = self.state();
             submissions
                 .into_iter()
-                .map(|submission| execute_submission(&self.state, submission))
+                .map(|submission| execute_submission(&state, submission))
                 .collect()
         })
     }
@@ -179,10 +278,15 @@ fn action_name_for_key(key: fathom_capability_domain::CapabilityActionKey) -> Op
         fs_get_base_path::FS_GET_BASE_PATH_ACTION_KEY => Some("get_base_path"),
         fs_list::FS_LIST_ACTION_KEY => Some("list"),
         fs_read::FS_READ_ACTION_KEY => Some("read"),
+        fs_read_json::FS_READ_JSON_ACTION_KEY => Some("read_json"),
         fs_write::FS_WRITE_ACTION_KEY => Some("write"),
         fs_replace::FS_REPLACE_ACTION_KEY => Some("replace"),
+        fs_delete::FS_DELETE_ACTION_KEY => Some("delete"),
+        fs_move::FS_MOVE_ACTION_KEY => Some("move"),
         fs_glob::FS_GLOB_ACTION_KEY => Some("glob"),
         fs_search::FS_SEARCH_ACTION_KEY => Some("search"),
+        fs_usage::FS_USAGE_ACTION_KEY => Some("usage"),
+        fs_stat::FS_STAT_ACTION_KEY => Some("stat"),
         _ => None,
     }
 }
@@ -194,7 +298,7 @@ mod tests {
     use std::sync::Arc;
     use std::task::{Context, Poll, Wake, Waker};
 
-    use super::{FilesystemDomainFactory, fs_list};
+    use super::{FilesystemDomainFactory, WriteExtensionDenylist, fs_list, fs_write};
     use fathom_capability_domain::{
         CapabilityActionSubmission, CapabilityDomainSessionContext, DomainFactory,
     };
@@ -218,6 +322,36 @@ mod tests {
         assert!(results[0].outcome.is_ok());
     }
 
+    #[test]
+    fn filesystem_factory_instance_applies_write_extension_denylist() {
+        let root = std::env::temp_dir().join(format!(
+            "fathom-fs-factory-denylist-{}",
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .expect("clock should be after epoch")
+                .as_nanos()
+        ));
+        std::fs::create_dir_all(&root).expect("create temp root");
+        let denylist = WriteExtensionDenylist::default();
+        let mut instance = FilesystemDomainFactory::new(root.clone())
+            .with_write_extension_denylist(denylist.clone())
+            .create_instance(CapabilityDomainSessionContext {
+                session_id: "session-test".to_string(),
+            });
+
+        denylist.replace([".sh"]);
+        let results = block_on(instance.execute_actions(vec![CapabilityActionSubmission {
+            action_key: fs_write::FS_WRITE_ACTION_KEY,
+            args: json!({ "path": "run.sh", "content": "echo hi", "allow_override": false }),
+        }]));
+
+        assert_eq!(results.len(), 1);
+        assert!(results[0].outcome.is_err());
+        assert!(!root.join("run.sh").exists());
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
     fn block_on<F>(future: F) -> F::Output
     where
         F: Future,
@@ -0,0 +1,36 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::execute::{LIST_DEFAULT_MAX_ENTRIES, LIST_MAX_ENTRIES_CAP};
+
+#[derive(Debug, Clone)]
+pub struct ListMaxEntries {
+    max_entries: Arc<AtomicUsize>,
+}
+
+impl Default for ListMaxEntries {
+    fn default() -> Self {
+        Self {
+            max_entries: Arc::new(AtomicUsize::new(LIST_DEFAULT_MAX_ENTRIES)),
+        }
+    }
+}
+
+impl ListMaxEntries {
+    pub fn new(max_entries: usize) -> Self {
+        let limit = Self::default();
+        limit.set(max_entries);
+        limit
+    }
+
+    pub fn set(&self, max_entries: usize) {
+        self.max_entries.store(
+            max_entries.clamp(1, LIST_MAX_ENTRIES_CAP),
+            Ordering::Relaxed,
+        );
+    }
+
+    pub fn get(&self) -> usize {
+        self.max_entries.load(Ordering::Relaxed)
+    }
+}
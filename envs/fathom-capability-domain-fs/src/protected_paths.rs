@@ -0,0 +1,45 @@
+use std::collections::BTreeSet;
+use std::path::PathBuf;
+use std::sync::{Arc, RwLock};
+
+#[derive(Debug, Clone, Default)]
+pub struct ProtectedPaths {
+    paths: Arc<RwLock<BTreeSet<PathBuf>>>,
+}
+
+impl ProtectedPaths {
+    pub fn new<I, P>(paths: I) -> Self
+    where
+        I: IntoIterator<Item = P>,
+        P: Into<PathBuf>,
+    {
+        let protected = Self::default();
+        protected.replace(paths);
+        protected
+    }
+
+    pub fn replace<I, P>(&self, paths: I)
+    where
+        I: IntoIterator<Item = P>,
+        P: Into<PathBuf>,
+    {
+        let paths = paths
+            .into_iter()
+            .map(Into::into)
+            .filter(|path| !path.as_os_str().is_empty())
+            .collect::<BTreeSet<_>>();
+        *self
+            .paths
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner()) = paths;
+    }
+
+    pub fn paths(&self) -> Vec<PathBuf> {
+        self.paths
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .iter()
+            .cloned()
+            .collect()
+    }
+}
@@ -0,0 +1,48 @@
+use std::collections::BTreeSet;
+use std::sync::{Arc, RwLock};
+
+#[derive(Debug, Clone, Default)]
+pub struct WriteExtensionDenylist {
+    extensions: Arc<RwLock<BTreeSet<String>>>,
+}
+
+impl WriteExtensionDenylist {
+    pub fn new<I, S>(extensions: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let denylist = Self::default();
+        denylist.replace(extensions);
+        denylist
+    }
+
+    pub fn replace<I, S>(&self, extensions: I)
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let normalized = extensions
+            .into_iter()
+            .filter_map(|extension| normalize_extension(extension.as_ref()))
+            .collect::<BTreeSet<_>>();
+        *self
+            .extensions
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner()) = normalized;
+    }
+
+    pub fn extensions(&self) -> Vec<String> {
+        self.extensions
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .iter()
+            .cloned()
+            .collect()
+    }
+}
+
+pub(crate) fn normalize_extension(raw: &str) -> Option<String> {
+    let extension = raw.trim().trim_start_matches('.').to_ascii_lowercase();
+    (!extension.is_empty()).then_some(extension)
+}
@@ -1,4 +1,6 @@
 mod execute;
+mod export;
+mod fuzzy;
 mod heartbeat;
 mod parse;
 mod registry;
@@ -6,5 +8,5 @@ mod spec;
 
 pub(crate) use execute::{SlashExecution, execute_slash_command};
 pub(crate) use parse::completion_query;
-pub(crate) use registry::completion_items;
+pub(crate) use registry::{completion_items, palette_items};
 pub(crate) use spec::CommandSpec;
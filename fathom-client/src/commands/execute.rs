@@ -1,5 +1,6 @@
-use crate::runtime::ClientSession;
+use crate::runtime::{ClientConfig, ClientSession};
 
+use super::export;
 use super::heartbeat;
 use super::parse::parse_slash_command;
 use super::registry::{CommandId, resolve};
@@ -15,7 +16,7 @@ pub(crate) enum SlashExecution {
 
 pub(crate) async fn execute_slash_command(
     input: &str,
-    server: &str,
+    config: &ClientConfig,
     session: &ClientSession,
 ) -> SlashExecution {
     let Some(parsed) = parse_slash_command(input) else {
@@ -32,7 +33,7 @@ pub(crate) async fn execute_slash_command(
 
     match command {
         CommandId::Heartbeat => {
-            match heartbeat::execute(server, &session.session_id, &parsed.args).await {
+            match heartbeat::execute(config, &session.session_id, &parsed.args).await {
                 Ok(trigger_id) => SlashExecution::Handled {
                     status: format!("heartbeat queued ({trigger_id})"),
                     local_log: Some(format!("[local] heartbeat queued id={trigger_id}")),
@@ -40,6 +41,14 @@ pub(crate) async fn execute_slash_command(
                 Err(error) => local_error(format!("heartbeat failed: {error}")),
             }
         }
+        CommandId::Export => match export::execute(config, &session.session_id, &parsed.args).await
+        {
+            Ok(path) => SlashExecution::Handled {
+                status: format!("history exported ({})", path.display()),
+                local_log: Some(format!("[local] history exported path={}", path.display())),
+            },
+            Err(error) => local_error(format!("export failed: {error}")),
+        },
     }
 }
 
@@ -54,7 +63,7 @@ fn local_error(message: impl Into<String>) -> SlashExecution {
 #[cfg(test)]
 mod tests {
     use super::{SlashExecution, execute_slash_command};
-    use crate::runtime::ClientSession;
+    use crate::runtime::{ClientConfig, ClientSession};
 
     fn test_session() -> ClientSession {
         ClientSession {
@@ -66,13 +75,23 @@ mod tests {
 
     #[tokio::test]
     async fn not_slash_input_is_not_handled() {
-        let execution = execute_slash_command("hello", "http://127.0.0.1:1", &test_session()).await;
+        let execution = execute_slash_command(
+            "hello",
+            &ClientConfig::new("http://127.0.0.1:1"),
+            &test_session(),
+        )
+        .await;
         assert_eq!(execution, SlashExecution::NotSlashInput);
     }
 
     #[tokio::test]
     async fn reports_missing_command_name() {
-        let execution = execute_slash_command("/", "http://127.0.0.1:1", &test_session()).await;
+        let execution = execute_slash_command(
+            "/",
+            &ClientConfig::new("http://127.0.0.1:1"),
+            &test_session(),
+        )
+        .await;
         let SlashExecution::Handled { status, local_log } = execution else {
             panic!("expected handled command result");
         };
@@ -85,7 +104,12 @@ mod tests {
 
     #[tokio::test]
     async fn reports_unknown_command() {
-        let execution = execute_slash_command("/hb", "http://127.0.0.1:1", &test_session()).await;
+        let execution = execute_slash_command(
+            "/hb",
+            &ClientConfig::new("http://127.0.0.1:1"),
+            &test_session(),
+        )
+        .await;
         let SlashExecution::Handled { status, local_log } = execution else {
             panic!("expected handled command result");
         };
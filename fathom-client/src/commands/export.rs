@@ -0,0 +1,102 @@
+use std::path::PathBuf;
+
+use anyhow::{Result, anyhow};
+
+use crate::runtime::{ClientConfig, get_history};
+use crate::util::now_unix_ms;
+use fathom_protocol::pb;
+
+use super::spec::CommandSpec;
+
+const EXPORT_PAGE_SIZE: u64 = 1000;
+
+pub(crate) const SPEC: CommandSpec = CommandSpec {
+    name: "export",
+    description: "write session history to a local NDJSON file",
+};
+
+pub(crate) async fn execute(
+    config: &ClientConfig,
+    session_id: &str,
+    args: &str,
+) -> Result<PathBuf> {
+    let path = export_path(session_id, args, now_unix_ms());
+    let entries = get_history(config, session_id, 0, EXPORT_PAGE_SIZE).await?;
+    let contents = history_ndjson(&entries);
+
+    let write_path = path.clone();
+    tokio::task::spawn_blocking(move || std::fs::write(write_path, contents))
+        .await
+        .map_err(|error| anyhow!("export task failed: {error}"))??;
+    Ok(path)
+}
+
+fn export_path(session_id: &str, args: &str, timestamp_unix_ms: i64) -> PathBuf {
+    if args.is_empty() {
+        PathBuf::from(format!(
+            "./fathom-export-{session_id}-{timestamp_unix_ms}.jsonl"
+        ))
+    } else {
+        PathBuf::from(args)
+    }
+}
+
+fn history_ndjson(entries: &[pb::HistoryEntry]) -> String {
+    entries
+        .iter()
+        .map(|entry| {
+            let event = serde_json::from_str::<serde_json::Value>(&entry.event_json)
+                .unwrap_or_else(|_| serde_json::Value::String(entry.event_json.clone()));
+            let line = serde_json::json!({
+                "index": entry.index,
+                "created_at_unix_ms": entry.created_at_unix_ms,
+                "event": event,
+            });
+            format!("{line}\n")
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::{export_path, history_ndjson};
+    use fathom_protocol::pb;
+
+    #[test]
+    fn export_path_defaults_to_session_and_timestamp() {
+        assert_eq!(
+            export_path("session-1", "", 42),
+            PathBuf::from("./fathom-export-session-1-42.jsonl")
+        );
+        assert_eq!(
+            export_path("session-1", "out/history.jsonl", 42),
+            PathBuf::from("out/history.jsonl")
+        );
+    }
+
+    #[test]
+    fn history_ndjson_writes_one_object_per_entry() {
+        let ndjson = history_ndjson(&[
+            pb::HistoryEntry {
+                index: 3,
+                created_at_unix_ms: 10,
+                event_json: r#"{"event":"assistant_output"}"#.to_string(),
+            },
+            pb::HistoryEntry {
+                index: 4,
+                created_at_unix_ms: 11,
+                event_json: "not json".to_string(),
+            },
+        ]);
+
+        let lines = ndjson.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 2);
+        let first = serde_json::from_str::<serde_json::Value>(lines[0]).expect("first line json");
+        assert_eq!(first["index"], 3);
+        assert_eq!(first["event"]["event"], "assistant_output");
+        let second = serde_json::from_str::<serde_json::Value>(lines[1]).expect("second line json");
+        assert_eq!(second["event"], "not json");
+    }
+}
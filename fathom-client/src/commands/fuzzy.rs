@@ -0,0 +1,54 @@
+pub(crate) fn fuzzy_score(query: &str, candidate: &str) -> Option<i32> {
+    let query = query.to_ascii_lowercase();
+    let candidate = candidate.to_ascii_lowercase().chars().collect::<Vec<_>>();
+
+    let mut score = 0;
+    let mut next_index = 0;
+    let mut previous_match: Option<usize> = None;
+    for query_char in query.chars() {
+        let offset = candidate[next_index..]
+            .iter()
+            .position(|candidate_char| *candidate_char == query_char)?;
+        let index = next_index + offset;
+
+        score += 1;
+        if index == 0 {
+            score += 3;
+        }
+        match previous_match {
+            Some(previous) if index == previous + 1 => score += 2,
+            Some(previous) => score -= (index - previous - 1) as i32,
+            None => score -= index as i32,
+        }
+
+        previous_match = Some(index);
+        next_index = index + 1;
+    }
+
+    Some(score)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::fuzzy_score;
+
+    #[test]
+    fn requires_query_characters_in_order() {
+        assert_eq!(fuzzy_score("", "heartbeat"), Some(0));
+        assert!(fuzzy_score("hb", "heartbeat").is_some());
+        assert!(fuzzy_score("bh", "heartbeat").is_none());
+        assert!(fuzzy_score("HB", "heartbeat").is_some());
+        assert!(fuzzy_score("x", "heartbeat").is_none());
+    }
+
+    #[test]
+    fn ranks_prefix_and_contiguous_matches_higher() {
+        let prefix = fuzzy_score("hea", "heartbeat").expect("prefix match");
+        let scattered = fuzzy_score("hbt", "heartbeat").expect("scattered match");
+        assert!(prefix > scattered);
+
+        let at_start = fuzzy_score("et", "export").expect("start match");
+        let mid_word = fuzzy_score("et", "heartbeat").expect("mid-word match");
+        assert!(at_start > mid_word);
+    }
+}
@@ -1,6 +1,6 @@
 use anyhow::{Result, anyhow};
 
-use crate::runtime::enqueue_heartbeat;
+use crate::runtime::{ClientConfig, enqueue_heartbeat};
 
 use super::spec::CommandSpec;
 
@@ -9,10 +9,10 @@ pub(crate) const SPEC: CommandSpec = CommandSpec {
     description: "enqueue a heartbeat trigger",
 };
 
-pub(crate) async fn execute(server: &str, session_id: &str, args: &str) -> Result<String> {
+pub(crate) async fn execute(config: &ClientConfig, session_id: &str, args: &str) -> Result<String> {
     if !args.is_empty() {
         return Err(anyhow!("`/heartbeat` does not accept arguments"));
     }
 
-    enqueue_heartbeat(server, session_id).await
+    enqueue_heartbeat(config, session_id).await
 }
@@ -1,12 +1,18 @@
+use super::export;
+use super::fuzzy::fuzzy_score;
 use super::heartbeat;
 use super::spec::CommandSpec;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub(crate) enum CommandId {
     Heartbeat,
+    Export,
 }
 
-const COMMANDS: [(CommandId, CommandSpec); 1] = [(CommandId::Heartbeat, heartbeat::SPEC)];
+const COMMANDS: [(CommandId, CommandSpec); 2] = [
+    (CommandId::Heartbeat, heartbeat::SPEC),
+    (CommandId::Export, export::SPEC),
+];
 
 pub(crate) fn completion_items(prefix: &str) -> Vec<CommandSpec> {
     let normalized = prefix.to_ascii_lowercase();
@@ -16,6 +22,15 @@ pub(crate) fn completion_items(prefix: &str) -> Vec<CommandSpec> {
         .collect()
 }
 
+pub(crate) fn palette_items(query: &str) -> Vec<CommandSpec> {
+    let mut ranked = COMMANDS
+        .iter()
+        .filter_map(|(_, spec)| fuzzy_score(query, spec.name).map(|score| (score, *spec)))
+        .collect::<Vec<_>>();
+    ranked.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+    ranked.into_iter().map(|(_, spec)| spec).collect()
+}
+
 pub(crate) fn resolve(name: &str) -> Option<CommandId> {
     COMMANDS
         .iter()
@@ -24,13 +39,14 @@ pub(crate) fn resolve(name: &str) -> Option<CommandId> {
 
 #[cfg(test)]
 mod tests {
-    use super::{CommandId, completion_items, resolve};
+    use super::{CommandId, completion_items, palette_items, resolve};
 
     #[test]
     fn filters_command_completions_by_prefix() {
         let all = completion_items("");
-        assert_eq!(all.len(), 1);
+        assert_eq!(all.len(), 2);
         assert_eq!(all[0].name, "heartbeat");
+        assert_eq!(all[1].name, "export");
 
         let filtered = completion_items("hea");
         assert_eq!(filtered.len(), 1);
@@ -39,10 +55,32 @@ mod tests {
         assert!(completion_items("zzz").is_empty());
     }
 
+    #[test]
+    fn ranks_palette_items_by_fuzzy_score() {
+        let all = palette_items("");
+        assert_eq!(
+            all.iter().map(|spec| spec.name).collect::<Vec<_>>(),
+            ["heartbeat", "export"]
+        );
+
+        let ranked = palette_items("et");
+        assert_eq!(
+            ranked.iter().map(|spec| spec.name).collect::<Vec<_>>(),
+            ["export", "heartbeat"]
+        );
+
+        let filtered = palette_items("hb");
+        assert_eq!(filtered.len(), 1);
+        assert_eq!(filtered[0].name, "heartbeat");
+
+        assert!(palette_items("zzz").is_empty());
+    }
+
     #[test]
     fn resolves_commands_case_insensitively() {
         assert_eq!(resolve("heartbeat"), Some(CommandId::Heartbeat));
         assert_eq!(resolve("HEARTBEAT"), Some(CommandId::Heartbeat));
+        assert_eq!(resolve("export"), Some(CommandId::Export));
         assert_eq!(resolve("hb"), None);
     }
 }
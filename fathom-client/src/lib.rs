@@ -1,9 +1,16 @@
 mod commands;
+mod profiles;
 mod runtime;
+mod selftest;
 mod tabs;
 mod tui;
 mod util;
 mod view;
 
-pub use runtime::wait_for_server;
+pub use profiles::{ProfileKind, ProfileRow, ProfileTable, list_profiles};
+pub use runtime::{
+    ClientConfig, ServerNotReady, ServerNotReadyReason, run_action, wait_for_execution,
+    wait_for_server,
+};
+pub use selftest::{SelftestReport, SelftestStep, run_selftest};
 pub use tui::run_tui;
@@ -0,0 +1,92 @@
+use std::fmt;
+
+use anyhow::Result;
+
+use crate::runtime::{ClientConfig, list_agent_profiles, list_user_profiles};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProfileKind {
+    Agents,
+    Users,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProfileRow {
+    pub id: String,
+    pub display_name: String,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ProfileTable {
+    pub rows: Vec<ProfileRow>,
+}
+
+impl fmt::Display for ProfileTable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.rows.is_empty() {
+            return write!(f, "no profiles");
+        }
+        let id_width = self
+            .rows
+            .iter()
+            .map(|row| row.id.chars().count())
+            .chain(std::iter::once("ID".len()))
+            .max()
+            .unwrap_or_default();
+        write!(f, "{:<id_width$}  DISPLAY NAME", "ID")?;
+        for row in &self.rows {
+            write!(f, "\n{:<id_width$}  {}", row.id, row.display_name)?;
+        }
+        Ok(())
+    }
+}
+
+pub async fn list_profiles(config: &ClientConfig, kind: ProfileKind) -> Result<ProfileTable> {
+    let rows = match kind {
+        ProfileKind::Agents => list_agent_profiles(config)
+            .await?
+            .into_iter()
+            .map(|profile| ProfileRow {
+                id: profile.agent_id,
+                display_name: profile.display_name,
+            })
+            .collect(),
+        ProfileKind::Users => list_user_profiles(config)
+            .await?
+            .into_iter()
+            .map(|profile| ProfileRow {
+                id: profile.user_id,
+                display_name: profile.name,
+            })
+            .collect(),
+    };
+    Ok(ProfileTable { rows })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ProfileRow, ProfileTable};
+
+    #[test]
+    fn profile_table_aligns_display_names_after_the_longest_id() {
+        let table = ProfileTable {
+            rows: vec![
+                ProfileRow {
+                    id: "agent-a".to_string(),
+                    display_name: "Fathom".to_string(),
+                },
+                ProfileRow {
+                    id: "agent-research".to_string(),
+                    display_name: "Researcher".to_string(),
+                },
+            ],
+        };
+        assert_eq!(
+            table.to_string(),
+            "ID              DISPLAY NAME\n\
+             agent-a         Fathom\n\
+             agent-research  Researcher"
+        );
+        assert_eq!(ProfileTable::default().to_string(), "no profiles");
+    }
+}
@@ -1,14 +1,22 @@
+use std::fmt;
+use std::future::Future;
+use std::path::Path;
 use std::time::{Duration, Instant};
 
-use anyhow::{Result, anyhow};
-use tonic::transport::Channel;
+use anyhow::{Context, Result, anyhow, bail};
+use tonic::transport::{Certificate, Channel, ClientTlsConfig, Endpoint};
 
 use crate::util::now_unix_ms;
-use fathom_protocol::pb;
 use fathom_protocol::pb::runtime_service_client::RuntimeServiceClient;
+use fathom_protocol::{PROTOCOL_VERSION, pb};
+use tracing::warn;
 
 const DEFAULT_AGENT_ID: &str = "agent-default";
 const DEFAULT_USER_ID: &str = "user-default";
+const EXECUTION_POLL_INTERVAL: Duration = Duration::from_millis(200);
+const DEFAULT_RPC_TIMEOUT: Duration = Duration::from_secs(10);
+const DEFAULT_SERVER_WAIT_BACKOFF_BASE: Duration = Duration::from_millis(50);
+const DEFAULT_SERVER_WAIT_BACKOFF_CAP: Duration = Duration::from_secs(1);
 
 #[derive(Debug, Clone)]
 pub struct ClientSession {
@@ -17,41 +25,245 @@ pub struct ClientSession {
     pub user_id: String,
 }
 
-async fn runtime_client(server: &str) -> Result<RuntimeServiceClient<Channel>> {
+#[derive(Debug, Clone)]
+pub struct ClientConfig {
+    server: String,
+    rpc_timeout: Duration,
+    server_wait_backoff: ServerWaitBackoff,
+    tls_config: Option<ClientTlsConfig>,
+}
+
+impl ClientConfig {
+    pub fn new(server: impl Into<String>) -> Self {
+        Self {
+            server: server.into(),
+            rpc_timeout: DEFAULT_RPC_TIMEOUT,
+            server_wait_backoff: ServerWaitBackoff {
+                base: DEFAULT_SERVER_WAIT_BACKOFF_BASE,
+                cap: DEFAULT_SERVER_WAIT_BACKOFF_CAP,
+            },
+            tls_config: None,
+        }
+    }
+
+    pub fn server(&self) -> &str {
+        &self.server
+    }
+
+    pub fn with_rpc_timeout(mut self, timeout: Duration) -> Self {
+        self.rpc_timeout = timeout.max(Duration::from_millis(1));
+        self
+    }
+
+    pub fn with_server_wait_backoff(mut self, base: Duration, cap: Duration) -> Self {
+        let base = base.max(Duration::from_millis(1));
+        self.server_wait_backoff = ServerWaitBackoff {
+            base,
+            cap: cap.max(base),
+        };
+        self
+    }
+
+    pub fn with_tls_ca(mut self, ca_path: &Path) -> Result<Self> {
+        let ca = std::fs::read(ca_path).with_context(|| {
+            format!("failed to read TLS CA certificate `{}`", ca_path.display())
+        })?;
+        self.tls_config = Some(ClientTlsConfig::new().ca_certificate(Certificate::from_pem(ca)));
+        Ok(self)
+    }
+}
+
+fn server_endpoint(server: &str, tls_config: Option<ClientTlsConfig>) -> Result<Endpoint> {
     let endpoint = Channel::from_shared(server.to_string())?;
-    let channel = endpoint.connect().await?;
+    let Some(tls_config) = tls_config else {
+        return Ok(endpoint);
+    };
+    if !server.starts_with("https://") {
+        bail!("--tls-ca requires an https:// server address, got `{server}`");
+    }
+    endpoint
+        .tls_config(tls_config)
+        .context("invalid TLS client configuration")
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct ServerWaitBackoff {
+    base: Duration,
+    cap: Duration,
+}
+
+impl ServerWaitBackoff {
+    fn delay(self, attempt: u32) -> Duration {
+        self.base
+            .saturating_mul(2_u32.saturating_pow(attempt.min(16)))
+            .min(self.cap)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ServerNotReadyReason {
+    ConnectionRefused,
+    ProtocolMismatch,
+    Other,
+}
+
+#[derive(Debug, Clone)]
+pub struct ServerNotReady {
+    pub server: String,
+    pub reason: ServerNotReadyReason,
+    pub attempts: u32,
+    pub last_error: String,
+}
+
+impl fmt::Display for ServerNotReady {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let reason = match self.reason {
+            ServerNotReadyReason::ConnectionRefused => "connection refused",
+            ServerNotReadyReason::ProtocolMismatch => {
+                return write!(
+                    f,
+                    "server at {} does not implement Ping, so it speaks an older protocol than this client (version {PROTOCOL_VERSION}): {}",
+                    self.server, self.last_error
+                );
+            }
+            ServerNotReadyReason::Other => "server unavailable",
+        };
+        write!(
+            f,
+            "server at {} was not ready in time ({reason} after {} attempts): {}",
+            self.server, self.attempts, self.last_error
+        )
+    }
+}
+
+impl std::error::Error for ServerNotReady {}
+
+fn is_unimplemented(error: &anyhow::Error) -> bool {
+    error.chain().any(|cause| {
+        cause
+            .downcast_ref::<tonic::Status>()
+            .is_some_and(|status| status.code() == tonic::Code::Unimplemented)
+    })
+}
+
+fn is_connection_refused(error: &anyhow::Error) -> bool {
+    error.chain().any(|cause| {
+        cause
+            .downcast_ref::<std::io::Error>()
+            .is_some_and(|error| error.kind() == std::io::ErrorKind::ConnectionRefused)
+    })
+}
+
+async fn runtime_client(config: &ClientConfig) -> Result<RuntimeServiceClient<Channel>> {
+    let endpoint = server_endpoint(&config.server, config.tls_config.clone())?;
+    let channel = within_timeout(config.rpc_timeout, "connect", endpoint.connect()).await?;
     Ok(RuntimeServiceClient::new(channel))
 }
 
-pub async fn wait_for_server(server: &str, timeout: Duration) -> Result<()> {
+async fn rpc<T, F>(config: &ClientConfig, operation: &str, call: F) -> Result<T>
+where
+    F: Future<Output = Result<tonic::Response<T>, tonic::Status>>,
+{
+    within_timeout(config.rpc_timeout, operation, call)
+        .await
+        .map(tonic::Response::into_inner)
+}
+
+async fn within_timeout<T, E, F>(timeout: Duration, operation: &str, call: F) -> Result<T>
+where
+    F: Future<Output = Result<T, E>>,
+    E: Into<anyhow::Error>,
+{
+    match tokio::time::timeout(timeout, call).await {
+        Ok(result) => result.map_err(Into::into),
+        Err(_) => Err(anyhow!(
+            "{operation} timed out after {timeout:?} waiting for the server"
+        )),
+    }
+}
+
+pub async fn wait_for_server(config: &ClientConfig, timeout: Duration) -> Result<Option<String>> {
+    let server = config.server();
+    let response = retry_until_ready(server, timeout, config.server_wait_backoff, || async {
+        let mut client = runtime_client(config).await?;
+        rpc(
+            config,
+            "ping",
+            client.ping(pb::PingRequest {
+                client_protocol_version: PROTOCOL_VERSION,
+            }),
+        )
+        .await
+    })
+    .await?;
+
+    let warning = protocol_version_mismatch(response.protocol_version);
+    if let Some(warning) = &warning {
+        warn!(server, server_version = %response.server_version, "{warning}");
+    }
+    Ok(warning)
+}
+
+async fn retry_until_ready<T, F, Fut>(
+    server: &str,
+    timeout: Duration,
+    backoff: ServerWaitBackoff,
+    mut attempt: F,
+) -> Result<T, ServerNotReady>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T>>,
+{
     let deadline = Instant::now() + timeout;
+    let mut attempts = 0;
     loop {
-        let result = async {
-            let mut client = runtime_client(server).await?;
-            client.list_sessions(pb::ListSessionsRequest {}).await?;
-            Ok::<(), anyhow::Error>(())
+        let error = match attempt().await {
+            Ok(value) => return Ok(value),
+            Err(error) => error,
+        };
+        attempts += 1;
+        if is_unimplemented(&error) {
+            return Err(ServerNotReady {
+                server: server.to_string(),
+                reason: ServerNotReadyReason::ProtocolMismatch,
+                attempts,
+                last_error: format!("{error:#}"),
+            });
         }
-        .await;
 
-        match result {
-            Ok(()) => return Ok(()),
-            Err(error) if Instant::now() < deadline => {
-                let _ = error;
-                tokio::time::sleep(Duration::from_millis(120)).await;
-            }
-            Err(error) => {
-                return Err(anyhow!("server at {server} was not ready in time: {error}"));
-            }
+        let now = Instant::now();
+        if now >= deadline {
+            return Err(ServerNotReady {
+                server: server.to_string(),
+                reason: if is_connection_refused(&error) {
+                    ServerNotReadyReason::ConnectionRefused
+                } else {
+                    ServerNotReadyReason::Other
+                },
+                attempts,
+                last_error: format!("{error:#}"),
+            });
         }
+        tokio::time::sleep(backoff.delay(attempts - 1).min(deadline - now)).await;
     }
 }
 
-pub async fn setup_default_session(server: &str) -> Result<ClientSession> {
-    let mut client = runtime_client(server).await?;
+fn protocol_version_mismatch(server_protocol_version: u32) -> Option<String> {
+    (server_protocol_version != PROTOCOL_VERSION).then(|| {
+        format!(
+            "server speaks protocol version {server_protocol_version} but this client expects {PROTOCOL_VERSION}"
+        )
+    })
+}
+
+pub async fn setup_default_session(config: &ClientConfig) -> Result<ClientSession> {
+    let mut client = runtime_client(config).await?;
 
     let now = now_unix_ms();
-    client
-        .upsert_agent_profile(pb::UpsertAgentProfileRequest {
+    rpc(
+        config,
+        "upsert_agent_profile",
+        client.upsert_agent_profile(pb::UpsertAgentProfileRequest {
             profile: Some(pb::AgentProfile {
                 agent_id: DEFAULT_AGENT_ID.to_string(),
                 display_name: "Fathom".to_string(),
@@ -75,11 +287,14 @@ pub async fn setup_default_session(server: &str) -> Result<ClientSession> {
                 spec_version: 1,
                 updated_at_unix_ms: now,
             }),
-        })
-        .await?;
+        }),
+    )
+    .await?;
 
-    client
-        .upsert_user_profile(pb::UpsertUserProfileRequest {
+    rpc(
+        config,
+        "upsert_user_profile",
+        client.upsert_user_profile(pb::UpsertUserProfileRequest {
             profile: Some(pb::UserProfile {
                 user_id: DEFAULT_USER_ID.to_string(),
                 name: "User".to_string(),
@@ -96,16 +311,22 @@ pub async fn setup_default_session(server: &str) -> Result<ClientSession> {
                 .to_string(),
                 updated_at_unix_ms: now,
             }),
-        })
-        .await?;
+        }),
+    )
+    .await?;
 
-    let create_response = client
-        .create_session(pb::CreateSessionRequest {
+    let create_response = rpc(
+        config,
+        "create_session",
+        client.create_session(pb::CreateSessionRequest {
             agent_id: DEFAULT_AGENT_ID.to_string(),
             participant_user_ids: vec![DEFAULT_USER_ID.to_string()],
-        })
-        .await?
-        .into_inner();
+            execution_capacity: 0,
+            validate_only: false,
+            resume_session_id: String::new(),
+        }),
+    )
+    .await?;
 
     let session_id = create_response
         .session
@@ -120,10 +341,10 @@ pub async fn setup_default_session(server: &str) -> Result<ClientSession> {
 }
 
 pub async fn attach_session_events(
-    server: &str,
+    config: &ClientConfig,
     session_id: &str,
 ) -> Result<tonic::Streaming<pb::SessionEvent>> {
-    let mut client = runtime_client(server).await?;
+    let mut client = runtime_client(config).await?;
     let stream = client
         .attach_session_events(pb::AttachSessionEventsRequest {
             session_id: session_id.to_string(),
@@ -134,14 +355,16 @@ pub async fn attach_session_events(
 }
 
 pub async fn enqueue_user_message(
-    server: &str,
+    config: &ClientConfig,
     session_id: &str,
     user_id: &str,
     text: &str,
 ) -> Result<String> {
-    let mut client = runtime_client(server).await?;
-    let response = client
-        .enqueue_trigger(pb::EnqueueTriggerRequest {
+    let mut client = runtime_client(config).await?;
+    let response = rpc(
+        config,
+        "enqueue_trigger",
+        client.enqueue_trigger(pb::EnqueueTriggerRequest {
             session_id: session_id.to_string(),
             trigger: Some(pb::Trigger {
                 trigger_id: String::new(),
@@ -151,26 +374,544 @@ pub async fn enqueue_user_message(
                     text: text.to_string(),
                 })),
             }),
-        })
-        .await?
-        .into_inner();
+        }),
+    )
+    .await?;
 
     Ok(response.trigger_id)
 }
 
-pub async fn enqueue_heartbeat(server: &str, session_id: &str) -> Result<String> {
-    let mut client = runtime_client(server).await?;
-    let response = client
-        .enqueue_trigger(pb::EnqueueTriggerRequest {
+pub async fn enqueue_heartbeat(config: &ClientConfig, session_id: &str) -> Result<String> {
+    let mut client = runtime_client(config).await?;
+    let response = rpc(
+        config,
+        "enqueue_trigger",
+        client.enqueue_trigger(pb::EnqueueTriggerRequest {
             session_id: session_id.to_string(),
             trigger: Some(pb::Trigger {
                 trigger_id: String::new(),
                 created_at_unix_ms: 0,
                 kind: Some(pb::trigger::Kind::Heartbeat(pb::HeartbeatTrigger {})),
             }),
-        })
-        .await?
-        .into_inner();
+        }),
+    )
+    .await?;
 
     Ok(response.trigger_id)
 }
+
+pub async fn get_history(
+    config: &ClientConfig,
+    session_id: &str,
+    since_index: u64,
+    page_size: u64,
+) -> Result<Vec<pb::HistoryEntry>> {
+    let mut client = runtime_client(config).await?;
+    let mut entries = Vec::new();
+    let mut offset = since_index;
+    loop {
+        let response = rpc(
+            config,
+            "get_history",
+            client.get_history(pb::GetHistoryRequest {
+                session_id: session_id.to_string(),
+                offset,
+                page_size,
+            }),
+        )
+        .await?;
+        let page_is_empty = response.entries.is_empty();
+        entries.extend(response.entries);
+        if page_is_empty || response.next_offset >= response.total_count {
+            return Ok(entries);
+        }
+        offset = response.next_offset;
+    }
+}
+
+pub async fn list_user_profiles(config: &ClientConfig) -> Result<Vec<pb::UserProfile>> {
+    let mut client = runtime_client(config).await?;
+    let response = rpc(
+        config,
+        "list_user_profiles",
+        client.list_user_profiles(pb::ListUserProfilesRequest {}),
+    )
+    .await?;
+    Ok(response.profiles)
+}
+
+pub async fn list_agent_profiles(config: &ClientConfig) -> Result<Vec<pb::AgentProfile>> {
+    let mut client = runtime_client(config).await?;
+    let response = rpc(
+        config,
+        "list_agent_profiles",
+        client.list_agent_profiles(pb::ListAgentProfilesRequest {}),
+    )
+    .await?;
+    Ok(response.profiles)
+}
+
+pub async fn run_action(
+    config: &ClientConfig,
+    session_id: &str,
+    action_id: &str,
+    args_json: &str,
+) -> Result<pb::Execution> {
+    let mut client = runtime_client(config).await?;
+    let response = rpc(
+        config,
+        "run_action",
+        client.run_action(pb::RunActionRequest {
+            session_id: session_id.to_string(),
+            action_id: action_id.to_string(),
+            args_json: args_json.to_string(),
+        }),
+    )
+    .await?;
+    response
+        .execution
+        .ok_or_else(|| anyhow!("missing execution in run_action response"))
+}
+
+pub async fn delete_session(config: &ClientConfig, session_id: &str) -> Result<()> {
+    let mut client = runtime_client(config).await?;
+    rpc(
+        config,
+        "delete_session",
+        client.delete_session(pb::DeleteSessionRequest {
+            session_id: session_id.to_string(),
+        }),
+    )
+    .await?;
+    Ok(())
+}
+
+pub async fn wait_for_execution(
+    config: &ClientConfig,
+    session_id: &str,
+    execution_id: &str,
+    timeout: Duration,
+) -> Result<pb::Execution> {
+    let client = runtime_client(config).await?;
+    poll_execution_until_terminal(execution_id, timeout, EXECUTION_POLL_INTERVAL, || {
+        let mut client = client.clone();
+        async move {
+            let response = rpc(
+                config,
+                "get_execution",
+                client.get_execution(pb::GetExecutionRequest {
+                    session_id: session_id.to_string(),
+                    execution_id: execution_id.to_string(),
+                }),
+            )
+            .await?;
+            Ok(response.execution)
+        }
+    })
+    .await
+}
+
+async fn poll_execution_until_terminal<F, Fut>(
+    execution_id: &str,
+    timeout: Duration,
+    interval: Duration,
+    mut fetch: F,
+) -> Result<pb::Execution>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<Option<pb::Execution>>>,
+{
+    let deadline = Instant::now() + timeout;
+    loop {
+        let execution = fetch()
+            .await?
+            .ok_or_else(|| anyhow!("execution {execution_id} not found"))?;
+        if is_terminal_execution_status(execution.status) {
+            return Ok(execution);
+        }
+
+        let now = Instant::now();
+        if now >= deadline {
+            return Err(anyhow!(
+                "execution {execution_id} did not reach a terminal status within {timeout:?} (last status {})",
+                execution_status_name(execution.status)
+            ));
+        }
+        tokio::time::sleep(interval.min(deadline - now)).await;
+    }
+}
+
+fn is_terminal_execution_status(status: i32) -> bool {
+    matches!(
+        pb::ExecutionStatus::try_from(status),
+        Ok(pb::ExecutionStatus::Succeeded
+            | pb::ExecutionStatus::Failed
+            | pb::ExecutionStatus::Canceled)
+    )
+}
+
+fn execution_status_name(status: i32) -> &'static str {
+    pb::ExecutionStatus::try_from(status)
+        .map(|status| status.as_str_name())
+        .unwrap_or("EXECUTION_STATUS_UNKNOWN")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::VecDeque;
+    use std::path::PathBuf;
+    use std::time::{Duration, Instant};
+
+    use tokio::net::TcpListener;
+    use tonic::transport::{Certificate, ClientTlsConfig};
+
+    use super::{
+        ClientConfig, DEFAULT_RPC_TIMEOUT, DEFAULT_SERVER_WAIT_BACKOFF_CAP, ServerNotReady,
+        ServerNotReadyReason, ServerWaitBackoff, attach_session_events,
+        poll_execution_until_terminal, protocol_version_mismatch, retry_until_ready, rpc,
+        runtime_client, server_endpoint, setup_default_session, wait_for_server,
+    };
+    use fathom_protocol::pb::runtime_service_client::RuntimeServiceClient;
+    use fathom_protocol::{PROTOCOL_VERSION, pb};
+    use fathom_server::ServerOptions;
+
+    fn free_local_addr() -> std::net::SocketAddr {
+        std::net::TcpListener::bind("127.0.0.1:0")
+            .expect("bind ephemeral port")
+            .local_addr()
+            .expect("local addr")
+    }
+
+    fn execution(status: pb::ExecutionStatus) -> pb::Execution {
+        pb::Execution {
+            execution_id: "execution-1".to_string(),
+            session_id: "session-1".to_string(),
+            action_id: "filesystem__list".to_string(),
+            status: status as i32,
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn poll_execution_waits_until_succeeded() {
+        let mut statuses = VecDeque::from([
+            pb::ExecutionStatus::Pending,
+            pb::ExecutionStatus::Running,
+            pb::ExecutionStatus::Running,
+            pb::ExecutionStatus::Succeeded,
+        ]);
+        let mut polls = 0;
+
+        let finished = poll_execution_until_terminal(
+            "execution-1",
+            Duration::from_secs(5),
+            Duration::from_millis(1),
+            || {
+                polls += 1;
+                let status = statuses.pop_front().expect("poll after terminal status");
+                async move { Ok(Some(execution(status))) }
+            },
+        )
+        .await
+        .expect("execution should finish");
+
+        assert_eq!(finished.status, pb::ExecutionStatus::Succeeded as i32);
+        assert_eq!(polls, 4);
+    }
+
+    #[tokio::test]
+    async fn poll_execution_times_out_while_running() {
+        let error = poll_execution_until_terminal(
+            "execution-1",
+            Duration::from_millis(20),
+            Duration::from_millis(5),
+            || async { Ok(Some(execution(pb::ExecutionStatus::Running))) },
+        )
+        .await
+        .expect_err("running execution should time out");
+
+        assert!(error.to_string().contains("EXECUTION_STATUS_RUNNING"));
+    }
+
+    #[tokio::test]
+    async fn poll_execution_reports_missing_execution() {
+        let error = poll_execution_until_terminal(
+            "execution-9",
+            Duration::from_secs(1),
+            Duration::from_millis(1),
+            || async { Ok(None) },
+        )
+        .await
+        .expect_err("missing execution should fail");
+
+        assert!(error.to_string().contains("execution-9 not found"));
+    }
+
+    #[tokio::test]
+    async fn rpc_times_out_when_server_never_responds() {
+        let listener = TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("bind silent listener");
+        let server = format!(
+            "http://{}",
+            listener.local_addr().expect("listener address")
+        );
+        let silent_server = tokio::spawn(async move {
+            let mut connections = Vec::new();
+            while let Ok((stream, _)) = listener.accept().await {
+                connections.push(stream);
+            }
+        });
+
+        let config = ClientConfig::new(server).with_rpc_timeout(Duration::from_millis(200));
+        let started_at = Instant::now();
+        let mut client = runtime_client(&config)
+            .await
+            .expect("connect to silent server");
+        let error = rpc(
+            &config,
+            "list_sessions",
+            client.list_sessions(pb::ListSessionsRequest {}),
+        )
+        .await
+        .expect_err("silent server should time out");
+
+        assert!(started_at.elapsed() < Duration::from_secs(5));
+        assert!(error.to_string().contains("list_sessions timed out"));
+        silent_server.abort();
+    }
+
+    #[test]
+    fn protocol_version_mismatch_is_detected() {
+        assert_eq!(protocol_version_mismatch(PROTOCOL_VERSION), None);
+
+        let warning =
+            protocol_version_mismatch(PROTOCOL_VERSION + 1).expect("mismatch is reported");
+        assert!(warning.contains(&format!("protocol version {}", PROTOCOL_VERSION + 1)));
+        assert!(warning.contains(&format!("client expects {PROTOCOL_VERSION}")));
+    }
+
+    #[tokio::test]
+    async fn wait_for_server_succeeds_once_the_server_comes_up() {
+        let addr = free_local_addr();
+        let server = tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(300)).await;
+            fathom_server::serve_with_options(addr, ServerOptions::default()).await
+        });
+
+        wait_for_server(
+            &ClientConfig::new(format!("http://{addr}")),
+            Duration::from_secs(10),
+        )
+        .await
+        .expect("server should become ready");
+
+        server.abort();
+        let _ = server.await;
+    }
+
+    #[tokio::test]
+    async fn server_shutdown_expires_live_sessions_and_closes_their_streams() {
+        let addr = free_local_addr();
+        let (shutdown_tx, shutdown_rx) = tokio::sync::oneshot::channel::<()>();
+        let server = tokio::spawn(fathom_server::serve_with_shutdown(
+            addr,
+            ServerOptions::default(),
+            async {
+                let _ = shutdown_rx.await;
+            },
+        ));
+        let config = ClientConfig::new(format!("http://{addr}"));
+        wait_for_server(&config, Duration::from_secs(10))
+            .await
+            .expect("server should become ready");
+        let session = setup_default_session(&config)
+            .await
+            .expect("create session");
+        let mut stream = attach_session_events(&config, &session.session_id)
+            .await
+            .expect("attach events");
+
+        shutdown_tx.send(()).expect("signal shutdown");
+        let expired = tokio::time::timeout(Duration::from_secs(5), async {
+            let mut expired = false;
+            while let Ok(Some(event)) = stream.message().await {
+                expired |= matches!(event.kind, Some(pb::session_event::Kind::SessionExpired(_)));
+            }
+            expired
+        })
+        .await
+        .expect("event stream should close on shutdown");
+        assert!(expired);
+        tokio::time::timeout(Duration::from_secs(5), server)
+            .await
+            .expect("server should stop")
+            .expect("server task")
+            .expect("server result");
+    }
+
+    #[test]
+    fn client_configs_keep_their_settings_independent() {
+        let tuned = ClientConfig::new("http://127.0.0.1:50051")
+            .with_rpc_timeout(Duration::ZERO)
+            .with_server_wait_backoff(Duration::from_millis(40), Duration::from_millis(10));
+        let default = ClientConfig::new("http://127.0.0.1:50052");
+
+        assert_eq!(tuned.rpc_timeout, Duration::from_millis(1));
+        assert_eq!(
+            tuned.server_wait_backoff,
+            ServerWaitBackoff {
+                base: Duration::from_millis(40),
+                cap: Duration::from_millis(40),
+            }
+        );
+        assert_eq!(default.rpc_timeout, DEFAULT_RPC_TIMEOUT);
+        assert_eq!(
+            default.server_wait_backoff.cap,
+            DEFAULT_SERVER_WAIT_BACKOFF_CAP
+        );
+    }
+
+    #[test]
+    fn tls_endpoints_require_an_https_server_address() {
+        assert!(server_endpoint("http://127.0.0.1:50051", None).is_ok());
+        assert!(server_endpoint("https://localhost:50051", Some(ClientTlsConfig::new())).is_ok());
+
+        let error = server_endpoint("http://127.0.0.1:50051", Some(ClientTlsConfig::new()))
+            .expect_err("plaintext address with tls");
+        assert!(error.to_string().contains("https://"));
+    }
+
+    #[tokio::test]
+    async fn tls_server_accepts_clients_that_trust_its_ca() {
+        let (Some(cert_path), Some(key_path), Some(ca_path)) = (
+            std::env::var_os("FATHOM_TEST_TLS_CERT").map(PathBuf::from),
+            std::env::var_os("FATHOM_TEST_TLS_KEY").map(PathBuf::from),
+            std::env::var_os("FATHOM_TEST_TLS_CA").map(PathBuf::from),
+        ) else {
+            return;
+        };
+        if ![&cert_path, &key_path, &ca_path]
+            .iter()
+            .all(|path| path.is_file())
+        {
+            return;
+        }
+
+        let addr = free_local_addr();
+        let server = tokio::spawn(fathom_server::serve_with_options(
+            addr,
+            ServerOptions {
+                tls_cert: Some(cert_path),
+                tls_key: Some(key_path),
+                ..ServerOptions::default()
+            },
+        ));
+        let ca = std::fs::read(&ca_path).expect("read ca");
+        let endpoint = server_endpoint(
+            &format!("https://localhost:{}", addr.port()),
+            Some(ClientTlsConfig::new().ca_certificate(Certificate::from_pem(ca))),
+        )
+        .expect("tls endpoint");
+
+        let response = retry_until_ready(
+            "tls server",
+            Duration::from_secs(10),
+            ServerWaitBackoff {
+                base: Duration::from_millis(50),
+                cap: Duration::from_millis(200),
+            },
+            || {
+                let endpoint = endpoint.clone();
+                async move {
+                    let channel = endpoint.connect().await?;
+                    let response = RuntimeServiceClient::new(channel)
+                        .ping(pb::PingRequest {
+                            client_protocol_version: PROTOCOL_VERSION,
+                        })
+                        .await?;
+                    Ok(response.into_inner())
+                }
+            },
+        )
+        .await
+        .expect("ping over tls");
+        assert_eq!(response.protocol_version, PROTOCOL_VERSION);
+
+        server.abort();
+        let _ = server.await;
+    }
+
+    #[tokio::test]
+    async fn retry_until_ready_backs_off_between_attempts() {
+        let backoff = ServerWaitBackoff {
+            base: Duration::from_millis(10),
+            cap: Duration::from_millis(40),
+        };
+        let mut attempted_at = Vec::new();
+
+        let value = retry_until_ready("http://test", Duration::from_secs(5), backoff, || {
+            attempted_at.push(Instant::now());
+            let ready = attempted_at.len() == 5;
+            async move {
+                if ready {
+                    Ok("ready")
+                } else {
+                    Err(anyhow::anyhow!("not yet"))
+                }
+            }
+        })
+        .await
+        .expect("fifth attempt should succeed");
+
+        assert_eq!(value, "ready");
+        let gaps = attempted_at
+            .windows(2)
+            .map(|pair| pair[1] - pair[0])
+            .collect::<Vec<_>>();
+        for (gap, expected) in gaps.iter().zip([10, 20, 40, 40]) {
+            assert!(*gap >= Duration::from_millis(expected), "{gaps:?}");
+        }
+    }
+
+    #[tokio::test]
+    async fn unimplemented_ping_is_reported_as_a_protocol_mismatch_without_retrying() {
+        let mut calls = 0;
+        let error = retry_until_ready(
+            "http://old-server",
+            Duration::from_secs(10),
+            ServerWaitBackoff {
+                base: Duration::from_secs(1),
+                cap: Duration::from_secs(1),
+            },
+            || {
+                calls += 1;
+                async { Err::<(), _>(tonic::Status::unimplemented("unknown method Ping").into()) }
+            },
+        )
+        .await
+        .expect_err("unimplemented ping should fail");
+
+        assert_eq!(calls, 1);
+        assert_eq!(error.reason, ServerNotReadyReason::ProtocolMismatch);
+        assert!(error.to_string().contains("does not implement Ping"));
+    }
+
+    #[tokio::test]
+    async fn wait_for_server_reports_connection_refused_when_nothing_listens() {
+        let addr = free_local_addr();
+
+        let error = wait_for_server(
+            &ClientConfig::new(format!("http://{addr}")),
+            Duration::from_millis(200),
+        )
+        .await
+        .expect_err("nothing is listening");
+
+        let not_ready = error
+            .downcast_ref::<ServerNotReady>()
+            .expect("structured not-ready error");
+        assert_eq!(not_ready.reason, ServerNotReadyReason::ConnectionRefused);
+        assert!(not_ready.attempts >= 2);
+    }
+}
@@ -0,0 +1,300 @@
+use std::fmt;
+use std::future::Future;
+use std::time::{Duration, Instant};
+
+use anyhow::{Result, anyhow};
+use serde_json::{Value, json};
+
+use crate::runtime::{
+    ClientConfig, delete_session, run_action, setup_default_session, wait_for_execution,
+    wait_for_server,
+};
+use crate::util::now_unix_ms;
+use fathom_protocol::pb;
+
+const SELFTEST_READY_TIMEOUT: Duration = Duration::from_secs(5);
+const SELFTEST_EXECUTION_TIMEOUT: Duration = Duration::from_secs(15);
+const SELFTEST_FILE_PATH: &str = ".fathom-selftest.txt";
+
+#[derive(Debug, Clone)]
+pub struct SelftestStep {
+    pub name: &'static str,
+    pub elapsed: Duration,
+    pub outcome: Result<String, String>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SelftestReport {
+    pub steps: Vec<SelftestStep>,
+}
+
+impl SelftestReport {
+    pub fn passed(&self) -> bool {
+        !self.steps.is_empty() && self.steps.iter().all(|step| step.outcome.is_ok())
+    }
+
+    async fn step<T, F>(&mut self, name: &'static str, run: F) -> Option<T>
+    where
+        F: Future<Output = Result<(T, String)>>,
+    {
+        let started_at = Instant::now();
+        let result = run.await;
+        let elapsed = started_at.elapsed();
+        match result {
+            Ok((value, detail)) => {
+                self.steps.push(SelftestStep {
+                    name,
+                    elapsed,
+                    outcome: Ok(detail),
+                });
+                Some(value)
+            }
+            Err(error) => {
+                self.steps.push(SelftestStep {
+                    name,
+                    elapsed,
+                    outcome: Err(format!("{error:#}")),
+                });
+                None
+            }
+        }
+    }
+}
+
+impl fmt::Display for SelftestReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for step in &self.steps {
+            let (status, detail) = match &step.outcome {
+                Ok(detail) => ("PASS", detail),
+                Err(error) => ("FAIL", error),
+            };
+            writeln!(
+                f,
+                "{status} {:<16} {:>6}ms  {detail}",
+                step.name,
+                step.elapsed.as_millis()
+            )?;
+        }
+        let total = self.steps.iter().map(|step| step.elapsed).sum::<Duration>();
+        write!(
+            f,
+            "selftest {} in {}ms",
+            if self.passed() { "passed" } else { "failed" },
+            total.as_millis()
+        )
+    }
+}
+
+pub async fn run_selftest(config: &ClientConfig) -> SelftestReport {
+    let mut report = SelftestReport::default();
+
+    let ready = report
+        .step("connect", async {
+            let detail = match wait_for_server(config, SELFTEST_READY_TIMEOUT).await? {
+                Some(warning) => format!("server {} is ready ({warning})", config.server()),
+                None => format!("server {} is ready", config.server()),
+            };
+            Ok(((), detail))
+        })
+        .await;
+    if ready.is_none() {
+        return report;
+    }
+
+    let Some(session_id) = report
+        .step("create_session", async {
+            let session = setup_default_session(config).await?;
+            let detail = format!("session {}", session.session_id);
+            Ok((session.session_id, detail))
+        })
+        .await
+    else {
+        return report;
+    };
+
+    let marker = format!("fathom selftest {}", now_unix_ms());
+    let written = report
+        .step(
+            "filesystem__write",
+            run_action_to_success(
+                config,
+                &session_id,
+                "filesystem__write",
+                json!({
+                    "path": SELFTEST_FILE_PATH,
+                    "content": marker,
+                    "allow_override": true,
+                }),
+            ),
+        )
+        .await;
+    if written.is_some() {
+        report
+            .step("filesystem__read", async {
+                let (data, detail) = run_action_to_success(
+                    config,
+                    &session_id,
+                    "filesystem__read",
+                    json!({ "path": SELFTEST_FILE_PATH }),
+                )
+                .await?;
+                let content = data
+                    .get("content")
+                    .and_then(Value::as_str)
+                    .unwrap_or_default();
+                if content != marker {
+                    return Err(anyhow!("read back `{content}` but expected `{marker}`"));
+                }
+                Ok(((), detail))
+            })
+            .await;
+
+        report
+            .step(
+                "filesystem__delete",
+                run_action_to_success(
+                    config,
+                    &session_id,
+                    "filesystem__delete",
+                    json!({ "path": SELFTEST_FILE_PATH }),
+                ),
+            )
+            .await;
+    }
+
+    report
+        .step("delete_session", async {
+            delete_session(config, &session_id).await?;
+            Ok(((), format!("session {session_id}")))
+        })
+        .await;
+
+    report
+}
+
+async fn run_action_to_success(
+    config: &ClientConfig,
+    session_id: &str,
+    action_id: &str,
+    args: Value,
+) -> Result<(Value, String)> {
+    let execution = run_action(config, session_id, action_id, &args.to_string()).await?;
+    let execution = wait_for_execution(
+        config,
+        session_id,
+        &execution.execution_id,
+        SELFTEST_EXECUTION_TIMEOUT,
+    )
+    .await?;
+    if execution.status != pb::ExecutionStatus::Succeeded as i32 {
+        return Err(anyhow!(
+            "execution {} ended as {}: {}",
+            execution.execution_id,
+            pb::ExecutionStatus::try_from(execution.status)
+                .map(|status| status.as_str_name())
+                .unwrap_or("EXECUTION_STATUS_UNKNOWN"),
+            execution.result_message
+        ));
+    }
+    let result = serde_json::from_str::<Value>(&execution.result_message)?;
+    let data = result.pointer("/data/data").cloned().unwrap_or(Value::Null);
+    Ok((data, format!("execution {}", execution.execution_id)))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::{SocketAddr, TcpListener};
+    use std::path::PathBuf;
+
+    use fathom_server::ServerOptions;
+
+    use super::{SELFTEST_FILE_PATH, run_selftest};
+    use crate::runtime::ClientConfig;
+
+    fn unique_workspace_root(prefix: &str) -> PathBuf {
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .expect("system clock after epoch")
+            .as_nanos();
+        let root = std::env::temp_dir().join(format!("{prefix}-{nanos}"));
+        std::fs::create_dir_all(&root).expect("create workspace root");
+        root
+    }
+
+    fn free_local_addr() -> SocketAddr {
+        TcpListener::bind("127.0.0.1:0")
+            .expect("bind ephemeral port")
+            .local_addr()
+            .expect("local addr")
+    }
+
+    async fn run_selftest_against_fresh_server(admin_rpc_enabled: bool) -> super::SelftestReport {
+        let workspace_root = unique_workspace_root("fathom-selftest");
+        let addr = free_local_addr();
+        let server = tokio::spawn(fathom_server::serve_with_options(
+            addr,
+            ServerOptions {
+                workspace_root: Some(workspace_root.clone()),
+                admin_rpc_enabled,
+                ..ServerOptions::default()
+            },
+        ));
+
+        let report = run_selftest(&ClientConfig::new(format!("http://{addr}"))).await;
+
+        let leftover = workspace_root.join(SELFTEST_FILE_PATH).exists();
+        server.abort();
+        let _ = server.await;
+        let _ = std::fs::remove_dir_all(workspace_root);
+        assert!(!leftover, "selftest left {SELFTEST_FILE_PATH} behind");
+        report
+    }
+
+    #[tokio::test]
+    async fn selftest_passes_against_a_fresh_server() {
+        let report = run_selftest_against_fresh_server(true).await;
+
+        assert!(report.passed(), "{report}");
+        assert_eq!(
+            report
+                .steps
+                .iter()
+                .map(|step| step.name)
+                .collect::<Vec<_>>(),
+            vec![
+                "connect",
+                "create_session",
+                "filesystem__write",
+                "filesystem__read",
+                "filesystem__delete",
+                "delete_session"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn selftest_fails_when_server_rejects_run_action() {
+        let report = run_selftest_against_fresh_server(false).await;
+
+        assert!(!report.passed());
+        let failed = report
+            .steps
+            .iter()
+            .find(|step| step.outcome.is_err())
+            .expect("failed step");
+        assert_eq!(failed.name, "filesystem__write");
+        assert_eq!(
+            report
+                .steps
+                .last()
+                .map(|step| (step.name, step.outcome.is_ok())),
+            Some(("delete_session", true))
+        );
+        assert!(
+            failed
+                .outcome
+                .as_ref()
+                .is_err_and(|error| error.contains("admin rpc is disabled"))
+        );
+    }
+}
@@ -1,24 +1,40 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use ratatui::Frame;
 use ratatui::layout::Rect;
 use ratatui::widgets::{Block, Borders, Paragraph};
 
-use crate::tabs::{LineBuffer, Tab};
-use crate::view::{EventRecord, render_event_record};
+use crate::tabs::{LineBuffer, Tab, TabKeyResult};
+use crate::view::{EventRecord, SessionEventRecordKind, render_event_record};
 
 pub(crate) struct FullEventsTab {
     lines: LineBuffer,
+    show_diagnostics: bool,
 }
 
 impl FullEventsTab {
     pub(crate) fn new() -> Self {
         Self {
             lines: LineBuffer::new(),
+            show_diagnostics: false,
         }
     }
+
+    fn is_diagnostic(event: &EventRecord) -> bool {
+        matches!(
+            event,
+            EventRecord::Session {
+                kind: SessionEventRecordKind::Diagnostic { .. },
+                ..
+            }
+        )
+    }
 }
 
 impl Tab for FullEventsTab {
     fn on_event(&mut self, event: &EventRecord) {
+        if !self.show_diagnostics && Self::is_diagnostic(event) {
+            return;
+        }
         let _ = self.lines.push_line(render_event_record(event));
     }
 
@@ -28,10 +44,14 @@ impl Tab for FullEventsTab {
         } else {
             "scroll"
         };
+        let diagnostics = if self.show_diagnostics { "on" } else { "off" };
         let panel = Paragraph::new(self.lines.rendered_text(self.viewport_width(area)))
             .block(
                 Block::default()
-                    .title(format!("events:full [{}] ({mode})", session_id))
+                    .title(format!(
+                        "events:full [{}] ({mode}, diagnostics {diagnostics}; Ctrl+D toggles)",
+                        session_id
+                    ))
                     .borders(Borders::ALL),
             )
             .scroll((self.lines.scroll_value(), 0));
@@ -66,10 +86,26 @@ impl Tab for FullEventsTab {
     fn scroll_to_bottom(&mut self, viewport_height: u16, viewport_width: u16) {
         self.lines.scroll_to_bottom(viewport_height, viewport_width);
     }
+
+    fn handle_key(
+        &mut self,
+        key: &KeyEvent,
+        _input_is_empty: bool,
+        _viewport_height: u16,
+        _viewport_width: u16,
+    ) -> TabKeyResult {
+        if key.code == KeyCode::Char('d') && key.modifiers.contains(KeyModifiers::CONTROL) {
+            self.show_diagnostics = !self.show_diagnostics;
+            return TabKeyResult::Handled;
+        }
+        TabKeyResult::Ignored
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
     use super::FullEventsTab;
     use crate::tabs::Tab;
     use crate::view::{EventRecord, SessionEventRecordKind};
@@ -87,4 +123,27 @@ mod tests {
 
         assert_eq!(tab.lines.line_count(), 1);
     }
+
+    #[test]
+    fn hides_diagnostics_until_toggled() {
+        let mut tab = FullEventsTab::new();
+        let diagnostic = EventRecord::Session {
+            session_id: "s1".to_string(),
+            kind: SessionEventRecordKind::Diagnostic {
+                detail: "dispatched action_call=call-1 name=filesystem__list".to_string(),
+            },
+        };
+
+        tab.on_event(&diagnostic);
+        assert_eq!(tab.lines.line_count(), 0);
+
+        tab.handle_key(
+            &KeyEvent::new(KeyCode::Char('d'), KeyModifiers::CONTROL),
+            true,
+            10,
+            80,
+        );
+        tab.on_event(&diagnostic);
+        assert_eq!(tab.lines.line_count(), 1);
+    }
 }
@@ -3,10 +3,14 @@ use std::io::{self, IsTerminal};
 use std::time::Duration;
 
 use anyhow::{Result, anyhow};
-use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use crossterm::event::{
+    self, Event, KeyCode, KeyEventKind, KeyModifiers, KeyboardEnhancementFlags,
+    PopKeyboardEnhancementFlags, PushKeyboardEnhancementFlags,
+};
 use crossterm::execute;
 use crossterm::terminal::{
     EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode,
+    supports_keyboard_enhancement,
 };
 use ratatui::Terminal;
 use ratatui::backend::CrosstermBackend;
@@ -17,10 +21,11 @@ use tokio::sync::mpsc;
 
 use crate::commands::{
     CommandSpec, SlashExecution, completion_items, completion_query, execute_slash_command,
+    palette_items,
 };
 use crate::runtime::{
-    ClientSession, attach_session_events, enqueue_user_message, setup_default_session,
-    wait_for_server,
+    ClientConfig, ClientSession, attach_session_events, enqueue_user_message,
+    setup_default_session, wait_for_server,
 };
 use crate::tabs::{
     ConversationTab, ExecutionDetail, ExecutionsEventsTab, FullEventsTab, RunningExecutionsTab,
@@ -29,9 +34,12 @@ use crate::tabs::{
 use crate::view::{EventRecord, SessionEventRecordKind, session_event_to_record};
 
 const MAX_COMPLETION_ROWS: usize = 8;
+const MAX_PALETTE_ROWS: usize = 10;
+const MAX_INPUT_LINES: u16 = 5;
 
 enum AppEvent {
     Record(EventRecord),
+    Session { seq: u64, record: EventRecord },
     Status(String),
 }
 
@@ -104,15 +112,70 @@ impl SlashCompletionState {
     }
 }
 
+#[derive(Default)]
+struct CommandPaletteState {
+    open: bool,
+    query: String,
+    items: Vec<CommandSpec>,
+    selected_index: usize,
+}
+
+impl CommandPaletteState {
+    fn open(&mut self) {
+        self.open = true;
+        self.query.clear();
+        self.refresh();
+    }
+
+    fn close(&mut self) {
+        self.open = false;
+        self.query.clear();
+        self.items.clear();
+        self.selected_index = 0;
+    }
+
+    fn push_char(&mut self, ch: char) {
+        self.query.push(ch);
+        self.refresh();
+    }
+
+    fn pop_char(&mut self) {
+        self.query.pop();
+        self.refresh();
+    }
+
+    fn refresh(&mut self) {
+        self.items = palette_items(self.query.as_str());
+        self.selected_index = 0;
+    }
+
+    fn select_prev(&mut self) {
+        self.selected_index = self.selected_index.saturating_sub(1);
+    }
+
+    fn select_next(&mut self) {
+        self.selected_index = self
+            .selected_index
+            .saturating_add(1)
+            .min(self.items.len().saturating_sub(1));
+    }
+
+    fn selected(&self) -> Option<CommandSpec> {
+        self.items.get(self.selected_index).copied()
+    }
+}
+
 struct App {
     session: ClientSession,
     input: String,
     status: String,
     activity: ActivityState,
     completion: SlashCompletionState,
+    palette: CommandPaletteState,
     execution_detail: Option<ExecutionDetailModal>,
     tabs: Vec<Box<dyn Tab>>,
     active_tab_index: usize,
+    last_seq: u64,
 }
 
 impl App {
@@ -123,6 +186,7 @@ impl App {
             status: "connected".to_string(),
             activity: ActivityState::default(),
             completion: SlashCompletionState::default(),
+            palette: CommandPaletteState::default(),
             execution_detail: None,
             tabs: vec![
                 Box::new(ConversationTab::new()),
@@ -131,6 +195,7 @@ impl App {
                 Box::new(FullEventsTab::new()),
             ],
             active_tab_index: 0,
+            last_seq: 0,
         }
     }
 
@@ -141,6 +206,21 @@ impl App {
         }
     }
 
+    fn push_session_event(&mut self, seq: u64, event: EventRecord) -> bool {
+        if seq <= self.last_seq {
+            return false;
+        }
+        if self.last_seq != 0 && seq > self.last_seq + 1 {
+            self.push_event(EventRecord::local(format!(
+                "[stream] missed {} session event(s)",
+                seq - self.last_seq - 1
+            )));
+        }
+        self.last_seq = seq;
+        self.push_event(event);
+        true
+    }
+
     fn active_tab(&self) -> &dyn Tab {
         self.tabs[self.active_tab_index].as_ref()
     }
@@ -199,10 +279,12 @@ impl App {
     }
 
     fn footer_text(&self) -> &'static str {
-        if self.completion_is_visible() {
+        if self.palette.open {
+            "Palette: type to filter | ↑/↓ select | Enter run | Esc close"
+        } else if self.completion_is_visible() {
             "Commands: ↑/↓ select | Tab/Enter accept | Esc close"
         } else {
-            "Keys: Shift+Tab switch | Enter send | Ctrl+Enter execution detail (events; Ctrl+J/M fallback) | / opens commands | ↑/↓ scroll/select | Esc clear input | Ctrl+C quit"
+            "Keys: Shift+Tab switch | Enter send | Shift+Enter newline | Ctrl+Enter execution detail (events; Ctrl+J/M fallback) | / opens commands | : opens palette | ↑/↓ scroll/select | Esc clear input | Ctrl+C quit"
         }
     }
 
@@ -214,6 +296,7 @@ impl App {
 #[derive(Default)]
 struct ActivityState {
     agent_invoking: bool,
+    agent_thinking: bool,
     active_executions: BTreeMap<String, ActiveExecution>,
 }
 
@@ -230,14 +313,18 @@ impl ActivityState {
         };
 
         match kind {
-            SessionEventRecordKind::AgentStream { phase, .. } => {
-                if phase == "agent.turn.attempt" || phase == "openai.request.start" {
-                    self.agent_invoking = true;
-                }
+            SessionEventRecordKind::AgentStream { phase, .. }
+                if phase == "agent.turn.attempt" || phase == "openai.request.start" =>
+            {
+                self.agent_invoking = true;
+            }
+            SessionEventRecordKind::AgentThinking { thinking, .. } => {
+                self.agent_thinking = *thinking;
             }
             SessionEventRecordKind::TurnEnded { .. }
             | SessionEventRecordKind::TurnFailure { .. } => {
                 self.agent_invoking = false;
+                self.agent_thinking = false;
             }
             SessionEventRecordKind::ExecutionStateChanged {
                 execution_id,
@@ -262,7 +349,9 @@ impl ActivityState {
     }
 
     fn render_line(&self) -> String {
-        let agent = if self.agent_invoking {
+        let agent = if self.agent_thinking {
+            "thinking"
+        } else if self.agent_invoking {
             "invoking"
         } else {
             "idle"
@@ -295,27 +384,34 @@ impl ActivityState {
     }
 }
 
-pub async fn run_tui(server: &str) -> Result<()> {
+pub async fn run_tui(config: &ClientConfig) -> Result<()> {
     if !io::stdout().is_terminal() {
         return Err(anyhow!(
             "interactive TUI requires a real terminal (TTY); run `cargo run` directly in your shell"
         ));
     }
 
-    wait_for_server(server, Duration::from_secs(12)).await?;
-    let session = setup_default_session(server).await?;
-    run_interactive(server, session).await
+    let protocol_warning = wait_for_server(config, Duration::from_secs(12)).await?;
+    let session = setup_default_session(config).await?;
+    run_interactive(config, session, protocol_warning).await
 }
 
-async fn run_interactive(server: &str, session: ClientSession) -> Result<()> {
+async fn run_interactive(
+    config: &ClientConfig,
+    session: ClientSession,
+    protocol_warning: Option<String>,
+) -> Result<()> {
     let mut app = App::new(session.clone());
     app.push_event(EventRecord::local(format!(
         "[local] session={} agent={} user={}",
         session.session_id, session.agent_id, session.user_id
     )));
+    if let Some(warning) = protocol_warning {
+        app.push_event(EventRecord::local(format!("[warning] {warning}")));
+    }
 
     let (event_tx, mut event_rx) = mpsc::unbounded_channel::<AppEvent>();
-    let mut stream = attach_session_events(server, &session.session_id).await?;
+    let mut stream = attach_session_events(config, &session.session_id).await?;
     let stream_event_tx = event_tx.clone();
 
     tokio::spawn(async move {
@@ -323,7 +419,10 @@ async fn run_interactive(server: &str, session: ClientSession) -> Result<()> {
             match stream.message().await {
                 Ok(Some(event)) => {
                     if stream_event_tx
-                        .send(AppEvent::Record(session_event_to_record(&event)))
+                        .send(AppEvent::Session {
+                            seq: event.seq,
+                            record: session_event_to_record(&event),
+                        })
                         .is_err()
                     {
                         break;
@@ -349,11 +448,21 @@ async fn run_interactive(server: &str, session: ClientSession) -> Result<()> {
     enable_raw_mode()?;
     let mut stdout = io::stdout();
     execute!(stdout, EnterAlternateScreen)?;
+    let keyboard_enhanced = supports_keyboard_enhancement().unwrap_or(false);
+    if keyboard_enhanced {
+        execute!(
+            stdout,
+            PushKeyboardEnhancementFlags(KeyboardEnhancementFlags::DISAMBIGUATE_ESCAPE_CODES)
+        )?;
+    }
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
-    let run_result = run_loop(server, &mut app, &event_tx, &mut event_rx, &mut terminal).await;
+    let run_result = run_loop(config, &mut app, &event_tx, &mut event_rx, &mut terminal).await;
 
+    if keyboard_enhanced {
+        execute!(terminal.backend_mut(), PopKeyboardEnhancementFlags)?;
+    }
     disable_raw_mode()?;
     execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
     terminal.show_cursor()?;
@@ -362,7 +471,7 @@ async fn run_interactive(server: &str, session: ClientSession) -> Result<()> {
 }
 
 async fn run_loop(
-    server: &str,
+    config: &ClientConfig,
     app: &mut App,
     event_tx: &mpsc::UnboundedSender<AppEvent>,
     event_rx: &mut mpsc::UnboundedReceiver<AppEvent>,
@@ -372,13 +481,16 @@ async fn run_loop(
         while let Ok(event) = event_rx.try_recv() {
             match event {
                 AppEvent::Record(record) => app.push_event(record),
+                AppEvent::Session { seq, record } => {
+                    app.push_session_event(seq, record);
+                }
                 AppEvent::Status(status) => app.status = status,
             }
         }
 
         let terminal_area: Rect = terminal.size()?.into();
         let footer_height = wrapped_line_count(app.footer_text(), terminal_area.width);
-        let rows = main_layout(terminal_area, footer_height);
+        let rows = main_layout(terminal_area, footer_height, app.input.as_str());
         let viewport_height = app.active_tab().viewport_height(rows[0]);
         let viewport_width = app.active_tab().viewport_width(rows[0]);
         app.active_tab_mut()
@@ -391,7 +503,7 @@ async fn run_loop(
 
         terminal.draw(|frame| {
             let footer_height = wrapped_line_count(app.footer_text(), frame.area().width);
-            let rows = main_layout(frame.area(), footer_height);
+            let rows = main_layout(frame.area(), footer_height, app.input.as_str());
             app.active_tab()
                 .render(frame, rows[0], &app.session.session_id);
 
@@ -400,17 +512,24 @@ async fn run_loop(
                 .block(Block::default().title("Activity").borders(Borders::ALL));
             frame.render_widget(activity_panel, rows[1]);
 
-            let input_panel = Paragraph::new(app.input.as_str()).block(
-                Block::default()
-                    .title(format!("Input ({})", app.status))
-                    .borders(Borders::ALL),
-            );
+            let input_cursor = input_cursor_offset(app.input.as_str());
+            let input_panel = Paragraph::new(app.input.as_str())
+                .scroll((input_cursor.scroll, 0))
+                .block(
+                    Block::default()
+                        .title(format!("Input ({})", app.status))
+                        .borders(Borders::ALL),
+                );
             frame.render_widget(input_panel, rows[2]);
 
             if app.completion_is_visible() {
                 render_completion_popup(frame, rows[0], &app.completion);
             }
 
+            if app.palette.open {
+                render_command_palette(frame, frame.area(), &app.palette);
+            }
+
             if let Some(detail) = app.execution_detail() {
                 render_execution_detail_popup(frame, frame.area(), detail);
             }
@@ -420,12 +539,12 @@ async fn run_loop(
                 rows[3],
             );
 
-            if app.execution_detail().is_none() {
+            if app.execution_detail().is_none() && !app.palette.open {
                 let x = rows[2]
                     .x
                     .saturating_add(1)
-                    .saturating_add(app.input.chars().count() as u16);
-                let y = rows[2].y.saturating_add(1);
+                    .saturating_add(input_cursor.column);
+                let y = rows[2].y.saturating_add(1).saturating_add(input_cursor.row);
                 frame.set_cursor_position((x, y));
             }
         })?;
@@ -483,6 +602,32 @@ async fn run_loop(
             continue;
         }
 
+        if app.palette.open {
+            match key.code {
+                KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    return Ok(());
+                }
+                KeyCode::Esc => app.palette.close(),
+                KeyCode::Up => app.palette.select_prev(),
+                KeyCode::Down => app.palette.select_next(),
+                KeyCode::Backspace => app.palette.pop_char(),
+                KeyCode::Enter => {
+                    if let Some(selected) = app.palette.selected() {
+                        app.palette.close();
+                        spawn_slash_command(config, app, event_tx, format!("/{}", selected.name));
+                    }
+                }
+                KeyCode::Char(ch) => app.palette.push_char(ch),
+                _ => {}
+            }
+            continue;
+        }
+
+        if key.code == KeyCode::Char(':') && app.input.is_empty() {
+            app.palette.open();
+            continue;
+        }
+
         if app.completion_is_visible() {
             match key.code {
                 KeyCode::Up => {
@@ -539,6 +684,10 @@ async fn run_loop(
             KeyCode::End => app
                 .active_tab_mut()
                 .scroll_to_bottom(viewport_height, viewport_width),
+            KeyCode::Enter if key.modifiers.contains(KeyModifiers::SHIFT) => {
+                app.input.push('\n');
+                app.refresh_completion();
+            }
             KeyCode::Enter if !key.modifiers.contains(KeyModifiers::CONTROL) => {
                 let text = normalized_submit_text(app.input.as_str());
                 app.input.clear();
@@ -548,32 +697,17 @@ async fn run_loop(
                 };
 
                 if text.starts_with('/') {
-                    app.status = "running command...".to_string();
-                    let server = server.to_string();
-                    let session = app.session.clone();
-                    let event_tx = event_tx.clone();
-                    tokio::spawn(async move {
-                        match execute_slash_command(&text, &server, &session).await {
-                            SlashExecution::NotSlashInput => {}
-                            SlashExecution::Handled { status, local_log } => {
-                                let _ = event_tx.send(AppEvent::Status(status));
-                                if let Some(local_log) = local_log {
-                                    let _ = event_tx
-                                        .send(AppEvent::Record(EventRecord::local(local_log)));
-                                }
-                            }
-                        }
-                    });
+                    spawn_slash_command(config, app, event_tx, text);
                     continue;
                 }
 
                 app.status = "queueing message...".to_string();
-                let server = server.to_string();
+                let config = config.clone();
                 let session_id = app.session.session_id.clone();
                 let user_id = app.session.user_id.clone();
                 let event_tx = event_tx.clone();
                 tokio::spawn(async move {
-                    match enqueue_user_message(&server, &session_id, &user_id, &text).await {
+                    match enqueue_user_message(&config, &session_id, &user_id, &text).await {
                         Ok(trigger_id) => {
                             let _ = event_tx
                                 .send(AppEvent::Status(format!("message queued ({trigger_id})")));
@@ -608,6 +742,99 @@ async fn run_loop(
     }
 }
 
+fn spawn_slash_command(
+    config: &ClientConfig,
+    app: &mut App,
+    event_tx: &mpsc::UnboundedSender<AppEvent>,
+    text: String,
+) {
+    app.status = "running command...".to_string();
+    let config = config.clone();
+    let session = app.session.clone();
+    let event_tx = event_tx.clone();
+    tokio::spawn(async move {
+        match execute_slash_command(&text, &config, &session).await {
+            SlashExecution::NotSlashInput => {}
+            SlashExecution::Handled { status, local_log } => {
+                let _ = event_tx.send(AppEvent::Status(status));
+                if let Some(local_log) = local_log {
+                    let _ = event_tx.send(AppEvent::Record(EventRecord::local(local_log)));
+                }
+            }
+        }
+    });
+}
+
+fn render_command_palette(
+    frame: &mut ratatui::Frame<'_>,
+    area: Rect,
+    palette: &CommandPaletteState,
+) {
+    let visible_rows = palette.items.len().clamp(1, MAX_PALETTE_ROWS);
+    let width = area.width.saturating_mul(3).saturating_div(5).max(40);
+    let width = width.min(area.width.max(1));
+    let height = (visible_rows as u16)
+        .saturating_add(3)
+        .min(area.height.max(1));
+    let popup = Rect::new(
+        area.x.saturating_add(area.width.saturating_sub(width) / 2),
+        area.y
+            .saturating_add(area.height.saturating_sub(height) / 3),
+        width,
+        height,
+    );
+    frame.render_widget(Clear, popup);
+
+    let block = Block::default()
+        .title("Command Palette")
+        .borders(Borders::ALL);
+    let inner = block.inner(popup);
+    frame.render_widget(block, popup);
+    if inner.height == 0 {
+        return;
+    }
+
+    let query_area = Rect::new(inner.x, inner.y, inner.width, 1);
+    frame.render_widget(Paragraph::new(format!(": {}", palette.query)), query_area);
+    frame.set_cursor_position((
+        query_area
+            .x
+            .saturating_add(2)
+            .saturating_add(palette.query.chars().count() as u16),
+        query_area.y,
+    ));
+
+    let list_area = Rect::new(
+        inner.x,
+        inner.y.saturating_add(1),
+        inner.width,
+        inner.height.saturating_sub(1),
+    );
+    if palette.items.is_empty() {
+        frame.render_widget(Paragraph::new("no matching commands"), list_area);
+        return;
+    }
+
+    let selected = palette
+        .selected_index
+        .min(palette.items.len().saturating_sub(1));
+    let start_index = selected.saturating_sub(visible_rows.saturating_sub(1));
+    let end_index = start_index
+        .saturating_add(visible_rows)
+        .min(palette.items.len());
+    let items = palette.items[start_index..end_index]
+        .iter()
+        .map(|spec| ListItem::new(format!("/{} - {}", spec.name, spec.description)))
+        .collect::<Vec<_>>();
+    let list = List::new(items)
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+        .highlight_symbol("> ");
+
+    let mut state = ListState::default();
+    state.select(Some(selected.saturating_sub(start_index)));
+    frame.render_stateful_widget(list, list_area, &mut state);
+}
+
 fn render_completion_popup(
     frame: &mut ratatui::Frame<'_>,
     history_area: Rect,
@@ -750,13 +977,13 @@ fn pretty_json_or_raw(source: &str) -> String {
     }
 }
 
-fn main_layout(area: Rect, footer_height: u16) -> [Rect; 4] {
+fn main_layout(area: Rect, footer_height: u16, input: &str) -> [Rect; 4] {
     let rows = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
             Constraint::Min(5),
             Constraint::Length(3),
-            Constraint::Length(3),
+            Constraint::Length(input_panel_height(input)),
             Constraint::Length(footer_height.max(1)),
         ])
         .split(area);
@@ -778,6 +1005,32 @@ fn wrapped_line_count(text: &str, width: u16) -> u16 {
     wrapped.max(1)
 }
 
+fn input_line_count(input: &str) -> u16 {
+    input.split('\n').count().min(u16::MAX as usize) as u16
+}
+
+fn input_panel_height(input: &str) -> u16 {
+    input_line_count(input).clamp(1, MAX_INPUT_LINES) + 2
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct InputCursorOffset {
+    column: u16,
+    row: u16,
+    scroll: u16,
+}
+
+fn input_cursor_offset(input: &str) -> InputCursorOffset {
+    let line_count = input_line_count(input);
+    let last_line = input.rsplit('\n').next().unwrap_or_default();
+    let scroll = line_count.saturating_sub(MAX_INPUT_LINES);
+    InputCursorOffset {
+        column: last_line.chars().count().min(u16::MAX as usize) as u16,
+        row: line_count.saturating_sub(1) - scroll,
+        scroll,
+    }
+}
+
 fn normalized_submit_text(input: &str) -> Option<String> {
     let trimmed = input.trim();
     if trimmed.is_empty() {
@@ -788,9 +1041,14 @@ fn normalized_submit_text(input: &str) -> Option<String> {
 
 #[cfg(test)]
 mod tests {
-    use super::{ActivityState, App, SlashCompletionState, normalized_submit_text};
+    use super::{
+        ActivityState, App, CommandPaletteState, InputCursorOffset, SlashCompletionState,
+        input_cursor_offset, input_panel_height, normalized_submit_text,
+    };
     use crate::runtime::ClientSession;
     use crate::view::{EventRecord, SessionEventRecordKind};
+    use ratatui::Terminal;
+    use ratatui::backend::TestBackend;
 
     fn test_session() -> ClientSession {
         ClientSession {
@@ -827,6 +1085,31 @@ mod tests {
         assert!(!app.completion_is_visible());
     }
 
+    #[test]
+    fn command_palette_filters_as_query_is_typed() {
+        let mut palette = CommandPaletteState::default();
+        palette.open();
+        assert!(palette.open);
+        assert_eq!(palette.items.len(), 2);
+
+        palette.select_next();
+        assert_eq!(palette.selected().map(|spec| spec.name), Some("export"));
+
+        palette.push_char('e');
+        palette.push_char('t');
+        assert_eq!(palette.selected().map(|spec| spec.name), Some("export"));
+
+        palette.push_char('z');
+        assert!(palette.selected().is_none());
+
+        palette.pop_char();
+        assert_eq!(palette.items.len(), 2);
+
+        palette.close();
+        assert!(!palette.open);
+        assert!(palette.query.is_empty());
+    }
+
     #[test]
     fn normalized_submit_text_rejects_blank_and_trims() {
         assert_eq!(normalized_submit_text(""), None);
@@ -837,6 +1120,49 @@ mod tests {
         );
     }
 
+    #[test]
+    fn normalized_submit_text_preserves_inner_newlines() {
+        assert_eq!(
+            normalized_submit_text("first line\n\nsecond line\n"),
+            Some("first line\n\nsecond line".to_string())
+        );
+    }
+
+    #[test]
+    fn input_panel_grows_with_newlines_up_to_limit() {
+        assert_eq!(input_panel_height(""), 3);
+        assert_eq!(input_panel_height("a\nb\nc"), 5);
+        assert_eq!(input_panel_height("1\n2\n3\n4\n5\n6\n7"), 7);
+    }
+
+    #[test]
+    fn input_cursor_tracks_last_line_and_scrolls_past_limit() {
+        assert_eq!(
+            input_cursor_offset("hello"),
+            InputCursorOffset {
+                column: 5,
+                row: 0,
+                scroll: 0,
+            }
+        );
+        assert_eq!(
+            input_cursor_offset("hello\nwo"),
+            InputCursorOffset {
+                column: 2,
+                row: 1,
+                scroll: 0,
+            }
+        );
+        assert_eq!(
+            input_cursor_offset("1\n2\n3\n4\n5\n6\n"),
+            InputCursorOffset {
+                column: 0,
+                row: 4,
+                scroll: 2,
+            }
+        );
+    }
+
     #[test]
     fn activity_line_updates_from_agent_and_execution_events() {
         let mut activity = ActivityState::default();
@@ -885,8 +1211,110 @@ mod tests {
                 turn_id: 1,
                 reason: "done".to_string(),
                 history_size: 0,
+                agent_attempts: 1,
+                model_request_retries: 0,
             },
         });
         assert_eq!(activity.render_line(), "agent=idle | active_executions=0");
     }
+
+    #[test]
+    fn activity_line_shows_thinking_until_first_model_output() {
+        let mut activity = ActivityState::default();
+        let thinking = |thinking| EventRecord::Session {
+            session_id: "s1".to_string(),
+            kind: SessionEventRecordKind::AgentThinking {
+                turn_id: 1,
+                thinking,
+            },
+        };
+
+        activity.on_event(&EventRecord::Session {
+            session_id: "s1".to_string(),
+            kind: SessionEventRecordKind::AgentStream {
+                phase: "openai.request.start".to_string(),
+                detail: "attempt=1".to_string(),
+            },
+        });
+        activity.on_event(&thinking(true));
+        assert!(activity.render_line().contains("agent=thinking"));
+
+        activity.on_event(&thinking(false));
+        assert!(activity.render_line().contains("agent=invoking"));
+
+        activity.on_event(&thinking(true));
+        activity.on_event(&EventRecord::Session {
+            session_id: "s1".to_string(),
+            kind: SessionEventRecordKind::TurnFailure {
+                turn_id: 1,
+                reason_code: "model_http_error".to_string(),
+                message: "bad request".to_string(),
+            },
+        });
+        assert_eq!(activity.render_line(), "agent=idle | active_executions=0");
+    }
+
+    #[test]
+    fn replayed_session_events_with_seen_seq_are_dropped() {
+        let mut app = App::new(test_session());
+        let turn_ended = || EventRecord::Session {
+            session_id: "session-test".to_string(),
+            kind: SessionEventRecordKind::TurnEnded {
+                turn_id: 1,
+                reason: "done".to_string(),
+                history_size: 0,
+                agent_attempts: 1,
+                model_request_retries: 0,
+            },
+        };
+        let turn_attempt = EventRecord::Session {
+            session_id: "session-test".to_string(),
+            kind: SessionEventRecordKind::AgentStream {
+                phase: "agent.turn.attempt".to_string(),
+                detail: "semantic_attempt=1".to_string(),
+            },
+        };
+
+        assert!(app.push_session_event(5, turn_ended()));
+        assert!(app.push_session_event(6, turn_attempt));
+        assert!(!app.push_session_event(5, turn_ended()));
+        assert!(!app.push_session_event(6, turn_ended()));
+
+        assert_eq!(app.last_seq, 6);
+        assert_eq!(
+            app.activity.render_line(),
+            "agent=invoking | active_executions=0"
+        );
+    }
+
+    #[test]
+    fn session_event_seq_gaps_are_reported_as_missed_events() {
+        let mut app = App::new(test_session());
+        let diagnostic = |detail: &str| EventRecord::Session {
+            session_id: "session-test".to_string(),
+            kind: SessionEventRecordKind::AgentStream {
+                phase: "agent.turn.attempt".to_string(),
+                detail: detail.to_string(),
+            },
+        };
+
+        assert!(app.push_session_event(3, diagnostic("first")));
+        assert!(app.push_session_event(4, diagnostic("second")));
+        assert!(app.push_session_event(7, diagnostic("third")));
+        assert_eq!(app.last_seq, 7);
+
+        let mut terminal = Terminal::new(TestBackend::new(80, 12)).expect("test terminal");
+        terminal
+            .draw(|frame| app.tabs[3].render(frame, frame.area(), "session-test"))
+            .expect("draw events tab");
+        let rendered = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect::<String>();
+        assert_eq!(rendered.matches("missed").count(), 1);
+        assert!(rendered.contains("[stream] missed 2 session event(s)"));
+    }
 }
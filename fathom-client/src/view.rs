@@ -33,6 +33,8 @@ pub(crate) enum SessionEventRecordKind {
         turn_id: u64,
         reason: String,
         history_size: u64,
+        agent_attempts: u64,
+        model_request_retries: u64,
     },
     AssistantOutput {
         content: String,
@@ -75,11 +77,34 @@ pub(crate) enum SessionEventRecordKind {
         phase: String,
         detail: String,
     },
+    AgentThinking {
+        turn_id: u64,
+        thinking: bool,
+    },
     TurnFailure {
         turn_id: u64,
         reason_code: String,
         message: String,
     },
+    SessionExpired {
+        reason: String,
+    },
+    Diagnostic {
+        detail: String,
+    },
+    TriggerQueueCleared {
+        cleared_count: u64,
+    },
+    QueueDepthWarning {
+        queue_depth: u64,
+        threshold: u64,
+    },
+    CompactionDone {
+        block_id: String,
+        source_range_start: u64,
+        source_range_end: u64,
+        summary_bytes: u64,
+    },
     Unknown,
 }
 
@@ -116,6 +141,8 @@ pub(crate) fn session_event_to_record(event: &pb::SessionEvent) -> EventRecord {
             turn_id: data.turn_id,
             reason: data.reason.clone(),
             history_size: data.history_size,
+            agent_attempts: data.agent_attempts,
+            model_request_retries: data.model_request_retries,
         },
         pb::session_event::Kind::AssistantOutput(data) => SessionEventRecordKind::AssistantOutput {
             content: data.content.clone(),
@@ -152,10 +179,9 @@ pub(crate) fn session_event_to_record(event: &pb::SessionEvent) -> EventRecord {
                     .to_string(),
                 args_preview: summarize_for_preview(&args_json, EXECUTION_ARGS_PREVIEW_MAX_CHARS),
                 args_json,
-                result_preview: summarize_for_preview(
-                    &result_message,
-                    EXECUTION_RESULT_PREVIEW_MAX_CHARS,
-                ),
+                result_preview: summarize_execution_result(&result_message).unwrap_or_else(|| {
+                    summarize_for_preview(&result_message, EXECUTION_RESULT_PREVIEW_MAX_CHARS)
+                }),
                 result_message,
             }
         }
@@ -205,11 +231,38 @@ pub(crate) fn session_event_to_record(event: &pb::SessionEvent) -> EventRecord {
             phase: data.phase.clone(),
             detail: data.detail.clone(),
         },
+        pb::session_event::Kind::AgentThinking(data) => SessionEventRecordKind::AgentThinking {
+            turn_id: data.turn_id,
+            thinking: data.thinking,
+        },
         pb::session_event::Kind::TurnFailure(data) => SessionEventRecordKind::TurnFailure {
             turn_id: data.turn_id,
             reason_code: data.reason_code.clone(),
             message: data.message.clone(),
         },
+        pb::session_event::Kind::SessionExpired(data) => SessionEventRecordKind::SessionExpired {
+            reason: data.reason.clone(),
+        },
+        pb::session_event::Kind::Diagnostic(data) => SessionEventRecordKind::Diagnostic {
+            detail: data.detail.clone(),
+        },
+        pb::session_event::Kind::TriggerQueueCleared(data) => {
+            SessionEventRecordKind::TriggerQueueCleared {
+                cleared_count: data.cleared_count,
+            }
+        }
+        pb::session_event::Kind::QueueDepthWarning(data) => {
+            SessionEventRecordKind::QueueDepthWarning {
+                queue_depth: data.queue_depth,
+                threshold: data.threshold,
+            }
+        }
+        pb::session_event::Kind::CompactionDone(data) => SessionEventRecordKind::CompactionDone {
+            block_id: data.block_id.clone(),
+            source_range_start: data.source_range_start,
+            source_range_end: data.source_range_end,
+            summary_bytes: data.summary_bytes,
+        },
     };
 
     EventRecord::Session {
@@ -240,8 +293,16 @@ pub(crate) fn render_event_record(record: &EventRecord) -> String {
                     turn_id,
                     reason,
                     history_size,
+                    agent_attempts,
+                    model_request_retries,
                 } => {
-                    format!("{prefix} turn {turn_id} ended: {reason} (history={history_size})")
+                    if *agent_attempts > 1 || *model_request_retries > 0 {
+                        format!(
+                            "{prefix} turn {turn_id} ended: {reason} (history={history_size}, attempts={agent_attempts}, request_retries={model_request_retries})"
+                        )
+                    } else {
+                        format!("{prefix} turn {turn_id} ended: {reason} (history={history_size})")
+                    }
                 }
                 SessionEventRecordKind::AssistantOutput { content, stream_id } => {
                     if stream_id.is_empty() {
@@ -281,7 +342,9 @@ pub(crate) fn render_event_record(record: &EventRecord) -> String {
                     let mut line = format!(
                         "{prefix} execution {execution_id} {action_id} -> {status} args={args_preview}"
                     );
-                    if (status == "failed" || status == "canceled") && !result_preview.is_empty() {
+                    if (status == "succeeded" || status == "failed" || status == "canceled")
+                        && !result_preview.is_empty()
+                    {
                         line.push_str(&format!(" result={result_preview}"));
                     }
                     line
@@ -344,6 +407,13 @@ pub(crate) fn render_event_record(record: &EventRecord) -> String {
                 SessionEventRecordKind::AgentStream { phase, detail } => {
                     format!("{prefix} agent stream [{phase}] {detail}")
                 }
+                SessionEventRecordKind::AgentThinking { turn_id, thinking } => {
+                    if *thinking {
+                        format!("{prefix} turn {turn_id} agent thinking")
+                    } else {
+                        format!("{prefix} turn {turn_id} agent responding")
+                    }
+                }
                 SessionEventRecordKind::TurnFailure {
                     turn_id,
                     reason_code,
@@ -351,6 +421,29 @@ pub(crate) fn render_event_record(record: &EventRecord) -> String {
                 } => {
                     format!("{prefix} turn {turn_id} failed [{reason_code}]: {message}")
                 }
+                SessionEventRecordKind::SessionExpired { reason } => {
+                    format!("{prefix} session expired: {reason}")
+                }
+                SessionEventRecordKind::Diagnostic { detail } => {
+                    format!("{prefix} diagnostic {detail}")
+                }
+                SessionEventRecordKind::TriggerQueueCleared { cleared_count } => {
+                    format!("{prefix} trigger queue cleared ({cleared_count} dropped)")
+                }
+                SessionEventRecordKind::QueueDepthWarning {
+                    queue_depth,
+                    threshold,
+                } => format!(
+                    "{prefix} trigger queue depth {queue_depth} reached warning threshold {threshold}"
+                ),
+                SessionEventRecordKind::CompactionDone {
+                    block_id,
+                    source_range_start,
+                    source_range_end,
+                    summary_bytes,
+                } => format!(
+                    "{prefix} history compacted {block_id} source=[{source_range_start},{source_range_end}) summary_bytes={summary_bytes}"
+                ),
                 SessionEventRecordKind::Unknown => format!("{prefix} event without payload"),
             }
         }
@@ -378,6 +471,48 @@ fn summarize_for_preview(source: &str, max_chars: usize) -> String {
     format!("{prefix}... ({omitted} chars omitted)")
 }
 
+fn summarize_execution_result(result_message: &str) -> Option<String> {
+    let result = serde_json::from_str::<serde_json::Value>(result_message.trim()).ok()?;
+    let ok = result.get("ok")?.as_bool()?;
+    let (status, detail) = if ok {
+        ("ok".to_string(), result.get("data")?)
+    } else {
+        let error = result.get("error")?;
+        let code = error.get("code")?.as_str()?;
+        (
+            format!("error_code={code}"),
+            error.get("details").unwrap_or(&serde_json::Value::Null),
+        )
+    };
+
+    let mut parts = vec![status];
+    for key in ["op", "path", "target"] {
+        if let Some(value) = detail.get(key).and_then(serde_json::Value::as_str)
+            && !(key == "target" && detail.get("path") == detail.get("target"))
+        {
+            parts.push(format!("{key}={value}"));
+        }
+    }
+    if ok && parts.len() == 1 && !detail.is_null() {
+        parts.push(format!(
+            "data={}",
+            summarize_for_preview(&detail.to_string(), EXECUTION_RESULT_PREVIEW_MAX_CHARS)
+        ));
+    }
+    if !ok
+        && let Some(message) = result
+            .pointer("/error/message")
+            .and_then(serde_json::Value::as_str)
+            .filter(|message| !message.trim().is_empty())
+    {
+        parts.push(format!(
+            "message={}",
+            summarize_for_preview(message, EXECUTION_RESULT_PREVIEW_MAX_CHARS)
+        ));
+    }
+    Some(parts.join(" "))
+}
+
 fn normalize_json_if_possible(source: &str) -> String {
     let trimmed = source.trim();
     if trimmed.is_empty() {
@@ -403,6 +538,7 @@ mod tests {
         let event = pb::SessionEvent {
             session_id: "s1".to_string(),
             created_at_unix_ms: 0,
+            seq: 0,
             kind: Some(pb::session_event::Kind::ExecutionStateChanged(
                 pb::ExecutionStateChangedEvent {
                     execution: Some(pb::Execution {
@@ -414,6 +550,8 @@ mod tests {
                         result_message: String::new(),
                         created_at_unix_ms: 0,
                         updated_at_unix_ms: 0,
+                        call_id: String::new(),
+                        turn_id: 0,
                     }),
                 },
             )),
@@ -430,6 +568,7 @@ mod tests {
         let event = pb::SessionEvent {
             session_id: "s1".to_string(),
             created_at_unix_ms: 0,
+            seq: 0,
             kind: Some(pb::session_event::Kind::ExecutionStateChanged(
                 pb::ExecutionStateChangedEvent {
                     execution: Some(pb::Execution {
@@ -442,6 +581,8 @@ mod tests {
                             .to_string(),
                         created_at_unix_ms: 0,
                         updated_at_unix_ms: 0,
+                        call_id: String::new(),
+                        turn_id: 0,
                     }),
                 },
             )),
@@ -453,11 +594,65 @@ mod tests {
         assert!(line.contains("result=not found\\nthis file does not exist"));
     }
 
+    fn execution_event_with_result(
+        status: pb::ExecutionStatus,
+        result_message: &str,
+    ) -> pb::SessionEvent {
+        pb::SessionEvent {
+            session_id: "s1".to_string(),
+            created_at_unix_ms: 0,
+            seq: 0,
+            kind: Some(pb::session_event::Kind::ExecutionStateChanged(
+                pb::ExecutionStateChangedEvent {
+                    execution: Some(pb::Execution {
+                        execution_id: "execution-3".to_string(),
+                        session_id: "s1".to_string(),
+                        action_id: "filesystem__read".to_string(),
+                        args_json: r#"{"path":"notes.txt"}"#.to_string(),
+                        status: status as i32,
+                        result_message: result_message.to_string(),
+                        created_at_unix_ms: 0,
+                        updated_at_unix_ms: 0,
+                        call_id: String::new(),
+                        turn_id: 0,
+                    }),
+                },
+            )),
+        }
+    }
+
+    #[test]
+    fn execution_event_render_summarizes_structured_success_result() {
+        let event = execution_event_with_result(
+            pb::ExecutionStatus::Succeeded,
+            r#"{"ok":true,"data":{"ok":true,"op":"read","path":"notes.txt","target":"notes.txt","data":{"content":"hello\nworld","total_lines":2}},"execution_time_ms":3}"#,
+        );
+        let line = render_event_record(&session_event_to_record(&event));
+
+        assert!(line.contains("-> succeeded"));
+        assert!(line.ends_with("result=ok op=read path=notes.txt"));
+        assert!(!line.contains("total_lines"));
+    }
+
+    #[test]
+    fn execution_event_render_summarizes_structured_error_result() {
+        let event = execution_event_with_result(
+            pb::ExecutionStatus::Failed,
+            r#"{"ok":false,"error":{"kind":"runtime_error","code":"not_found","message":"path does not exist","details":{"ok":false,"op":"read","path":"notes.txt","error_code":"not_found","message":"path does not exist"}},"execution_time_ms":1}"#,
+        );
+        let line = render_event_record(&session_event_to_record(&event));
+
+        assert!(line.ends_with(
+            "result=error_code=not_found op=read path=notes.txt message=path does not exist"
+        ));
+    }
+
     #[test]
     fn execution_update_render_includes_phase_and_execution() {
         let event = pb::SessionEvent {
             session_id: "s1".to_string(),
             created_at_unix_ms: 0,
+            seq: 0,
             kind: Some(pb::session_event::Kind::ExecutionUpdate(
                 pb::ExecutionUpdateEvent {
                     phase: pb::ExecutionUpdatePhase::ExecutionBackgrounded as i32,
@@ -486,6 +681,7 @@ mod tests {
         let event = pb::SessionEvent {
             session_id: "s1".to_string(),
             created_at_unix_ms: 0,
+            seq: 0,
             kind: Some(pb::session_event::Kind::SystemNotice(
                 pb::SystemNoticeEvent {
                     level: pb::SystemNoticeLevel::Info as i32,
@@ -500,4 +696,26 @@ mod tests {
         assert!(line.contains("system notice [info]"));
         assert!(line.contains("profile_refresh"));
     }
+
+    #[test]
+    fn queue_depth_warning_event_render_includes_depth_and_threshold() {
+        let event = pb::SessionEvent {
+            session_id: "s1".to_string(),
+            created_at_unix_ms: 0,
+            seq: 0,
+            kind: Some(pb::session_event::Kind::QueueDepthWarning(
+                pb::QueueDepthWarningEvent {
+                    queue_depth: 32,
+                    threshold: 32,
+                },
+            )),
+        };
+        let record = session_event_to_record(&event);
+        let line = render_event_record(&record);
+
+        assert_eq!(
+            line,
+            "[s1] trigger queue depth 32 reached warning threshold 32"
+        );
+    }
 }
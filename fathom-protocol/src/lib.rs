@@ -1,5 +1,7 @@
 mod labels;
 
+pub const PROTOCOL_VERSION: u32 = 1;
+
 pub mod pb {
     tonic::include_proto!("fathom.v1");
 }
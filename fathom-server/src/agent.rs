@@ -1,42 +1,64 @@
 mod action_catalog;
+mod anthropic;
+mod error;
 mod model_adapter;
 mod openai;
 mod prompt;
 mod prompt_input_builder;
 mod retry;
+#[cfg(test)]
+mod testing;
 mod types;
 
 #[cfg(test)]
 pub(crate) use types::{ActionArgDeltaNote, ActionArgDoneNote};
 pub(crate) use types::{
-    ActionInvocation, AgentInvocationContext, AgentTurnOutcome, CapabilityAction, CapabilityDomain,
-    CapabilityRecipe, CapabilitySurface, CompiledPrompt, HarnessContract, IdentityEnvelope,
-    ModelDeltaEvent, ModelInvocationOutcome, ParticipantEnvelope, PromptMessage,
-    ResolvedPayloadLookupHint, SessionAnchor, SessionBaseline, SessionCompaction, StreamNote,
-    SummaryBlockRef,
+    ActionInvocation, AgentInvocationContext, AgentTurnOutcome, AgentTurnRetrySummary,
+    CapabilityAction, CapabilityDomain, CapabilityRecipe, CapabilitySurface, CompiledPrompt,
+    HarnessContract, IdentityEnvelope, ModelDeltaEvent, ModelInvocationOutcome,
+    ParticipantEnvelope, PromptMessage, ResolvedPayloadLookupHint, SessionAnchor, SessionBaseline,
+    SessionCompaction, StreamNote, SummaryBlockRef,
 };
 
+use std::collections::BTreeSet;
 use std::sync::Arc;
 
+use tokio::sync::{Semaphore, SemaphorePermit};
+
 use crate::capability_domain::CapabilityDomainRegistry;
 pub(crate) use action_catalog::SessionActionCatalog;
-use model_adapter::{ModelAdapter, UnavailableModelAdapter};
+use anthropic::AnthropicModelAdapter;
+use model_adapter::{ModelAdapter, ModelProvider, UnavailableModelAdapter};
 use openai::OpenAiModelAdapter;
 use prompt::PromptCompiler;
 use prompt_input_builder::build_prompt_input;
 
+const HISTORY_SUMMARY_INSTRUCTIONS: &str = "Summarize the session history events below so the agent can continue the session without them. Keep user requests, decisions, action outcomes, and open follow-ups. Reply with the summary as plain text only.";
+
 #[derive(Clone)]
 pub(crate) struct AgentOrchestrator {
     model_adapter: Arc<dyn ModelAdapter>,
     capability_domain_registry: CapabilityDomainRegistry,
     prompt_compiler: PromptCompiler,
+    action_allowlist: Option<Arc<BTreeSet<String>>>,
+    model_request_limiter: Option<Arc<Semaphore>>,
 }
 
 impl AgentOrchestrator {
     pub(crate) fn new(capability_domain_registry: CapabilityDomainRegistry) -> Self {
-        let model_adapter: Arc<dyn ModelAdapter> = match OpenAiModelAdapter::new() {
-            Ok(adapter) => Arc::new(adapter),
-            Err(error) => Arc::new(UnavailableModelAdapter::new("openai", error)),
+        let model_adapter: Arc<dyn ModelAdapter> = match ModelProvider::from_env() {
+            Ok(provider) => {
+                let adapter = match provider {
+                    ModelProvider::OpenAi => OpenAiModelAdapter::new()
+                        .map(|adapter| Arc::new(adapter) as Arc<dyn ModelAdapter>),
+                    ModelProvider::Anthropic => AnthropicModelAdapter::new()
+                        .map(|adapter| Arc::new(adapter) as Arc<dyn ModelAdapter>),
+                };
+                adapter.unwrap_or_else(|error| {
+                    Arc::new(UnavailableModelAdapter::new(provider.name(), error))
+                })
+            }
+            Err(error) => Arc::new(UnavailableModelAdapter::new("unknown", error)),
         };
         Self::from_parts(
             model_adapter,
@@ -54,8 +76,54 @@ impl AgentOrchestrator {
         self.prompt_compiler.compile(&input)
     }
 
+    pub(crate) fn with_action_allowlist(mut self, action_ids: Vec<String>) -> Result<Self, String> {
+        let mut action_allowlist = BTreeSet::new();
+        for action_id in action_ids {
+            let action_id = action_id.trim();
+            if action_id.is_empty() {
+                continue;
+            }
+            let canonical_action_id = CapabilityDomainRegistry::canonicalize_action_id(action_id)
+                .filter(|canonical_action_id| {
+                    self.capability_domain_registry
+                        .resolve(canonical_action_id)
+                        .is_some()
+                })
+                .ok_or_else(|| format!("unknown action `{action_id}` in action allowlist"))?;
+            action_allowlist.insert(canonical_action_id);
+        }
+        if action_allowlist.is_empty() {
+            return Err("action allowlist must keep at least one action".to_string());
+        }
+        self.action_allowlist = Some(Arc::new(action_allowlist));
+        Ok(self)
+    }
+
+    pub(crate) fn action_allowlist(&self) -> Option<Arc<BTreeSet<String>>> {
+        self.action_allowlist.clone()
+    }
+
+    pub(crate) fn with_max_concurrent_model_requests(
+        mut self,
+        limit: usize,
+    ) -> Result<Self, String> {
+        if limit == 0 {
+            return Err("max concurrent model requests must be at least 1".to_string());
+        }
+        self.model_request_limiter = Some(Arc::new(Semaphore::new(limit)));
+        Ok(self)
+    }
+
+    async fn acquire_model_request_permit(&self) -> Option<SemaphorePermit<'_>> {
+        match &self.model_request_limiter {
+            Some(limiter) => limiter.acquire().await.ok(),
+            None => None,
+        }
+    }
+
     fn session_action_catalog(&self, context: &AgentInvocationContext) -> SessionActionCatalog {
         SessionActionCatalog::from_context(self.capability_domain_registry.clone(), context)
+            .with_action_allowlist(self.action_allowlist.clone())
     }
 
     fn from_parts(
@@ -67,6 +135,8 @@ impl AgentOrchestrator {
             model_adapter,
             capability_domain_registry,
             prompt_compiler,
+            action_allowlist: None,
+            model_request_limiter: None,
         }
     }
 
@@ -82,12 +152,67 @@ impl AgentOrchestrator {
         )
     }
 
+    pub(crate) async fn summarize_history(&self, transcript: &str) -> Result<String, String> {
+        if let Some(error) = self.model_adapter.availability_error() {
+            return Err(error.message().to_string());
+        }
+
+        let prompt_messages = vec![
+            PromptMessage::new(
+                "system",
+                "history_summary_instructions",
+                HISTORY_SUMMARY_INSTRUCTIONS.to_string(),
+            ),
+            PromptMessage::new("user", "history_batch", transcript.to_string()),
+        ];
+        let action_catalog =
+            SessionActionCatalog::without_actions(self.capability_domain_registry.clone());
+        let mut discard_event = |_event: ModelDeltaEvent| {};
+        let permit = self.acquire_model_request_permit().await;
+        let event_sink: &mut model_adapter::ModelEventSink<'_> = &mut discard_event;
+        let result = self
+            .model_adapter
+            .stream_prompt(&prompt_messages, &action_catalog, event_sink)
+            .await;
+        drop(permit);
+
+        let outcome = result.map_err(|error| error.message().to_string())?;
+        let summary = outcome.assistant_outputs.join("\n").trim().to_string();
+        if summary.is_empty() {
+            return Err("model returned an empty history summary".to_string());
+        }
+        Ok(summary)
+    }
+
     pub(crate) async fn run_turn<F>(
         &self,
         context: &AgentInvocationContext,
         initial_prompt_bundle: CompiledPrompt,
         mut on_event: F,
     ) -> AgentTurnOutcome
+    where
+        F: FnMut(ModelDeltaEvent) + Send,
+    {
+        let mut retry_summary = AgentTurnRetrySummary::default();
+        let mut outcome = self
+            .run_turn_attempts(
+                context,
+                initial_prompt_bundle,
+                &mut on_event,
+                &mut retry_summary,
+            )
+            .await;
+        outcome.retry_summary = retry_summary;
+        outcome
+    }
+
+    async fn run_turn_attempts<F>(
+        &self,
+        context: &AgentInvocationContext,
+        initial_prompt_bundle: CompiledPrompt,
+        on_event: &mut F,
+        retry_summary: &mut AgentTurnRetrySummary,
+    ) -> AgentTurnOutcome
     where
         F: FnMut(ModelDeltaEvent) + Send,
     {
@@ -95,8 +220,9 @@ impl AgentOrchestrator {
             return AgentTurnOutcome::failure(
                 "agent_init_error",
                 format!(
-                    "model adapter `{}` initialization failed: {error}",
-                    self.model_adapter.provider_name()
+                    "model adapter `{}` initialization failed: {}",
+                    self.model_adapter.provider_name(),
+                    error.message()
                 ),
                 Vec::new(),
             );
@@ -107,6 +233,7 @@ impl AgentOrchestrator {
         let action_catalog = self.session_action_catalog(context);
 
         for semantic_attempt in 0..=1usize {
+            retry_summary.attempts = semantic_attempt + 1;
             on_event(ModelDeltaEvent::StreamNote(StreamNote {
                 phase: "agent.turn.attempt".to_string(),
                 detail: format!("semantic_attempt={}", semantic_attempt + 1),
@@ -127,11 +254,20 @@ impl AgentOrchestrator {
                     prompt_bundle.diagnostics.dedup_dropped_events
                 ),
             }));
-            let event_sink: &mut model_adapter::ModelEventSink<'_> = &mut on_event;
+            let permit = self.acquire_model_request_permit().await;
+            let event_sink: &mut model_adapter::ModelEventSink<'_> = on_event;
             let result = self
                 .model_adapter
                 .stream_prompt(&prompt_bundle.messages, &action_catalog, event_sink)
                 .await;
+            drop(permit);
+
+            if let Ok(invocation_outcome) = &result {
+                retry_summary.model_request_retries += invocation_outcome.request_retries;
+                if let Some(effort) = &invocation_outcome.fallback_effort {
+                    retry_summary.fallback_effort = Some(effort.clone());
+                }
+            }
 
             match result {
                 Ok(invocation_outcome)
@@ -206,7 +342,7 @@ You MUST emit at least one valid action call or assistant output."
                         continue;
                     }
                     return AgentTurnOutcome::failure(
-                        "model_adapter_error",
+                        error.failure_code(),
                         error.message(),
                         diagnostics,
                     );
@@ -242,19 +378,22 @@ fn is_optional_string_validation_error(error: &str) -> bool {
 #[cfg(test)]
 mod tests {
     use std::collections::VecDeque;
+    use std::sync::atomic::{AtomicUsize, Ordering};
     use std::sync::{Arc, Mutex};
+    use std::time::Duration;
 
     use super::build_retry_feedback;
-    use super::model_adapter::{
-        ModelAdapter, ModelAdapterError, ModelAdapterFuture, ModelEventSink,
-    };
+    use super::error::AgentError;
+    use super::model_adapter::{ModelAdapter, ModelAdapterFuture, ModelEventSink};
     use super::types::PromptDiagnostics;
     use super::{
-        AgentInvocationContext, AgentOrchestrator, CapabilityDomain, CapabilitySurface,
-        CompiledPrompt, HarnessContract, IdentityEnvelope, ModelDeltaEvent, ModelInvocationOutcome,
-        ParticipantEnvelope, PromptMessage, SessionAnchor, SessionBaseline, SessionCompaction,
+        AgentInvocationContext, AgentOrchestrator, AgentTurnRetrySummary, CapabilityDomain,
+        CapabilitySurface, CompiledPrompt, HarnessContract, IdentityEnvelope, ModelDeltaEvent,
+        ModelInvocationOutcome, ParticipantEnvelope, PromptMessage, SessionAnchor, SessionBaseline,
+        SessionCompaction,
     };
     use crate::capability_domain::build_default_capability_domain_registry;
+    use crate::history::PreviewLimits;
     use crate::util::default_agent_profile;
     use serde_json::json;
 
@@ -265,13 +404,13 @@ mod tests {
     }
 
     struct FakeModelAdapter {
-        availability_error: Option<String>,
-        outcomes: Mutex<VecDeque<Result<ModelInvocationOutcome, ModelAdapterError>>>,
+        availability_error: Option<AgentError>,
+        outcomes: Mutex<VecDeque<Result<ModelInvocationOutcome, AgentError>>>,
         prompt_message_counts: Mutex<Vec<usize>>,
     }
 
     impl FakeModelAdapter {
-        fn with_outcomes(outcomes: Vec<Result<ModelInvocationOutcome, ModelAdapterError>>) -> Self {
+        fn with_outcomes(outcomes: Vec<Result<ModelInvocationOutcome, AgentError>>) -> Self {
             Self {
                 availability_error: None,
                 outcomes: Mutex::new(VecDeque::from(outcomes)),
@@ -281,7 +420,7 @@ mod tests {
 
         fn unavailable(message: &str) -> Self {
             Self {
-                availability_error: Some(message.to_string()),
+                availability_error: Some(AgentError::Config(message.to_string())),
                 outcomes: Mutex::new(VecDeque::new()),
                 prompt_message_counts: Mutex::new(Vec::new()),
             }
@@ -293,8 +432,8 @@ mod tests {
             "fake"
         }
 
-        fn availability_error(&self) -> Option<&str> {
-            self.availability_error.as_deref()
+        fn availability_error(&self) -> Option<&AgentError> {
+            self.availability_error.as_ref()
         }
 
         fn stream_prompt<'a>(
@@ -317,12 +456,47 @@ mod tests {
         }
     }
 
+    struct ConcurrencyProbeAdapter {
+        active: AtomicUsize,
+        peak: AtomicUsize,
+        calls: AtomicUsize,
+    }
+
+    impl ModelAdapter for ConcurrencyProbeAdapter {
+        fn provider_name(&self) -> &'static str {
+            "probe"
+        }
+
+        fn stream_prompt<'a>(
+            &'a self,
+            _prompt_messages: &'a [PromptMessage],
+            _action_catalog: &'a super::SessionActionCatalog,
+            _on_event: &'a mut ModelEventSink<'a>,
+        ) -> ModelAdapterFuture<'a> {
+            Box::pin(async move {
+                let active = self.active.fetch_add(1, Ordering::SeqCst) + 1;
+                self.peak.fetch_max(active, Ordering::SeqCst);
+                self.calls.fetch_add(1, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(30)).await;
+                self.active.fetch_sub(1, Ordering::SeqCst);
+                Ok(ModelInvocationOutcome {
+                    action_call_count: 1,
+                    assistant_outputs: vec![],
+                    diagnostics: vec![],
+                    request_retries: 0,
+                    fallback_effort: None,
+                })
+            })
+        }
+    }
+
     fn test_context() -> AgentInvocationContext {
         let agent_profile = default_agent_profile("agent-default");
         AgentInvocationContext {
             harness_contract: HarnessContract {
                 runtime_version: "0.1.0".to_string(),
                 contract_schema_version: 1,
+                system_preamble: None,
             },
             identity_envelope: IdentityEnvelope {
                 schema_version: 1,
@@ -360,6 +534,7 @@ mod tests {
             triggers: vec![],
             recent_history: vec![],
             compaction: SessionCompaction::default(),
+            result_preview_limits: PreviewLimits::default(),
         }
     }
 
@@ -380,10 +555,48 @@ mod tests {
         assert!(!feedback.contains("omit the field instead of sending empty strings"));
     }
 
+    #[tokio::test]
+    async fn concurrent_model_requests_are_capped_across_orchestrator_clones() {
+        let probe = Arc::new(ConcurrencyProbeAdapter {
+            active: AtomicUsize::new(0),
+            peak: AtomicUsize::new(0),
+            calls: AtomicUsize::new(0),
+        });
+        let orchestrator = AgentOrchestrator::with_model_adapter(probe.clone(), test_registry())
+            .with_max_concurrent_model_requests(2)
+            .expect("model request limit");
+        let context = test_context();
+        let prompt_bundle = orchestrator.assemble_prompt_bundle(&context, None);
+
+        let turns = (0..6).map(|_| {
+            let orchestrator = orchestrator.clone();
+            let context = context.clone();
+            let prompt_bundle = prompt_bundle.clone();
+            tokio::spawn(
+                async move { orchestrator.run_turn(&context, prompt_bundle, |_| {}).await },
+            )
+        });
+        for turn in futures_util::future::join_all(turns).await {
+            assert!(!turn.expect("turn task").failed);
+        }
+
+        assert_eq!(probe.calls.load(Ordering::SeqCst), 6);
+        assert_eq!(probe.peak.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn zero_model_request_limit_is_rejected() {
+        let error = AgentOrchestrator::new(test_registry())
+            .with_max_concurrent_model_requests(0)
+            .err()
+            .expect("zero limit should be rejected");
+        assert_eq!(error, "max concurrent model requests must be at least 1");
+    }
+
     #[tokio::test]
     async fn run_turn_retries_after_recoverable_model_adapter_error() {
         let fake_adapter = Arc::new(FakeModelAdapter::with_outcomes(vec![
-            Err(ModelAdapterError::semantic_retryable(
+            Err(AgentError::Validation(
                 "action `filesystem__read` validation failed: missing or invalid string field `path`"
                     .to_string(),
             )),
@@ -391,6 +604,8 @@ mod tests {
                 action_call_count: 1,
                 assistant_outputs: vec![],
                 diagnostics: vec!["adapter success".to_string()],
+                request_retries: 0,
+                fallback_effort: None,
             }),
         ]));
         let orchestrator =
@@ -436,6 +651,41 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn run_turn_reports_retry_summary_for_a_run_that_retried_once() {
+        let fake_adapter = Arc::new(FakeModelAdapter::with_outcomes(vec![
+            Ok(ModelInvocationOutcome {
+                action_call_count: 0,
+                assistant_outputs: vec![],
+                diagnostics: vec![],
+                request_retries: 1,
+                fallback_effort: None,
+            }),
+            Ok(ModelInvocationOutcome {
+                action_call_count: 1,
+                assistant_outputs: vec![],
+                diagnostics: vec![],
+                request_retries: 1,
+                fallback_effort: Some("low".to_string()),
+            }),
+        ]));
+        let orchestrator = AgentOrchestrator::with_model_adapter(fake_adapter, test_registry());
+
+        let outcome = orchestrator
+            .run_turn(&test_context(), CompiledPrompt::default(), |_| {})
+            .await;
+
+        assert!(!outcome.failed);
+        assert_eq!(
+            outcome.retry_summary,
+            AgentTurnRetrySummary {
+                attempts: 2,
+                model_request_retries: 2,
+                fallback_effort: Some("low".to_string()),
+            }
+        );
+    }
+
     #[tokio::test]
     async fn run_turn_short_circuits_when_model_adapter_is_unavailable() {
         let orchestrator = AgentOrchestrator::with_model_adapter(
@@ -452,4 +702,59 @@ mod tests {
         assert_eq!(outcome.failure_code, "agent_init_error");
         assert!(outcome.failure_message.contains("model adapter `fake`"));
     }
+
+    #[tokio::test]
+    async fn run_turn_maps_fatal_adapter_error_to_its_failure_code() {
+        let fake_adapter = Arc::new(FakeModelAdapter::with_outcomes(vec![Err(
+            AgentError::Http {
+                status: 401,
+                message: "OpenAI request failed: status=401 body=unauthorized".to_string(),
+            },
+        )]));
+        let orchestrator =
+            AgentOrchestrator::with_model_adapter(fake_adapter.clone(), test_registry());
+
+        let outcome = orchestrator
+            .run_turn(&test_context(), CompiledPrompt::default(), |_| {})
+            .await;
+
+        assert!(outcome.failed);
+        assert_eq!(outcome.failure_code, "model_http_error");
+        assert!(outcome.failure_message.contains("status=401"));
+        assert_eq!(outcome.retry_summary.attempts, 1);
+    }
+
+    #[test]
+    fn action_allowlist_rejects_unknown_and_empty_lists() {
+        let orchestrator = || {
+            AgentOrchestrator::with_model_adapter(
+                Arc::new(FakeModelAdapter::with_outcomes(vec![])),
+                test_registry(),
+            )
+        };
+
+        let error = orchestrator()
+            .with_action_allowlist(vec![" ".to_string()])
+            .err()
+            .expect("blank allowlist should be rejected");
+        assert!(error.contains("at least one action"));
+
+        let error = orchestrator()
+            .with_action_allowlist(vec!["filesystem__teleport".to_string()])
+            .err()
+            .expect("unknown action should be rejected");
+        assert!(error.contains("unknown action `filesystem__teleport`"));
+
+        let orchestrator = orchestrator()
+            .with_action_allowlist(vec!["filesystem__read".to_string()])
+            .expect("known action should be accepted");
+        let definitions = orchestrator
+            .session_action_catalog(&test_context())
+            .openai_action_definitions();
+        let names = definitions
+            .iter()
+            .filter_map(|definition| definition.get("name").and_then(|name| name.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["filesystem__read"]);
+    }
 }
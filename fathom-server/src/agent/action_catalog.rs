@@ -1,4 +1,5 @@
 use std::collections::BTreeSet;
+use std::sync::Arc;
 
 use serde_json::Value;
 
@@ -10,6 +11,7 @@ use super::types::AgentInvocationContext;
 pub(crate) struct SessionActionCatalog {
     registry: CapabilityDomainRegistry,
     engaged_capability_domain_ids: BTreeSet<String>,
+    action_allowlist: Option<Arc<BTreeSet<String>>>,
 }
 
 impl SessionActionCatalog {
@@ -26,20 +28,73 @@ impl SessionActionCatalog {
                 .iter()
                 .map(|environment| environment.id.clone())
                 .collect(),
+            action_allowlist: None,
         }
     }
 
+    pub(crate) fn without_actions(registry: CapabilityDomainRegistry) -> Self {
+        Self {
+            registry,
+            engaged_capability_domain_ids: BTreeSet::new(),
+            action_allowlist: None,
+        }
+    }
+
+    pub(crate) fn with_action_allowlist(
+        mut self,
+        action_allowlist: Option<Arc<BTreeSet<String>>>,
+    ) -> Self {
+        self.action_allowlist = action_allowlist;
+        self
+    }
+
     pub(crate) fn openai_action_definitions(&self) -> Vec<Value> {
-        self.registry
-            .openai_action_definitions_for_capability_domains(&self.engaged_capability_domain_ids)
+        self.retain_allowlisted(
+            self.registry
+                .openai_action_definitions_for_capability_domains(
+                    &self.engaged_capability_domain_ids,
+                ),
+        )
+    }
+
+    pub(crate) fn anthropic_action_definitions(&self) -> Vec<Value> {
+        self.retain_allowlisted(
+            self.registry
+                .anthropic_action_definitions_for_capability_domains(
+                    &self.engaged_capability_domain_ids,
+                ),
+        )
+    }
+
+    fn retain_allowlisted(&self, definitions: Vec<Value>) -> Vec<Value> {
+        let Some(action_allowlist) = self.action_allowlist.as_deref() else {
+            return definitions;
+        };
+        definitions
+            .into_iter()
+            .filter(|definition| {
+                definition
+                    .get("name")
+                    .and_then(Value::as_str)
+                    .is_some_and(|name| action_allowlist.contains(name))
+            })
+            .collect()
     }
 
     pub(crate) fn validate_action(&self, action_id: &str, args: &Value) -> Result<String, String> {
-        self.registry.validate_in_capability_domains(
+        let canonical_action_id = self.registry.validate_in_capability_domains(
             action_id,
             args,
             &self.engaged_capability_domain_ids,
-        )
+        )?;
+        if let Some(action_allowlist) = self.action_allowlist.as_deref()
+            && !action_allowlist.contains(&canonical_action_id)
+        {
+            return Err(format!(
+                "action `{canonical_action_id}` is not offered by this runtime"
+            ));
+        }
+        Ok(canonical_action_id)
     }
 }
 
@@ -52,7 +107,10 @@ mod tests {
         SessionCompaction,
     };
     use crate::capability_domain::build_default_capability_domain_registry;
+    use crate::history::PreviewLimits;
     use serde_json::json;
+    use std::collections::BTreeSet;
+    use std::sync::Arc;
 
     fn test_registry() -> crate::capability_domain::CapabilityDomainRegistry {
         build_default_capability_domain_registry(
@@ -67,6 +125,7 @@ mod tests {
             harness_contract: HarnessContract {
                 runtime_version: "0.1.0".to_string(),
                 contract_schema_version: 1,
+                system_preamble: None,
             },
             identity_envelope: IdentityEnvelope {
                 schema_version: 1,
@@ -89,6 +148,7 @@ mod tests {
             triggers: vec![],
             recent_history: vec![],
             compaction: SessionCompaction::default(),
+            result_preview_limits: PreviewLimits::default(),
         }
     }
 
@@ -137,4 +197,28 @@ mod tests {
 
         assert!(error.contains("is not available in this session"));
     }
+
+    #[test]
+    fn action_catalog_rejects_actions_outside_the_allowlist() {
+        let context = context_with_capability_domains(vec![CapabilityDomain {
+            id: "filesystem".to_string(),
+            name: "Filesystem".to_string(),
+            description: "Filesystem".to_string(),
+            actions: vec![],
+            recipes: vec![],
+        }]);
+
+        let catalog = SessionActionCatalog::from_context(test_registry(), &context)
+            .with_action_allowlist(Some(Arc::new(BTreeSet::from([
+                "filesystem__read".to_string()
+            ]))));
+
+        catalog
+            .validate_action("filesystem__read", &json!({"path": "a.txt"}))
+            .expect("allowlisted action should validate");
+        let error = catalog
+            .validate_action("filesystem__list", &json!({"path": "."}))
+            .expect_err("action outside the allowlist should be rejected");
+        assert!(error.contains("is not offered by this runtime"));
+    }
 }
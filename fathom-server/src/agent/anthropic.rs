@@ -0,0 +1,643 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use futures_util::StreamExt;
+use serde_json::{Value, json};
+
+use crate::agent::SessionActionCatalog;
+use crate::agent::error::AgentError;
+use crate::agent::model_adapter::{ModelAdapter, ModelAdapterFuture, ModelEventSink};
+use crate::agent::openai::{
+    ActionCallCandidate, DispatchLedger, Utf8ChunkDecoder, flush_assistant_output,
+    maybe_dispatch_partial, parse_retry_after, should_retry_transport, truncate_for_log,
+};
+use crate::agent::retry::RetryPolicy;
+use crate::agent::types::{
+    ActionArgDeltaNote, ActionArgDoneNote, ModelDeltaEvent, ModelInvocationOutcome, PromptMessage,
+    StreamNote,
+};
+
+const MESSAGES_API_URL: &str = "https://api.anthropic.com/v1/messages";
+const API_VERSION: &str = "2023-06-01";
+const DEFAULT_MODEL: &str = "claude-sonnet-4-5";
+const DEFAULT_MAX_TOKENS: u64 = 8_192;
+const DEFAULT_TIMEOUT_SECS: u64 = 45;
+
+#[derive(Debug)]
+enum ContentBlock {
+    Text,
+    ToolUse {
+        id: String,
+        name: String,
+        input_json: String,
+    },
+    Other,
+}
+
+#[derive(Debug, Default)]
+struct StreamState {
+    blocks: HashMap<u64, ContentBlock>,
+    active_assistant_output: String,
+    assistant_outputs: Vec<String>,
+    diagnostics: Vec<String>,
+    input_tokens: u64,
+    cached_input_tokens: u64,
+    output_tokens: u64,
+}
+
+impl StreamState {
+    fn handle_event<F>(
+        &mut self,
+        value: &Value,
+        action_catalog: &SessionActionCatalog,
+        on_event: &mut F,
+        ledger: &mut DispatchLedger,
+    ) -> Result<bool, AgentError>
+    where
+        F: FnMut(ModelDeltaEvent) + Send,
+    {
+        let event_type = value
+            .get("type")
+            .and_then(Value::as_str)
+            .unwrap_or("unknown");
+        on_event(ModelDeltaEvent::StreamNote(StreamNote {
+            phase: "anthropic.stream.event".to_string(),
+            detail: event_type.to_string(),
+        }));
+
+        match event_type {
+            "message_start" => {
+                if let Some(usage) = value.pointer("/message/usage") {
+                    self.record_usage(usage);
+                }
+            }
+            "content_block_start" => {
+                let index = block_index(value);
+                let block = value.get("content_block").unwrap_or(&Value::Null);
+                let state = match block.get("type").and_then(Value::as_str) {
+                    Some("text") => {
+                        let text = block
+                            .get("text")
+                            .and_then(Value::as_str)
+                            .unwrap_or_default();
+                        self.push_text(text, on_event);
+                        ContentBlock::Text
+                    }
+                    Some("tool_use") => ContentBlock::ToolUse {
+                        id: string_field(block, "id"),
+                        name: string_field(block, "name"),
+                        input_json: block
+                            .get("input")
+                            .filter(|input| input.as_object().is_some_and(|map| !map.is_empty()))
+                            .map(Value::to_string)
+                            .unwrap_or_default(),
+                    },
+                    _ => ContentBlock::Other,
+                };
+                self.blocks.insert(index, state);
+            }
+            "content_block_delta" => {
+                let index = block_index(value);
+                let delta = value.get("delta").unwrap_or(&Value::Null);
+                match delta.get("type").and_then(Value::as_str) {
+                    Some("text_delta") => {
+                        let text = delta
+                            .get("text")
+                            .and_then(Value::as_str)
+                            .unwrap_or_default();
+                        self.push_text(text, on_event);
+                    }
+                    Some("input_json_delta") => {
+                        let partial_json = delta
+                            .get("partial_json")
+                            .and_then(Value::as_str)
+                            .unwrap_or_default();
+                        if let Some(ContentBlock::ToolUse {
+                            id,
+                            name,
+                            input_json,
+                        }) = self.blocks.get_mut(&index)
+                            && !partial_json.is_empty()
+                        {
+                            input_json.push_str(partial_json);
+                            on_event(ModelDeltaEvent::ActionArgsDelta(ActionArgDeltaNote {
+                                call_key: id.clone(),
+                                call_id: Some(id.clone()),
+                                action_id: Some(name.clone()),
+                                args_delta: partial_json.to_string(),
+                            }));
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            "content_block_stop" => match self.blocks.remove(&block_index(value)) {
+                Some(ContentBlock::Text) => flush_assistant_output(
+                    &mut self.active_assistant_output,
+                    &mut self.assistant_outputs,
+                    on_event,
+                ),
+                Some(ContentBlock::ToolUse {
+                    id,
+                    name,
+                    input_json,
+                }) => {
+                    on_event(ModelDeltaEvent::ActionArgsDone(ActionArgDoneNote {
+                        call_key: id.clone(),
+                        call_id: Some(id.clone()),
+                        action_id: Some(name.clone()),
+                        args_json: input_json.clone(),
+                    }));
+                    maybe_dispatch_partial(
+                        action_catalog,
+                        ActionCallCandidate {
+                            key: id.clone(),
+                            raw_action_id: name,
+                            arguments_raw: input_json,
+                            arguments_complete: true,
+                            call_id: Some(id),
+                        },
+                        on_event,
+                        ledger,
+                        &mut self.diagnostics,
+                    )?;
+                }
+                Some(ContentBlock::Other) | None => {}
+            },
+            "message_delta" => {
+                if let Some(usage) = value.get("usage") {
+                    self.record_usage(usage);
+                }
+            }
+            "message_stop" => return Ok(true),
+            "error" => {
+                return Err(AgentError::StreamParse(format!(
+                    "Anthropic stream error payload: {value}"
+                )));
+            }
+            _ => {}
+        }
+
+        Ok(false)
+    }
+
+    fn push_text<F>(&mut self, text: &str, on_event: &mut F)
+    where
+        F: FnMut(ModelDeltaEvent) + Send,
+    {
+        if text.is_empty() {
+            return;
+        }
+        self.active_assistant_output.push_str(text);
+        on_event(ModelDeltaEvent::AssistantTextDelta(text.to_string()));
+    }
+
+    fn record_usage(&mut self, usage: &Value) {
+        if let Some(input_tokens) = usage.get("input_tokens").and_then(Value::as_u64) {
+            self.input_tokens = input_tokens;
+        }
+        if let Some(cached) = usage.get("cache_read_input_tokens").and_then(Value::as_u64) {
+            self.cached_input_tokens = cached;
+        }
+        if let Some(output_tokens) = usage.get("output_tokens").and_then(Value::as_u64) {
+            self.output_tokens = output_tokens;
+        }
+    }
+
+    fn finish<F>(mut self, on_event: &mut F, ledger: &DispatchLedger) -> ModelInvocationOutcome
+    where
+        F: FnMut(ModelDeltaEvent) + Send,
+    {
+        flush_assistant_output(
+            &mut self.active_assistant_output,
+            &mut self.assistant_outputs,
+            on_event,
+        );
+        if self.input_tokens > 0 || self.output_tokens > 0 {
+            let detail = format!(
+                "input_tokens={} cached_input_tokens={} output_tokens={} total_tokens={}",
+                self.input_tokens,
+                self.cached_input_tokens,
+                self.output_tokens,
+                self.input_tokens + self.output_tokens
+            );
+            on_event(ModelDeltaEvent::StreamNote(StreamNote {
+                phase: "anthropic.response.usage".to_string(),
+                detail: detail.clone(),
+            }));
+            self.diagnostics.push(format!("anthropic_usage {detail}"));
+        }
+        ModelInvocationOutcome {
+            action_call_count: ledger.action_call_count,
+            assistant_outputs: self.assistant_outputs,
+            diagnostics: self.diagnostics,
+            request_retries: 0,
+            fallback_effort: None,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub(crate) struct AnthropicModelAdapter {
+    http: reqwest::Client,
+    api_key: Option<String>,
+    retry_policy: RetryPolicy,
+}
+
+impl AnthropicModelAdapter {
+    pub(crate) fn new() -> Result<Self, AgentError> {
+        let http = reqwest::Client::builder()
+            .timeout(Duration::from_secs(DEFAULT_TIMEOUT_SECS))
+            .build()
+            .map_err(|error| {
+                AgentError::Config(format!("failed to construct reqwest client: {error}"))
+            })?;
+        let api_key = std::env::var("ANTHROPIC_API_KEY")
+            .ok()
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty());
+
+        Ok(Self {
+            http,
+            api_key,
+            retry_policy: RetryPolicy::conservative(),
+        })
+    }
+
+    async fn stream_actions<F>(
+        &self,
+        prompt_messages: &[PromptMessage],
+        action_catalog: &SessionActionCatalog,
+        mut on_event: F,
+    ) -> Result<ModelInvocationOutcome, AgentError>
+    where
+        F: FnMut(ModelDeltaEvent) + Send,
+    {
+        let Some(api_key) = self.api_key.as_deref() else {
+            return Err(AgentError::Config(
+                "ANTHROPIC_API_KEY is required but not configured".to_string(),
+            ));
+        };
+
+        let mut attempts = 0usize;
+        let max_retries = self.retry_policy.max_retries();
+        let mut last_error: Option<AgentError> = None;
+        let mut ledger = DispatchLedger::default();
+
+        while attempts <= max_retries {
+            on_event(ModelDeltaEvent::StreamNote(StreamNote {
+                phase: "anthropic.request.start".to_string(),
+                detail: format!("attempt={}", attempts + 1),
+            }));
+
+            let body = build_request_body(prompt_messages, action_catalog);
+            let response = self
+                .http
+                .post(MESSAGES_API_URL)
+                .header("x-api-key", api_key)
+                .header("anthropic-version", API_VERSION)
+                .json(&body)
+                .send()
+                .await;
+
+            let (error, retry_after, reason) = match response {
+                Ok(response) if response.status().is_success() => {
+                    match self
+                        .parse_stream(response, action_catalog, &mut on_event, &mut ledger)
+                        .await
+                    {
+                        Ok(outcome) => {
+                            return Ok(ModelInvocationOutcome {
+                                request_retries: attempts,
+                                ..outcome
+                            });
+                        }
+                        Err(error) => {
+                            ledger.begin_retry();
+                            (error, None, "stream_parse_error".to_string())
+                        }
+                    }
+                }
+                Ok(response) => {
+                    let status = response.status();
+                    let retry_after = parse_retry_after(response.headers());
+                    let text = response.text().await.unwrap_or_default();
+                    let error = AgentError::Http {
+                        status: status.as_u16(),
+                        message: format!(
+                            "Anthropic request failed: status={} body={}",
+                            status.as_u16(),
+                            truncate_for_log(&text)
+                        ),
+                    };
+                    (error, retry_after, format!("status={}", status.as_u16()))
+                }
+                Err(error) => {
+                    let retryable = should_retry_transport(&error);
+                    let error = AgentError::Transport {
+                        message: format!("Anthropic transport error: {error}"),
+                        retryable,
+                    };
+                    (error, None, "transport_error".to_string())
+                }
+            };
+
+            let retryable = error.is_request_retryable();
+            last_error = Some(error);
+            if !retryable || attempts >= max_retries {
+                break;
+            }
+            let delay = self.retry_policy.compute_delay(attempts, retry_after);
+            on_event(ModelDeltaEvent::StreamNote(StreamNote {
+                phase: "anthropic.request.retry".to_string(),
+                detail: format!("{reason} waiting {}ms before retry", delay.as_millis()),
+            }));
+            tokio::time::sleep(delay).await;
+            attempts += 1;
+        }
+
+        Err(last_error.unwrap_or_else(|| AgentError::Transport {
+            message: "Anthropic request failed without an error payload".to_string(),
+            retryable: false,
+        }))
+    }
+
+    async fn parse_stream<F>(
+        &self,
+        response: reqwest::Response,
+        action_catalog: &SessionActionCatalog,
+        on_event: &mut F,
+        ledger: &mut DispatchLedger,
+    ) -> Result<ModelInvocationOutcome, AgentError>
+    where
+        F: FnMut(ModelDeltaEvent) + Send,
+    {
+        let mut stream = response.bytes_stream();
+        let mut line_buffer = String::new();
+        let mut chunk_decoder = Utf8ChunkDecoder::default();
+        let mut state = StreamState::default();
+
+        while let Some(chunk_result) = stream.next().await {
+            let bytes = chunk_result.map_err(|error| AgentError::Transport {
+                message: format!("stream chunk error: {error}"),
+                retryable: true,
+            })?;
+            line_buffer.push_str(&chunk_decoder.decode(&bytes));
+
+            while let Some(newline_index) = line_buffer.find('\n') {
+                let line = line_buffer[..newline_index]
+                    .trim_end_matches('\r')
+                    .to_string();
+                line_buffer.drain(..=newline_index);
+
+                let Some(payload) = line.strip_prefix("data:") else {
+                    continue;
+                };
+                let value: Value = serde_json::from_str(payload.trim()).map_err(|error| {
+                    AgentError::StreamParse(format!("invalid stream json payload: {error}"))
+                })?;
+                if state.handle_event(&value, action_catalog, on_event, ledger)? {
+                    return Ok(state.finish(on_event, ledger));
+                }
+            }
+        }
+
+        Err(AgentError::StreamParse(
+            "Anthropic stream ended before message_stop".to_string(),
+        ))
+    }
+}
+
+impl ModelAdapter for AnthropicModelAdapter {
+    fn provider_name(&self) -> &'static str {
+        "anthropic"
+    }
+
+    fn stream_prompt<'a>(
+        &'a self,
+        prompt_messages: &'a [PromptMessage],
+        action_catalog: &'a SessionActionCatalog,
+        on_event: &'a mut ModelEventSink<'a>,
+    ) -> ModelAdapterFuture<'a> {
+        Box::pin(async move {
+            self.stream_actions(prompt_messages, action_catalog, on_event)
+                .await
+        })
+    }
+}
+
+fn block_index(value: &Value) -> u64 {
+    value.get("index").and_then(Value::as_u64).unwrap_or(0)
+}
+
+fn string_field(value: &Value, field: &str) -> String {
+    value
+        .get(field)
+        .and_then(Value::as_str)
+        .unwrap_or_default()
+        .to_string()
+}
+
+fn build_request_body(
+    prompt_messages: &[PromptMessage],
+    action_catalog: &SessionActionCatalog,
+) -> Value {
+    let system = prompt_messages
+        .iter()
+        .filter(|message| message.role == "system")
+        .map(|message| json!({ "type": "text", "text": message.content }))
+        .collect::<Vec<_>>();
+
+    let mut messages: Vec<Value> = Vec::new();
+    for message in prompt_messages
+        .iter()
+        .filter(|message| message.role != "system")
+    {
+        let block = json!({ "type": "text", "text": message.content });
+        match messages.last_mut() {
+            Some(last) if last["role"] == json!(message.role) => {
+                if let Some(content) = last["content"].as_array_mut() {
+                    content.push(block);
+                }
+            }
+            _ => messages.push(json!({ "role": message.role, "content": [block] })),
+        }
+    }
+
+    let mut body = json!({
+        "model": DEFAULT_MODEL,
+        "max_tokens": DEFAULT_MAX_TOKENS,
+        "stream": true,
+        "system": system,
+        "messages": messages,
+    });
+    let tools = action_catalog.anthropic_action_definitions();
+    if !tools.is_empty() {
+        body["tools"] = json!(tools);
+        body["tool_choice"] = json!({ "type": "auto" });
+    }
+    body
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{StreamState, build_request_body};
+    use crate::agent::SessionActionCatalog;
+    use crate::agent::openai::DispatchLedger;
+    use crate::agent::types::{
+        AgentInvocationContext, CapabilityDomain, CapabilitySurface, HarnessContract,
+        IdentityEnvelope, ModelDeltaEvent, ParticipantEnvelope, PromptMessage, SessionAnchor,
+        SessionBaseline, SessionCompaction,
+    };
+    use crate::capability_domain::build_default_capability_domain_registry;
+    use crate::history::PreviewLimits;
+
+    fn filesystem_action_catalog() -> SessionActionCatalog {
+        SessionActionCatalog::from_context(
+            build_default_capability_domain_registry(
+                &std::env::current_dir().expect("current directory for registry"),
+            ),
+            &AgentInvocationContext {
+                harness_contract: HarnessContract {
+                    runtime_version: "0.1.0".to_string(),
+                    contract_schema_version: 1,
+                    system_preamble: None,
+                },
+                identity_envelope: IdentityEnvelope {
+                    schema_version: 1,
+                    source_revision: "agent-default@spec:1@updated:1".to_string(),
+                    material: json!({"display_name": "Agent Default"}),
+                },
+                session_baseline: SessionBaseline {
+                    session_anchor: SessionAnchor {
+                        session_id: "session-1".to_string(),
+                        started_at_unix_ms: 1,
+                    },
+                    capability_surface: CapabilitySurface {
+                        capability_domains: vec![CapabilityDomain {
+                            id: "filesystem".to_string(),
+                            name: "Filesystem".to_string(),
+                            description: "Filesystem rooted at a base path.".to_string(),
+                            actions: vec![],
+                            recipes: vec![],
+                        }],
+                    },
+                    participant_envelope: ParticipantEnvelope {
+                        schema_version: 1,
+                        source_revision: "participants@1".to_string(),
+                        material: json!({"participants": []}),
+                    },
+                },
+                resolved_payload_lookups: vec![],
+                triggers: vec![],
+                recent_history: vec![],
+                compaction: SessionCompaction::default(),
+                result_preview_limits: PreviewLimits::default(),
+            },
+        )
+    }
+
+    #[test]
+    fn request_body_moves_system_messages_and_wraps_action_definitions() {
+        let messages = vec![
+            PromptMessage::new("system", "contract", "be careful".to_string()),
+            PromptMessage::new("user", "history", "earlier".to_string()),
+            PromptMessage::new("user", "turn", "hello".to_string()),
+        ];
+
+        let body = build_request_body(&messages, &filesystem_action_catalog());
+
+        assert_eq!(
+            body["system"],
+            json!([{"type": "text", "text": "be careful"}])
+        );
+        assert_eq!(body["messages"].as_array().map(Vec::len), Some(1));
+        assert_eq!(body["messages"][0]["role"], json!("user"));
+        assert_eq!(
+            body["messages"][0]["content"][1],
+            json!({"type": "text", "text": "hello"})
+        );
+        let read = body["tools"]
+            .as_array()
+            .expect("tools array")
+            .iter()
+            .find(|tool| tool["name"] == json!("filesystem__read"))
+            .expect("filesystem__read tool");
+        assert_eq!(read["input_schema"]["type"], json!("object"));
+        assert!(read.get("parameters").is_none());
+        assert_eq!(body["tool_choice"], json!({"type": "auto"}));
+    }
+
+    #[test]
+    fn stream_events_produce_text_output_and_dispatch_tool_use() {
+        let action_catalog = filesystem_action_catalog();
+        let mut events = Vec::<ModelDeltaEvent>::new();
+        let mut on_event = |event| events.push(event);
+        let mut ledger = DispatchLedger::default();
+        let mut state = StreamState::default();
+
+        let stream = [
+            json!({"type": "message_start", "message": {"usage": {"input_tokens": 12, "cache_read_input_tokens": 4, "output_tokens": 1}}}),
+            json!({"type": "content_block_start", "index": 0, "content_block": {"type": "text", "text": ""}}),
+            json!({"type": "content_block_delta", "index": 0, "delta": {"type": "text_delta", "text": "Reading "}}),
+            json!({"type": "content_block_delta", "index": 0, "delta": {"type": "text_delta", "text": "it now."}}),
+            json!({"type": "content_block_stop", "index": 0}),
+            json!({"type": "content_block_start", "index": 1, "content_block": {"type": "tool_use", "id": "toolu_1", "name": "filesystem__read", "input": {}}}),
+            json!({"type": "content_block_delta", "index": 1, "delta": {"type": "input_json_delta", "partial_json": "{\"path\": "}}),
+            json!({"type": "content_block_delta", "index": 1, "delta": {"type": "input_json_delta", "partial_json": "\"notes.txt\"}"}}),
+            json!({"type": "content_block_stop", "index": 1}),
+            json!({"type": "message_delta", "delta": {"stop_reason": "tool_use"}, "usage": {"output_tokens": 30}}),
+        ];
+        for event in &stream {
+            let stopped = state
+                .handle_event(event, &action_catalog, &mut on_event, &mut ledger)
+                .expect("event handled");
+            assert!(!stopped);
+        }
+        let stopped = state
+            .handle_event(
+                &json!({"type": "message_stop"}),
+                &action_catalog,
+                &mut on_event,
+                &mut ledger,
+            )
+            .expect("message_stop handled");
+        assert!(stopped);
+        let outcome = state.finish(&mut on_event, &ledger);
+
+        assert_eq!(
+            outcome.assistant_outputs,
+            vec!["Reading it now.".to_string()]
+        );
+        assert_eq!(outcome.action_call_count, 1);
+        assert!(outcome.diagnostics.iter().any(|line| {
+            line == "anthropic_usage input_tokens=12 cached_input_tokens=4 output_tokens=30 total_tokens=42"
+        }));
+        let invocation = events
+            .iter()
+            .find_map(|event| match event {
+                ModelDeltaEvent::ActionInvocation(invocation) => Some(invocation),
+                _ => None,
+            })
+            .expect("action invocation");
+        assert_eq!(invocation.action_id, "filesystem__read");
+        assert_eq!(invocation.call_id.as_deref(), Some("toolu_1"));
+        assert_eq!(invocation.args_json, r#"{"path":"notes.txt"}"#);
+    }
+
+    #[test]
+    fn stream_error_event_is_retryable() {
+        let mut ledger = DispatchLedger::default();
+        let error = StreamState::default()
+            .handle_event(
+                &json!({"type": "error", "error": {"type": "overloaded_error", "message": "Overloaded"}}),
+                &filesystem_action_catalog(),
+                &mut |_| {},
+                &mut ledger,
+            )
+            .expect_err("error events fail the attempt");
+        assert!(error.is_request_retryable());
+    }
+}
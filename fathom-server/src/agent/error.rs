@@ -0,0 +1,120 @@
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum AgentError {
+    Transport { message: String, retryable: bool },
+    Http { status: u16, message: String },
+    StreamParse(String),
+    Validation(String),
+    Config(String),
+}
+
+impl AgentError {
+    pub(crate) fn message(&self) -> &str {
+        match self {
+            Self::Transport { message, .. } | Self::Http { message, .. } => message,
+            Self::StreamParse(message) | Self::Validation(message) | Self::Config(message) => {
+                message
+            }
+        }
+    }
+
+    pub(crate) fn failure_code(&self) -> &'static str {
+        match self {
+            Self::Transport { .. } => "model_transport_error",
+            Self::Http { .. } => "model_http_error",
+            Self::StreamParse(_) => "model_stream_parse_error",
+            Self::Validation(_) => "action_validation_error",
+            Self::Config(_) => "agent_config_error",
+        }
+    }
+
+    pub(crate) fn is_semantic_retryable(&self) -> bool {
+        matches!(self, Self::Validation(_))
+    }
+
+    pub(crate) fn is_request_retryable(&self) -> bool {
+        match self {
+            Self::Transport { retryable, .. } => *retryable,
+            Self::Http { status, .. } => {
+                *status == 408 || *status == 409 || *status == 429 || *status >= 500
+            }
+            Self::StreamParse(_) => true,
+            Self::Validation(_) | Self::Config(_) => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AgentError;
+
+    #[test]
+    fn each_variant_maps_to_its_failure_code_and_retry_decision() {
+        let cases = [
+            (
+                AgentError::Transport {
+                    message: "connection reset".to_string(),
+                    retryable: true,
+                },
+                "model_transport_error",
+                true,
+                false,
+            ),
+            (
+                AgentError::Transport {
+                    message: "invalid request builder".to_string(),
+                    retryable: false,
+                },
+                "model_transport_error",
+                false,
+                false,
+            ),
+            (
+                AgentError::Http {
+                    status: 429,
+                    message: "rate limited".to_string(),
+                },
+                "model_http_error",
+                true,
+                false,
+            ),
+            (
+                AgentError::Http {
+                    status: 400,
+                    message: "bad request".to_string(),
+                },
+                "model_http_error",
+                false,
+                false,
+            ),
+            (
+                AgentError::StreamParse("invalid stream json payload".to_string()),
+                "model_stream_parse_error",
+                true,
+                false,
+            ),
+            (
+                AgentError::Validation("action `filesystem__read` validation failed".to_string()),
+                "action_validation_error",
+                false,
+                true,
+            ),
+            (
+                AgentError::Config("OPENAI_API_KEY is required but not configured".to_string()),
+                "agent_config_error",
+                false,
+                false,
+            ),
+        ];
+
+        for (error, failure_code, request_retryable, semantic_retryable) in cases {
+            assert_eq!(error.failure_code(), failure_code, "{error:?}");
+            assert_eq!(error.is_request_retryable(), request_retryable, "{error:?}");
+            assert_eq!(
+                error.is_semantic_retryable(),
+                semantic_retryable,
+                "{error:?}"
+            );
+            assert!(!error.message().is_empty());
+        }
+    }
+}
@@ -1,46 +1,54 @@
 use std::future::Future;
 use std::pin::Pin;
 
+use super::error::AgentError;
 use crate::agent::{ModelDeltaEvent, ModelInvocationOutcome, PromptMessage, SessionActionCatalog};
 
-pub(crate) type ModelEventSink<'a> = dyn FnMut(ModelDeltaEvent) + Send + 'a;
-pub(crate) type ModelAdapterFuture<'a> =
-    Pin<Box<dyn Future<Output = Result<ModelInvocationOutcome, ModelAdapterError>> + Send + 'a>>;
+const PROVIDER_ENV: &str = "FATHOM_AGENT_PROVIDER";
 
-#[derive(Debug, Clone)]
-pub(crate) struct ModelAdapterError {
-    message: String,
-    semantic_retryable: bool,
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) enum ModelProvider {
+    #[default]
+    OpenAi,
+    Anthropic,
 }
 
-impl ModelAdapterError {
-    pub(crate) fn non_retryable(message: impl Into<String>) -> Self {
-        Self {
-            message: message.into(),
-            semantic_retryable: false,
-        }
+impl ModelProvider {
+    pub(crate) fn from_env() -> Result<Self, AgentError> {
+        Self::parse(std::env::var(PROVIDER_ENV).ok().as_deref())
     }
 
-    pub(crate) fn semantic_retryable(message: impl Into<String>) -> Self {
-        Self {
-            message: message.into(),
-            semantic_retryable: true,
+    fn parse(raw: Option<&str>) -> Result<Self, AgentError> {
+        match raw
+            .map(str::trim)
+            .filter(|raw| !raw.is_empty())
+            .map(str::to_ascii_lowercase)
+            .as_deref()
+        {
+            None | Some("openai") => Ok(Self::OpenAi),
+            Some("anthropic") => Ok(Self::Anthropic),
+            Some(other) => Err(AgentError::Config(format!(
+                "{PROVIDER_ENV} must be `openai` or `anthropic`, got `{other}`"
+            ))),
         }
     }
 
-    pub(crate) fn message(&self) -> &str {
-        &self.message
-    }
-
-    pub(crate) fn is_semantic_retryable(&self) -> bool {
-        self.semantic_retryable
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            Self::OpenAi => "openai",
+            Self::Anthropic => "anthropic",
+        }
     }
 }
 
+pub(crate) type ModelEventSink<'a> = dyn FnMut(ModelDeltaEvent) + Send + 'a;
+pub(crate) type ModelAdapterFuture<'a> =
+    Pin<Box<dyn Future<Output = Result<ModelInvocationOutcome, AgentError>> + Send + 'a>>;
+
 pub(crate) trait ModelAdapter: Send + Sync {
     fn provider_name(&self) -> &'static str;
 
-    fn availability_error(&self) -> Option<&str> {
+    fn availability_error(&self) -> Option<&AgentError> {
         None
     }
 
@@ -54,11 +62,11 @@ pub(crate) trait ModelAdapter: Send + Sync {
 
 pub(crate) struct UnavailableModelAdapter {
     provider_name: &'static str,
-    init_error: String,
+    init_error: AgentError,
 }
 
 impl UnavailableModelAdapter {
-    pub(crate) fn new(provider_name: &'static str, init_error: String) -> Self {
+    pub(crate) fn new(provider_name: &'static str, init_error: AgentError) -> Self {
         Self {
             provider_name,
             init_error,
@@ -71,7 +79,7 @@ impl ModelAdapter for UnavailableModelAdapter {
         self.provider_name
     }
 
-    fn availability_error(&self) -> Option<&str> {
+    fn availability_error(&self) -> Option<&AgentError> {
         Some(&self.init_error)
     }
 
@@ -82,6 +90,22 @@ impl ModelAdapter for UnavailableModelAdapter {
         _on_event: &'a mut ModelEventSink<'a>,
     ) -> ModelAdapterFuture<'a> {
         let error = self.init_error.clone();
-        Box::pin(async move { Err(ModelAdapterError::non_retryable(error)) })
+        Box::pin(async move { Err(error) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ModelProvider;
+
+    #[test]
+    fn provider_defaults_to_openai_and_rejects_unknown_values() {
+        assert_eq!(ModelProvider::parse(None), Ok(ModelProvider::OpenAi));
+        assert_eq!(ModelProvider::parse(Some(" ")), Ok(ModelProvider::OpenAi));
+        assert_eq!(
+            ModelProvider::parse(Some("Anthropic")),
+            Ok(ModelProvider::Anthropic)
+        );
+        assert!(ModelProvider::parse(Some("gemini")).is_err());
     }
 }
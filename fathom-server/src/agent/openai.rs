@@ -6,19 +6,187 @@ use reqwest::header::RETRY_AFTER;
 use serde_json::{Value, json};
 
 use crate::agent::SessionActionCatalog;
-use crate::agent::model_adapter::{
-    ModelAdapter, ModelAdapterError, ModelAdapterFuture, ModelEventSink,
-};
+use crate::agent::error::AgentError;
+use crate::agent::model_adapter::{ModelAdapter, ModelAdapterFuture, ModelEventSink};
 use crate::agent::retry::RetryPolicy;
 use crate::agent::types::{
     ActionArgDeltaNote, ActionArgDoneNote, ActionInvocation, ModelDeltaEvent,
     ModelInvocationOutcome, PromptMessage, StreamNote,
 };
 
-const RESPONSES_API_URL: &str = "https://api.openai.com/v1/responses";
+const DEFAULT_BASE_URL: &str = "https://api.openai.com";
+const RESPONSES_PATH: &str = "/v1/responses";
 const DEFAULT_MODEL: &str = "gpt-5.4";
 const DEFAULT_REASONING_EFFORT: &str = "high";
 const DEFAULT_TIMEOUT_SECS: u64 = 45;
+const MODEL_ENV: &str = "OPENAI_MODEL";
+const BASE_URL_ENV: &str = "OPENAI_BASE_URL";
+const REASONING_EFFORT_ENV: &str = "OPENAI_REASONING_EFFORT";
+const FALLBACK_REASONING_EFFORT_ENV: &str = "OPENAI_FALLBACK_REASONING_EFFORT";
+const TEMPERATURE_ENV: &str = "OPENAI_TEMPERATURE";
+const TOP_P_ENV: &str = "OPENAI_TOP_P";
+const DISPATCH_ORDER_ENV: &str = "OPENAI_ACTION_DISPATCH_ORDER";
+const MAX_TOOL_CALLS_ENV: &str = "OPENAI_MAX_TOOL_CALLS";
+const MAX_TEMPERATURE: f64 = 2.0;
+const MAX_TOP_P: f64 = 1.0;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct EndpointConfig {
+    responses_url: String,
+    model: String,
+    reasoning_effort: String,
+    fallback_reasoning_effort: Option<String>,
+}
+
+impl Default for EndpointConfig {
+    fn default() -> Self {
+        Self {
+            responses_url: format!("{DEFAULT_BASE_URL}{RESPONSES_PATH}"),
+            model: DEFAULT_MODEL.to_string(),
+            reasoning_effort: DEFAULT_REASONING_EFFORT.to_string(),
+            fallback_reasoning_effort: None,
+        }
+    }
+}
+
+impl EndpointConfig {
+    fn from_env() -> Result<Self, AgentError> {
+        Self::parse(
+            std::env::var(BASE_URL_ENV).ok().as_deref(),
+            std::env::var(MODEL_ENV).ok().as_deref(),
+            std::env::var(REASONING_EFFORT_ENV).ok().as_deref(),
+            std::env::var(FALLBACK_REASONING_EFFORT_ENV).ok().as_deref(),
+        )
+    }
+
+    fn parse(
+        base_url: Option<&str>,
+        model: Option<&str>,
+        reasoning_effort: Option<&str>,
+        fallback_reasoning_effort: Option<&str>,
+    ) -> Result<Self, AgentError> {
+        let base_url = non_empty(base_url).unwrap_or(DEFAULT_BASE_URL);
+        if !base_url.starts_with("http://") && !base_url.starts_with("https://") {
+            return Err(AgentError::Config(format!(
+                "{BASE_URL_ENV} must be an http(s) URL, got `{base_url}`"
+            )));
+        }
+
+        Ok(Self {
+            responses_url: format!("{}{RESPONSES_PATH}", base_url.trim_end_matches('/')),
+            model: non_empty(model).unwrap_or(DEFAULT_MODEL).to_string(),
+            reasoning_effort: non_empty(reasoning_effort)
+                .unwrap_or(DEFAULT_REASONING_EFFORT)
+                .to_string(),
+            fallback_reasoning_effort: non_empty(fallback_reasoning_effort).map(str::to_string),
+        })
+    }
+
+    fn fallback_effort_for_attempt(&self, attempt: usize) -> Option<&str> {
+        if attempt == 0 {
+            return None;
+        }
+        self.fallback_reasoning_effort.as_deref()
+    }
+
+    fn reasoning_effort_for_attempt(&self, attempt: usize) -> &str {
+        self.fallback_effort_for_attempt(attempt)
+            .unwrap_or(&self.reasoning_effort)
+    }
+}
+
+fn non_empty(raw: Option<&str>) -> Option<&str> {
+    raw.map(str::trim).filter(|raw| !raw.is_empty())
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+struct SamplingConfig {
+    temperature: Option<f64>,
+    top_p: Option<f64>,
+}
+
+impl SamplingConfig {
+    fn from_env() -> Result<Self, AgentError> {
+        Self::parse(
+            std::env::var(TEMPERATURE_ENV).ok().as_deref(),
+            std::env::var(TOP_P_ENV).ok().as_deref(),
+        )
+    }
+
+    fn parse(temperature: Option<&str>, top_p: Option<&str>) -> Result<Self, AgentError> {
+        Ok(Self {
+            temperature: parse_sampling_value(TEMPERATURE_ENV, temperature, MAX_TEMPERATURE)?,
+            top_p: parse_sampling_value(TOP_P_ENV, top_p, MAX_TOP_P)?,
+        })
+    }
+
+    fn apply(&self, body: &mut Value) {
+        if let Some(temperature) = self.temperature {
+            body["temperature"] = json!(temperature);
+        }
+        if let Some(top_p) = self.top_p {
+            body["top_p"] = json!(top_p);
+        }
+    }
+}
+
+fn parse_sampling_value(
+    name: &str,
+    raw: Option<&str>,
+    max: f64,
+) -> Result<Option<f64>, AgentError> {
+    let Some(raw) = raw.map(str::trim).filter(|raw| !raw.is_empty()) else {
+        return Ok(None);
+    };
+    let value = raw
+        .parse::<f64>()
+        .map_err(|_| AgentError::Config(format!("{name} must be a number, got `{raw}`")))?;
+    if !(0.0..=max).contains(&value) {
+        return Err(AgentError::Config(format!(
+            "{name} must be between 0 and {max}, got {value}"
+        )));
+    }
+    Ok(Some(value))
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+enum ActionDispatchOrder {
+    #[default]
+    Stream,
+    CallId,
+}
+
+impl ActionDispatchOrder {
+    fn from_env() -> Result<Self, AgentError> {
+        Self::parse(std::env::var(DISPATCH_ORDER_ENV).ok().as_deref())
+    }
+
+    fn parse(raw: Option<&str>) -> Result<Self, AgentError> {
+        match raw.map(str::trim).filter(|raw| !raw.is_empty()) {
+            None | Some("stream") => Ok(Self::Stream),
+            Some("call_id") => Ok(Self::CallId),
+            Some(other) => Err(AgentError::Config(format!(
+                "{DISPATCH_ORDER_ENV} must be `stream` or `call_id`, got `{other}`"
+            ))),
+        }
+    }
+}
+
+fn max_tool_calls_from_env() -> Result<Option<usize>, AgentError> {
+    parse_max_tool_calls(std::env::var(MAX_TOOL_CALLS_ENV).ok().as_deref())
+}
+
+fn parse_max_tool_calls(raw: Option<&str>) -> Result<Option<usize>, AgentError> {
+    let Some(raw) = non_empty(raw) else {
+        return Ok(None);
+    };
+    match raw.parse::<usize>() {
+        Ok(value) if value > 0 => Ok(Some(value)),
+        _ => Err(AgentError::Config(format!(
+            "{MAX_TOOL_CALLS_ENV} must be a positive integer, got `{raw}`"
+        ))),
+    }
+}
 
 #[derive(Debug, Clone)]
 struct PartialActionCall {
@@ -27,6 +195,108 @@ struct PartialActionCall {
     arguments: String,
 }
 
+#[derive(Debug)]
+struct DeferredInvocation {
+    dispatch_key: String,
+    signature: String,
+    invocation: ActionInvocation,
+}
+
+#[derive(Debug, Default)]
+pub(super) struct DispatchLedger {
+    dispatched_keys: HashSet<String>,
+    attempt_signatures: HashSet<String>,
+    prior_attempt_signatures: HashSet<String>,
+    pub(super) action_call_count: usize,
+    max_action_calls: Option<usize>,
+    deferred: Option<Vec<DeferredInvocation>>,
+}
+
+impl DispatchLedger {
+    fn new(order: ActionDispatchOrder) -> Self {
+        Self {
+            deferred: (order == ActionDispatchOrder::CallId).then(Vec::new),
+            ..Self::default()
+        }
+    }
+
+    fn with_max_action_calls(mut self, max_action_calls: Option<usize>) -> Self {
+        self.max_action_calls = max_action_calls;
+        self
+    }
+
+    fn at_action_call_cap(&self) -> bool {
+        self.max_action_calls
+            .is_some_and(|max_action_calls| self.action_call_count >= max_action_calls)
+    }
+
+    pub(super) fn begin_retry(&mut self) {
+        self.prior_attempt_signatures
+            .extend(self.attempt_signatures.drain());
+        if let Some(deferred) = self.deferred.as_mut() {
+            deferred.clear();
+        }
+    }
+
+    fn is_settled(&self, dispatch_key: &str) -> bool {
+        self.dispatched_keys.contains(dispatch_key)
+            || self
+                .deferred
+                .iter()
+                .flatten()
+                .any(|deferred| deferred.dispatch_key == dispatch_key)
+    }
+
+    fn already_dispatched(&self, dispatch_key: &str, signature: &str) -> bool {
+        self.dispatched_keys.contains(dispatch_key)
+            || self.prior_attempt_signatures.contains(signature)
+    }
+
+    fn record(&mut self, dispatch_key: String, signature: String) {
+        self.dispatched_keys.insert(dispatch_key);
+        self.attempt_signatures.insert(signature);
+        self.action_call_count += 1;
+    }
+}
+
+#[derive(Debug, Default)]
+pub(super) struct Utf8ChunkDecoder {
+    pending: Vec<u8>,
+}
+
+impl Utf8ChunkDecoder {
+    pub(super) fn decode(&mut self, bytes: &[u8]) -> String {
+        self.pending.extend_from_slice(bytes);
+        let mut decoded = String::new();
+        let mut rest = self.pending.as_slice();
+        loop {
+            match std::str::from_utf8(rest) {
+                Ok(text) => {
+                    decoded.push_str(text);
+                    rest = &[];
+                    break;
+                }
+                Err(error) => {
+                    let (valid, remainder) = rest.split_at(error.valid_up_to());
+                    decoded.push_str(std::str::from_utf8(valid).unwrap_or_default());
+                    match error.error_len() {
+                        Some(invalid_len) => {
+                            decoded.push(char::REPLACEMENT_CHARACTER);
+                            rest = &remainder[invalid_len..];
+                        }
+                        None => {
+                            rest = remainder;
+                            break;
+                        }
+                    }
+                }
+            }
+        }
+        self.pending = rest.to_vec();
+        decoded
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 struct OpenAiUsageMetrics {
     input_tokens: u64,
@@ -40,23 +310,39 @@ pub(crate) struct OpenAiModelAdapter {
     http: reqwest::Client,
     api_key: Option<String>,
     retry_policy: RetryPolicy,
+    endpoint: EndpointConfig,
+    sampling: SamplingConfig,
+    dispatch_order: ActionDispatchOrder,
+    max_tool_calls: Option<usize>,
 }
 
 impl OpenAiModelAdapter {
-    pub(crate) fn new() -> Result<Self, String> {
+    pub(crate) fn new() -> Result<Self, AgentError> {
         let http = reqwest::Client::builder()
             .timeout(Duration::from_secs(DEFAULT_TIMEOUT_SECS))
             .build()
-            .map_err(|error| format!("failed to construct reqwest client: {error}"))?;
+            .map_err(|error| {
+                AgentError::Config(format!("failed to construct reqwest client: {error}"))
+            })?;
         let api_key = std::env::var("OPENAI_API_KEY")
             .ok()
             .map(|value| value.trim().to_string())
             .filter(|value| !value.is_empty());
 
+        let endpoint = EndpointConfig::from_env()?;
+        let sampling = SamplingConfig::from_env()?;
+        let dispatch_order = ActionDispatchOrder::from_env()?;
+        let max_tool_calls = max_tool_calls_from_env()?;
+        let retry_policy = RetryPolicy::from_env().map_err(AgentError::Config)?;
+
         Ok(Self {
             http,
             api_key,
-            retry_policy: RetryPolicy::conservative(),
+            retry_policy,
+            endpoint,
+            sampling,
+            dispatch_order,
+            max_tool_calls,
         })
     }
 
@@ -65,19 +351,21 @@ impl OpenAiModelAdapter {
         prompt_messages: &[PromptMessage],
         action_catalog: &SessionActionCatalog,
         mut on_event: F,
-    ) -> Result<ModelInvocationOutcome, ModelAdapterError>
+    ) -> Result<ModelInvocationOutcome, AgentError>
     where
         F: FnMut(ModelDeltaEvent) + Send,
     {
         let Some(api_key) = self.api_key.as_deref() else {
-            return Err(ModelAdapterError::non_retryable(
-                "OPENAI_API_KEY is required but not configured",
+            return Err(AgentError::Config(
+                "OPENAI_API_KEY is required but not configured".to_string(),
             ));
         };
 
         let mut attempts = 0usize;
         let max_retries = self.retry_policy.max_retries();
-        let mut last_error: Option<ModelAdapterError> = None;
+        let mut last_error: Option<AgentError> = None;
+        let mut ledger =
+            DispatchLedger::new(self.dispatch_order).with_max_action_calls(self.max_tool_calls);
 
         while attempts <= max_retries {
             on_event(ModelDeltaEvent::StreamNote(StreamNote {
@@ -85,34 +373,18 @@ impl OpenAiModelAdapter {
                 detail: format!("attempt={}", attempts + 1),
             }));
 
-            let input_messages = prompt_messages
-                .iter()
-                .map(|message| {
-                    json!({
-                        "role": message.role,
-                        "content": [
-                            {
-                                "type": "input_text",
-                                "text": message.content,
-                            }
-                        ],
-                    })
-                })
-                .collect::<Vec<_>>();
-            let body = json!({
-                "model": DEFAULT_MODEL,
-                "reasoning": {
-                    "effort": DEFAULT_REASONING_EFFORT,
-                },
-                "stream": true,
-                "input": input_messages,
-                "tools": action_catalog.openai_action_definitions(),
-                "tool_choice": "auto"
-            });
+            let body = build_request_body(
+                prompt_messages,
+                action_catalog,
+                &self.endpoint,
+                attempts,
+                &self.sampling,
+                self.max_tool_calls,
+            );
 
             let response = self
                 .http
-                .post(RESPONSES_API_URL)
+                .post(&self.endpoint.responses_url)
                 .bearer_auth(api_key)
                 .json(&body)
                 .send()
@@ -121,18 +393,28 @@ impl OpenAiModelAdapter {
             match response {
                 Ok(response) if response.status().is_success() => {
                     let result = self
-                        .parse_stream(response, action_catalog, &mut on_event)
+                        .parse_stream(response, action_catalog, &mut on_event, &mut ledger)
                         .await;
                     match result {
-                        Ok(outcome) => return Ok(outcome),
+                        Ok(outcome) => {
+                            return Ok(ModelInvocationOutcome {
+                                request_retries: attempts,
+                                fallback_effort: self
+                                    .endpoint
+                                    .fallback_effort_for_attempt(attempts)
+                                    .map(str::to_string),
+                                ..outcome
+                            });
+                        }
                         Err(error) => {
-                            if error.is_semantic_retryable() {
+                            if !error.is_request_retryable() {
                                 return Err(error);
                             }
                             last_error = Some(error);
                             if attempts >= max_retries {
                                 break;
                             }
+                            ledger.begin_retry();
                             let delay = self.retry_policy.compute_delay(attempts, None);
                             on_event(ModelDeltaEvent::StreamNote(StreamNote {
                                 phase: "openai.request.retry".to_string(),
@@ -150,13 +432,18 @@ impl OpenAiModelAdapter {
                     let status = response.status();
                     let retry_after = parse_retry_after(response.headers());
                     let text = response.text().await.unwrap_or_default();
-                    last_error = Some(ModelAdapterError::non_retryable(format!(
-                        "OpenAI request failed: status={} body={}",
-                        status.as_u16(),
-                        truncate_for_log(&text)
-                    )));
-
-                    if should_retry_status(status.as_u16()) && attempts < max_retries {
+                    let error = AgentError::Http {
+                        status: status.as_u16(),
+                        message: format!(
+                            "OpenAI request failed: status={} body={}",
+                            status.as_u16(),
+                            truncate_for_log(&text)
+                        ),
+                    };
+                    let retryable = error.is_request_retryable();
+                    last_error = Some(error);
+
+                    if retryable && attempts < max_retries {
                         let delay = self.retry_policy.compute_delay(attempts, retry_after);
                         on_event(ModelDeltaEvent::StreamNote(StreamNote {
                             phase: "openai.request.retry".to_string(),
@@ -174,10 +461,12 @@ impl OpenAiModelAdapter {
                     break;
                 }
                 Err(error) => {
-                    last_error = Some(ModelAdapterError::non_retryable(format!(
-                        "OpenAI transport error: {error}"
-                    )));
-                    if should_retry_transport(&error) && attempts < max_retries {
+                    let retryable = should_retry_transport(&error);
+                    last_error = Some(AgentError::Transport {
+                        message: format!("OpenAI transport error: {error}"),
+                        retryable,
+                    });
+                    if retryable && attempts < max_retries {
                         let delay = self.retry_policy.compute_delay(attempts, None);
                         on_event(ModelDeltaEvent::StreamNote(StreamNote {
                             phase: "openai.request.retry".to_string(),
@@ -196,8 +485,9 @@ impl OpenAiModelAdapter {
             }
         }
 
-        Err(last_error.unwrap_or_else(|| {
-            ModelAdapterError::non_retryable("OpenAI request failed without an error payload")
+        Err(last_error.unwrap_or_else(|| AgentError::Transport {
+            message: "OpenAI request failed without an error payload".to_string(),
+            retryable: false,
         }))
     }
 
@@ -206,25 +496,22 @@ impl OpenAiModelAdapter {
         response: reqwest::Response,
         action_catalog: &SessionActionCatalog,
         on_event: &mut F,
-    ) -> Result<ModelInvocationOutcome, ModelAdapterError>
+        ledger: &mut DispatchLedger,
+    ) -> Result<ModelInvocationOutcome, AgentError>
     where
         F: FnMut(ModelDeltaEvent) + Send,
     {
         let mut stream = response.bytes_stream();
         let mut line_buffer = String::new();
-        let mut partial_calls: HashMap<String, PartialActionCall> = HashMap::new();
-        let mut dispatched_keys = HashSet::new();
-        let mut action_call_count = 0usize;
-        let mut diagnostics = Vec::new();
-        let mut active_assistant_output = String::new();
-        let mut assistant_outputs = Vec::new();
-        let mut usage_emitted = false;
+        let mut chunk_decoder = Utf8ChunkDecoder::default();
+        let mut state = StreamState::default();
 
         while let Some(chunk_result) = stream.next().await {
-            let bytes = chunk_result.map_err(|error| {
-                ModelAdapterError::non_retryable(format!("stream chunk error: {error}"))
+            let bytes = chunk_result.map_err(|error| AgentError::Transport {
+                message: format!("stream chunk error: {error}"),
+                retryable: true,
             })?;
-            line_buffer.push_str(&String::from_utf8_lossy(&bytes));
+            line_buffer.push_str(&chunk_decoder.decode(&bytes));
 
             while let Some(newline_index) = line_buffer.find('\n') {
                 let mut line = line_buffer[..newline_index].to_string();
@@ -237,49 +524,17 @@ impl OpenAiModelAdapter {
 
                 let payload = line[5..].trim();
                 if payload == "[DONE]" {
-                    flush_assistant_output(
-                        &mut active_assistant_output,
-                        &mut assistant_outputs,
-                        on_event,
-                    );
-                    return Ok(ModelInvocationOutcome {
-                        action_call_count,
-                        assistant_outputs,
-                        diagnostics,
-                    });
+                    return Ok(state.finish(on_event, ledger));
                 }
 
                 let value: Value = serde_json::from_str(payload).map_err(|error| {
-                    ModelAdapterError::non_retryable(format!(
-                        "invalid stream json payload: {error}"
-                    ))
+                    AgentError::StreamParse(format!("invalid stream json payload: {error}"))
                 })?;
-                handle_stream_event(
-                    value,
-                    action_catalog,
-                    on_event,
-                    &mut partial_calls,
-                    &mut dispatched_keys,
-                    &mut action_call_count,
-                    &mut diagnostics,
-                    &mut active_assistant_output,
-                    &mut assistant_outputs,
-                    &mut usage_emitted,
-                )?;
+                state.handle_event(value, action_catalog, on_event, ledger)?;
             }
         }
 
-        flush_assistant_output(
-            &mut active_assistant_output,
-            &mut assistant_outputs,
-            on_event,
-        );
-
-        Ok(ModelInvocationOutcome {
-            action_call_count,
-            assistant_outputs,
-            diagnostics,
-        })
+        Ok(state.finish(on_event, ledger))
     }
 }
 
@@ -301,257 +556,309 @@ impl ModelAdapter for OpenAiModelAdapter {
     }
 }
 
-#[allow(clippy::too_many_arguments)]
-fn handle_stream_event<F>(
-    value: Value,
-    action_catalog: &SessionActionCatalog,
-    on_event: &mut F,
-    partial_calls: &mut HashMap<String, PartialActionCall>,
-    dispatched_keys: &mut HashSet<String>,
-    action_call_count: &mut usize,
-    diagnostics: &mut Vec<String>,
-    active_assistant_output: &mut String,
-    assistant_outputs: &mut Vec<String>,
-    usage_emitted: &mut bool,
-) -> Result<(), ModelAdapterError>
-where
-    F: FnMut(ModelDeltaEvent) + Send,
-{
-    let event_type = value
-        .get("type")
-        .and_then(Value::as_str)
-        .unwrap_or("unknown");
+#[derive(Debug, Default)]
+struct StreamState {
+    partial_calls: HashMap<String, PartialActionCall>,
+    diagnostics: Vec<String>,
+    active_assistant_output: String,
+    assistant_outputs: Vec<String>,
+    usage_emitted: bool,
+}
 
-    on_event(ModelDeltaEvent::StreamNote(StreamNote {
-        phase: "openai.stream.event".to_string(),
-        detail: event_type.to_string(),
-    }));
+impl StreamState {
+    fn handle_event<F>(
+        &mut self,
+        value: Value,
+        action_catalog: &SessionActionCatalog,
+        on_event: &mut F,
+        ledger: &mut DispatchLedger,
+    ) -> Result<(), AgentError>
+    where
+        F: FnMut(ModelDeltaEvent) + Send,
+    {
+        let event_type = value
+            .get("type")
+            .and_then(Value::as_str)
+            .unwrap_or("unknown");
+
+        on_event(ModelDeltaEvent::StreamNote(StreamNote {
+            phase: "openai.stream.event".to_string(),
+            detail: event_type.to_string(),
+        }));
+
+        maybe_emit_usage_metrics(
+            &value,
+            &mut self.usage_emitted,
+            &mut self.diagnostics,
+            on_event,
+        );
 
-    maybe_emit_usage_metrics(&value, usage_emitted, diagnostics, on_event);
-
-    match event_type {
-        "response.output_item.added" | "response.output_item.done" => {
-            if let Some(item) = value.get("item") {
-                maybe_finalize_item(
-                    item,
-                    action_catalog,
-                    on_event,
-                    partial_calls,
-                    dispatched_keys,
-                    action_call_count,
-                    diagnostics,
-                )?;
-                maybe_capture_assistant_from_item(
-                    item,
-                    on_event,
-                    active_assistant_output,
-                    assistant_outputs,
-                );
+        match event_type {
+            "response.output_item.added" | "response.output_item.done" => {
+                if let Some(item) = value.get("item") {
+                    self.finalize_item(
+                        item,
+                        event_type == "response.output_item.done",
+                        action_catalog,
+                        on_event,
+                        ledger,
+                    )?;
+                    maybe_capture_assistant_from_item(
+                        item,
+                        on_event,
+                        &mut self.active_assistant_output,
+                        &mut self.assistant_outputs,
+                    );
+                }
             }
-        }
-        "response.output_text.delta" => {
-            let delta = value
-                .get("delta")
-                .and_then(Value::as_str)
-                .unwrap_or_default();
-            if !delta.is_empty() {
-                active_assistant_output.push_str(delta);
-                on_event(ModelDeltaEvent::AssistantTextDelta(delta.to_string()));
+            "response.output_text.delta" => {
+                let delta = value
+                    .get("delta")
+                    .and_then(Value::as_str)
+                    .unwrap_or_default();
+                if !delta.is_empty() {
+                    self.active_assistant_output.push_str(delta);
+                    on_event(ModelDeltaEvent::AssistantTextDelta(delta.to_string()));
+                }
             }
-        }
-        "response.output_text.done" => {
-            let text = value
-                .get("text")
-                .and_then(Value::as_str)
-                .unwrap_or_default()
-                .to_string();
-            if text.is_empty() {
-                flush_assistant_output(active_assistant_output, assistant_outputs, on_event);
-            } else {
-                finalize_assistant_output(
-                    text,
-                    on_event,
-                    active_assistant_output,
-                    assistant_outputs,
-                );
+            "response.output_text.done" => {
+                let text = value
+                    .get("text")
+                    .and_then(Value::as_str)
+                    .unwrap_or_default()
+                    .to_string();
+                if text.is_empty() {
+                    flush_assistant_output(
+                        &mut self.active_assistant_output,
+                        &mut self.assistant_outputs,
+                        on_event,
+                    );
+                } else {
+                    finalize_assistant_output(
+                        text,
+                        on_event,
+                        &mut self.active_assistant_output,
+                        &mut self.assistant_outputs,
+                    );
+                }
             }
-        }
-        "response.function_call_arguments.delta" => {
-            let key = extract_call_key(&value).unwrap_or_else(|| "unknown_call".to_string());
-            let delta = value
-                .get("delta")
-                .and_then(Value::as_str)
-                .unwrap_or_default();
-            let partial = partial_calls
-                .entry(key.clone())
-                .or_insert(PartialActionCall {
-                    call_id: value
-                        .get("call_id")
-                        .and_then(Value::as_str)
-                        .map(str::to_string),
-                    name: value
-                        .get("name")
-                        .and_then(Value::as_str)
-                        .map(str::to_string),
-                    arguments: String::new(),
-                });
-            if let Some(name) = value.get("name").and_then(Value::as_str) {
-                partial.name = Some(name.to_string());
+            "response.function_call_arguments.delta" => {
+                let key = extract_call_key(&value).unwrap_or_else(|| "unknown_call".to_string());
+                let delta = value
+                    .get("delta")
+                    .and_then(Value::as_str)
+                    .unwrap_or_default();
+                let partial = self
+                    .partial_calls
+                    .entry(key.clone())
+                    .or_insert(PartialActionCall {
+                        call_id: value
+                            .get("call_id")
+                            .and_then(Value::as_str)
+                            .map(str::to_string),
+                        name: value
+                            .get("name")
+                            .and_then(Value::as_str)
+                            .map(str::to_string),
+                        arguments: String::new(),
+                    });
+                if let Some(name) = value.get("name").and_then(Value::as_str) {
+                    partial.name = Some(name.to_string());
+                }
+                partial.arguments.push_str(delta);
+
+                if !delta.is_empty() {
+                    on_event(ModelDeltaEvent::ActionArgsDelta(ActionArgDeltaNote {
+                        call_key: key,
+                        call_id: partial.call_id.clone(),
+                        action_id: partial.name.clone(),
+                        args_delta: delta.to_string(),
+                    }));
+                }
             }
-            partial.arguments.push_str(delta);
+            "response.function_call_arguments.done" => {
+                let key = extract_call_key(&value).unwrap_or_else(|| "unknown_call".to_string());
+                let arguments = value
+                    .get("arguments")
+                    .and_then(Value::as_str)
+                    .unwrap_or_default();
+
+                let partial = self
+                    .partial_calls
+                    .entry(key.clone())
+                    .or_insert(PartialActionCall {
+                        call_id: value
+                            .get("call_id")
+                            .and_then(Value::as_str)
+                            .map(str::to_string),
+                        name: value
+                            .get("name")
+                            .and_then(Value::as_str)
+                            .map(str::to_string),
+                        arguments: String::new(),
+                    });
+                if let Some(name) = value.get("name").and_then(Value::as_str) {
+                    partial.name = Some(name.to_string());
+                }
+                partial.arguments = arguments.to_string();
 
-            if !delta.is_empty() {
-                on_event(ModelDeltaEvent::ActionArgsDelta(ActionArgDeltaNote {
-                    call_key: key,
+                on_event(ModelDeltaEvent::ActionArgsDone(ActionArgDoneNote {
+                    call_key: key.clone(),
                     call_id: partial.call_id.clone(),
                     action_id: partial.name.clone(),
-                    args_delta: delta.to_string(),
+                    args_json: partial.arguments.clone(),
                 }));
+
+                if let Some(name) = partial.name.clone() {
+                    maybe_dispatch_partial(
+                        action_catalog,
+                        ActionCallCandidate {
+                            key,
+                            raw_action_id: name,
+                            arguments_raw: partial.arguments.clone(),
+                            arguments_complete: true,
+                            call_id: partial.call_id.clone(),
+                        },
+                        on_event,
+                        ledger,
+                        &mut self.diagnostics,
+                    )?;
+                }
             }
-        }
-        "response.function_call_arguments.done" => {
-            let key = extract_call_key(&value).unwrap_or_else(|| "unknown_call".to_string());
-            let arguments = value
-                .get("arguments")
-                .and_then(Value::as_str)
-                .unwrap_or_default();
-
-            let partial = partial_calls
-                .entry(key.clone())
-                .or_insert(PartialActionCall {
-                    call_id: value
-                        .get("call_id")
-                        .and_then(Value::as_str)
-                        .map(str::to_string),
-                    name: value
-                        .get("name")
-                        .and_then(Value::as_str)
-                        .map(str::to_string),
-                    arguments: String::new(),
-                });
-            if let Some(name) = value.get("name").and_then(Value::as_str) {
-                partial.name = Some(name.to_string());
+            "response.error" => {
+                return Err(AgentError::StreamParse(format!(
+                    "OpenAI stream error payload: {value}"
+                )));
             }
-            partial.arguments = arguments.to_string();
+            _ => {}
+        }
 
-            on_event(ModelDeltaEvent::ActionArgsDone(ActionArgDoneNote {
-                call_key: key.clone(),
-                call_id: partial.call_id.clone(),
-                action_id: partial.name.clone(),
-                args_json: partial.arguments.clone(),
-            }));
+        Ok(())
+    }
 
-            if let Some(name) = partial.name.clone() {
-                maybe_dispatch_partial(
-                    action_catalog,
-                    key,
-                    name,
-                    partial.arguments.clone(),
-                    partial.call_id.clone(),
-                    on_event,
-                    dispatched_keys,
-                    action_call_count,
-                    diagnostics,
-                )?;
-            }
-        }
-        "response.error" => {
-            return Err(ModelAdapterError::non_retryable(format!(
-                "OpenAI stream error payload: {value}"
-            )));
+    fn finalize_item<F>(
+        &mut self,
+        item: &Value,
+        arguments_complete: bool,
+        action_catalog: &SessionActionCatalog,
+        on_event: &mut F,
+        ledger: &mut DispatchLedger,
+    ) -> Result<(), AgentError>
+    where
+        F: FnMut(ModelDeltaEvent) + Send,
+    {
+        if item.get("type").and_then(Value::as_str) != Some("function_call") {
+            return Ok(());
         }
-        _ => {}
-    }
 
-    Ok(())
-}
+        let key = item
+            .get("id")
+            .and_then(Value::as_str)
+            .or_else(|| item.get("call_id").and_then(Value::as_str))
+            .unwrap_or("unknown_call")
+            .to_string();
+
+        let entry = self
+            .partial_calls
+            .entry(key.clone())
+            .or_insert(PartialActionCall {
+                call_id: item
+                    .get("call_id")
+                    .and_then(Value::as_str)
+                    .map(str::to_string),
+                name: item.get("name").and_then(Value::as_str).map(str::to_string),
+                arguments: String::new(),
+            });
 
-fn maybe_finalize_item<F>(
-    item: &Value,
-    action_catalog: &SessionActionCatalog,
-    on_event: &mut F,
-    partial_calls: &mut HashMap<String, PartialActionCall>,
-    dispatched_keys: &mut HashSet<String>,
-    action_call_count: &mut usize,
-    diagnostics: &mut Vec<String>,
-) -> Result<(), ModelAdapterError>
-where
-    F: FnMut(ModelDeltaEvent) + Send,
-{
-    if item.get("type").and_then(Value::as_str) != Some("function_call") {
-        return Ok(());
-    }
+        if let Some(name) = item.get("name").and_then(Value::as_str) {
+            entry.name = Some(name.to_string());
+        }
+        if let Some(arguments) = item.get("arguments").and_then(Value::as_str)
+            && !arguments.is_empty()
+        {
+            entry.arguments = arguments.to_string();
+        }
 
-    let key = item
-        .get("id")
-        .and_then(Value::as_str)
-        .or_else(|| item.get("call_id").and_then(Value::as_str))
-        .unwrap_or("unknown_call")
-        .to_string();
-
-    let entry = partial_calls
-        .entry(key.clone())
-        .or_insert(PartialActionCall {
-            call_id: item
-                .get("call_id")
-                .and_then(Value::as_str)
-                .map(str::to_string),
-            name: item.get("name").and_then(Value::as_str).map(str::to_string),
-            arguments: String::new(),
-        });
+        if let Some(name) = entry.name.clone() {
+            maybe_dispatch_partial(
+                action_catalog,
+                ActionCallCandidate {
+                    key,
+                    raw_action_id: name,
+                    arguments_raw: entry.arguments.clone(),
+                    arguments_complete,
+                    call_id: entry.call_id.clone(),
+                },
+                on_event,
+                ledger,
+                &mut self.diagnostics,
+            )?;
+        }
 
-    if let Some(name) = item.get("name").and_then(Value::as_str) {
-        entry.name = Some(name.to_string());
-    }
-    if let Some(arguments) = item.get("arguments").and_then(Value::as_str)
-        && !arguments.is_empty()
-    {
-        entry.arguments = arguments.to_string();
+        Ok(())
     }
 
-    if let Some(name) = entry.name.clone() {
-        maybe_dispatch_partial(
-            action_catalog,
-            key,
-            name,
-            entry.arguments.clone(),
-            entry.call_id.clone(),
+    fn finish<F>(mut self, on_event: &mut F, ledger: &mut DispatchLedger) -> ModelInvocationOutcome
+    where
+        F: FnMut(ModelDeltaEvent) + Send,
+    {
+        flush_deferred_invocations(on_event, ledger, &mut self.diagnostics);
+        flush_assistant_output(
+            &mut self.active_assistant_output,
+            &mut self.assistant_outputs,
             on_event,
-            dispatched_keys,
-            action_call_count,
-            diagnostics,
-        )?;
+        );
+        ModelInvocationOutcome {
+            action_call_count: ledger.action_call_count,
+            assistant_outputs: self.assistant_outputs,
+            diagnostics: self.diagnostics,
+            request_retries: 0,
+            fallback_effort: None,
+        }
     }
+}
 
-    Ok(())
+pub(super) struct ActionCallCandidate {
+    pub(super) key: String,
+    pub(super) raw_action_id: String,
+    pub(super) arguments_raw: String,
+    pub(super) arguments_complete: bool,
+    pub(super) call_id: Option<String>,
 }
 
-#[allow(clippy::too_many_arguments)]
-fn maybe_dispatch_partial<F>(
+pub(super) fn maybe_dispatch_partial<F>(
     action_catalog: &SessionActionCatalog,
-    key: String,
-    raw_action_id: String,
-    arguments_raw: String,
-    call_id: Option<String>,
+    candidate: ActionCallCandidate,
     on_event: &mut F,
-    dispatched_keys: &mut HashSet<String>,
-    action_call_count: &mut usize,
+    ledger: &mut DispatchLedger,
     diagnostics: &mut Vec<String>,
-) -> Result<(), ModelAdapterError>
+) -> Result<(), AgentError>
 where
     F: FnMut(ModelDeltaEvent) + Send,
 {
-    if arguments_raw.trim().is_empty() {
-        return Ok(());
-    }
+    let ActionCallCandidate {
+        key,
+        raw_action_id,
+        arguments_raw,
+        arguments_complete,
+        call_id,
+    } = candidate;
+    let arguments_raw = if arguments_raw.trim().is_empty() {
+        if !arguments_complete {
+            return Ok(());
+        }
+        "{}".to_string()
+    } else {
+        arguments_raw
+    };
 
     let dispatch_key = call_id.clone().unwrap_or_else(|| key.clone());
-    if dispatched_keys.contains(&dispatch_key) {
+    if ledger.is_settled(&dispatch_key) {
         return Ok(());
     }
 
     let args_value: Value = serde_json::from_str(&arguments_raw).map_err(|error| {
-        ModelAdapterError::semantic_retryable(format!(
+        AgentError::Validation(format!(
             "invalid arguments JSON for action `{raw_action_id}`: {error}; payload={arguments_raw}"
         ))
     })?;
@@ -559,31 +866,110 @@ where
     let canonical_action_id = action_catalog
         .validate_action(&raw_action_id, &args_value)
         .map_err(|error| {
-            ModelAdapterError::semantic_retryable(format!(
+            AgentError::Validation(format!(
                 "action `{raw_action_id}` validation failed: {error}; args={}",
                 truncate_for_log(&arguments_raw)
             ))
         })?;
 
     let args_json = serde_json::to_string(&args_value).map_err(|error| {
-        ModelAdapterError::non_retryable(format!("failed to canonicalize action args: {error}"))
+        AgentError::StreamParse(format!("failed to canonicalize action args: {error}"))
     })?;
 
-    on_event(ModelDeltaEvent::ActionInvocation(ActionInvocation {
-        action_id: canonical_action_id.clone(),
+    let signature = format!("{canonical_action_id}:{args_json}");
+    if ledger.already_dispatched(&dispatch_key, &signature) {
+        diagnostics.push(format!(
+            "skipped action_call={dispatch_key} name={canonical_action_id}; already dispatched before stream reconnect"
+        ));
+        ledger.dispatched_keys.insert(dispatch_key);
+        return Ok(());
+    }
+
+    let invocation = ActionInvocation {
+        action_id: canonical_action_id,
         args_json,
-        call_key: key.clone(),
-        call_id: call_id.clone(),
-    }));
+        call_key: key,
+        call_id,
+    };
+    if let Some(deferred) = ledger.deferred.as_mut() {
+        deferred.push(DeferredInvocation {
+            dispatch_key,
+            signature,
+            invocation,
+        });
+        return Ok(());
+    }
+
+    emit_invocation(
+        invocation,
+        dispatch_key,
+        signature,
+        on_event,
+        ledger,
+        diagnostics,
+    );
+    Ok(())
+}
+
+fn flush_deferred_invocations<F>(
+    on_event: &mut F,
+    ledger: &mut DispatchLedger,
+    diagnostics: &mut Vec<String>,
+) where
+    F: FnMut(ModelDeltaEvent) + Send,
+{
+    let Some(mut deferred) = ledger.deferred.as_mut().map(std::mem::take) else {
+        return;
+    };
+    deferred.sort_by(|left, right| {
+        let left_id = left.invocation.call_id.as_ref();
+        let right_id = right.invocation.call_id.as_ref();
+        left_id
+            .unwrap_or(&left.invocation.call_key)
+            .cmp(right_id.unwrap_or(&right.invocation.call_key))
+            .then_with(|| left.invocation.call_key.cmp(&right.invocation.call_key))
+    });
+    for entry in deferred {
+        emit_invocation(
+            entry.invocation,
+            entry.dispatch_key,
+            entry.signature,
+            on_event,
+            ledger,
+            diagnostics,
+        );
+    }
+}
 
+fn emit_invocation<F>(
+    invocation: ActionInvocation,
+    dispatch_key: String,
+    signature: String,
+    on_event: &mut F,
+    ledger: &mut DispatchLedger,
+    diagnostics: &mut Vec<String>,
+) where
+    F: FnMut(ModelDeltaEvent) + Send,
+{
+    if ledger.at_action_call_cap() {
+        let max_action_calls = ledger.max_action_calls.unwrap_or_default();
+        diagnostics.push(format!(
+            "skipped action_call={dispatch_key} name={}; per-turn cap of {max_action_calls} action calls reached",
+            invocation.action_id
+        ));
+        on_event(ModelDeltaEvent::StreamNote(StreamNote {
+            phase: "openai.action_call_cap".to_string(),
+            detail: format!("max_tool_calls={max_action_calls} skipped={dispatch_key}"),
+        }));
+        ledger.dispatched_keys.insert(dispatch_key);
+        return;
+    }
     diagnostics.push(format!(
-        "dispatched action_call={} name={canonical_action_id}",
-        dispatch_key
+        "dispatched action_call={} name={}",
+        dispatch_key, invocation.action_id
     ));
-    dispatched_keys.insert(dispatch_key);
-    *action_call_count += 1;
-
-    Ok(())
+    on_event(ModelDeltaEvent::ActionInvocation(invocation));
+    ledger.record(dispatch_key, signature);
 }
 
 fn maybe_capture_assistant_from_item<F>(
@@ -655,7 +1041,7 @@ fn finalize_assistant_output<F>(
     flush_assistant_output(active_assistant_output, assistant_outputs, on_event);
 }
 
-fn flush_assistant_output<F>(
+pub(super) fn flush_assistant_output<F>(
     active_assistant_output: &mut String,
     assistant_outputs: &mut Vec<String>,
     on_event: &mut F,
@@ -691,21 +1077,59 @@ fn extract_call_key(value: &Value) -> Option<String> {
         .map(str::to_string)
 }
 
-fn parse_retry_after(headers: &reqwest::header::HeaderMap) -> Option<Duration> {
+fn build_request_body(
+    prompt_messages: &[PromptMessage],
+    action_catalog: &SessionActionCatalog,
+    endpoint: &EndpointConfig,
+    attempt: usize,
+    sampling: &SamplingConfig,
+    max_tool_calls: Option<usize>,
+) -> Value {
+    let input_messages = prompt_messages
+        .iter()
+        .map(|message| {
+            json!({
+                "role": message.role,
+                "content": [
+                    {
+                        "type": "input_text",
+                        "text": message.content,
+                    }
+                ],
+            })
+        })
+        .collect::<Vec<_>>();
+    let mut body = json!({
+        "model": endpoint.model,
+        "reasoning": {
+            "effort": endpoint.reasoning_effort_for_attempt(attempt),
+        },
+        "stream": true,
+        "input": input_messages,
+    });
+    let tools = action_catalog.openai_action_definitions();
+    if !tools.is_empty() {
+        body["tools"] = json!(tools);
+        body["tool_choice"] = json!("auto");
+    }
+    sampling.apply(&mut body);
+    if let Some(max_tool_calls) = max_tool_calls {
+        body["max_tool_calls"] = json!(max_tool_calls);
+    }
+    body
+}
+
+pub(super) fn parse_retry_after(headers: &reqwest::header::HeaderMap) -> Option<Duration> {
     let raw = headers.get(RETRY_AFTER)?.to_str().ok()?;
     let seconds = raw.trim().parse::<u64>().ok()?;
     Some(Duration::from_secs(seconds))
 }
 
-fn should_retry_status(status: u16) -> bool {
-    status == 408 || status == 409 || status == 429 || status >= 500
-}
-
-fn should_retry_transport(error: &reqwest::Error) -> bool {
+pub(super) fn should_retry_transport(error: &reqwest::Error) -> bool {
     error.is_timeout() || error.is_connect() || error.is_request() || error.is_body()
 }
 
-fn truncate_for_log(value: &str) -> String {
+pub(super) fn truncate_for_log(value: &str) -> String {
     const MAX: usize = 1024;
     if value.len() <= MAX {
         return value.to_string();
@@ -785,21 +1209,43 @@ fn extract_usage_metrics(value: &Value) -> Option<OpenAiUsageMetrics> {
 
 #[cfg(test)]
 mod tests {
-    use std::collections::{HashMap, HashSet};
+    use std::collections::BTreeSet;
+    use std::sync::Arc;
 
     use serde_json::json;
 
     use super::{
-        OpenAiUsageMetrics, PartialActionCall, extract_usage_metrics, handle_stream_event,
+        ActionDispatchOrder, DispatchLedger, EndpointConfig, OpenAiUsageMetrics, SamplingConfig,
+        StreamState, Utf8ChunkDecoder, build_request_body, extract_usage_metrics,
+        flush_deferred_invocations, parse_max_tool_calls,
     };
     use crate::agent::SessionActionCatalog;
+    use crate::agent::error::AgentError;
     use crate::agent::types::{
-        AgentInvocationContext, CapabilitySurface, HarnessContract, IdentityEnvelope,
-        ModelDeltaEvent, ParticipantEnvelope, SessionAnchor, SessionBaseline, SessionCompaction,
+        AgentInvocationContext, CapabilityDomain, CapabilitySurface, HarnessContract,
+        IdentityEnvelope, ModelDeltaEvent, ParticipantEnvelope, PromptMessage, SessionAnchor,
+        SessionBaseline, SessionCompaction,
     };
     use crate::capability_domain::build_default_capability_domain_registry;
+    use crate::history::PreviewLimits;
 
     fn empty_action_catalog() -> SessionActionCatalog {
+        action_catalog_with_domains(vec![])
+    }
+
+    fn filesystem_action_catalog() -> SessionActionCatalog {
+        action_catalog_with_domains(vec![CapabilityDomain {
+            id: "filesystem".to_string(),
+            name: "Filesystem".to_string(),
+            description: "Stateful filesystem environment rooted at a base path.".to_string(),
+            actions: vec![],
+            recipes: vec![],
+        }])
+    }
+
+    fn action_catalog_with_domains(
+        capability_domains: Vec<CapabilityDomain>,
+    ) -> SessionActionCatalog {
         SessionActionCatalog::from_context(
             build_default_capability_domain_registry(
                 &std::env::current_dir().expect("current directory for registry"),
@@ -808,6 +1254,7 @@ mod tests {
                 harness_contract: HarnessContract {
                     runtime_version: "0.1.0".to_string(),
                     contract_schema_version: 1,
+                    system_preamble: None,
                 },
                 identity_envelope: IdentityEnvelope {
                     schema_version: 1,
@@ -819,9 +1266,7 @@ mod tests {
                         session_id: "session-1".to_string(),
                         started_at_unix_ms: 1,
                     },
-                    capability_surface: CapabilitySurface {
-                        capability_domains: vec![],
-                    },
+                    capability_surface: CapabilitySurface { capability_domains },
                     participant_envelope: ParticipantEnvelope {
                         schema_version: 1,
                         source_revision: "participants@1".to_string(),
@@ -832,6 +1277,7 @@ mod tests {
                 triggers: vec![],
                 recent_history: vec![],
                 compaction: SessionCompaction::default(),
+                result_preview_limits: PreviewLimits::default(),
             },
         )
     }
@@ -868,13 +1314,8 @@ mod tests {
     fn response_completed_emits_usage_note_once() {
         let action_catalog = empty_action_catalog();
         let mut events = Vec::<ModelDeltaEvent>::new();
-        let mut partial_calls = HashMap::<String, PartialActionCall>::new();
-        let mut dispatched_keys = HashSet::<String>::new();
-        let mut action_call_count = 0usize;
-        let mut diagnostics = Vec::<String>::new();
-        let mut active_assistant_output = String::new();
-        let mut assistant_outputs = Vec::<String>::new();
-        let mut usage_emitted = false;
+        let mut state = StreamState::default();
+        let mut ledger = DispatchLedger::default();
 
         let usage_event = json!({
             "type": "response.completed",
@@ -890,32 +1331,22 @@ mod tests {
             }
         });
 
-        handle_stream_event(
-            usage_event.clone(),
-            &action_catalog,
-            &mut |event| events.push(event),
-            &mut partial_calls,
-            &mut dispatched_keys,
-            &mut action_call_count,
-            &mut diagnostics,
-            &mut active_assistant_output,
-            &mut assistant_outputs,
-            &mut usage_emitted,
-        )
-        .expect("usage event should succeed");
-        handle_stream_event(
-            usage_event,
-            &action_catalog,
-            &mut |event| events.push(event),
-            &mut partial_calls,
-            &mut dispatched_keys,
-            &mut action_call_count,
-            &mut diagnostics,
-            &mut active_assistant_output,
-            &mut assistant_outputs,
-            &mut usage_emitted,
-        )
-        .expect("duplicate usage event should succeed");
+        state
+            .handle_event(
+                usage_event.clone(),
+                &action_catalog,
+                &mut |event| events.push(event),
+                &mut ledger,
+            )
+            .expect("usage event should succeed");
+        state
+            .handle_event(
+                usage_event,
+                &action_catalog,
+                &mut |event| events.push(event),
+                &mut ledger,
+            )
+            .expect("duplicate usage event should succeed");
 
         let usage_notes = events
             .iter()
@@ -932,11 +1363,490 @@ mod tests {
             vec!["input_tokens=1200 cached_input_tokens=900 output_tokens=50 total_tokens=1250"]
         );
         assert_eq!(
-            diagnostics,
+            state.diagnostics,
             vec![
                 "openai_usage input_tokens=1200 cached_input_tokens=900 output_tokens=50 total_tokens=1250"
                     .to_string()
             ]
         );
     }
+
+    #[test]
+    fn completed_action_call_with_empty_arguments_is_validated_as_object() {
+        let action_catalog = empty_action_catalog();
+        let mut events = Vec::<ModelDeltaEvent>::new();
+        let mut state = StreamState::default();
+        let mut ledger = DispatchLedger::default();
+
+        state
+            .handle_event(
+                json!({
+                    "type": "response.output_item.added",
+                    "item": {
+                        "type": "function_call",
+                        "id": "item-1",
+                        "call_id": "call-1",
+                        "name": "filesystem__read",
+                        "arguments": ""
+                    }
+                }),
+                &action_catalog,
+                &mut |event| events.push(event),
+                &mut ledger,
+            )
+            .expect("pending call without arguments should keep waiting");
+        assert_eq!(ledger.action_call_count, 0);
+
+        let error = state
+            .handle_event(
+                json!({
+                    "type": "response.function_call_arguments.done",
+                    "item_id": "item-1",
+                    "arguments": ""
+                }),
+                &action_catalog,
+                &mut |event| events.push(event),
+                &mut ledger,
+            )
+            .expect_err("completed call with empty arguments should be validated");
+
+        assert!(error.is_semantic_retryable());
+        assert!(
+            error
+                .message()
+                .contains("action `filesystem__read` validation failed")
+        );
+        assert!(error.message().contains("args={}"));
+        assert_eq!(ledger.action_call_count, 0);
+    }
+
+    #[test]
+    fn stream_retry_does_not_redispatch_action_calls_from_the_dropped_attempt() {
+        let action_catalog = filesystem_action_catalog();
+        let mut events = Vec::<ModelDeltaEvent>::new();
+        let mut ledger = DispatchLedger::default();
+        let mut diagnostics = Vec::<String>::new();
+
+        let mut feed_completed_call =
+            |ledger: &mut DispatchLedger, item_id: &str, call_id: &str, path: &str| {
+                let mut state = StreamState::default();
+                state
+                    .handle_event(
+                        json!({
+                            "type": "response.output_item.done",
+                            "item": {
+                                "type": "function_call",
+                                "id": item_id,
+                                "call_id": call_id,
+                                "name": "filesystem__read",
+                                "arguments": json!({"path": path}).to_string()
+                            }
+                        }),
+                        &action_catalog,
+                        &mut |event| events.push(event),
+                        ledger,
+                    )
+                    .expect("completed action call should dispatch");
+                diagnostics.extend(state.diagnostics);
+            };
+
+        feed_completed_call(&mut ledger, "item-1", "call-1", "a.txt");
+        ledger.begin_retry();
+        feed_completed_call(&mut ledger, "item-9", "call-9", "a.txt");
+        feed_completed_call(&mut ledger, "item-10", "call-10", "b.txt");
+
+        let dispatched = events
+            .iter()
+            .filter_map(|event| match event {
+                ModelDeltaEvent::ActionInvocation(invocation) => Some(invocation.args_json.clone()),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            dispatched,
+            vec![
+                json!({"path": "a.txt"}).to_string(),
+                json!({"path": "b.txt"}).to_string()
+            ]
+        );
+        assert_eq!(ledger.action_call_count, 2);
+        assert!(
+            diagnostics
+                .iter()
+                .any(|line| line.contains("already dispatched before stream reconnect"))
+        );
+    }
+
+    #[test]
+    fn call_id_dispatch_order_is_stable_regardless_of_finalize_timing() {
+        let action_catalog = filesystem_action_catalog();
+        let call = |call_id: &str, path: &str| {
+            json!({
+                "type": "function_call",
+                "id": format!("item-{call_id}"),
+                "call_id": call_id,
+                "name": "filesystem__read",
+                "arguments": json!({"path": path}).to_string()
+            })
+        };
+        let args_done = |call_id: &str, path: &str| {
+            json!({
+                "type": "response.function_call_arguments.done",
+                "item_id": format!("item-{call_id}"),
+                "call_id": call_id,
+                "name": "filesystem__read",
+                "arguments": json!({"path": path}).to_string()
+            })
+        };
+        let first_run = vec![
+            json!({"type": "response.output_item.done", "item": call("call-b", "b.txt")}),
+            args_done("call-a", "a.txt"),
+            json!({"type": "response.output_item.done", "item": call("call-a", "a.txt")}),
+            args_done("call-c", "c.txt"),
+        ];
+        let second_run = vec![
+            args_done("call-c", "c.txt"),
+            args_done("call-a", "a.txt"),
+            json!({"type": "response.output_item.done", "item": call("call-b", "b.txt")}),
+            json!({"type": "response.output_item.done", "item": call("call-a", "a.txt")}),
+        ];
+
+        let dispatch = |stream: Vec<serde_json::Value>, order: ActionDispatchOrder| {
+            let mut events = Vec::<ModelDeltaEvent>::new();
+            let mut state = StreamState::default();
+            let mut ledger = DispatchLedger::new(order);
+            for event in stream {
+                state
+                    .handle_event(
+                        event,
+                        &action_catalog,
+                        &mut |event| events.push(event),
+                        &mut ledger,
+                    )
+                    .expect("stream event should succeed");
+            }
+            flush_deferred_invocations(
+                &mut |event| events.push(event),
+                &mut ledger,
+                &mut state.diagnostics,
+            );
+            assert_eq!(ledger.action_call_count, 3);
+            events
+                .into_iter()
+                .filter_map(|event| match event {
+                    ModelDeltaEvent::ActionInvocation(invocation) => invocation.call_id,
+                    _ => None,
+                })
+                .collect::<Vec<_>>()
+        };
+
+        assert_ne!(
+            dispatch(first_run.clone(), ActionDispatchOrder::Stream),
+            dispatch(second_run.clone(), ActionDispatchOrder::Stream)
+        );
+        let expected = vec!["call-a", "call-b", "call-c"];
+        assert_eq!(dispatch(first_run, ActionDispatchOrder::CallId), expected);
+        assert_eq!(dispatch(second_run, ActionDispatchOrder::CallId), expected);
+    }
+
+    #[test]
+    fn action_call_cap_stops_dispatch_even_when_the_stream_offers_more() {
+        let action_catalog = filesystem_action_catalog();
+        let call = |call_id: &str, path: &str| {
+            json!({
+                "type": "response.output_item.done",
+                "item": {
+                    "type": "function_call",
+                    "id": format!("item-{call_id}"),
+                    "call_id": call_id,
+                    "name": "filesystem__read",
+                    "arguments": json!({"path": path}).to_string()
+                }
+            })
+        };
+        let stream = vec![
+            call("call-a", "a.txt"),
+            call("call-b", "b.txt"),
+            call("call-c", "c.txt"),
+            call("call-c", "c.txt"),
+        ];
+
+        let mut events = Vec::<ModelDeltaEvent>::new();
+        let mut state = StreamState::default();
+        let mut ledger =
+            DispatchLedger::new(ActionDispatchOrder::Stream).with_max_action_calls(Some(2));
+        for event in stream {
+            state
+                .handle_event(
+                    event,
+                    &action_catalog,
+                    &mut |event| events.push(event),
+                    &mut ledger,
+                )
+                .expect("stream event should succeed");
+        }
+
+        let dispatched = events
+            .iter()
+            .filter_map(|event| match event {
+                ModelDeltaEvent::ActionInvocation(invocation) => invocation.call_id.clone(),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(dispatched, vec!["call-a", "call-b"]);
+        assert_eq!(ledger.action_call_count, 2);
+        let skipped = state
+            .diagnostics
+            .iter()
+            .filter(|line| line.contains("per-turn cap of 2 action calls reached"))
+            .collect::<Vec<_>>();
+        assert_eq!(skipped.len(), 1);
+        assert!(skipped[0].contains("call-c"));
+        assert!(events.iter().any(|event| matches!(
+            event,
+            ModelDeltaEvent::StreamNote(note) if note.phase == "openai.action_call_cap"
+        )));
+    }
+
+    #[test]
+    fn max_tool_calls_is_validated_and_sent_as_a_request_hint() {
+        assert_eq!(parse_max_tool_calls(None).expect("unset"), None);
+        assert_eq!(parse_max_tool_calls(Some(" 3 ")).expect("three"), Some(3));
+        assert!(parse_max_tool_calls(Some("0")).is_err());
+        assert!(parse_max_tool_calls(Some("many")).is_err());
+
+        let messages = vec![PromptMessage::new("user", "turn", "hello".to_string())];
+        let capped = build_request_body(
+            &messages,
+            &empty_action_catalog(),
+            &EndpointConfig::default(),
+            0,
+            &SamplingConfig::default(),
+            Some(3),
+        );
+        assert_eq!(capped["max_tool_calls"], json!(3));
+        let uncapped = build_request_body(
+            &messages,
+            &empty_action_catalog(),
+            &EndpointConfig::default(),
+            0,
+            &SamplingConfig::default(),
+            None,
+        );
+        assert!(uncapped.get("max_tool_calls").is_none());
+    }
+
+    #[test]
+    fn dispatch_order_config_rejects_unknown_values() {
+        assert_eq!(
+            ActionDispatchOrder::parse(None).expect("unset"),
+            ActionDispatchOrder::Stream
+        );
+        assert_eq!(
+            ActionDispatchOrder::parse(Some(" call_id ")).expect("call_id"),
+            ActionDispatchOrder::CallId
+        );
+        assert!(ActionDispatchOrder::parse(Some("random")).is_err());
+    }
+
+    #[test]
+    fn chunk_decoder_reassembles_multibyte_characters_split_across_chunks() {
+        let line = "data: {\"type\":\"response.output_text.delta\",\"delta\":\"안녕 👋\"}\n";
+        let bytes = line.as_bytes();
+        let hangul_start = line.find('안').expect("hangul offset");
+        let emoji_start = line.find('👋').expect("emoji offset");
+
+        let mut decoder = Utf8ChunkDecoder::default();
+        let mut reassembled = String::new();
+        for chunk in [
+            &bytes[..hangul_start + 1],
+            &bytes[hangul_start + 1..emoji_start + 2],
+            &bytes[emoji_start + 2..emoji_start + 3],
+            &bytes[emoji_start + 3..],
+        ] {
+            let decoded = decoder.decode(chunk);
+            assert!(!decoded.contains(char::REPLACEMENT_CHARACTER));
+            reassembled.push_str(&decoded);
+        }
+
+        assert_eq!(reassembled, line);
+        assert!(decoder.pending.is_empty());
+    }
+
+    #[test]
+    fn chunk_decoder_replaces_invalid_bytes_without_stalling() {
+        let mut decoder = Utf8ChunkDecoder::default();
+
+        assert_eq!(decoder.decode(b"ok \xff next"), "ok \u{fffd} next");
+        assert_eq!(decoder.decode(b"\xe2\x82"), "");
+        assert_eq!(decoder.decode(b"\xac!"), "\u{20ac}!");
+    }
+
+    #[test]
+    fn request_body_includes_configured_sampling_parameters() {
+        let messages = vec![PromptMessage::new("user", "turn", "hello".to_string())];
+        let sampling = SamplingConfig::parse(Some("0.3"), Some(" 0.9 ")).expect("valid sampling");
+
+        let body = build_request_body(
+            &messages,
+            &empty_action_catalog(),
+            &EndpointConfig::default(),
+            0,
+            &sampling,
+            None,
+        );
+
+        assert_eq!(body["temperature"], json!(0.3));
+        assert_eq!(body["top_p"], json!(0.9));
+    }
+
+    #[test]
+    fn request_body_offers_only_allowlisted_actions() {
+        let messages = vec![PromptMessage::new("user", "turn", "hello".to_string())];
+        let action_catalog =
+            filesystem_action_catalog().with_action_allowlist(Some(Arc::new(BTreeSet::from([
+                "filesystem__read".to_string(),
+                "shell__run".to_string(),
+            ]))));
+
+        let body = build_request_body(
+            &messages,
+            &action_catalog,
+            &EndpointConfig::default(),
+            0,
+            &SamplingConfig::default(),
+            None,
+        );
+
+        let offered = body["tools"]
+            .as_array()
+            .expect("tools array")
+            .iter()
+            .filter_map(|tool| tool["name"].as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(offered, vec!["filesystem__read"]);
+    }
+
+    #[test]
+    fn request_body_omits_sampling_parameters_when_unset() {
+        let messages = vec![PromptMessage::new("user", "turn", "hello".to_string())];
+        let sampling = SamplingConfig::parse(None, Some("")).expect("valid sampling");
+
+        let body = build_request_body(
+            &messages,
+            &empty_action_catalog(),
+            &EndpointConfig::default(),
+            0,
+            &sampling,
+            None,
+        );
+
+        assert!(body.get("temperature").is_none());
+        assert!(body.get("top_p").is_none());
+    }
+
+    #[test]
+    fn endpoint_config_targets_configured_base_url_and_model() {
+        let defaults = EndpointConfig::parse(None, Some(" "), None, Some("")).expect("defaults");
+        assert_eq!(defaults, EndpointConfig::default());
+        assert_eq!(
+            defaults.responses_url,
+            "https://api.openai.com/v1/responses"
+        );
+
+        let endpoint = EndpointConfig::parse(
+            Some(" https://proxy.example.com/openai/ "),
+            Some("gpt-custom"),
+            Some("low"),
+            Some(" minimal "),
+        )
+        .expect("custom endpoint");
+        assert_eq!(
+            endpoint.responses_url,
+            "https://proxy.example.com/openai/v1/responses"
+        );
+
+        let messages = vec![PromptMessage::new("user", "turn", "hello".to_string())];
+        let body = build_request_body(
+            &messages,
+            &empty_action_catalog(),
+            &endpoint,
+            0,
+            &SamplingConfig::default(),
+            None,
+        );
+        assert_eq!(body["model"], "gpt-custom");
+        assert_eq!(body["reasoning"]["effort"], "low");
+        let retry_body = build_request_body(
+            &messages,
+            &empty_action_catalog(),
+            &endpoint,
+            1,
+            &SamplingConfig::default(),
+            None,
+        );
+        assert_eq!(retry_body["reasoning"]["effort"], "minimal");
+
+        let error = EndpointConfig::parse(Some("proxy.example.com"), None, None, None)
+            .expect_err("scheme required");
+        assert!(
+            error
+                .message()
+                .contains("OPENAI_BASE_URL must be an http(s) URL")
+        );
+    }
+
+    #[test]
+    fn sampling_config_rejects_out_of_range_values() {
+        let error = SamplingConfig::parse(Some("2.5"), None).expect_err("temperature too high");
+        assert!(matches!(error, AgentError::Config(_)));
+        assert!(
+            error
+                .message()
+                .contains("OPENAI_TEMPERATURE must be between 0 and 2")
+        );
+
+        let error = SamplingConfig::parse(None, Some("abc")).expect_err("top_p not a number");
+        assert!(error.message().contains("OPENAI_TOP_P must be a number"));
+    }
+
+    #[test]
+    fn dispatched_args_json_is_independent_of_key_order() {
+        let action_catalog = filesystem_action_catalog();
+        let dispatched_args = |arguments: &str| {
+            let mut events = Vec::<ModelDeltaEvent>::new();
+            let mut state = StreamState::default();
+            let mut ledger = DispatchLedger::default();
+            state
+                .handle_event(
+                    json!({
+                        "type": "response.output_item.done",
+                        "item": {
+                            "type": "function_call",
+                            "id": "item-1",
+                            "call_id": "call-1",
+                            "name": "filesystem__read",
+                            "arguments": arguments
+                        }
+                    }),
+                    &action_catalog,
+                    &mut |event| events.push(event),
+                    &mut ledger,
+                )
+                .expect("stream event should succeed");
+            events
+                .into_iter()
+                .find_map(|event| match event {
+                    ModelDeltaEvent::ActionInvocation(invocation) => Some(invocation.args_json),
+                    _ => None,
+                })
+                .expect("dispatched action invocation")
+        };
+
+        let first = dispatched_args(r#"{"path":"notes.txt","offset":1,"length":2}"#);
+        let second = dispatched_args(r#"{"length":2,"offset":1,"path":"notes.txt"}"#);
+        assert_eq!(first, second);
+        assert_eq!(first, r#"{"length":2,"offset":1,"path":"notes.txt"}"#);
+    }
 }
@@ -130,6 +130,7 @@ fn summarize_timeline_batch(index: usize, batch: &[TimelineEvent]) -> String {
     for event in batch {
         let key = match event.kind {
             TimelineKind::UserMessage => "user_message",
+            TimelineKind::ExternalEvent => "external_event",
             TimelineKind::AssistantOutput => "assistant_output",
             TimelineKind::ExecutionRequested => "execution_requested",
             TimelineKind::ExecutionSucceeded => "execution_succeeded",
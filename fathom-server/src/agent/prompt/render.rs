@@ -6,17 +6,30 @@ use super::timeline::TimelineEvent;
 use super::util::truncate_inline;
 
 pub(super) fn build_harness_contract_block(input: &PromptInput) -> String {
-    [
+    let harness_contract = &input.stable_prefix.harness_contract;
+    let mut lines = vec![
         "# Harness Contract".to_string(),
-        format!(
-            "- `runtime_version`: {}",
-            input.stable_prefix.harness_contract.runtime_version
-        ),
+        format!("- `runtime_version`: {}", harness_contract.runtime_version),
         format!(
             "- `contract_schema_version`: {}",
-            input.stable_prefix.harness_contract.contract_schema_version
+            harness_contract.contract_schema_version
         ),
         String::new(),
+    ];
+    match harness_contract
+        .system_preamble
+        .as_deref()
+        .map(str::trim)
+        .filter(|preamble| !preamble.is_empty())
+    {
+        Some(preamble) => lines.push(preamble.to_string()),
+        None => lines.extend(default_preamble_lines()),
+    }
+    lines.join("\n")
+}
+
+fn default_preamble_lines() -> Vec<String> {
+    vec![
         "## Your Task".to_string(),
         "You operate inside a session runtime that provides a stable session prefix, an additive event transcript, and a capability surface of callable actions.".to_string(),
         "Your job is to choose the next best move for the session.".to_string(),
@@ -67,7 +80,6 @@ pub(super) fn build_harness_contract_block(input: &PromptInput) -> String {
         "- Do not describe your capabilities unless the user asks.".to_string(),
         "- Do not over-explain internal execution mechanics unless they matter to the user.".to_string(),
     ]
-    .join("\n")
 }
 
 pub(super) fn build_identity_envelope_block(input: &PromptInput) -> String {
@@ -194,10 +206,39 @@ pub(super) fn build_session_baseline_block(input: &PromptInput) -> String {
             .material,
     ));
     lines.push("```".to_string());
+    if has_no_participants(
+        &input
+            .stable_prefix
+            .session_baseline
+            .participant_envelope
+            .material,
+    ) {
+        lines.push(String::new());
+        lines.push("### Autonomous Session".to_string());
+        lines.push("- No user participates in this session; it runs autonomously.".to_string());
+        lines.push(
+            "- Act on triggers such as heartbeats, cron events, and execution results without waiting for user input.".to_string(),
+        );
+        lines.push(
+            "- Do not ask questions or address a user; nobody will reply. Keep assistant output to brief records of progress and results.".to_string(),
+        );
+    }
 
     lines.join("\n")
 }
 
+fn has_no_participants(material: &Value) -> bool {
+    let participant_count = material
+        .get("participants")
+        .and_then(Value::as_array)
+        .map_or(0, Vec::len);
+    let omitted_participant_count = material
+        .get("omitted_participant_count")
+        .and_then(Value::as_u64)
+        .unwrap_or_default();
+    participant_count == 0 && omitted_participant_count == 0
+}
+
 fn render_identity_material_markdown(material: &Value) -> String {
     render_markdown_material(material)
 }
@@ -243,6 +284,19 @@ fn render_participant_material_markdown(material: &Value) -> String {
         }
     }
 
+    let omitted_participant_count = material
+        .get("omitted_participant_count")
+        .and_then(Value::as_u64)
+        .unwrap_or_default();
+    if omitted_participant_count > 0 {
+        if !lines.is_empty() {
+            lines.push(String::new());
+        }
+        lines.push(format!(
+            "_{omitted_participant_count} more participant(s) omitted from this prompt._"
+        ));
+    }
+
     if lines.is_empty() {
         "_No participant material provided._".to_string()
     } else {
@@ -461,6 +515,7 @@ fn render_pending_prompt_event_lines(event: &PromptEvent) -> Vec<String> {
             )]
         }
         PromptEvent::UserMessage(_)
+        | PromptEvent::ExternalEvent(_)
         | PromptEvent::AssistantOutput(_)
         | PromptEvent::ExecutionRequested(_)
         | PromptEvent::ExecutionSucceeded(_)
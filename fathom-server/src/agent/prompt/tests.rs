@@ -34,6 +34,7 @@ fn base_input() -> PromptInput {
             harness_contract: HarnessContract {
                 runtime_version: "0.1.0".to_string(),
                 contract_schema_version: 1,
+                system_preamble: None,
             },
             identity_envelope: IdentityEnvelope {
                 schema_version: 1,
@@ -370,3 +371,22 @@ fn bundle_includes_session_compaction_summaries() {
             .contains("session_summary_blocks=1")
     );
 }
+
+#[test]
+fn autonomous_session_note_appears_only_without_participants() {
+    let input = base_input();
+    let debug_prompt = compile_input(&input).as_debug_prompt();
+    assert!(debug_prompt.contains("## user-default"));
+    assert!(!debug_prompt.contains("### Autonomous Session"));
+
+    let mut autonomous_input = base_input();
+    autonomous_input
+        .stable_prefix
+        .session_baseline
+        .participant_envelope
+        .material = json!({ "participants": [] });
+    let debug_prompt = compile_input(&autonomous_input).as_debug_prompt();
+    assert!(debug_prompt.contains("_No participant material provided._"));
+    assert!(debug_prompt.contains("### Autonomous Session"));
+    assert!(debug_prompt.contains("- No user participates in this session; it runs autonomously."));
+}
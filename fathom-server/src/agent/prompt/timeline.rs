@@ -6,6 +6,7 @@ use super::util::{preview_to_inline, truncate_inline};
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub(super) enum TimelineKind {
     UserMessage,
+    ExternalEvent,
     AssistantOutput,
     ExecutionRequested,
     ExecutionSucceeded,
@@ -63,6 +64,19 @@ fn timeline_event_from_prompt_event(event: &PromptEvent, seq: usize) -> Option<T
                 line: format!("user_message user={} text={}", payload.user_id, text),
             })
         }
+        PromptEvent::ExternalEvent(payload) => {
+            let payload_json = truncate_inline(&payload.payload_json, MAX_INLINE_TEXT_CHARS);
+            Some(TimelineEvent {
+                ts_unix_ms: seq as i64,
+                seq,
+                kind: TimelineKind::ExternalEvent,
+                action_id: None,
+                line: format!(
+                    "external_event source={} payload={payload_json}",
+                    payload.source
+                ),
+            })
+        }
         PromptEvent::AssistantOutput(payload) => {
             let content = truncate_inline(&payload.content, MAX_INLINE_TEXT_CHARS);
             Some(TimelineEvent {
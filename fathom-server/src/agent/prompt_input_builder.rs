@@ -3,11 +3,12 @@ use std::collections::HashSet;
 use crate::agent::types::{
     AgentInvocationContext, PromptAssistantOutput, PromptCron, PromptEvent,
     PromptExecutionBackgrounded, PromptExecutionCanceled, PromptExecutionFailed,
-    PromptExecutionRejected, PromptExecutionRequested, PromptExecutionSucceeded, PromptInput,
-    PromptPayloadLookupAvailable, PromptRefreshProfile, PromptStablePrefix, PromptUserMessage,
+    PromptExecutionRejected, PromptExecutionRequested, PromptExecutionSucceeded,
+    PromptExternalEvent, PromptInput, PromptPayloadLookupAvailable, PromptRefreshProfile,
+    PromptStablePrefix, PromptUserMessage,
 };
-use crate::history::build_payload_preview;
 use crate::history::{HistoryEvent, HistoryEventKind};
+use crate::history::{PreviewLimits, build_bounded_payload_preview};
 use fathom_protocol::pb;
 
 pub(crate) fn build_prompt_input(
@@ -23,7 +24,7 @@ pub(crate) fn build_prompt_input(
     for event in context
         .triggers
         .iter()
-        .filter_map(prompt_event_from_trigger)
+        .filter_map(|trigger| prompt_event_from_trigger(trigger, context.result_preview_limits))
     {
         if is_append_only_prompt_event(&event) {
             transcript_events.push(event);
@@ -83,6 +84,7 @@ fn is_append_only_prompt_event(event: &PromptEvent) -> bool {
     matches!(
         event,
         PromptEvent::UserMessage(_)
+            | PromptEvent::ExternalEvent(_)
             | PromptEvent::AssistantOutput(_)
             | PromptEvent::ExecutionRequested(_)
             | PromptEvent::ExecutionSucceeded(_)
@@ -101,6 +103,12 @@ fn prompt_event_from_history_event(event: &HistoryEvent) -> Option<PromptEvent>
                 text: payload.text.clone(),
             }))
         }
+        HistoryEventKind::TriggerExternalEvent(payload) => {
+            Some(PromptEvent::ExternalEvent(PromptExternalEvent {
+                source: event.actor_id.clone(),
+                payload_json: payload.payload_json.clone(),
+            }))
+        }
         HistoryEventKind::AssistantOutput(payload) => {
             Some(PromptEvent::AssistantOutput(PromptAssistantOutput {
                 content: payload.content.clone(),
@@ -155,7 +163,10 @@ fn prompt_event_from_history_event(event: &HistoryEvent) -> Option<PromptEvent>
     }
 }
 
-fn prompt_event_from_trigger(trigger: &pb::Trigger) -> Option<PromptEvent> {
+fn prompt_event_from_trigger(
+    trigger: &pb::Trigger,
+    preview_limits: PreviewLimits,
+) -> Option<PromptEvent> {
     let kind = trigger.kind.as_ref()?;
     match kind {
         pb::trigger::Kind::UserMessage(message) => {
@@ -164,11 +175,19 @@ fn prompt_event_from_trigger(trigger: &pb::Trigger) -> Option<PromptEvent> {
                 text: message.text.clone(),
             }))
         }
-        pb::trigger::Kind::ExecutionUpdate(update) => prompt_event_from_execution_update(update),
+        pb::trigger::Kind::ExecutionUpdate(update) => {
+            prompt_event_from_execution_update(update, preview_limits)
+        }
         pb::trigger::Kind::Heartbeat(_) => Some(PromptEvent::Heartbeat),
         pb::trigger::Kind::Cron(cron) => Some(PromptEvent::Cron(PromptCron {
             key: cron.key.clone(),
         })),
+        pb::trigger::Kind::ExternalEvent(external) => {
+            Some(PromptEvent::ExternalEvent(PromptExternalEvent {
+                source: external.source.clone(),
+                payload_json: external.payload_json.clone(),
+            }))
+        }
         pb::trigger::Kind::RefreshProfile(refresh) => {
             Some(PromptEvent::RefreshProfile(PromptRefreshProfile {
                 scope: refresh.scope.to_string(),
@@ -178,13 +197,17 @@ fn prompt_event_from_trigger(trigger: &pb::Trigger) -> Option<PromptEvent> {
     }
 }
 
-fn prompt_event_from_execution_update(update: &pb::ExecutionUpdateTrigger) -> Option<PromptEvent> {
+fn prompt_event_from_execution_update(
+    update: &pb::ExecutionUpdateTrigger,
+    preview_limits: PreviewLimits,
+) -> Option<PromptEvent> {
     let payload_preview = if update.payload_message.trim().is_empty() {
         None
     } else {
-        Some(build_payload_preview(
+        Some(build_bounded_payload_preview(
             &update.payload_message,
             format!("execution://{}/result", update.execution_id),
+            preview_limits,
         ))
     };
     let kind = pb::ExecutionUpdateKind::try_from(update.kind)
@@ -230,14 +253,15 @@ fn prompt_event_from_execution_update(update: &pb::ExecutionUpdateTrigger) -> Op
 
 #[cfg(test)]
 mod tests {
+    use crate::agent::prompt::PromptCompiler;
     use crate::agent::prompt_input_builder::build_prompt_input;
     use crate::agent::types::{
         AgentInvocationContext, CapabilityAction, CapabilityDomain, CapabilityRecipe,
         CapabilitySurface, HarnessContract, IdentityEnvelope, ParticipantEnvelope, PromptEvent,
         ResolvedPayloadLookupHint, SessionAnchor, SessionBaseline, SessionCompaction,
     };
-    use crate::history::HistoryEvent;
     use crate::history::schema::{HistoryActorKind, HistoryEventKind, UserMessageHistoryPayload};
+    use crate::history::{HistoryEvent, PreviewLimits};
     use crate::util::default_agent_profile;
     use fathom_protocol::pb;
     use serde_json::json;
@@ -248,6 +272,7 @@ mod tests {
             harness_contract: HarnessContract {
                 runtime_version: "0.1.0".to_string(),
                 contract_schema_version: 1,
+                system_preamble: None,
             },
             identity_envelope: IdentityEnvelope {
                 schema_version: 1,
@@ -305,6 +330,7 @@ mod tests {
             triggers: vec![],
             recent_history,
             compaction: SessionCompaction::default(),
+            result_preview_limits: PreviewLimits::default(),
         }
     }
 
@@ -464,6 +490,31 @@ mod tests {
         assert!(input.pending_events.is_empty());
     }
 
+    #[test]
+    fn external_event_triggers_reach_the_prompt_transcript() {
+        let mut context = base_context(vec![]);
+        context.triggers = vec![pb::Trigger {
+            trigger_id: "trigger-1".to_string(),
+            created_at_unix_ms: 1_765_000_000_100,
+            kind: Some(pb::trigger::Kind::ExternalEvent(pb::ExternalEventTrigger {
+                source: "github-webhook".to_string(),
+                payload_json: "{\"action\":\"opened\",\"number\":42}".to_string(),
+            })),
+        }];
+
+        let input = build_prompt_input(&context, None);
+
+        assert!(input.pending_events.is_empty());
+        assert!(matches!(
+            input.transcript_events.as_slice(),
+            [PromptEvent::ExternalEvent(event)] if event.source == "github-webhook"
+        ));
+        let debug_prompt = PromptCompiler::new().compile(&input).as_debug_prompt();
+        assert!(debug_prompt.contains(
+            "external_event source=github-webhook payload={\"action\":\"opened\",\"number\":42}"
+        ));
+    }
+
     #[test]
     fn build_prompt_input_deduplicates_payload_lookup_slices() {
         let mut context = base_context(vec![]);
@@ -2,6 +2,12 @@ use std::time::Duration;
 
 use crate::util::now_unix_ms;
 
+const MAX_RETRIES_ENV: &str = "FATHOM_OPENAI_MAX_RETRIES";
+const BASE_DELAY_MS_ENV: &str = "FATHOM_OPENAI_BASE_DELAY_MS";
+const MAX_DELAY_MS_ENV: &str = "FATHOM_OPENAI_MAX_DELAY_MS";
+const JITTER_MS_ENV: &str = "FATHOM_OPENAI_JITTER_MS";
+const MAX_RETRIES_LIMIT: usize = 10;
+
 #[derive(Debug, Clone)]
 pub(crate) struct RetryPolicy {
     max_retries: usize,
@@ -20,6 +26,62 @@ impl RetryPolicy {
         }
     }
 
+    #[cfg(test)]
+    pub(crate) fn aggressive() -> Self {
+        Self {
+            max_retries: 5,
+            base_delay_ms: 100,
+            max_delay_ms: 2_000,
+            jitter_ms: 0,
+        }
+    }
+
+    pub(crate) fn from_env() -> Result<Self, String> {
+        Self::parse(
+            std::env::var(MAX_RETRIES_ENV).ok().as_deref(),
+            std::env::var(BASE_DELAY_MS_ENV).ok().as_deref(),
+            std::env::var(MAX_DELAY_MS_ENV).ok().as_deref(),
+            std::env::var(JITTER_MS_ENV).ok().as_deref(),
+        )
+    }
+
+    fn parse(
+        max_retries: Option<&str>,
+        base_delay_ms: Option<&str>,
+        max_delay_ms: Option<&str>,
+        jitter_ms: Option<&str>,
+    ) -> Result<Self, String> {
+        let defaults = Self::conservative();
+        let policy = Self {
+            max_retries: parse_env_value(MAX_RETRIES_ENV, max_retries)?
+                .unwrap_or(defaults.max_retries),
+            base_delay_ms: parse_env_value(BASE_DELAY_MS_ENV, base_delay_ms)?
+                .unwrap_or(defaults.base_delay_ms),
+            max_delay_ms: parse_env_value(MAX_DELAY_MS_ENV, max_delay_ms)?
+                .unwrap_or(defaults.max_delay_ms),
+            jitter_ms: parse_env_value(JITTER_MS_ENV, jitter_ms)?.unwrap_or(defaults.jitter_ms),
+        };
+
+        if policy.max_retries > MAX_RETRIES_LIMIT {
+            return Err(format!(
+                "{MAX_RETRIES_ENV} must be at most {MAX_RETRIES_LIMIT}, got {}",
+                policy.max_retries
+            ));
+        }
+        if policy.base_delay_ms > policy.max_delay_ms {
+            return Err(format!(
+                "{BASE_DELAY_MS_ENV} ({}) must not exceed {MAX_DELAY_MS_ENV} ({})",
+                policy.base_delay_ms, policy.max_delay_ms
+            ));
+        }
+        if policy.max_delay_ms.checked_add(policy.jitter_ms).is_none() {
+            return Err(format!(
+                "{MAX_DELAY_MS_ENV} plus {JITTER_MS_ENV} overflows a millisecond delay"
+            ));
+        }
+        Ok(policy)
+    }
+
     pub(crate) fn max_retries(&self) -> usize {
         self.max_retries
     }
@@ -36,9 +98,97 @@ impl RetryPolicy {
         let jitter = if self.jitter_ms == 0 {
             0
         } else {
-            (now_unix_ms().unsigned_abs() % self.jitter_ms) as u64
+            now_unix_ms().unsigned_abs() % self.jitter_ms
         };
 
         Duration::from_millis(bounded.saturating_add(jitter))
     }
 }
+
+fn parse_env_value<T: std::str::FromStr>(
+    name: &str,
+    raw: Option<&str>,
+) -> Result<Option<T>, String> {
+    let Some(raw) = raw.map(str::trim).filter(|raw| !raw.is_empty()) else {
+        return Ok(None);
+    };
+    raw.parse::<T>()
+        .map(Some)
+        .map_err(|_| format!("{name} must be a non-negative integer, got `{raw}`"))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::RetryPolicy;
+
+    #[test]
+    fn compute_delay_grows_exponentially_up_to_the_cap() {
+        let policy = RetryPolicy::aggressive();
+        let delays = [0, 1, 2, 3, 4, 5, 63, 200]
+            .map(|attempt| policy.compute_delay(attempt, None).as_millis());
+        assert_eq!(delays, [100, 200, 400, 800, 1_600, 2_000, 2_000, 2_000]);
+
+        let retry_after = Duration::from_secs(7);
+        assert_eq!(policy.compute_delay(3, Some(retry_after)), retry_after);
+    }
+
+    #[test]
+    fn compute_delay_adds_bounded_jitter() {
+        let policy = RetryPolicy::conservative();
+        for attempt in 0..4 {
+            let delay = policy.compute_delay(attempt, None).as_millis() as u64;
+            let base = (400u64 << attempt).min(4_000);
+            assert!((base..base + 300).contains(&delay));
+        }
+    }
+
+    #[test]
+    fn parse_falls_back_to_conservative_defaults() {
+        let policy = RetryPolicy::parse(None, Some(" "), None, None).expect("defaults");
+        assert_eq!(policy.max_retries(), 2);
+
+        let custom = RetryPolicy::parse(Some("4"), Some("50"), Some("500"), Some("0"))
+            .expect("custom policy");
+        assert_eq!(custom.max_retries(), 4);
+        assert_eq!(custom.compute_delay(2, None), Duration::from_millis(200));
+        assert_eq!(custom.compute_delay(9, None), Duration::from_millis(500));
+    }
+
+    #[test]
+    fn parse_rejects_invalid_values() {
+        let cases = [
+            (
+                Some("eleven"),
+                None,
+                None,
+                None,
+                "FATHOM_OPENAI_MAX_RETRIES",
+            ),
+            (Some("-1"), None, None, None, "FATHOM_OPENAI_MAX_RETRIES"),
+            (Some("11"), None, None, None, "at most 10"),
+            (None, Some("1.5"), None, None, "FATHOM_OPENAI_BASE_DELAY_MS"),
+            (None, Some("5000"), Some("1000"), None, "must not exceed"),
+            (
+                None,
+                None,
+                Some("18446744073709551615"),
+                Some("1"),
+                "overflows",
+            ),
+            (
+                None,
+                None,
+                None,
+                Some("99999999999999999999"),
+                "FATHOM_OPENAI_JITTER_MS",
+            ),
+        ];
+        for (max_retries, base, max, jitter, expected) in cases {
+            let error = RetryPolicy::parse(max_retries, base, max, jitter)
+                .expect_err("invalid retry policy");
+            assert!(error.contains(expected), "{error}");
+        }
+    }
+}
@@ -0,0 +1,116 @@
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use super::model_adapter::{ModelAdapter, ModelAdapterFuture, ModelEventSink};
+use super::prompt::PromptCompiler;
+use super::{
+    ActionInvocation, AgentOrchestrator, CompiledPrompt, ModelDeltaEvent, ModelInvocationOutcome,
+    PromptMessage,
+};
+use crate::capability_domain::CapabilityDomainRegistry;
+
+struct StalledModelAdapter {
+    stall: Duration,
+}
+
+impl ModelAdapter for StalledModelAdapter {
+    fn provider_name(&self) -> &'static str {
+        "stalled"
+    }
+
+    fn stream_prompt<'a>(
+        &'a self,
+        _prompt_messages: &'a [PromptMessage],
+        _action_catalog: &'a super::SessionActionCatalog,
+        on_event: &'a mut ModelEventSink<'a>,
+    ) -> ModelAdapterFuture<'a> {
+        on_event(ModelDeltaEvent::ActionInvocation(ActionInvocation {
+            action_id: "system__list_executions".to_string(),
+            args_json: "{}".to_string(),
+            call_key: "call-key-stalled".to_string(),
+            call_id: Some("call-id-stalled".to_string()),
+        }));
+        let stall = self.stall;
+        Box::pin(async move {
+            tokio::time::sleep(stall).await;
+            Err(super::error::AgentError::Config(
+                "stalled model adapter finished unexpectedly".to_string(),
+            ))
+        })
+    }
+}
+
+struct RecordingModelAdapter {
+    prompts: Arc<Mutex<Vec<CompiledPrompt>>>,
+    reply: Option<String>,
+}
+
+impl ModelAdapter for RecordingModelAdapter {
+    fn provider_name(&self) -> &'static str {
+        "recording"
+    }
+
+    fn stream_prompt<'a>(
+        &'a self,
+        prompt_messages: &'a [PromptMessage],
+        _action_catalog: &'a super::SessionActionCatalog,
+        _on_event: &'a mut ModelEventSink<'a>,
+    ) -> ModelAdapterFuture<'a> {
+        self.prompts
+            .lock()
+            .expect("recorded prompts lock")
+            .push(CompiledPrompt {
+                messages: prompt_messages.to_vec(),
+                diagnostics: Default::default(),
+            });
+        let assistant_outputs = self.reply.iter().cloned().collect();
+        Box::pin(async move {
+            Ok(ModelInvocationOutcome {
+                action_call_count: 0,
+                assistant_outputs,
+                diagnostics: Vec::new(),
+                request_retries: 0,
+                fallback_effort: None,
+            })
+        })
+    }
+}
+
+impl AgentOrchestrator {
+    pub(crate) fn recording(
+        registry: CapabilityDomainRegistry,
+    ) -> (Self, Arc<Mutex<Vec<CompiledPrompt>>>) {
+        Self::recording_with_reply(registry, None)
+    }
+
+    pub(crate) fn replying(
+        registry: CapabilityDomainRegistry,
+        reply: &str,
+    ) -> (Self, Arc<Mutex<Vec<CompiledPrompt>>>) {
+        Self::recording_with_reply(registry, Some(reply.to_string()))
+    }
+
+    fn recording_with_reply(
+        registry: CapabilityDomainRegistry,
+        reply: Option<String>,
+    ) -> (Self, Arc<Mutex<Vec<CompiledPrompt>>>) {
+        let prompts = Arc::new(Mutex::new(Vec::new()));
+        let orchestrator = Self::from_parts(
+            Arc::new(RecordingModelAdapter {
+                prompts: prompts.clone(),
+                reply,
+            }),
+            registry,
+            PromptCompiler::new(),
+        );
+        (orchestrator, prompts)
+    }
+
+    pub(crate) fn stalled(registry: CapabilityDomainRegistry, stall: Duration) -> Self {
+        Self::from_parts(
+            Arc::new(StalledModelAdapter { stall }),
+            registry,
+            PromptCompiler::new(),
+        )
+    }
+}
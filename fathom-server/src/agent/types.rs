@@ -4,7 +4,7 @@ use std::hash::{Hash, Hasher};
 use serde::Serialize;
 use serde_json::Value;
 
-use crate::history::{HistoryEvent, PayloadPreview};
+use crate::history::{HistoryEvent, PayloadPreview, PreviewLimits};
 use fathom_protocol::pb;
 
 #[derive(Debug, Clone, Serialize)]
@@ -46,12 +46,15 @@ pub(crate) struct AgentInvocationContext {
     pub(crate) triggers: Vec<pb::Trigger>,
     pub(crate) recent_history: Vec<HistoryEvent>,
     pub(crate) compaction: SessionCompaction,
+    pub(crate) result_preview_limits: PreviewLimits,
 }
 
 #[derive(Debug, Clone, Serialize)]
 pub(crate) struct HarnessContract {
     pub(crate) runtime_version: String,
     pub(crate) contract_schema_version: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) system_preamble: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -180,6 +183,12 @@ pub(crate) struct PromptCron {
     pub(crate) key: String,
 }
 
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct PromptExternalEvent {
+    pub(crate) source: String,
+    pub(crate) payload_json: String,
+}
+
 #[derive(Debug, Clone, Serialize)]
 pub(crate) struct PromptRefreshProfile {
     pub(crate) scope: String,
@@ -201,6 +210,7 @@ pub(crate) enum PromptEvent {
     RetryFeedback(PromptAssistantOutput),
     Heartbeat,
     Cron(PromptCron),
+    ExternalEvent(PromptExternalEvent),
     RefreshProfile(PromptRefreshProfile),
 }
 
@@ -329,6 +339,15 @@ pub(crate) struct ModelInvocationOutcome {
     pub(crate) action_call_count: usize,
     pub(crate) assistant_outputs: Vec<String>,
     pub(crate) diagnostics: Vec<String>,
+    pub(crate) request_retries: usize,
+    pub(crate) fallback_effort: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct AgentTurnRetrySummary {
+    pub(crate) attempts: usize,
+    pub(crate) model_request_retries: usize,
+    pub(crate) fallback_effort: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -339,6 +358,7 @@ pub(crate) struct AgentTurnOutcome {
     pub(crate) failed: bool,
     pub(crate) failure_code: String,
     pub(crate) failure_message: String,
+    pub(crate) retry_summary: AgentTurnRetrySummary,
 }
 
 impl AgentTurnOutcome {
@@ -354,6 +374,7 @@ impl AgentTurnOutcome {
             failed: false,
             failure_code: String::new(),
             failure_message: String::new(),
+            retry_summary: AgentTurnRetrySummary::default(),
         }
     }
 
@@ -369,6 +390,7 @@ impl AgentTurnOutcome {
             failed: true,
             failure_code: failure_code.into(),
             failure_message: failure_message.into(),
+            retry_summary: AgentTurnRetrySummary::default(),
         }
     }
 }
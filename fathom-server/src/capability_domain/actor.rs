@@ -1,4 +1,9 @@
-use tokio::sync::mpsc;
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::sync::{mpsc, watch};
+use tokio::task::AbortHandle;
+use tracing::error;
 
 use crate::session::state::SessionCommand;
 
@@ -25,12 +30,19 @@ pub(crate) struct CapabilityDomainCommittedAction {
 #[derive(Clone)]
 pub(crate) struct CapabilityDomainActorHandle {
     command_tx: mpsc::Sender<CapabilityDomainActionSubmission>,
+    shutdown: Arc<watch::Sender<bool>>,
+    abort_handle: AbortHandle,
 }
 
 impl CapabilityDomainActorHandle {
     pub(crate) async fn submit(&self, submission: CapabilityDomainActionSubmission) {
         let _ = self.command_tx.send(submission).await;
     }
+
+    pub(crate) fn shutdown(&self) {
+        self.shutdown.send_replace(true);
+        self.abort_handle.abort();
+    }
 }
 
 #[derive(Clone)]
@@ -48,29 +60,98 @@ pub(crate) struct CapabilityDomainActionExecution {
 
 pub(crate) fn spawn_capability_domain_actor(
     capability_domain_id: String,
-    mut domain_instance: Box<dyn DomainInstance>,
-    session_command_tx: mpsc::Sender<SessionCommand>,
+    domain_instance: Box<dyn DomainInstance>,
+    session_command_tx: mpsc::UnboundedSender<SessionCommand>,
+    action_timeout: Duration,
 ) -> CapabilityDomainActorHandle {
     let (command_tx, mut command_rx) = mpsc::channel::<CapabilityDomainActionSubmission>(128);
-    let handle = CapabilityDomainActorHandle {
-        command_tx: command_tx.clone(),
-    };
+    let (shutdown, task_shutdown) = watch::channel(false);
+    let shutdown = Arc::new(shutdown);
 
-    tokio::spawn(async move {
+    let task = tokio::spawn(async move {
+        let mut idle_instance = Some(domain_instance);
         while let Some(submission) = command_rx.recv().await {
-            let executions = execute_submission(&mut *domain_instance, &submission).await;
+            let Some(mut domain_instance) = idle_instance.take() else {
+                break;
+            };
+            let runtime = tokio::runtime::Handle::current();
+            let blocking_submission = submission.clone();
+            let mut blocking_shutdown = task_shutdown.clone();
+            let mut blocking = tokio::task::spawn_blocking(move || {
+                let executions = runtime.block_on(async {
+                    tokio::select! {
+                        executions = tokio::time::timeout(
+                            action_timeout,
+                            execute_submission(&mut *domain_instance, &blocking_submission),
+                        ) => executions.ok(),
+                        _ = blocking_shutdown.wait_for(|shutdown| *shutdown) => None,
+                    }
+                });
+                (domain_instance, executions)
+            });
+            let (executions, still_running) =
+                match tokio::time::timeout(action_timeout, &mut blocking).await {
+                    Ok(Ok((instance, executions))) => {
+                        idle_instance = Some(instance);
+                        let executions = executions
+                            .unwrap_or_else(|| timed_out_executions(&submission, action_timeout));
+                        (executions, None)
+                    }
+                    Ok(Err(join_error)) => {
+                        error!(
+                            %capability_domain_id,
+                            %join_error,
+                            "capability domain execution task failed"
+                        );
+                        break;
+                    }
+                    Err(_) => (
+                        timed_out_executions(&submission, action_timeout),
+                        Some(blocking),
+                    ),
+                };
+            if *task_shutdown.borrow() {
+                break;
+            }
             let committed = CapabilityDomainCommittedAction {
                 submission_id: submission.submission_id,
                 capability_domain_id: capability_domain_id.clone(),
                 executions,
             };
-            let _ = session_command_tx
+            if session_command_tx
                 .send(SessionCommand::CapabilityDomainActionCommitted { committed })
-                .await;
+                .is_err()
+            {
+                if *task_shutdown.borrow() {
+                    break;
+                }
+                error!(
+                    %capability_domain_id,
+                    "session actor is gone; dropping committed capability domain action"
+                );
+                break;
+            }
+            if let Some(blocking) = still_running {
+                match blocking.await {
+                    Ok((instance, _)) => idle_instance = Some(instance),
+                    Err(join_error) => {
+                        error!(
+                            %capability_domain_id,
+                            %join_error,
+                            "capability domain execution task failed"
+                        );
+                        break;
+                    }
+                }
+            }
         }
     });
 
-    handle
+    CapabilityDomainActorHandle {
+        command_tx,
+        shutdown,
+        abort_handle: task.abort_handle(),
+    }
 }
 
 async fn execute_submission(
@@ -142,6 +223,27 @@ async fn execute_submission(
         .collect()
 }
 
+fn timed_out_executions(
+    submission: &CapabilityDomainActionSubmission,
+    action_timeout: Duration,
+) -> Vec<CapabilityDomainCommittedExecution> {
+    let timeout_ms = action_timeout.as_millis() as u64;
+    let result = CapabilityActionResult::runtime_error(
+        "timeout",
+        format!("action did not complete within {timeout_ms}ms"),
+        None,
+        timeout_ms,
+    );
+    submission
+        .executions
+        .iter()
+        .map(|execution| CapabilityDomainCommittedExecution {
+            execution_id: execution.execution_id.clone(),
+            result: result.clone(),
+        })
+        .collect()
+}
+
 fn parse_submission_args(args_json: &str) -> Result<Value, CapabilityActionResult> {
     let mut value: Value = serde_json::from_str(args_json).map_err(|error| {
         CapabilityActionResult::input_error(
@@ -162,3 +264,289 @@ fn parse_submission_args(args_json: &str) -> Result<Value, CapabilityActionResul
     object.remove(ACTION_BACKGROUND_KEY);
     Ok(value)
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use fathom_capability_domain::{
+        ActionError, CapabilityActionKey, CapabilityActionResult, CapabilityActionSubmission,
+        DomainInstance, DomainInstanceFuture,
+    };
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    use tokio::sync::{Notify, mpsc};
+
+    use super::{
+        CapabilityDomainActionExecution, CapabilityDomainActionSubmission,
+        spawn_capability_domain_actor,
+    };
+    use crate::session::state::SessionCommand;
+
+    struct EchoDomainInstance;
+
+    impl DomainInstance for EchoDomainInstance {
+        fn execute_actions<'a>(
+            &'a mut self,
+            submissions: Vec<CapabilityActionSubmission>,
+        ) -> DomainInstanceFuture<'a> {
+            Box::pin(async move {
+                submissions
+                    .into_iter()
+                    .map(|submission| CapabilityActionResult::success(submission.args, 1))
+                    .collect()
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn completions_land_while_the_session_is_not_draining_commands() {
+        let (session_command_tx, mut session_command_rx) = mpsc::unbounded_channel();
+        let handle = spawn_capability_domain_actor(
+            "echo".to_string(),
+            Box::new(EchoDomainInstance),
+            session_command_tx,
+            Duration::from_secs(30),
+        );
+
+        let submission_count = 512;
+        tokio::time::timeout(Duration::from_secs(5), async {
+            for index in 0..submission_count {
+                handle
+                    .submit(CapabilityDomainActionSubmission {
+                        submission_id: format!("submission-{index}"),
+                        executions: vec![CapabilityDomainActionExecution {
+                            execution_id: format!("execution-{index}"),
+                            action_key: CapabilityActionKey(0),
+                            args_json: format!(r#"{{"index":{index}}}"#),
+                        }],
+                    })
+                    .await;
+            }
+        })
+        .await
+        .expect("submissions should not stall behind undrained completions");
+
+        for index in 0..submission_count {
+            let command = tokio::time::timeout(Duration::from_secs(5), session_command_rx.recv())
+                .await
+                .expect("completion should arrive")
+                .expect("completion channel open");
+            let SessionCommand::CapabilityDomainActionCommitted { committed } = command else {
+                panic!("expected committed capability domain action");
+            };
+            assert_eq!(committed.submission_id, format!("submission-{index}"));
+            assert_eq!(committed.executions.len(), 1);
+        }
+    }
+
+    struct PendingDomainInstance {
+        started: Arc<Notify>,
+        dropped: Arc<AtomicBool>,
+    }
+
+    struct DropFlag(Arc<AtomicBool>);
+
+    impl Drop for DropFlag {
+        fn drop(&mut self) {
+            self.0.store(true, Ordering::SeqCst);
+        }
+    }
+
+    impl DomainInstance for PendingDomainInstance {
+        fn execute_actions<'a>(
+            &'a mut self,
+            _submissions: Vec<CapabilityActionSubmission>,
+        ) -> DomainInstanceFuture<'a> {
+            let started = self.started.clone();
+            let guard = DropFlag(self.dropped.clone());
+            Box::pin(async move {
+                let _guard = guard;
+                started.notify_one();
+                std::future::pending::<Vec<CapabilityActionResult>>().await
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn shutdown_aborts_running_submissions_without_delivering_completions() {
+        let (session_command_tx, mut session_command_rx) = mpsc::unbounded_channel();
+        let started = Arc::new(Notify::new());
+        let dropped = Arc::new(AtomicBool::new(false));
+        let handle = spawn_capability_domain_actor(
+            "pending".to_string(),
+            Box::new(PendingDomainInstance {
+                started: started.clone(),
+                dropped: dropped.clone(),
+            }),
+            session_command_tx,
+            Duration::from_secs(30),
+        );
+
+        handle
+            .submit(CapabilityDomainActionSubmission {
+                submission_id: "submission-1".to_string(),
+                executions: vec![CapabilityDomainActionExecution {
+                    execution_id: "execution-1".to_string(),
+                    action_key: CapabilityActionKey(0),
+                    args_json: "{}".to_string(),
+                }],
+            })
+            .await;
+        tokio::time::timeout(Duration::from_secs(5), started.notified())
+            .await
+            .expect("submission should start running");
+
+        handle.shutdown();
+
+        let closed = tokio::time::timeout(Duration::from_secs(5), session_command_rx.recv())
+            .await
+            .expect("actor should stop after shutdown");
+        assert!(closed.is_none());
+        assert!(dropped.load(Ordering::SeqCst));
+    }
+
+    struct SlowFirstDomainInstance {
+        calls: usize,
+        dropped: Arc<AtomicBool>,
+    }
+
+    impl DomainInstance for SlowFirstDomainInstance {
+        fn execute_actions<'a>(
+            &'a mut self,
+            submissions: Vec<CapabilityActionSubmission>,
+        ) -> DomainInstanceFuture<'a> {
+            self.calls += 1;
+            let hang = self.calls == 1;
+            let guard = DropFlag(self.dropped.clone());
+            Box::pin(async move {
+                let _guard = guard;
+                if hang {
+                    std::future::pending::<()>().await;
+                }
+                submissions
+                    .into_iter()
+                    .map(|submission| CapabilityActionResult::success(submission.args, 1))
+                    .collect()
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn hung_actions_time_out_and_free_the_domain_for_later_submissions() {
+        let (session_command_tx, mut session_command_rx) = mpsc::unbounded_channel();
+        let dropped = Arc::new(AtomicBool::new(false));
+        let handle = spawn_capability_domain_actor(
+            "slow".to_string(),
+            Box::new(SlowFirstDomainInstance {
+                calls: 0,
+                dropped: dropped.clone(),
+            }),
+            session_command_tx,
+            Duration::from_millis(50),
+        );
+
+        for index in 0..2 {
+            handle
+                .submit(CapabilityDomainActionSubmission {
+                    submission_id: format!("submission-{index}"),
+                    executions: vec![CapabilityDomainActionExecution {
+                        execution_id: format!("execution-{index}"),
+                        action_key: CapabilityActionKey(0),
+                        args_json: "{}".to_string(),
+                    }],
+                })
+                .await;
+        }
+
+        let mut committed_results = Vec::new();
+        for _ in 0..2 {
+            let command = tokio::time::timeout(Duration::from_secs(5), session_command_rx.recv())
+                .await
+                .expect("completion should arrive")
+                .expect("completion channel open");
+            let SessionCommand::CapabilityDomainActionCommitted { committed } = command else {
+                panic!("expected committed capability domain action");
+            };
+            assert_eq!(committed.executions.len(), 1);
+            committed_results.push(committed.executions[0].result.clone());
+        }
+
+        let Err(ActionError::RuntimeError(timeout)) = &committed_results[0].outcome else {
+            panic!("hung action should fail with a runtime error");
+        };
+        assert_eq!(timeout.code, "timeout");
+        assert!(dropped.load(Ordering::SeqCst));
+        assert!(committed_results[1].outcome.is_ok());
+    }
+
+    struct BlockingFirstDomainInstance {
+        calls: usize,
+    }
+
+    impl DomainInstance for BlockingFirstDomainInstance {
+        fn execute_actions<'a>(
+            &'a mut self,
+            submissions: Vec<CapabilityActionSubmission>,
+        ) -> DomainInstanceFuture<'a> {
+            self.calls += 1;
+            let block = self.calls == 1;
+            Box::pin(async move {
+                if block {
+                    std::thread::sleep(Duration::from_millis(500));
+                }
+                submissions
+                    .into_iter()
+                    .map(|submission| CapabilityActionResult::success(submission.args, 1))
+                    .collect()
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn blocking_actions_time_out_without_waiting_for_the_blocking_call() {
+        let (session_command_tx, mut session_command_rx) = mpsc::unbounded_channel();
+        let handle = spawn_capability_domain_actor(
+            "blocking".to_string(),
+            Box::new(BlockingFirstDomainInstance { calls: 0 }),
+            session_command_tx,
+            Duration::from_millis(50),
+        );
+
+        for index in 0..2 {
+            handle
+                .submit(CapabilityDomainActionSubmission {
+                    submission_id: format!("submission-{index}"),
+                    executions: vec![CapabilityDomainActionExecution {
+                        execution_id: format!("execution-{index}"),
+                        action_key: CapabilityActionKey(0),
+                        args_json: "{}".to_string(),
+                    }],
+                })
+                .await;
+        }
+
+        let started = std::time::Instant::now();
+        let mut committed_results = Vec::new();
+        for _ in 0..2 {
+            let command = tokio::time::timeout(Duration::from_secs(5), session_command_rx.recv())
+                .await
+                .expect("completion should arrive")
+                .expect("completion channel open");
+            let SessionCommand::CapabilityDomainActionCommitted { committed } = command else {
+                panic!("expected committed capability domain action");
+            };
+            if committed_results.is_empty() {
+                assert!(started.elapsed() < Duration::from_millis(400));
+            }
+            committed_results.push(committed.executions[0].result.clone());
+        }
+
+        let Err(ActionError::RuntimeError(timeout)) = &committed_results[0].outcome else {
+            panic!("blocking action should fail with a runtime error");
+        };
+        assert_eq!(timeout.code, "timeout");
+        assert!(committed_results[1].outcome.is_ok());
+    }
+}
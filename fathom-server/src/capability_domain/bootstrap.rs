@@ -2,6 +2,7 @@ use std::path::Path;
 use std::sync::Arc;
 
 use fathom_capability_domain::DomainFactory;
+use fathom_capability_domain_fs::{ListMaxEntries, ProtectedPaths, WriteExtensionDenylist};
 
 use super::registry::CapabilityDomainRegistry;
 use super::{SystemDomainFactory, SystemInspectionService};
@@ -13,27 +14,45 @@ use super::UnavailableSystemInspectionService;
 pub(crate) fn build_default_capability_domain_registry(
     workspace_root: &Path,
 ) -> CapabilityDomainRegistry {
-    build_capability_domain_registry(workspace_root, Arc::new(UnavailableSystemInspectionService))
+    build_capability_domain_registry(
+        workspace_root,
+        Arc::new(UnavailableSystemInspectionService),
+        WriteExtensionDenylist::default(),
+        ProtectedPaths::default(),
+        ListMaxEntries::default(),
+    )
 }
 
 pub(crate) fn build_capability_domain_registry(
     workspace_root: &Path,
     system_inspection_service: Arc<dyn SystemInspectionService>,
+    write_extension_denylist: WriteExtensionDenylist,
+    protected_paths: ProtectedPaths,
+    list_max_entries: ListMaxEntries,
 ) -> CapabilityDomainRegistry {
     CapabilityDomainRegistry::from_domain_factories(default_domain_factories(
         workspace_root,
         system_inspection_service,
+        write_extension_denylist,
+        protected_paths,
+        list_max_entries,
     ))
 }
 
 fn default_domain_factories(
     workspace_root: &Path,
     system_inspection_service: Arc<dyn SystemInspectionService>,
+    write_extension_denylist: WriteExtensionDenylist,
+    protected_paths: ProtectedPaths,
+    list_max_entries: ListMaxEntries,
 ) -> Vec<Arc<dyn DomainFactory>> {
     vec![
-        Arc::new(fathom_capability_domain_fs::FilesystemDomainFactory::new(
-            workspace_root.to_path_buf(),
-        )),
+        Arc::new(
+            fathom_capability_domain_fs::FilesystemDomainFactory::new(workspace_root.to_path_buf())
+                .with_write_extension_denylist(write_extension_denylist)
+                .with_protected_paths(protected_paths)
+                .with_list_max_entries(list_max_entries),
+        ),
         Arc::new(fathom_capability_domain_brave_search::BraveSearchDomainFactory::new()),
         Arc::new(fathom_capability_domain_jina::JinaDomainFactory::new()),
         Arc::new(fathom_capability_domain_shell::ShellDomainFactory::new(
@@ -114,6 +114,24 @@ impl CapabilityDomainRegistry {
             .collect()
     }
 
+    pub(crate) fn anthropic_action_definitions_for_capability_domains(
+        &self,
+        capability_domain_ids: &BTreeSet<String>,
+    ) -> Vec<Value> {
+        self.inner
+            .actions
+            .values()
+            .filter(|entry| capability_domain_ids.contains(entry.capability_domain_id))
+            .map(|entry| {
+                json!({
+                    "name": entry.canonical_action_id,
+                    "description": entry.definition.description,
+                    "input_schema": with_runtime_action_schema(entry.definition.input_schema.clone()),
+                })
+            })
+            .collect()
+    }
+
     #[cfg(test)]
     pub(crate) fn known_action_ids(&self) -> Vec<String> {
         self.inner.actions.keys().cloned().collect()
@@ -1,9 +1,13 @@
 mod common;
 mod get_execution;
 mod list_executions;
+mod memory_append;
+mod memory_clear;
 mod payload;
 mod read_execution_input;
 mod read_execution_result;
+mod refresh_profile;
+mod schedule_heartbeat;
 mod service;
 
 use std::sync::Arc;
@@ -17,10 +21,13 @@ use fathom_capability_domain::{
 use serde::Deserialize;
 use serde_json::{Map, Value, json};
 
+use crate::profile_material::ProfileMemoryTarget;
 use crate::session::inspection::{
     ExecutionInspection, ExecutionInspectionState, ExecutionListQuery,
 };
 
+use fathom_protocol::pb;
+
 use common::SYSTEM_CAPABILITY_DOMAIN_ID;
 use payload::{preview_descriptor, slice_payload_response};
 #[cfg(test)]
@@ -42,7 +49,7 @@ impl DomainFactory for SystemDomainFactory {
         CapabilityDomainSpec {
             id: SYSTEM_CAPABILITY_DOMAIN_ID,
             name: "System",
-            description: "Privileged runtime inspection capability domain for current session execution state and execution payload access.",
+            description: "Privileged runtime capability domain for current session execution state, execution payload access, and profile memory maintenance.",
             schema_version: 1,
         }
     }
@@ -53,6 +60,10 @@ impl DomainFactory for SystemDomainFactory {
             get_execution::definition(),
             read_execution_input::definition(),
             read_execution_result::definition(),
+            memory_clear::definition(),
+            memory_append::definition(),
+            refresh_profile::definition(),
+            schedule_heartbeat::definition(),
         ]
     }
 
@@ -92,6 +103,36 @@ impl DomainFactory for SystemDomainFactory {
                     "Use bounded reads and move `offset` forward when the serialized result is larger than one slice.".to_string(),
                 ],
             },
+            CapabilityDomainRecipe {
+                title: "Wake up later".to_string(),
+                steps: vec![
+                    "Call `system__schedule_heartbeat` with `delay_ms` when you need to check back on something without waiting for the user.".to_string(),
+                    "A heartbeat trigger arrives after the delay and starts a new turn in this session.".to_string(),
+                ],
+            },
+            CapabilityDomainRecipe {
+                title: "Pick up profile changes".to_string(),
+                steps: vec![
+                    "Call `system__refresh_profile` after profile memory or settings were changed during this session.".to_string(),
+                    "Use `scope` `agent`, `user`, or `all`; pass `user_id` with `user` to refresh a single participant.".to_string(),
+                    "The refreshed copies apply from the next turn onward.".to_string(),
+                ],
+            },
+            CapabilityDomainRecipe {
+                title: "Remember a note".to_string(),
+                steps: vec![
+                    "Call `system__memory_append` with `target` set to `agent` or `user`, this session's agent id or one of its participant user ids in `target_id`, and the note text.".to_string(),
+                    "Keep each note to one self-contained fact; it is stored as a timestamped line in long-term memory.".to_string(),
+                ],
+            },
+            CapabilityDomainRecipe {
+                title: "Clear profile memory".to_string(),
+                steps: vec![
+                    "Call `system__memory_clear` with `target` set to `agent` or `user` and, in `target_id`, this session's agent id or one of its participant user ids.".to_string(),
+                    "Set `confirm` to true only when the whole long-term memory should be discarded.".to_string(),
+                    "Check `bytes_removed` to see whether the memory held anything.".to_string(),
+                ],
+            },
         ]
     }
 }
@@ -138,6 +179,10 @@ impl SystemDomainInstance {
             "get_execution" => self.execute_get_execution(submission.args).await,
             "read_execution_input" => self.execute_read_execution_input(submission.args).await,
             "read_execution_result" => self.execute_read_execution_result(submission.args).await,
+            "memory_clear" => self.execute_memory_clear(submission.args).await,
+            "memory_append" => self.execute_memory_append(submission.args).await,
+            "refresh_profile" => self.execute_refresh_profile(submission.args).await,
+            "schedule_heartbeat" => self.execute_schedule_heartbeat(submission.args).await,
             _ => Err(SystemInspectionError::Runtime(format!(
                 "system action `{action_name}` is not implemented"
             ))),
@@ -154,6 +199,14 @@ impl SystemDomainInstance {
                 None,
                 started_at.elapsed().as_millis().min(u128::from(u64::MAX)) as u64,
             ),
+            Err(SystemInspectionError::PermissionDenied(message)) => {
+                CapabilityActionResult::runtime_error(
+                    "permission_denied",
+                    message,
+                    None,
+                    started_at.elapsed().as_millis().min(u128::from(u64::MAX)) as u64,
+                )
+            }
             Err(SystemInspectionError::Runtime(message)) => CapabilityActionResult::runtime_error(
                 "inspection_failed",
                 message,
@@ -254,6 +307,143 @@ impl SystemDomainInstance {
             &slice.content,
         ))
     }
+
+    async fn execute_memory_clear(&self, args: Value) -> Result<Value, SystemInspectionError> {
+        let args = parse_args::<MemoryClearArgs>(args, "system__memory_clear")?;
+        let target = parse_memory_target(&args.target)?;
+        let target_id = require_non_empty(args.target_id, "target_id")?;
+        if !args.confirm {
+            return Err(SystemInspectionError::Input(
+                "`confirm` must be true to clear profile memory".to_string(),
+            ));
+        }
+
+        let bytes_removed = self
+            .inspection_service
+            .clear_profile_memory(&self.session_id, target, &target_id)
+            .await?;
+
+        Ok(json!({
+            "target": target.as_str(),
+            "target_id": target_id,
+            "bytes_removed": bytes_removed,
+        }))
+    }
+
+    async fn execute_memory_append(&self, args: Value) -> Result<Value, SystemInspectionError> {
+        let args = parse_args::<MemoryAppendArgs>(args, "system__memory_append")?;
+        let target = parse_memory_target(&args.target)?;
+        let target_id = require_non_empty(args.target_id, "target_id")?;
+        let note = require_non_empty(args.note, "note")?;
+
+        let bytes_written = self
+            .inspection_service
+            .append_profile_memory(&self.session_id, target, &target_id, &note)
+            .await?;
+
+        Ok(json!({
+            "target": target.as_str(),
+            "target_id": target_id,
+            "bytes_written": bytes_written,
+        }))
+    }
+
+    async fn execute_refresh_profile(&self, args: Value) -> Result<Value, SystemInspectionError> {
+        let args = parse_args::<RefreshProfileArgs>(args, "system__refresh_profile")?;
+        let scope = parse_refresh_scope(&args.scope)?;
+        let user_id = args
+            .user_id
+            .map(|user_id| user_id.trim().to_string())
+            .unwrap_or_default();
+        if !user_id.is_empty() && scope != pb::RefreshScope::User {
+            return Err(SystemInspectionError::Input(
+                "`user_id` is only allowed with scope `user`".to_string(),
+            ));
+        }
+
+        let accepted = self
+            .inspection_service
+            .request_profile_refresh(
+                &self.session_id,
+                pb::RefreshProfileTrigger {
+                    scope: scope as i32,
+                    user_id: user_id.clone(),
+                },
+            )
+            .await?;
+
+        Ok(json!({
+            "scope": args.scope.trim(),
+            "user_id": user_id,
+            "trigger_id": accepted.trigger_id,
+            "queue_depth": accepted.queue_depth,
+        }))
+    }
+
+    async fn execute_schedule_heartbeat(
+        &self,
+        args: Value,
+    ) -> Result<Value, SystemInspectionError> {
+        let args = parse_args::<ScheduleHeartbeatArgs>(args, "system__schedule_heartbeat")?;
+        let trigger_id = self
+            .inspection_service
+            .schedule_heartbeat(&self.session_id, args.delay_ms)
+            .await?;
+
+        Ok(json!({
+            "trigger_id": trigger_id,
+            "delay_ms": args.delay_ms,
+        }))
+    }
+}
+
+fn parse_refresh_scope(raw: &str) -> Result<pb::RefreshScope, SystemInspectionError> {
+    match raw.trim() {
+        "agent" => Ok(pb::RefreshScope::Agent),
+        "user" => Ok(pb::RefreshScope::User),
+        "all" => Ok(pb::RefreshScope::All),
+        other => Err(SystemInspectionError::Input(format!(
+            "invalid scope `{other}`; expected `agent`, `user`, or `all`"
+        ))),
+    }
+}
+
+fn parse_memory_target(raw: &str) -> Result<ProfileMemoryTarget, SystemInspectionError> {
+    ProfileMemoryTarget::parse(raw).ok_or_else(|| {
+        SystemInspectionError::Input(format!(
+            "invalid target `{raw}`; expected `agent` or `user`"
+        ))
+    })
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ScheduleHeartbeatArgs {
+    delay_ms: u64,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RefreshProfileArgs {
+    scope: String,
+    #[serde(default)]
+    user_id: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct MemoryAppendArgs {
+    target: String,
+    target_id: String,
+    note: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct MemoryClearArgs {
+    target: String,
+    target_id: String,
+    confirm: bool,
 }
 
 #[derive(Debug, Deserialize)]
@@ -290,6 +480,10 @@ fn action_name_for_key(key: fathom_capability_domain::CapabilityActionKey) -> Op
         common::SYSTEM_GET_EXECUTION_ACTION_KEY => Some("get_execution"),
         common::SYSTEM_READ_EXECUTION_INPUT_ACTION_KEY => Some("read_execution_input"),
         common::SYSTEM_READ_EXECUTION_RESULT_ACTION_KEY => Some("read_execution_result"),
+        common::SYSTEM_MEMORY_CLEAR_ACTION_KEY => Some("memory_clear"),
+        common::SYSTEM_MEMORY_APPEND_ACTION_KEY => Some("memory_append"),
+        common::SYSTEM_REFRESH_PROFILE_ACTION_KEY => Some("refresh_profile"),
+        common::SYSTEM_SCHEDULE_HEARTBEAT_ACTION_KEY => Some("schedule_heartbeat"),
         _ => None,
     }
 }
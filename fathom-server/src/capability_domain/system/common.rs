@@ -7,6 +7,10 @@ pub(super) const SYSTEM_READ_EXECUTION_INPUT_ACTION_KEY: CapabilityActionKey =
     CapabilityActionKey(2);
 pub(super) const SYSTEM_READ_EXECUTION_RESULT_ACTION_KEY: CapabilityActionKey =
     CapabilityActionKey(3);
+pub(super) const SYSTEM_MEMORY_CLEAR_ACTION_KEY: CapabilityActionKey = CapabilityActionKey(4);
+pub(super) const SYSTEM_MEMORY_APPEND_ACTION_KEY: CapabilityActionKey = CapabilityActionKey(5);
+pub(super) const SYSTEM_REFRESH_PROFILE_ACTION_KEY: CapabilityActionKey = CapabilityActionKey(6);
+pub(super) const SYSTEM_SCHEDULE_HEARTBEAT_ACTION_KEY: CapabilityActionKey = CapabilityActionKey(7);
 
 pub(super) fn system_spec(
     action_key: u16,
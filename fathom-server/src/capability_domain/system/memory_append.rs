@@ -0,0 +1,151 @@
+use fathom_capability_domain::CapabilityActionDefinition;
+use serde_json::json;
+
+use super::common::system_spec;
+
+pub(super) fn definition() -> CapabilityActionDefinition {
+    system_spec(
+        5,
+        "memory_append",
+        "Append a timestamped note to the long-term memory of one agent or user profile and report how many bytes were written.",
+        json!({
+            "type": "object",
+            "properties": {
+                "target": { "type": "string", "enum": ["agent", "user"] },
+                "target_id": { "type": "string" },
+                "note": { "type": "string" }
+            },
+            "required": ["target", "target_id", "note"],
+            "additionalProperties": false
+        }),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use fathom_capability_domain::ActionError;
+    use serde_json::json;
+
+    use fathom_protocol::pb;
+
+    use crate::runtime::Runtime;
+    use crate::runtime::testing::run_session_domain_action;
+
+    #[tokio::test]
+    async fn memory_append_adds_timestamped_notes_to_long_term_memory() {
+        let runtime = Runtime::new(2, 10);
+        runtime
+            .upsert_user_profile(pb::UserProfile {
+                user_id: "user-a".to_string(),
+                material_json: json!({"memory": {"long_term": "likes tea"}}).to_string(),
+                ..Default::default()
+            })
+            .await
+            .expect("upsert user profile");
+        let session = runtime
+            .create_session("agent-a".to_string(), vec!["user-a".to_string()], None)
+            .await
+            .expect("create session");
+
+        let mut written = 0;
+        for note in ["prefers metric units", "works in UTC+9"] {
+            let result = run_session_domain_action(
+                &runtime,
+                &session.session_id,
+                "system",
+                "memory_append",
+                json!({"target": "user", "target_id": "user-a", "note": note}),
+            )
+            .await;
+            let Ok(success) = result.outcome else {
+                panic!("memory append should succeed");
+            };
+            written += success.payload["bytes_written"]
+                .as_u64()
+                .expect("bytes_written");
+        }
+
+        let user = runtime
+            .fetch_user_profile("user-a")
+            .await
+            .expect("user profile");
+        let material: serde_json::Value =
+            serde_json::from_str(&user.material_json).expect("material json");
+        let long_term = material["memory"]["long_term"]
+            .as_str()
+            .expect("long-term memory");
+        let lines = long_term.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0], "likes tea");
+        assert!(lines[1].starts_with("- [") && lines[1].ends_with("] prefers metric units"));
+        assert!(lines[2].starts_with("- [") && lines[2].ends_with("] works in UTC+9"));
+        assert_eq!(written as usize, long_term.len() - "likes tea".len());
+
+        let bad_target = run_session_domain_action(
+            &runtime,
+            &session.session_id,
+            "system",
+            "memory_append",
+            json!({"target": "planet", "target_id": "user-a", "note": "hello"}),
+        )
+        .await;
+        assert!(matches!(
+            bad_target.outcome,
+            Err(ActionError::InputError(_))
+        ));
+        let user = runtime
+            .fetch_user_profile("user-a")
+            .await
+            .expect("user profile");
+        assert!(!user.material_json.contains("hello"));
+    }
+
+    #[tokio::test]
+    async fn memory_append_rejects_targets_outside_the_session() {
+        let runtime = Runtime::new(2, 10);
+        for user_id in ["user-a", "user-b"] {
+            runtime
+                .upsert_user_profile(pb::UserProfile {
+                    user_id: user_id.to_string(),
+                    material_json: "{}".to_string(),
+                    ..Default::default()
+                })
+                .await
+                .expect("upsert user profile");
+        }
+        let session = runtime
+            .create_session("agent-a".to_string(), vec!["user-a".to_string()], None)
+            .await
+            .expect("create session");
+
+        let own = run_session_domain_action(
+            &runtime,
+            &session.session_id,
+            "system",
+            "memory_append",
+            json!({"target": "user", "target_id": "user-a", "note": "likes tea"}),
+        )
+        .await;
+        assert!(own.outcome.is_ok());
+
+        for (target, target_id) in [("user", "user-b"), ("agent", "agent-b")] {
+            let foreign = run_session_domain_action(
+                &runtime,
+                &session.session_id,
+                "system",
+                "memory_append",
+                json!({"target": target, "target_id": target_id, "note": "hello"}),
+            )
+            .await;
+            let Err(ActionError::RuntimeError(error)) = foreign.outcome else {
+                panic!("write to `{target_id}` should be denied");
+            };
+            assert_eq!(error.code, "permission_denied");
+        }
+        let user = runtime
+            .fetch_user_profile("user-b")
+            .await
+            .expect("user profile");
+        assert!(!user.material_json.contains("hello"));
+    }
+}
@@ -0,0 +1,189 @@
+use fathom_capability_domain::CapabilityActionDefinition;
+use serde_json::json;
+
+use super::common::system_spec;
+
+pub(super) fn definition() -> CapabilityActionDefinition {
+    system_spec(
+        4,
+        "memory_clear",
+        "Empty the long-term memory of one agent or user profile and report how many bytes were removed. Requires `confirm: true`.",
+        json!({
+            "type": "object",
+            "properties": {
+                "target": { "type": "string", "enum": ["agent", "user"] },
+                "target_id": { "type": "string" },
+                "confirm": { "type": "boolean" }
+            },
+            "required": ["target", "target_id", "confirm"],
+            "additionalProperties": false
+        }),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use fathom_capability_domain::ActionError;
+    use serde_json::json;
+
+    use fathom_protocol::pb;
+
+    use crate::runtime::Runtime;
+    use crate::runtime::testing::run_session_domain_action;
+
+    #[tokio::test]
+    async fn memory_clear_empties_agent_and_user_long_term_memory() {
+        let runtime = Runtime::new(2, 10);
+        runtime
+            .upsert_agent_profile(pb::AgentProfile {
+                agent_id: "agent-a".to_string(),
+                material_json: json!({"memory": {"long_term": "agent notes"}}).to_string(),
+                spec_version: 3,
+                ..Default::default()
+            })
+            .await
+            .expect("upsert agent profile");
+        runtime
+            .upsert_user_profile(pb::UserProfile {
+                user_id: "user-a".to_string(),
+                material_json: json!({"name": "A", "memory": {"long_term": "likes tea"}})
+                    .to_string(),
+                ..Default::default()
+            })
+            .await
+            .expect("upsert user profile");
+        let session = runtime
+            .create_session("agent-a".to_string(), vec!["user-a".to_string()], None)
+            .await
+            .expect("create session");
+
+        let agent_result = run_session_domain_action(
+            &runtime,
+            &session.session_id,
+            "system",
+            "memory_clear",
+            json!({"target": "agent", "target_id": "agent-a", "confirm": true}),
+        )
+        .await;
+        let Ok(success) = agent_result.outcome else {
+            panic!("agent memory clear should succeed");
+        };
+        assert_eq!(success.payload["bytes_removed"], json!(11));
+        let agent = runtime
+            .fetch_agent_profile("agent-a")
+            .await
+            .expect("agent profile");
+        let material: serde_json::Value =
+            serde_json::from_str(&agent.material_json).expect("agent material");
+        assert_eq!(material["memory"]["long_term"], json!(""));
+        assert_eq!(agent.spec_version, 4);
+
+        let user_result = run_session_domain_action(
+            &runtime,
+            &session.session_id,
+            "system",
+            "memory_clear",
+            json!({"target": "user", "target_id": "user-a", "confirm": true}),
+        )
+        .await;
+        let Ok(success) = user_result.outcome else {
+            panic!("user memory clear should succeed");
+        };
+        assert_eq!(success.payload["bytes_removed"], json!(9));
+        let user = runtime
+            .fetch_user_profile("user-a")
+            .await
+            .expect("user profile");
+        let material: serde_json::Value =
+            serde_json::from_str(&user.material_json).expect("user material");
+        assert_eq!(material["memory"]["long_term"], json!(""));
+        assert_eq!(material["name"], json!("A"));
+    }
+
+    #[tokio::test]
+    async fn memory_clear_requires_confirmation() {
+        let runtime = Runtime::new(2, 10);
+        runtime
+            .upsert_user_profile(pb::UserProfile {
+                user_id: "user-a".to_string(),
+                material_json: json!({"memory": {"long_term": "keep me"}}).to_string(),
+                ..Default::default()
+            })
+            .await
+            .expect("upsert user profile");
+        let session = runtime
+            .create_session("agent-a".to_string(), vec!["user-a".to_string()], None)
+            .await
+            .expect("create session");
+
+        let unconfirmed = run_session_domain_action(
+            &runtime,
+            &session.session_id,
+            "system",
+            "memory_clear",
+            json!({"target": "user", "target_id": "user-a", "confirm": false}),
+        )
+        .await;
+        assert!(matches!(
+            unconfirmed.outcome,
+            Err(ActionError::InputError(error)) if error.message.contains("confirm")
+        ));
+        let user = runtime
+            .fetch_user_profile("user-a")
+            .await
+            .expect("user profile");
+        assert!(user.material_json.contains("keep me"));
+    }
+
+    #[tokio::test]
+    async fn memory_clear_rejects_targets_outside_the_session() {
+        let runtime = Runtime::new(2, 10);
+        for user_id in ["user-a", "user-b"] {
+            runtime
+                .upsert_user_profile(pb::UserProfile {
+                    user_id: user_id.to_string(),
+                    material_json: json!({"memory": {"long_term": "keep me"}}).to_string(),
+                    ..Default::default()
+                })
+                .await
+                .expect("upsert user profile");
+        }
+        runtime
+            .upsert_agent_profile(pb::AgentProfile {
+                agent_id: "agent-b".to_string(),
+                material_json: json!({"memory": {"long_term": "keep me"}}).to_string(),
+                ..Default::default()
+            })
+            .await
+            .expect("upsert agent profile");
+        let session = runtime
+            .create_session("agent-a".to_string(), vec!["user-a".to_string()], None)
+            .await
+            .expect("create session");
+
+        for (target, target_id) in [("user", "user-b"), ("agent", "agent-b")] {
+            let foreign = run_session_domain_action(
+                &runtime,
+                &session.session_id,
+                "system",
+                "memory_clear",
+                json!({"target": target, "target_id": target_id, "confirm": true}),
+            )
+            .await;
+            let Err(ActionError::RuntimeError(error)) = foreign.outcome else {
+                panic!("clearing `{target_id}` should be denied");
+            };
+            assert_eq!(error.code, "permission_denied");
+        }
+        let user = runtime
+            .fetch_user_profile("user-b")
+            .await
+            .expect("user profile");
+        assert!(user.material_json.contains("keep me"));
+        let agent = runtime
+            .fetch_agent_profile("agent-b")
+            .await
+            .expect("agent profile");
+        assert!(agent.material_json.contains("keep me"));
+    }
+}
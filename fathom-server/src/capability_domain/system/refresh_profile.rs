@@ -0,0 +1,89 @@
+use fathom_capability_domain::CapabilityActionDefinition;
+use serde_json::json;
+
+use super::common::system_spec;
+
+pub(super) fn definition() -> CapabilityActionDefinition {
+    system_spec(
+        6,
+        "refresh_profile",
+        "Queue a profile refresh for the current session so its agent and participant profile copies are reloaded from the runtime before the next turn. `user_id` narrows a `user` refresh to one participant.",
+        json!({
+            "type": "object",
+            "properties": {
+                "scope": { "type": "string", "enum": ["agent", "user", "all"] },
+                "user_id": { "type": "string" }
+            },
+            "required": ["scope"],
+            "additionalProperties": false
+        }),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use fathom_capability_domain::ActionError;
+    use serde_json::json;
+
+    use fathom_protocol::pb;
+
+    use crate::runtime::Runtime;
+    use crate::runtime::testing::run_session_domain_action;
+
+    #[tokio::test]
+    async fn refresh_profile_action_refreshes_session_profile_copies() {
+        let runtime = Runtime::new(2, 10);
+        let session = runtime
+            .create_session("agent-a".to_string(), vec!["user-a".to_string()], None)
+            .await
+            .expect("create session");
+        let mut events_rx = runtime
+            .get_session(&session.session_id)
+            .await
+            .expect("session runtime")
+            .events_tx
+            .subscribe();
+
+        let result = run_session_domain_action(
+            &runtime,
+            &session.session_id,
+            "system",
+            "refresh_profile",
+            json!({"scope": "user", "user_id": "user-a"}),
+        )
+        .await;
+        let Ok(success) = result.outcome else {
+            panic!("refresh_profile should be accepted");
+        };
+        assert!(
+            success.payload["trigger_id"]
+                .as_str()
+                .is_some_and(|trigger_id| trigger_id.starts_with("trigger-"))
+        );
+
+        let refreshed = tokio::time::timeout(Duration::from_secs(5), async {
+            loop {
+                let event = events_rx.recv().await.expect("session event");
+                if let Some(pb::session_event::Kind::ProfileRefreshed(refreshed)) = event.kind {
+                    return refreshed;
+                }
+            }
+        })
+        .await
+        .expect("profile refreshed event");
+        assert_eq!(refreshed.scope, pb::RefreshScope::User as i32);
+        assert_eq!(refreshed.refreshed_user_ids, vec!["user-a".to_string()]);
+
+        let rejected = run_session_domain_action(
+            &runtime,
+            &session.session_id,
+            "system",
+            "refresh_profile",
+            json!({"scope": "agent", "user_id": "user-a"}),
+        )
+        .await;
+        assert!(matches!(rejected.outcome, Err(ActionError::InputError(_))));
+    }
+}
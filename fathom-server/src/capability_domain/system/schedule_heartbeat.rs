@@ -0,0 +1,107 @@
+use fathom_capability_domain::CapabilityActionDefinition;
+use serde_json::json;
+
+use super::common::system_spec;
+
+pub(super) fn definition() -> CapabilityActionDefinition {
+    system_spec(
+        7,
+        "schedule_heartbeat",
+        "Schedule a heartbeat trigger for the current session after `delay_ms` milliseconds so the agent wakes up again without user input. The delay is capped by the runtime (24 hours by default).",
+        json!({
+            "type": "object",
+            "properties": {
+                "delay_ms": { "type": "integer", "minimum": 1 }
+            },
+            "required": ["delay_ms"],
+            "additionalProperties": false
+        }),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use fathom_capability_domain::ActionError;
+    use serde_json::json;
+
+    use fathom_protocol::pb;
+
+    use crate::runtime::Runtime;
+    use crate::runtime::testing::run_session_domain_action;
+
+    #[tokio::test]
+    async fn schedule_heartbeat_action_enqueues_heartbeat_after_delay() {
+        let runtime = Runtime::new(2, 10);
+        runtime.set_max_heartbeat_delay_ms(1_000);
+        let session = runtime
+            .create_session("agent-a".to_string(), vec!["user-a".to_string()], None)
+            .await
+            .expect("create session");
+        let mut events_rx = runtime
+            .get_session(&session.session_id)
+            .await
+            .expect("session runtime")
+            .events_tx
+            .subscribe();
+
+        let scheduled_at = Instant::now();
+        let result = run_session_domain_action(
+            &runtime,
+            &session.session_id,
+            "system",
+            "schedule_heartbeat",
+            json!({"delay_ms": 300}),
+        )
+        .await;
+        let Ok(success) = result.outcome else {
+            panic!("schedule_heartbeat should be accepted");
+        };
+        let trigger_id = success.payload["trigger_id"]
+            .as_str()
+            .expect("trigger id")
+            .to_string();
+        while let Ok(event) = events_rx.try_recv() {
+            if let Some(pb::session_event::Kind::TriggerAccepted(accepted)) = event.kind {
+                assert!(
+                    accepted
+                        .trigger
+                        .is_none_or(|trigger| trigger.trigger_id != trigger_id),
+                    "heartbeat must not be queued before its delay elapses"
+                );
+            }
+        }
+
+        let accepted = tokio::time::timeout(Duration::from_secs(5), async {
+            loop {
+                let event = events_rx.recv().await.expect("session event");
+                if let Some(pb::session_event::Kind::TriggerAccepted(accepted)) = event.kind
+                    && accepted
+                        .trigger
+                        .as_ref()
+                        .is_some_and(|trigger| trigger.trigger_id == trigger_id)
+                {
+                    return accepted.trigger.expect("trigger");
+                }
+            }
+        })
+        .await
+        .expect("heartbeat trigger accepted");
+        assert!(scheduled_at.elapsed() >= Duration::from_millis(300));
+        assert!(matches!(
+            accepted.kind,
+            Some(pb::trigger::Kind::Heartbeat(_))
+        ));
+
+        let rejected = run_session_domain_action(
+            &runtime,
+            &session.session_id,
+            "system",
+            "schedule_heartbeat",
+            json!({"delay_ms": 1_001}),
+        )
+        .await;
+        assert!(matches!(rejected.outcome, Err(ActionError::InputError(_))));
+    }
+}
@@ -1,9 +1,11 @@
 use std::future::Future;
 use std::pin::Pin;
 
+use crate::profile_material::ProfileMemoryTarget;
 use crate::session::inspection::{
     ExecutionInspection, ExecutionListPage, ExecutionListQuery, PayloadSlice,
 };
+use fathom_protocol::pb;
 
 pub(crate) type SystemInspectionFuture<'a, T> =
     Pin<Box<dyn Future<Output = Result<T, SystemInspectionError>> + Send + 'a>>;
@@ -11,6 +13,7 @@ pub(crate) type SystemInspectionFuture<'a, T> =
 #[derive(Debug, Clone)]
 pub(crate) enum SystemInspectionError {
     Input(String),
+    PermissionDenied(String),
     Runtime(String),
 }
 
@@ -42,6 +45,33 @@ pub(crate) trait SystemInspectionService: Send + Sync + 'static {
         offset: usize,
         limit: usize,
     ) -> SystemInspectionFuture<'a, PayloadSlice>;
+
+    fn clear_profile_memory<'a>(
+        &'a self,
+        session_id: &'a str,
+        target: ProfileMemoryTarget,
+        target_id: &'a str,
+    ) -> SystemInspectionFuture<'a, usize>;
+
+    fn append_profile_memory<'a>(
+        &'a self,
+        session_id: &'a str,
+        target: ProfileMemoryTarget,
+        target_id: &'a str,
+        note: &'a str,
+    ) -> SystemInspectionFuture<'a, usize>;
+
+    fn request_profile_refresh<'a>(
+        &'a self,
+        session_id: &'a str,
+        refresh: pb::RefreshProfileTrigger,
+    ) -> SystemInspectionFuture<'a, pb::EnqueueTriggerResponse>;
+
+    fn schedule_heartbeat<'a>(
+        &'a self,
+        session_id: &'a str,
+        delay_ms: u64,
+    ) -> SystemInspectionFuture<'a, String>;
 }
 
 #[cfg(test)]
@@ -100,4 +130,55 @@ impl SystemInspectionService for UnavailableSystemInspectionService {
             ))
         })
     }
+
+    fn clear_profile_memory<'a>(
+        &'a self,
+        _session_id: &'a str,
+        _target: ProfileMemoryTarget,
+        _target_id: &'a str,
+    ) -> SystemInspectionFuture<'a, usize> {
+        Box::pin(async {
+            Err(SystemInspectionError::Runtime(
+                "system inspection service is unavailable".to_string(),
+            ))
+        })
+    }
+
+    fn append_profile_memory<'a>(
+        &'a self,
+        _session_id: &'a str,
+        _target: ProfileMemoryTarget,
+        _target_id: &'a str,
+        _note: &'a str,
+    ) -> SystemInspectionFuture<'a, usize> {
+        Box::pin(async {
+            Err(SystemInspectionError::Runtime(
+                "system inspection service is unavailable".to_string(),
+            ))
+        })
+    }
+
+    fn request_profile_refresh<'a>(
+        &'a self,
+        _session_id: &'a str,
+        _refresh: pb::RefreshProfileTrigger,
+    ) -> SystemInspectionFuture<'a, pb::EnqueueTriggerResponse> {
+        Box::pin(async {
+            Err(SystemInspectionError::Runtime(
+                "system inspection service is unavailable".to_string(),
+            ))
+        })
+    }
+
+    fn schedule_heartbeat<'a>(
+        &'a self,
+        _session_id: &'a str,
+        _delay_ms: u64,
+    ) -> SystemInspectionFuture<'a, String> {
+        Box::pin(async {
+            Err(SystemInspectionError::Runtime(
+                "system inspection service is unavailable".to_string(),
+            ))
+        })
+    }
 }
@@ -0,0 +1,174 @@
+use std::collections::HashMap;
+use std::sync::{Mutex, MutexGuard};
+use std::time::Duration;
+
+use tokio::task::AbortHandle;
+use tokio::time::{Instant, MissedTickBehavior};
+use tonic::Code;
+use tracing::warn;
+
+use crate::runtime::Runtime;
+use crate::util::now_unix_ms;
+use fathom_protocol::pb;
+
+pub(crate) const MAX_CRON_INTERVAL_SECS: u64 = 365 * 24 * 60 * 60;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct CronEntry {
+    pub(crate) session_id: String,
+    pub(crate) key: String,
+    pub(crate) interval: Duration,
+}
+
+#[derive(Default)]
+pub(crate) struct CronScheduler {
+    entries: Mutex<HashMap<(String, String), AbortHandle>>,
+}
+
+impl CronScheduler {
+    pub(crate) fn schedule(&self, runtime: Runtime, entry: CronEntry) -> Result<bool, String> {
+        if entry.interval.is_zero() {
+            return Err("cron interval must be greater than zero".to_string());
+        }
+        if entry.interval > Duration::from_secs(MAX_CRON_INTERVAL_SECS) {
+            return Err(format!(
+                "cron interval must be at most {MAX_CRON_INTERVAL_SECS} seconds"
+            ));
+        }
+        if entry.key.trim().is_empty() {
+            return Err("cron key is required".to_string());
+        }
+
+        let id = (entry.session_id.clone(), entry.key.clone());
+        let timer = spawn_cron_timer(runtime, entry);
+        let previous = self.lock_entries().insert(id, timer);
+        if let Some(previous) = &previous {
+            previous.abort();
+        }
+        Ok(previous.is_some())
+    }
+
+    pub(crate) fn unschedule(&self, session_id: &str, key: &str) -> bool {
+        let removed = self
+            .lock_entries()
+            .remove(&(session_id.to_string(), key.to_string()));
+        match removed {
+            Some(timer) => {
+                timer.abort();
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub(crate) fn unschedule_session(&self, session_id: &str) {
+        self.lock_entries().retain(|(entry_session_id, _), timer| {
+            if entry_session_id == session_id {
+                timer.abort();
+                false
+            } else {
+                true
+            }
+        });
+    }
+
+    pub(crate) fn unschedule_all(&self) {
+        for (_, timer) in self.lock_entries().drain() {
+            timer.abort();
+        }
+    }
+
+    fn lock_entries(&self) -> MutexGuard<'_, HashMap<(String, String), AbortHandle>> {
+        self.entries
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+fn spawn_cron_timer(runtime: Runtime, entry: CronEntry) -> AbortHandle {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval_at(Instant::now() + entry.interval, entry.interval);
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Skip);
+        loop {
+            ticker.tick().await;
+            let trigger = pb::Trigger {
+                trigger_id: runtime.next_trigger_id(),
+                created_at_unix_ms: now_unix_ms(),
+                kind: Some(pb::trigger::Kind::Cron(pb::CronTrigger {
+                    key: entry.key.clone(),
+                })),
+            };
+            if let Err(status) = runtime.enqueue_trigger(&entry.session_id, trigger).await {
+                if status.code() == Code::NotFound {
+                    break;
+                }
+                warn!(
+                    session_id = %entry.session_id,
+                    key = %entry.key,
+                    error = %status.message(),
+                    "failed to enqueue cron trigger"
+                );
+            }
+        }
+    })
+    .abort_handle()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use crate::runtime::Runtime;
+
+    #[tokio::test]
+    async fn scheduled_cron_enqueues_cron_triggers_into_history() {
+        let runtime = Runtime::new(2, 10);
+        let session = runtime
+            .create_session("agent-a".to_string(), vec!["user-a".to_string()], None)
+            .await
+            .expect("create session");
+
+        let error = runtime
+            .schedule_cron(&session.session_id, "digest".to_string(), Duration::ZERO)
+            .await
+            .expect_err("zero interval should be rejected");
+        assert_eq!(error.code(), tonic::Code::InvalidArgument);
+        let error = runtime
+            .schedule_cron(&session.session_id, "digest".to_string(), Duration::MAX)
+            .await
+            .expect_err("unbounded interval should be rejected");
+        assert_eq!(error.code(), tonic::Code::InvalidArgument);
+
+        let replaced = runtime
+            .schedule_cron(
+                &session.session_id,
+                "digest".to_string(),
+                Duration::from_millis(50),
+            )
+            .await
+            .expect("schedule cron");
+        assert!(!replaced);
+
+        let started = Instant::now();
+        let fired = tokio::time::timeout(Duration::from_secs(5), async {
+            loop {
+                let history = runtime
+                    .get_history(&session.session_id, 0, 100)
+                    .await
+                    .expect("history");
+                if history.entries.iter().any(|entry| {
+                    entry.event_json.contains("trigger_cron") && entry.event_json.contains("digest")
+                }) {
+                    return;
+                }
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await;
+        assert!(fired.is_ok(), "cron trigger should reach session history");
+        assert!(started.elapsed() >= Duration::from_millis(50));
+
+        assert!(runtime.unschedule_cron(&session.session_id, "digest"));
+        assert!(!runtime.unschedule_cron(&session.session_id, "digest"));
+    }
+}
@@ -1,5 +1,6 @@
 mod compaction;
 mod constants;
+mod persistence;
 mod preview;
 pub(crate) mod schema;
 mod transform;
@@ -8,15 +9,57 @@ use crate::session::state::SessionState;
 use crate::util::now_unix_ms;
 use fathom_protocol::pb;
 
-use self::compaction::maybe_compact_history;
-
+pub(crate) use compaction::{
+    HistoryBatch, HistoryCompactionLimits, push_summary_block, take_history_batch,
+};
 pub(crate) use constants::{EXECUTION_INPUT_LOOKUP_ACTION, EXECUTION_RESULT_LOOKUP_ACTION};
-pub(crate) use preview::{PayloadPreview, build_payload_preview};
+pub(crate) use persistence::{
+    HistoryLog, SessionRecord, last_session_seq, persist_history, read_session_record,
+    restore_history, write_session_record,
+};
+pub(crate) use preview::{PayloadPreview, PreviewLimits, build_bounded_payload_preview};
 pub(crate) use schema::{HistoryEvent, HistoryEventKind};
 
+const DEFAULT_HISTORY_PAGE_SIZE: u64 = 100;
+const MAX_HISTORY_PAGE_SIZE: u64 = 1000;
+
+pub(crate) fn history_page(
+    state: &SessionState,
+    offset: u64,
+    page_size: u64,
+) -> pb::GetHistoryResponse {
+    let retained_start = state.compaction.last_compacted_history_index;
+    let total_count = retained_start + state.history.len() as u64;
+    let page_size = if page_size == 0 {
+        DEFAULT_HISTORY_PAGE_SIZE
+    } else {
+        page_size.min(MAX_HISTORY_PAGE_SIZE)
+    };
+    let start = offset.max(retained_start).min(total_count);
+
+    let entries = state
+        .history
+        .iter()
+        .enumerate()
+        .skip((start - retained_start) as usize)
+        .take(page_size as usize)
+        .map(|(position, event)| pb::HistoryEntry {
+            index: retained_start + position as u64,
+            created_at_unix_ms: event.ts_unix_ms,
+            event_json: serde_json::to_string(event).unwrap_or_default(),
+        })
+        .collect::<Vec<_>>();
+    let next_offset = start + entries.len() as u64;
+
+    pb::GetHistoryResponse {
+        entries,
+        total_count,
+        next_offset,
+    }
+}
+
 pub(crate) fn append_trigger_history(state: &mut SessionState, trigger: &pb::Trigger) {
     state.history.push(transform::trigger_line(state, trigger));
-    maybe_compact_history(state);
 }
 
 pub(crate) fn append_assistant_output_history(state: &mut SessionState, content: &str) {
@@ -25,7 +68,6 @@ pub(crate) fn append_assistant_output_history(state: &mut SessionState, content:
         now_unix_ms(),
         content,
     ));
-    maybe_compact_history(state);
 }
 
 pub(crate) fn append_execution_requested_history(
@@ -35,5 +77,109 @@ pub(crate) fn append_execution_requested_history(
     state
         .history
         .push(transform::execution_requested_line(state, execution));
-    maybe_compact_history(state);
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::{append_assistant_output_history, history_page};
+    use crate::session::testing::test_state;
+
+    #[test]
+    fn history_page_walks_retained_entries_with_global_indices() {
+        let mut state = test_state();
+        state.compaction.last_compacted_history_index = 10;
+        for index in 0..3 {
+            append_assistant_output_history(&mut state, &format!("output-{index}"));
+        }
+
+        let first = history_page(&state, 0, 2);
+        assert_eq!(first.total_count, 13);
+        assert_eq!(
+            first
+                .entries
+                .iter()
+                .map(|entry| entry.index)
+                .collect::<Vec<_>>(),
+            vec![10, 11]
+        );
+        assert!(first.entries[0].event_json.contains("output-0"));
+        assert_eq!(first.next_offset, 12);
+
+        let second = history_page(&state, first.next_offset, 2);
+        assert_eq!(second.entries.len(), 1);
+        assert!(second.entries[0].event_json.contains("output-2"));
+        assert_eq!(second.next_offset, 13);
+
+        let past_end = history_page(&state, 50, 2);
+        assert!(past_end.entries.is_empty());
+        assert_eq!(past_end.next_offset, 13);
+    }
+
+    #[test]
+    fn history_page_clamps_offsets_and_page_sizes_at_the_boundaries() {
+        let mut state = test_state();
+        for index in 0..5 {
+            append_assistant_output_history(&mut state, &format!("output-{index}"));
+        }
+
+        let at_end = history_page(&state, 5, 10);
+        assert!(at_end.entries.is_empty());
+        assert_eq!(at_end.total_count, 5);
+        assert_eq!(at_end.next_offset, 5);
+
+        let last = history_page(&state, 4, 10);
+        assert_eq!(last.entries.len(), 1);
+        assert_eq!(last.entries[0].index, 4);
+        assert_eq!(last.next_offset, 5);
+
+        let single = history_page(&state, 0, 1);
+        assert_eq!(single.entries.len(), 1);
+        assert_eq!(single.next_offset, 1);
+
+        let oversized = history_page(&state, 0, u64::MAX);
+        assert_eq!(oversized.entries.len(), 5);
+
+        state.compaction.last_compacted_history_index = 3;
+        let before_retained = history_page(&state, 0, 2);
+        assert_eq!(
+            before_retained
+                .entries
+                .iter()
+                .map(|entry| entry.index)
+                .collect::<Vec<_>>(),
+            vec![3, 4]
+        );
+        assert_eq!(before_retained.total_count, 8);
+    }
+
+    #[test]
+    fn history_page_fetches_only_entries_since_the_previous_fetch() {
+        let mut state = test_state();
+        append_assistant_output_history(&mut state, "turn-1");
+
+        let initial = history_page(&state, 0, 0);
+        assert_eq!(initial.entries.len(), 1);
+        assert_eq!(initial.total_count, 1);
+
+        append_assistant_output_history(&mut state, "turn-2");
+        append_assistant_output_history(&mut state, "turn-3");
+
+        let incremental = history_page(&state, initial.next_offset, 0);
+        assert_eq!(incremental.total_count, 3);
+        assert_eq!(
+            incremental
+                .entries
+                .iter()
+                .map(|entry| entry.index)
+                .collect::<Vec<_>>(),
+            vec![1, 2]
+        );
+        assert!(incremental.entries[0].event_json.contains("turn-2"));
+        assert_eq!(incremental.next_offset, 3);
+
+        let caught_up = history_page(&state, incremental.next_offset, 0);
+        assert!(caught_up.entries.is_empty());
+        assert_eq!(caught_up.total_count, 3);
+    }
 }
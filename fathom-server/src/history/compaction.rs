@@ -11,31 +11,110 @@ const COMPACTION_BATCH_EVENTS: usize = 24;
 const MAX_SUMMARY_ACTIONS: usize = 4;
 const MAX_SUMMARY_USERS: usize = 4;
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct HistoryCompactionLimits {
+    pub(crate) min_live_events: usize,
+    pub(crate) batch_events: usize,
+}
+
+impl Default for HistoryCompactionLimits {
+    fn default() -> Self {
+        Self {
+            min_live_events: MIN_LIVE_HISTORY_EVENTS,
+            batch_events: COMPACTION_BATCH_EVENTS,
+        }
+    }
+}
+
+impl HistoryCompactionLimits {
+    pub(crate) fn threshold(&self) -> usize {
+        self.min_live_events.saturating_add(self.batch_events)
+    }
+}
+
+pub(crate) struct HistoryBatch {
+    block_id: String,
+    source_range_start: u64,
+    source_range_end: u64,
+    events: Vec<HistoryEvent>,
+}
+
+impl HistoryBatch {
+    pub(crate) fn block_id(&self) -> &str {
+        &self.block_id
+    }
+
+    pub(crate) fn transcript(&self) -> String {
+        self.events
+            .iter()
+            .map(|event| serde_json::to_string(event).unwrap_or_default())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    pub(crate) fn model_summary(&self, summary: &str) -> String {
+        format!(
+            "{} source=[{},{}) events={} summary={summary}",
+            self.block_id,
+            self.source_range_start,
+            self.source_range_end,
+            self.events.len()
+        )
+    }
+
+    pub(crate) fn fallback_summary(&self) -> String {
+        summarize_history_batch(
+            &self.block_id,
+            &self.events,
+            self.source_range_start,
+            self.source_range_end,
+        )
+    }
+}
+
 pub(crate) fn maybe_compact_history(state: &mut SessionState) {
-    while state.history.len() > MIN_LIVE_HISTORY_EVENTS + COMPACTION_BATCH_EVENTS {
-        let compactable = state.history.len().saturating_sub(MIN_LIVE_HISTORY_EVENTS);
-        let batch_len =
-            adjusted_batch_len(&state.history, compactable.min(COMPACTION_BATCH_EVENTS));
-        if batch_len == 0 {
+    while state.history.len() > state.history_compaction_limits.threshold() {
+        let Some(batch) = take_history_batch(state) else {
             break;
-        }
+        };
+        let summary_text = batch.fallback_summary();
+        push_summary_block(state, &batch, summary_text);
+    }
+}
 
-        let source_range_start = state.compaction.last_compacted_history_index;
-        let source_range_end = source_range_start + batch_len as u64;
-        let batch = state.history.drain(0..batch_len).collect::<Vec<_>>();
-        let block_id = format!("history-summary-{source_range_end:06}");
-        let summary_text =
-            summarize_history_batch(&block_id, &batch, source_range_start, source_range_end);
-
-        state.compaction.summary_blocks.push(SummaryBlockRef {
-            id: block_id,
-            source_range_start,
-            source_range_end,
-            summary_text,
-            created_at_unix_ms: now_unix_ms(),
-        });
-        state.compaction.last_compacted_history_index = source_range_end;
+pub(crate) fn take_history_batch(state: &mut SessionState) -> Option<HistoryBatch> {
+    let limits = state.history_compaction_limits;
+    let compactable = state.history.len().saturating_sub(limits.min_live_events);
+    let batch_len = adjusted_batch_len(&state.history, compactable.min(limits.batch_events));
+    if batch_len == 0 {
+        return None;
     }
+
+    let source_range_start = state.compaction.last_compacted_history_index;
+    let source_range_end = source_range_start + batch_len as u64;
+    Some(HistoryBatch {
+        block_id: format!("history-summary-{source_range_end:06}"),
+        source_range_start,
+        source_range_end,
+        events: state.history.drain(0..batch_len).collect(),
+    })
+}
+
+pub(crate) fn push_summary_block(
+    state: &mut SessionState,
+    batch: &HistoryBatch,
+    summary_text: String,
+) -> SummaryBlockRef {
+    let block = SummaryBlockRef {
+        id: batch.block_id.clone(),
+        source_range_start: batch.source_range_start,
+        source_range_end: batch.source_range_end,
+        summary_text,
+        created_at_unix_ms: now_unix_ms(),
+    };
+    state.compaction.summary_blocks.push(block.clone());
+    state.compaction.last_compacted_history_index = batch.source_range_end;
+    block
 }
 
 fn adjusted_batch_len(history: &[HistoryEvent], proposed: usize) -> usize {
@@ -128,7 +207,7 @@ fn summarize_history_batch(
         .join(",");
 
     format!(
-        "{block_id} source=[{source_range_start},{source_range_end}) ts=[{first_ts},{last_ts}] events={} user_message={} assistant_output={} execution_requested={} execution_succeeded={} execution_failed={} execution_backgrounded={} execution_canceled={} execution_rejected={} refresh_profile={} heartbeat={} cron={} statuses=[{}] actions=[{}] users=[{}]",
+        "{block_id} source=[{source_range_start},{source_range_end}) ts=[{first_ts},{last_ts}] events={} user_message={} assistant_output={} execution_requested={} execution_succeeded={} execution_failed={} execution_backgrounded={} execution_canceled={} execution_rejected={} refresh_profile={} heartbeat={} cron={} external_event={} statuses=[{}] actions=[{}] users=[{}]",
         batch.len(),
         counts.get("user_message").copied().unwrap_or_default(),
         counts.get("assistant_output").copied().unwrap_or_default(),
@@ -156,6 +235,7 @@ fn summarize_history_batch(
         counts.get("refresh_profile").copied().unwrap_or_default(),
         counts.get("heartbeat").copied().unwrap_or_default(),
         counts.get("cron").copied().unwrap_or_default(),
+        counts.get("external_event").copied().unwrap_or_default(),
         statuses_preview,
         actions_preview,
         users_preview
@@ -166,7 +246,10 @@ fn summarize_history_batch(
 mod tests {
     use std::collections::{BTreeSet, HashMap};
 
-    use super::{COMPACTION_BATCH_EVENTS, MIN_LIVE_HISTORY_EVENTS, maybe_compact_history};
+    use super::{
+        COMPACTION_BATCH_EVENTS, HistoryCompactionLimits, MIN_LIVE_HISTORY_EVENTS,
+        maybe_compact_history,
+    };
     use crate::capability_domain::build_default_capability_domain_registry;
     use crate::history::schema::{
         ExecutionSucceededHistoryPayload, HistoryActorKind, HistoryEventKind,
@@ -239,4 +322,38 @@ mod tests {
         assert!(state.compaction.last_compacted_history_index > 0);
         assert!(state.history.len() <= MIN_LIVE_HISTORY_EVENTS + COMPACTION_BATCH_EVENTS);
     }
+
+    #[test]
+    fn configured_limits_drive_the_compaction_threshold() {
+        let mut state = test_state();
+        state.history_compaction_limits = HistoryCompactionLimits {
+            min_live_events: 3,
+            batch_events: 2,
+        };
+        for index in 0..5 {
+            state.history.push(HistoryEvent {
+                ts_unix_ms: index,
+                actor_kind: HistoryActorKind::User,
+                actor_id: "user-a".to_string(),
+                profile_ref: "test".to_string(),
+                kind: HistoryEventKind::TriggerUserMessage(UserMessageHistoryPayload {
+                    text: format!("message-{index}"),
+                }),
+            });
+        }
+
+        maybe_compact_history(&mut state);
+        assert!(state.compaction.summary_blocks.is_empty());
+
+        state.history.push(state.history[0].clone());
+        maybe_compact_history(&mut state);
+
+        assert_eq!(state.compaction.summary_blocks.len(), 1);
+        assert_eq!(state.compaction.last_compacted_history_index, 2);
+        assert_eq!(state.history.len(), 4);
+        let block = &state.compaction.summary_blocks[0];
+        assert_eq!((block.source_range_start, block.source_range_end), (0, 2));
+        assert!(block.summary_text.contains("events=2 user_message=2"));
+        assert!(block.summary_text.contains("users=[user-a]"));
+    }
 }
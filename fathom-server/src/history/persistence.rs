@@ -0,0 +1,253 @@
+use std::fs::{self, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use crate::history::HistoryEvent;
+use crate::history::compaction::maybe_compact_history;
+use crate::session::state::SessionState;
+
+#[derive(Debug, Clone)]
+pub(crate) struct HistoryLog {
+    path: PathBuf,
+    persisted_through: u64,
+}
+
+impl HistoryLog {
+    pub(crate) fn for_session(history_dir: &Path, session_id: &str) -> Self {
+        Self {
+            path: history_dir.join(format!("{session_id}.jsonl")),
+            persisted_through: 0,
+        }
+    }
+
+    pub(crate) fn load(&self) -> std::io::Result<Vec<HistoryEvent>> {
+        load_history_lines(&self.path)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct SessionRecord {
+    pub(crate) agent_id: String,
+    pub(crate) participant_user_ids: Vec<String>,
+}
+
+impl SessionRecord {
+    pub(crate) fn from_state(state: &SessionState) -> Self {
+        Self {
+            agent_id: state.agent_id.clone(),
+            participant_user_ids: {
+                let mut participant_user_ids = state.participant_user_ids.clone();
+                participant_user_ids.sort();
+                participant_user_ids
+            },
+        }
+    }
+}
+
+pub(crate) fn write_session_record(
+    history_dir: &Path,
+    session_id: &str,
+    record: &SessionRecord,
+) -> std::io::Result<()> {
+    fs::create_dir_all(history_dir)?;
+    fs::write(
+        session_record_path(history_dir, session_id),
+        serde_json::to_vec(record)?,
+    )
+}
+
+pub(crate) fn read_session_record(
+    history_dir: &Path,
+    session_id: &str,
+) -> std::io::Result<Option<SessionRecord>> {
+    match fs::read(session_record_path(history_dir, session_id)) {
+        Ok(bytes) => Ok(Some(serde_json::from_slice(&bytes)?)),
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(error) => Err(error),
+    }
+}
+
+pub(crate) fn last_session_seq(history_dir: &Path) -> u64 {
+    let Ok(entries) = fs::read_dir(history_dir) else {
+        return 0;
+    };
+    entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let name = entry.file_name().into_string().ok()?;
+            let stem = name
+                .strip_suffix(".session.json")
+                .or_else(|| name.strip_suffix(".jsonl"))?;
+            stem.strip_prefix("session-")?.parse::<u64>().ok()
+        })
+        .max()
+        .unwrap_or(0)
+}
+
+fn session_record_path(history_dir: &Path, session_id: &str) -> PathBuf {
+    history_dir.join(format!("{session_id}.session.json"))
+}
+
+pub(crate) fn restore_history(
+    state: &mut SessionState,
+    mut log: HistoryLog,
+    loaded: std::io::Result<Vec<HistoryEvent>>,
+) {
+    match loaded {
+        Ok(events) => {
+            log.persisted_through = events.len() as u64;
+            state.history = events;
+            maybe_compact_history(state);
+        }
+        Err(error) => warn!(
+            session_id = %state.session_id,
+            path = %log.path.display(),
+            %error,
+            "failed to restore session history"
+        ),
+    }
+    state.history_log = Some(log);
+}
+
+pub(crate) fn persist_history(state: &mut SessionState) {
+    let retained_start = state.compaction.last_compacted_history_index;
+    let total_count = retained_start + state.history.len() as u64;
+    let Some(log) = state.history_log.as_mut() else {
+        return;
+    };
+    let start = log.persisted_through.max(retained_start);
+    if start >= total_count {
+        return;
+    }
+
+    let pending = &state.history[(start - retained_start) as usize..];
+    match append_history_lines(&log.path, pending) {
+        Ok(()) => log.persisted_through = total_count,
+        Err(error) => warn!(
+            session_id = %state.session_id,
+            path = %log.path.display(),
+            %error,
+            "failed to persist session history"
+        ),
+    }
+}
+
+fn load_history_lines(path: &Path) -> std::io::Result<Vec<HistoryEvent>> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut events = Vec::new();
+    for line in BufReader::new(fs::File::open(path)?).lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        events.push(serde_json::from_str(&line)?);
+    }
+    Ok(events)
+}
+
+fn append_history_lines(path: &Path, events: &[HistoryEvent]) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let mut buffer = Vec::new();
+    for event in events {
+        serde_json::to_writer(&mut buffer, event)?;
+        buffer.push(b'\n');
+    }
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?
+        .write_all(&buffer)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    use super::{
+        HistoryLog, SessionRecord, last_session_seq, persist_history, read_session_record,
+        restore_history, write_session_record,
+    };
+    use crate::history::{append_assistant_output_history, history_page};
+    use crate::session::testing::test_state;
+
+    #[test]
+    fn persisted_history_is_restored_for_a_recreated_session() {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("system time")
+            .as_nanos();
+        let history_dir =
+            std::env::temp_dir().join(format!("fathom-history-persistence-test-{nanos}"));
+
+        let mut state = test_state();
+        let log = HistoryLog::for_session(&history_dir, "session-1");
+        let loaded = log.load();
+        restore_history(&mut state, log, loaded);
+        assert!(state.history.is_empty());
+
+        append_assistant_output_history(&mut state, "first output");
+        persist_history(&mut state);
+        append_assistant_output_history(&mut state, "second output");
+        persist_history(&mut state);
+        persist_history(&mut state);
+
+        let written = std::fs::read_to_string(history_dir.join("session-1.jsonl"))
+            .expect("history file should exist");
+        assert_eq!(written.lines().count(), 2);
+
+        let mut recreated = test_state();
+        let log = HistoryLog::for_session(&history_dir, "session-1");
+        let loaded = log.load();
+        restore_history(&mut recreated, log, loaded);
+        let page = history_page(&recreated, 0, 10);
+        assert_eq!(page.total_count, 2);
+        assert!(page.entries[0].event_json.contains("first output"));
+        assert!(page.entries[1].event_json.contains("second output"));
+
+        append_assistant_output_history(&mut recreated, "third output");
+        persist_history(&mut recreated);
+        let written = std::fs::read_to_string(history_dir.join("session-1.jsonl"))
+            .expect("history file should exist");
+        assert_eq!(written.lines().count(), 3);
+
+        let _ = std::fs::remove_dir_all(history_dir);
+    }
+
+    #[test]
+    fn session_records_round_trip_and_advance_the_session_sequence() {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("system time")
+            .as_nanos();
+        let history_dir = std::env::temp_dir().join(format!("fathom-session-record-test-{nanos}"));
+
+        assert_eq!(last_session_seq(&history_dir), 0);
+        assert!(
+            read_session_record(&history_dir, "session-4")
+                .expect("read missing record")
+                .is_none()
+        );
+
+        let record = SessionRecord::from_state(&test_state());
+        write_session_record(&history_dir, "session-4", &record).expect("write record");
+        std::fs::write(history_dir.join("session-7.jsonl"), "").expect("write history");
+        std::fs::write(history_dir.join("notes.txt"), "").expect("write unrelated file");
+
+        assert_eq!(
+            read_session_record(&history_dir, "session-4").expect("read record"),
+            Some(record)
+        );
+        assert_eq!(last_session_seq(&history_dir), 7);
+
+        let _ = std::fs::remove_dir_all(history_dir);
+    }
+}
@@ -1,11 +1,26 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 pub(crate) const PREVIEW_MAX_BYTES: usize = 512;
 pub(crate) const PREVIEW_MAX_LINES: usize = 8;
 const PREVIEW_HEAD_RATIO_NUM: usize = 3;
 const PREVIEW_HEAD_RATIO_DEN: usize = 5;
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct PreviewLimits {
+    pub(crate) max_bytes: usize,
+    pub(crate) max_lines: usize,
+}
+
+impl Default for PreviewLimits {
+    fn default() -> Self {
+        Self {
+            max_bytes: PREVIEW_MAX_BYTES,
+            max_lines: PREVIEW_MAX_LINES,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub(crate) struct PayloadPreview {
     pub(crate) head: String,
     pub(crate) tail: String,
@@ -18,9 +33,17 @@ pub(crate) struct PayloadPreview {
 }
 
 pub(crate) fn build_payload_preview(payload: &str, lookup_ref: String) -> PayloadPreview {
+    build_bounded_payload_preview(payload, lookup_ref, PreviewLimits::default())
+}
+
+pub(crate) fn build_bounded_payload_preview(
+    payload: &str,
+    lookup_ref: String,
+    limits: PreviewLimits,
+) -> PayloadPreview {
     let full_bytes = payload.len();
 
-    let full_visible_end = head_end_index(payload, PREVIEW_MAX_BYTES, PREVIEW_MAX_LINES);
+    let full_visible_end = head_end_index(payload, limits.max_bytes, limits.max_lines);
     if full_visible_end == full_bytes {
         return PayloadPreview {
             head: payload.to_string(),
@@ -34,15 +57,15 @@ pub(crate) fn build_payload_preview(payload: &str, lookup_ref: String) -> Payloa
         };
     }
 
-    let mut head_budget = PREVIEW_MAX_BYTES * PREVIEW_HEAD_RATIO_NUM / PREVIEW_HEAD_RATIO_DEN;
+    let mut head_budget = limits.max_bytes * PREVIEW_HEAD_RATIO_NUM / PREVIEW_HEAD_RATIO_DEN;
     if head_budget == 0 {
-        head_budget = PREVIEW_MAX_BYTES;
+        head_budget = limits.max_bytes;
     }
-    let head_end = head_end_index(payload, head_budget, PREVIEW_MAX_LINES);
+    let head_end = head_end_index(payload, head_budget, limits.max_lines);
     let head = payload[..head_end].to_string();
     let head_bytes = head.len();
 
-    let tail_budget = PREVIEW_MAX_BYTES.saturating_sub(head_bytes);
+    let tail_budget = limits.max_bytes.saturating_sub(head_bytes);
     let tail_start_target = full_bytes.saturating_sub(tail_budget);
     let mut tail_start = tail_start_target.max(head_bytes);
     while tail_start < full_bytes && !payload.is_char_boundary(tail_start) {
@@ -95,7 +118,9 @@ fn head_end_index(payload: &str, max_bytes: usize, max_lines: usize) -> usize {
 
 #[cfg(test)]
 mod tests {
-    use super::{PREVIEW_MAX_BYTES, build_payload_preview};
+    use super::{
+        PREVIEW_MAX_BYTES, PreviewLimits, build_bounded_payload_preview, build_payload_preview,
+    };
 
     #[test]
     fn preview_truncates_large_payload() {
@@ -108,6 +133,23 @@ mod tests {
         assert!(!preview.tail.is_empty());
     }
 
+    #[test]
+    fn bounded_preview_honors_configured_limits() {
+        let payload = "b".repeat(PREVIEW_MAX_BYTES);
+        let preview = build_bounded_payload_preview(
+            &payload,
+            "execution://execution-1/result".to_string(),
+            PreviewLimits {
+                max_bytes: 64,
+                max_lines: 8,
+            },
+        );
+
+        assert!(preview.truncated);
+        assert!(preview.head_bytes + preview.tail_bytes <= 64);
+        assert_eq!(preview.omitted_bytes, PREVIEW_MAX_BYTES - 64);
+    }
+
     #[test]
     fn preview_keeps_small_payload() {
         let payload = "{\"ok\":true}";
@@ -1,8 +1,8 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use crate::history::preview::PayloadPreview;
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub(crate) enum HistoryActorKind {
     User,
@@ -11,7 +11,7 @@ pub(crate) enum HistoryActorKind {
     Execution,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub(crate) struct HistoryEvent {
     pub(crate) ts_unix_ms: i64,
     pub(crate) actor_kind: HistoryActorKind,
@@ -21,7 +21,7 @@ pub(crate) struct HistoryEvent {
     pub(crate) kind: HistoryEventKind,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "event", content = "payload", rename_all = "snake_case")]
 pub(crate) enum HistoryEventKind {
     #[serde(rename = "trigger_unknown")]
@@ -44,6 +44,8 @@ pub(crate) enum HistoryEventKind {
     TriggerHeartbeat,
     #[serde(rename = "trigger_cron")]
     TriggerCron(CronHistoryPayload),
+    #[serde(rename = "trigger_external_event")]
+    TriggerExternalEvent(ExternalEventHistoryPayload),
     #[serde(rename = "trigger_refresh_profile")]
     TriggerRefreshProfile(RefreshProfileHistoryPayload),
     #[serde(rename = "assistant_output")]
@@ -63,6 +65,7 @@ impl HistoryEventKind {
             Self::ExecutionRejected(_) => "execution_rejected",
             Self::TriggerHeartbeat => "heartbeat",
             Self::TriggerCron(_) => "cron",
+            Self::TriggerExternalEvent(_) => "external_event",
             Self::TriggerRefreshProfile(_) => "refresh_profile",
             Self::AssistantOutput(_) => "assistant_output",
         }
@@ -88,28 +91,33 @@ impl HistoryEventKind {
     }
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub(crate) struct UserMessageHistoryPayload {
     pub(crate) text: String,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub(crate) struct CronHistoryPayload {
     pub(crate) key: String,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct ExternalEventHistoryPayload {
+    pub(crate) payload_json: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub(crate) struct RefreshProfileHistoryPayload {
     pub(crate) scope: String,
     pub(crate) user_id: String,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub(crate) struct AssistantOutputHistoryPayload {
     pub(crate) content: String,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub(crate) struct ExecutionRequestedHistoryPayload {
     pub(crate) canonical_action_id: String,
     pub(crate) capability_domain_id: String,
@@ -120,13 +128,13 @@ pub(crate) struct ExecutionRequestedHistoryPayload {
     pub(crate) lookup_action: String,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub(crate) struct ExecutionSucceededHistoryPayload {
     pub(crate) canonical_action_id: String,
     pub(crate) payload_preview: PayloadPreview,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub(crate) struct ExecutionFailedHistoryPayload {
     pub(crate) canonical_action_id: String,
     pub(crate) message: String,
@@ -134,17 +142,17 @@ pub(crate) struct ExecutionFailedHistoryPayload {
     pub(crate) payload_preview: Option<PayloadPreview>,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub(crate) struct ExecutionBackgroundedHistoryPayload {
     pub(crate) canonical_action_id: String,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub(crate) struct ExecutionCanceledHistoryPayload {
     pub(crate) canonical_action_id: String,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub(crate) struct ExecutionRejectedHistoryPayload {
     pub(crate) canonical_action_id: String,
     pub(crate) message: String,
@@ -1,13 +1,15 @@
 use fathom_capability_domain::parse_action_id;
 
 use crate::history::EXECUTION_INPUT_LOOKUP_ACTION;
-use crate::history::preview::build_payload_preview;
+use crate::history::preview::{
+    PreviewLimits, build_bounded_payload_preview, build_payload_preview,
+};
 use crate::history::schema::{
     AssistantOutputHistoryPayload, CronHistoryPayload, ExecutionBackgroundedHistoryPayload,
     ExecutionCanceledHistoryPayload, ExecutionFailedHistoryPayload,
     ExecutionRejectedHistoryPayload, ExecutionRequestedHistoryPayload,
-    ExecutionSucceededHistoryPayload, HistoryActorKind, HistoryEvent, HistoryEventKind,
-    RefreshProfileHistoryPayload, UserMessageHistoryPayload,
+    ExecutionSucceededHistoryPayload, ExternalEventHistoryPayload, HistoryActorKind, HistoryEvent,
+    HistoryEventKind, RefreshProfileHistoryPayload, UserMessageHistoryPayload,
 };
 use crate::session::state::SessionState;
 use fathom_protocol::pb;
@@ -39,7 +41,7 @@ pub(crate) fn trigger_line(state: &SessionState, trigger: &pb::Trigger) -> Histo
             actor_kind: HistoryActorKind::Execution,
             actor_id: update.execution_id.clone(),
             profile_ref: active_agent_profile_ref(state),
-            kind: execution_update_history_kind(update),
+            kind: execution_update_history_kind(update, state.result_preview_limits),
         },
         pb::trigger::Kind::Heartbeat(_) => HistoryEvent {
             ts_unix_ms: trigger.created_at_unix_ms,
@@ -57,6 +59,15 @@ pub(crate) fn trigger_line(state: &SessionState, trigger: &pb::Trigger) -> Histo
                 key: cron.key.clone(),
             }),
         },
+        pb::trigger::Kind::ExternalEvent(external) => HistoryEvent {
+            ts_unix_ms: trigger.created_at_unix_ms,
+            actor_kind: HistoryActorKind::System,
+            actor_id: external.source.clone(),
+            profile_ref: active_agent_profile_ref(state),
+            kind: HistoryEventKind::TriggerExternalEvent(ExternalEventHistoryPayload {
+                payload_json: external.payload_json.clone(),
+            }),
+        },
         pb::trigger::Kind::RefreshProfile(refresh) => {
             let scope = pb::RefreshScope::try_from(refresh.scope)
                 .map(refresh_scope_label)
@@ -132,13 +143,17 @@ fn background_requested_from_args_json(args_json: &str) -> bool {
         .unwrap_or(false)
 }
 
-fn execution_update_history_kind(update: &pb::ExecutionUpdateTrigger) -> HistoryEventKind {
+fn execution_update_history_kind(
+    update: &pb::ExecutionUpdateTrigger,
+    preview_limits: PreviewLimits,
+) -> HistoryEventKind {
     let payload_preview = if update.payload_message.trim().is_empty() {
         None
     } else {
-        Some(build_payload_preview(
+        Some(build_bounded_payload_preview(
             &update.payload_message,
             format!("execution://{}/result", update.execution_id),
+            preview_limits,
         ))
     };
     let kind = pb::ExecutionUpdateKind::try_from(update.kind)
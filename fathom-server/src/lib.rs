@@ -1,38 +1,155 @@
+use std::future::Future;
 use std::net::SocketAddr;
 use std::path::PathBuf;
+use std::time::Duration;
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use fathom_protocol::pb::runtime_service_server::RuntimeServiceServer;
 use tonic::transport::Server;
 use tracing::info;
 
 mod agent;
 mod capability_domain;
+mod cron;
 mod history;
 mod profile_material;
 mod runtime;
 mod service;
 mod session;
+mod tls;
 mod util;
 pub use service::FathomRuntimeService;
 
+#[derive(Debug, Clone, Default)]
+pub struct ServerOptions {
+    pub workspace_root: Option<PathBuf>,
+    pub tls_cert: Option<PathBuf>,
+    pub tls_key: Option<PathBuf>,
+    pub system_preamble_path: Option<PathBuf>,
+    pub history_dir: Option<PathBuf>,
+    pub profile_dir: Option<PathBuf>,
+    pub blocked_write_extensions: Vec<String>,
+    pub protected_paths: Vec<PathBuf>,
+    pub list_max_entries: Option<usize>,
+    pub session_command_capacity: Option<usize>,
+    pub execution_capacity: Option<usize>,
+    pub action_timeout_ms: Option<u64>,
+    pub turn_timeout_secs: Option<u64>,
+    pub queued_action_message_template: Option<String>,
+    pub admin_rpc_enabled: bool,
+    pub required_agent_profile_fields: Vec<String>,
+    pub max_prompt_participants: Option<usize>,
+    pub max_participant_profile_chars: Option<usize>,
+    pub prompt_result_preview_bytes: Option<usize>,
+    pub prompt_result_preview_lines: Option<usize>,
+    pub history_compaction_live_events: Option<usize>,
+    pub history_compaction_batch_events: Option<usize>,
+    pub max_concurrent_model_requests: Option<usize>,
+    pub max_execution_result_bytes: Option<usize>,
+    pub execution_update_grace_ms: u64,
+    pub allow_agent_only_sessions: bool,
+    pub disable_thinking_events: bool,
+    pub skip_idle_heartbeat_turns: bool,
+    pub max_assistant_outputs_per_turn: Option<usize>,
+    pub max_heartbeat_delay_ms: Option<u64>,
+    pub queue_depth_warning_threshold: Option<u64>,
+    pub action_allowlist: Vec<String>,
+}
+
 pub async fn serve(addr: SocketAddr) -> Result<()> {
-    serve_with_workspace_root(addr, None).await
+    serve_with_options(addr, ServerOptions::default()).await
 }
 
 pub async fn serve_with_workspace_root(
     addr: SocketAddr,
     workspace_root: Option<PathBuf>,
 ) -> Result<()> {
-    info!(%addr, "starting grpc server");
-    let service = match workspace_root {
-        Some(workspace_root) => FathomRuntimeService::with_workspace_root(workspace_root)?,
-        None => FathomRuntimeService::default(),
-    };
+    serve_with_options(
+        addr,
+        ServerOptions {
+            workspace_root,
+            ..ServerOptions::default()
+        },
+    )
+    .await
+}
+
+pub async fn serve_with_options(addr: SocketAddr, options: ServerOptions) -> Result<()> {
+    serve_with_shutdown(addr, options, async {
+        let _ = tokio::signal::ctrl_c().await;
+    })
+    .await
+}
+
+pub async fn serve_with_shutdown<F>(
+    addr: SocketAddr,
+    options: ServerOptions,
+    shutdown: F,
+) -> Result<()>
+where
+    F: Future<Output = ()> + Send,
+{
+    let tls_config =
+        tls::load_server_tls_config(options.tls_cert.as_deref(), options.tls_key.as_deref())?;
+    info!(%addr, tls = tls_config.is_some(), "starting grpc server");
+    let service = match (options.workspace_root, options.profile_dir) {
+        (None, None) => FathomRuntimeService::default(),
+        (workspace_root, profile_dir) => FathomRuntimeService::with_workspace_root(
+            workspace_root.unwrap_or_else(runtime::default_workspace_root),
+            profile_dir,
+        )?,
+    }
+    .with_system_preamble_path(options.system_preamble_path)
+    .with_history_dir(options.history_dir)
+    .with_blocked_write_extensions(options.blocked_write_extensions)
+    .with_protected_paths(options.protected_paths)
+    .with_list_max_entries(options.list_max_entries)
+    .with_session_command_capacity(options.session_command_capacity)
+    .with_execution_capacity(options.execution_capacity)?
+    .with_action_timeout_ms(options.action_timeout_ms)?
+    .with_turn_timeout_secs(options.turn_timeout_secs)?
+    .with_queued_action_message_template(options.queued_action_message_template)
+    .with_admin_rpc_enabled(options.admin_rpc_enabled)
+    .with_required_agent_profile_fields(options.required_agent_profile_fields)
+    .with_participant_prompt_limits(
+        options.max_prompt_participants,
+        options.max_participant_profile_chars,
+    )
+    .with_result_preview_limits(
+        options.prompt_result_preview_bytes,
+        options.prompt_result_preview_lines,
+    )
+    .with_history_compaction_limits(
+        options.history_compaction_live_events,
+        options.history_compaction_batch_events,
+    )
+    .with_max_execution_result_bytes(options.max_execution_result_bytes)
+    .with_max_concurrent_model_requests(options.max_concurrent_model_requests)?
+    .with_execution_update_grace(Duration::from_millis(options.execution_update_grace_ms))
+    .with_agent_only_sessions_allowed(options.allow_agent_only_sessions)
+    .with_thinking_events_enabled(!options.disable_thinking_events)
+    .with_idle_heartbeat_turns_skipped(options.skip_idle_heartbeat_turns)
+    .with_max_assistant_outputs_per_turn(options.max_assistant_outputs_per_turn)
+    .with_max_heartbeat_delay_ms(options.max_heartbeat_delay_ms)
+    .with_queue_depth_warning_threshold(options.queue_depth_warning_threshold)
+    .with_action_allowlist(options.action_allowlist)?;
 
-    Server::builder()
+    let mut server = Server::builder();
+    if let Some(tls_config) = tls_config {
+        server = server
+            .tls_config(tls_config)
+            .context("invalid TLS certificate or private key")?;
+    }
+    let shutdown_service = service.clone();
+    server
         .add_service(RuntimeServiceServer::new(service))
-        .serve(addr)
+        .serve_with_shutdown(addr, async move {
+            shutdown.await;
+            let expired = shutdown_service
+                .cancel_all_sessions("server shutting down")
+                .await;
+            info!(expired, "expired live sessions before shutdown");
+        })
         .await?;
 
     Ok(())
@@ -43,7 +43,78 @@ pub(crate) fn agent_identity_material(profile: &pb::AgentProfile) -> Value {
     Value::Object(material)
 }
 
-pub(crate) fn participant_profile_material(profile: &pb::UserProfile) -> Value {
+pub(crate) fn missing_required_agent_fields(
+    profile: &pb::AgentProfile,
+    required_fields: &[String],
+) -> Vec<String> {
+    let material = agent_identity_material(profile);
+    required_fields
+        .iter()
+        .filter(|field| !has_non_empty_field(&material, field))
+        .cloned()
+        .collect()
+}
+
+fn has_non_empty_field(material: &Value, field: &str) -> bool {
+    let value = field
+        .split('.')
+        .try_fold(material, |value, segment| value.get(segment));
+    match value {
+        None | Some(Value::Null) => false,
+        Some(Value::String(text)) => !text.trim().is_empty(),
+        Some(Value::Array(items)) => !items.is_empty(),
+        Some(Value::Object(entries)) => !entries.is_empty(),
+        Some(_) => true,
+    }
+}
+
+pub(crate) const DEFAULT_MAX_PROMPT_PARTICIPANTS: usize = 8;
+pub(crate) const DEFAULT_MAX_PARTICIPANT_PROFILE_CHARS: usize = 4_000;
+const PARTICIPANT_IDENTITY_KEYS: [&str; 3] = ["user_id", "name", "nickname"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct ParticipantPromptLimits {
+    pub(crate) max_participants: usize,
+    pub(crate) max_profile_chars: usize,
+}
+
+impl Default for ParticipantPromptLimits {
+    fn default() -> Self {
+        Self {
+            max_participants: DEFAULT_MAX_PROMPT_PARTICIPANTS,
+            max_profile_chars: DEFAULT_MAX_PARTICIPANT_PROFILE_CHARS,
+        }
+    }
+}
+
+pub(crate) fn bounded_participant_profile_material(
+    profile: &pb::UserProfile,
+    max_chars: usize,
+) -> Value {
+    let mut material = participant_profile_map(profile);
+    if Value::Object(material.clone()).to_string().chars().count() <= max_chars {
+        return Value::Object(material);
+    }
+
+    let mut bounded = Map::new();
+    for key in PARTICIPANT_IDENTITY_KEYS {
+        if let Some(value) = material.remove(key) {
+            bounded.insert(key.to_string(), value);
+        }
+    }
+    let remainder = Value::Object(material).to_string();
+    let remainder_chars = remainder.chars().count();
+    let excerpt = remainder.chars().take(max_chars).collect::<String>();
+    bounded.insert("material_truncated".to_string(), Value::Bool(true));
+    bounded.insert(
+        "material_omitted_chars".to_string(),
+        json!(remainder_chars.saturating_sub(max_chars)),
+    );
+    bounded.insert("material_excerpt".to_string(), Value::String(excerpt));
+    Value::Object(bounded)
+}
+
+fn participant_profile_map(profile: &pb::UserProfile) -> Map<String, Value> {
     let mut material = parse_material_object(&profile.material_json);
     material
         .entry("user_id".to_string())
@@ -54,7 +125,91 @@ pub(crate) fn participant_profile_material(profile: &pb::UserProfile) -> Value {
     material
         .entry("nickname".to_string())
         .or_insert_with(|| Value::String(profile.nickname.clone()));
-    Value::Object(material)
+    material
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ProfileMemoryTarget {
+    Agent,
+    User,
+}
+
+impl ProfileMemoryTarget {
+    pub(crate) fn parse(raw: &str) -> Option<Self> {
+        match raw.trim() {
+            "agent" => Some(Self::Agent),
+            "user" => Some(Self::User),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            Self::Agent => "agent",
+            Self::User => "user",
+        }
+    }
+}
+
+pub(crate) fn clear_long_term_memory(material_json: &str) -> Result<(String, usize), String> {
+    let mut material = parse_material_for_update(material_json)?;
+    let memory = memory_object(&mut material)?;
+    let bytes_removed = match memory.insert("long_term".to_string(), Value::String(String::new())) {
+        Some(Value::String(previous)) => previous.len(),
+        Some(previous) => previous.to_string().len(),
+        None => 0,
+    };
+    Ok((Value::Object(material).to_string(), bytes_removed))
+}
+
+pub(crate) fn append_long_term_memory(
+    material_json: &str,
+    note: &str,
+    timestamp_unix_ms: i64,
+) -> Result<(String, usize), String> {
+    let mut material = parse_material_for_update(material_json)?;
+    let memory = memory_object(&mut material)?;
+    let mut long_term = match memory.remove("long_term") {
+        Some(Value::String(previous)) => previous,
+        Some(Value::Null) | None => String::new(),
+        Some(_) => return Err("material_json.memory.long_term must be a string".to_string()),
+    };
+
+    let timestamp = chrono::DateTime::from_timestamp_millis(timestamp_unix_ms)
+        .map(|time| time.to_rfc3339_opts(chrono::SecondsFormat::Secs, true))
+        .unwrap_or_else(|| timestamp_unix_ms.to_string());
+    let mut entry = String::new();
+    if !long_term.is_empty() && !long_term.ends_with('\n') {
+        entry.push('\n');
+    }
+    let note = note
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ");
+    entry.push_str(&format!("- [{timestamp}] {note}\n"));
+    long_term.push_str(&entry);
+    memory.insert("long_term".to_string(), Value::String(long_term));
+    Ok((Value::Object(material).to_string(), entry.len()))
+}
+
+fn parse_material_for_update(material_json: &str) -> Result<Map<String, Value>, String> {
+    match serde_json::from_str::<Value>(material_json) {
+        Ok(Value::Object(map)) => Ok(map),
+        Ok(_) => Err("material_json must be a JSON object".to_string()),
+        Err(error) => Err(format!("material_json must be valid JSON: {error}")),
+    }
+}
+
+fn memory_object(material: &mut Map<String, Value>) -> Result<&mut Map<String, Value>, String> {
+    match material
+        .entry("memory".to_string())
+        .or_insert_with(|| json!({}))
+    {
+        Value::Object(memory) => Ok(memory),
+        _ => Err("material_json.memory must be a JSON object".to_string()),
+    }
 }
 
 pub(crate) fn validate_material_json_object(material_json: &str) -> Result<(), String> {
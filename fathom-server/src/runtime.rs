@@ -1,29 +1,47 @@
+mod actions;
 mod diagnostics;
 mod ids;
 mod invocation_context;
+mod limits;
+mod persistence;
+mod preamble;
 mod profiles;
 mod session_setup;
 mod sessions;
 mod system_inspection;
+#[cfg(test)]
+pub(crate) mod testing;
 mod workspace;
 
 use std::collections::HashMap;
 use std::path::PathBuf;
 use std::sync::Arc;
-use std::sync::atomic::AtomicU64;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::time::Duration;
 
 use tokio::sync::RwLock;
 
 use crate::agent::AgentOrchestrator;
 use crate::capability_domain::{CapabilityDomainRegistry, build_capability_domain_registry};
+use crate::cron::CronScheduler;
+use crate::history::{self, HistoryCompactionLimits, PreviewLimits};
+use crate::profile_material::ParticipantPromptLimits;
 use crate::session::SessionRuntime;
+use crate::session::action_messages::QueuedActionMessageFormat;
 use diagnostics::DiagnosticsSink;
+use fathom_capability_domain_fs::{ListMaxEntries, ProtectedPaths, WriteExtensionDenylist};
 use fathom_protocol::pb;
 use system_inspection::RuntimeSystemInspectionService;
 
+pub(crate) use workspace::default_workspace_root;
+
 pub(crate) const EVENT_BUFFER_SIZE: usize = 256;
 pub(crate) const SESSION_CMD_BUFFER_SIZE: usize = 128;
 pub(crate) const DEFAULT_EXECUTION_CAPACITY: usize = 4;
+pub(crate) const DEFAULT_FOREGROUND_WAIT_BUDGET_MS: u64 = 10_000;
+pub(crate) const DEFAULT_ACTION_TIMEOUT_MS: u64 = 30_000;
+pub(crate) const DEFAULT_MAX_HEARTBEAT_DELAY_MS: u64 = 24 * 60 * 60 * 1000;
+pub(crate) const DEFAULT_QUEUE_DEPTH_WARNING_THRESHOLD: u64 = 32;
 
 #[derive(Clone)]
 pub(crate) struct Runtime {
@@ -39,59 +57,156 @@ struct RuntimeInner {
     execution_seq: AtomicU64,
     execution_submission_seq: AtomicU64,
     capability_domain_registry: CapabilityDomainRegistry,
-    orchestrator: AgentOrchestrator,
+    orchestrator: std::sync::RwLock<AgentOrchestrator>,
     diagnostics: DiagnosticsSink,
+    system_preamble_path: std::sync::RwLock<Option<PathBuf>>,
+    system_preamble_cache: std::sync::Mutex<Option<preamble::CachedPreamble>>,
+    history_dir: std::sync::RwLock<Option<PathBuf>>,
+    profile_dir: Option<PathBuf>,
+    write_extension_denylist: WriteExtensionDenylist,
+    protected_paths: ProtectedPaths,
+    list_max_entries: ListMaxEntries,
+    configured_protected_paths: std::sync::RwLock<Vec<PathBuf>>,
+    diagnostics_dir: PathBuf,
+    session_command_capacity: AtomicUsize,
+    execution_capacity: AtomicUsize,
+    foreground_wait_budget_ms: AtomicU64,
+    action_timeout_ms: AtomicU64,
+    turn_timeout_ms: AtomicU64,
+    execution_update_grace_ms: AtomicU64,
+    allow_agent_only_sessions: AtomicBool,
+    thinking_events_enabled: AtomicBool,
+    skip_idle_heartbeat_turns: AtomicBool,
+    max_assistant_outputs_per_turn: AtomicUsize,
+    max_heartbeat_delay_ms: AtomicU64,
+    queue_depth_warning_threshold: AtomicU64,
+    queued_action_message_format: std::sync::RwLock<QueuedActionMessageFormat>,
+    participant_prompt_limits: std::sync::RwLock<ParticipantPromptLimits>,
+    required_agent_profile_fields: std::sync::RwLock<Vec<String>>,
+    result_preview_limits: std::sync::RwLock<PreviewLimits>,
+    history_compaction_limits: std::sync::RwLock<HistoryCompactionLimits>,
+    max_execution_result_bytes: AtomicUsize,
+    cron_scheduler: CronScheduler,
 }
 
 impl Runtime {
     pub(crate) fn new(execution_capacity: usize, _execution_runtime_ms: u64) -> Self {
-        let workspace_root = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
-        Self::new_with_workspace_root(execution_capacity, _execution_runtime_ms, workspace_root)
-            .unwrap_or_else(|_| {
-                Self::new_unchecked(
-                    execution_capacity,
-                    _execution_runtime_ms,
-                    PathBuf::from("."),
-                )
-            })
+        let workspace_root = workspace::default_workspace_root();
+        Self::new_with_workspace_root(
+            execution_capacity,
+            _execution_runtime_ms,
+            workspace_root,
+            None,
+        )
+        .unwrap_or_else(|_| {
+            Self::new_unchecked(
+                execution_capacity,
+                _execution_runtime_ms,
+                PathBuf::from("."),
+                None,
+                HashMap::new(),
+                HashMap::new(),
+            )
+        })
     }
 
     pub(crate) fn new_with_workspace_root(
         execution_capacity: usize,
         _execution_runtime_ms: u64,
         workspace_root: PathBuf,
+        profile_dir: Option<PathBuf>,
     ) -> anyhow::Result<Self> {
         let workspace_root = workspace::canonicalize_workspace_root(workspace_root)?;
+        let (user_profiles, agent_profiles) = match profile_dir.as_deref() {
+            Some(profile_dir) => (
+                persistence::load_user_profiles(profile_dir)?,
+                persistence::load_agent_profiles(profile_dir)?,
+            ),
+            None => (HashMap::new(), HashMap::new()),
+        };
         Ok(Self::new_unchecked(
             execution_capacity,
             _execution_runtime_ms,
             workspace_root,
+            profile_dir,
+            user_profiles,
+            agent_profiles,
         ))
     }
 
     fn new_unchecked(
-        _execution_capacity: usize,
+        execution_capacity: usize,
         _execution_runtime_ms: u64,
         workspace_root: PathBuf,
+        profile_dir: Option<PathBuf>,
+        user_profiles: HashMap<String, pb::UserProfile>,
+        agent_profiles: HashMap<String, pb::AgentProfile>,
     ) -> Self {
-        let diagnostics = DiagnosticsSink::new(workspace_root.join(".fathom").join("diagnostics"));
+        let diagnostics_dir = workspace_root.join(".fathom").join("diagnostics");
+        let diagnostics = DiagnosticsSink::new(diagnostics_dir.clone());
+        let write_extension_denylist = WriteExtensionDenylist::default();
+        let protected_paths = ProtectedPaths::new(
+            std::iter::once(diagnostics_dir.clone()).chain(profile_dir.clone()),
+        );
+        let list_max_entries = ListMaxEntries::default();
         Self {
             inner: Arc::new_cyclic(|weak_inner| {
                 let capability_domain_registry = build_capability_domain_registry(
                     &workspace_root,
                     Arc::new(RuntimeSystemInspectionService::new(weak_inner.clone())),
+                    write_extension_denylist.clone(),
+                    protected_paths.clone(),
+                    list_max_entries.clone(),
                 );
                 RuntimeInner {
                     sessions: RwLock::new(HashMap::new()),
-                    user_profiles: RwLock::new(HashMap::new()),
-                    agent_profiles: RwLock::new(HashMap::new()),
+                    user_profiles: RwLock::new(user_profiles),
+                    agent_profiles: RwLock::new(agent_profiles),
                     session_seq: AtomicU64::new(0),
                     trigger_seq: AtomicU64::new(0),
                     execution_seq: AtomicU64::new(0),
                     execution_submission_seq: AtomicU64::new(0),
                     capability_domain_registry: capability_domain_registry.clone(),
-                    orchestrator: AgentOrchestrator::new(capability_domain_registry),
+                    orchestrator: std::sync::RwLock::new(AgentOrchestrator::new(
+                        capability_domain_registry,
+                    )),
                     diagnostics: diagnostics.clone(),
+                    system_preamble_path: std::sync::RwLock::new(None),
+                    system_preamble_cache: std::sync::Mutex::new(None),
+                    history_dir: std::sync::RwLock::new(None),
+                    profile_dir,
+                    write_extension_denylist,
+                    protected_paths,
+                    list_max_entries,
+                    configured_protected_paths: std::sync::RwLock::new(Vec::new()),
+                    diagnostics_dir,
+                    session_command_capacity: AtomicUsize::new(SESSION_CMD_BUFFER_SIZE),
+                    execution_capacity: AtomicUsize::new(execution_capacity.max(1)),
+                    foreground_wait_budget_ms: AtomicU64::new(DEFAULT_FOREGROUND_WAIT_BUDGET_MS),
+                    action_timeout_ms: AtomicU64::new(DEFAULT_ACTION_TIMEOUT_MS),
+                    turn_timeout_ms: AtomicU64::new(0),
+                    execution_update_grace_ms: AtomicU64::new(0),
+                    allow_agent_only_sessions: AtomicBool::new(false),
+                    thinking_events_enabled: AtomicBool::new(true),
+                    skip_idle_heartbeat_turns: AtomicBool::new(false),
+                    max_assistant_outputs_per_turn: AtomicUsize::new(0),
+                    max_heartbeat_delay_ms: AtomicU64::new(DEFAULT_MAX_HEARTBEAT_DELAY_MS),
+                    queue_depth_warning_threshold: AtomicU64::new(
+                        DEFAULT_QUEUE_DEPTH_WARNING_THRESHOLD,
+                    ),
+                    queued_action_message_format: std::sync::RwLock::new(
+                        QueuedActionMessageFormat::default(),
+                    ),
+                    required_agent_profile_fields: std::sync::RwLock::new(Vec::new()),
+                    participant_prompt_limits: std::sync::RwLock::new(
+                        ParticipantPromptLimits::default(),
+                    ),
+                    result_preview_limits: std::sync::RwLock::new(PreviewLimits::default()),
+                    history_compaction_limits: std::sync::RwLock::new(
+                        HistoryCompactionLimits::default(),
+                    ),
+                    max_execution_result_bytes: AtomicUsize::new(0),
+                    cron_scheduler: CronScheduler::default(),
                 }
             }),
         }
@@ -102,12 +217,328 @@ impl Runtime {
     }
 
     pub(crate) fn agent_orchestrator(&self) -> AgentOrchestrator {
-        self.inner.orchestrator.clone()
+        self.inner
+            .orchestrator
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clone()
+    }
+
+    #[cfg(test)]
+    pub(crate) fn set_agent_orchestrator(&self, orchestrator: AgentOrchestrator) {
+        *self
+            .inner
+            .orchestrator
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner()) = orchestrator;
+    }
+
+    pub(crate) fn set_action_allowlist(&self, action_ids: Vec<String>) -> Result<(), String> {
+        let mut slot = self
+            .inner
+            .orchestrator
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        *slot = slot.clone().with_action_allowlist(action_ids)?;
+        Ok(())
+    }
+
+    pub(crate) fn set_max_concurrent_model_requests(&self, limit: usize) -> Result<(), String> {
+        let mut slot = self
+            .inner
+            .orchestrator
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        *slot = slot.clone().with_max_concurrent_model_requests(limit)?;
+        Ok(())
     }
 
     pub(crate) fn diagnostics(&self) -> DiagnosticsSink {
         self.inner.diagnostics.clone()
     }
+
+    pub(crate) fn set_history_dir(&self, history_dir: Option<PathBuf>) {
+        let mut slot = self
+            .inner
+            .history_dir
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Some(history_dir) = history_dir.as_deref() {
+            self.inner
+                .session_seq
+                .fetch_max(history::last_session_seq(history_dir), Ordering::Relaxed);
+        }
+        *slot = history_dir;
+        drop(slot);
+        self.sync_protected_paths();
+    }
+
+    pub(crate) fn history_dir(&self) -> Option<PathBuf> {
+        self.inner
+            .history_dir
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clone()
+    }
+
+    pub(crate) fn set_protected_paths(&self, paths: Vec<PathBuf>) {
+        let mut slot = self
+            .inner
+            .configured_protected_paths
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        *slot = paths;
+        drop(slot);
+        self.sync_protected_paths();
+    }
+
+    fn sync_protected_paths(&self) {
+        let mut paths = self
+            .inner
+            .configured_protected_paths
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clone();
+        paths.push(self.inner.diagnostics_dir.clone());
+        paths.extend(self.history_dir());
+        paths.extend(self.inner.profile_dir.clone());
+        self.inner.protected_paths.replace(paths);
+    }
+
+    pub(crate) fn set_blocked_write_extensions(&self, extensions: Vec<String>) {
+        self.inner.write_extension_denylist.replace(extensions);
+    }
+
+    pub(crate) fn set_list_max_entries(&self, max_entries: usize) {
+        self.inner.list_max_entries.set(max_entries);
+    }
+
+    pub(crate) fn set_queued_action_message_template(&self, template: Option<String>) {
+        let mut slot = self
+            .inner
+            .queued_action_message_format
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        *slot = QueuedActionMessageFormat::from_template(template);
+    }
+
+    pub(crate) fn queued_action_message_format(&self) -> QueuedActionMessageFormat {
+        self.inner
+            .queued_action_message_format
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clone()
+    }
+
+    pub(crate) fn set_required_agent_profile_fields(&self, fields: Vec<String>) {
+        let mut slot = self
+            .inner
+            .required_agent_profile_fields
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        *slot = fields
+            .into_iter()
+            .map(|field| field.trim().to_string())
+            .filter(|field| !field.is_empty())
+            .collect();
+    }
+
+    pub(crate) fn required_agent_profile_fields(&self) -> Vec<String> {
+        self.inner
+            .required_agent_profile_fields
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clone()
+    }
+
+    pub(crate) fn set_participant_prompt_limits(
+        &self,
+        max_participants: Option<usize>,
+        max_profile_chars: Option<usize>,
+    ) {
+        let mut slot = self
+            .inner
+            .participant_prompt_limits
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Some(max_participants) = max_participants {
+            slot.max_participants = max_participants.max(1);
+        }
+        if let Some(max_profile_chars) = max_profile_chars {
+            slot.max_profile_chars = max_profile_chars.max(1);
+        }
+    }
+
+    pub(crate) fn participant_prompt_limits(&self) -> ParticipantPromptLimits {
+        *self
+            .inner
+            .participant_prompt_limits
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    pub(crate) fn set_result_preview_limits(
+        &self,
+        max_bytes: Option<usize>,
+        max_lines: Option<usize>,
+    ) {
+        let mut slot = self
+            .inner
+            .result_preview_limits
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Some(max_bytes) = max_bytes {
+            slot.max_bytes = max_bytes.max(1);
+        }
+        if let Some(max_lines) = max_lines {
+            slot.max_lines = max_lines.max(1);
+        }
+    }
+
+    pub(crate) fn result_preview_limits(&self) -> PreviewLimits {
+        *self
+            .inner
+            .result_preview_limits
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    pub(crate) fn set_history_compaction_limits(
+        &self,
+        min_live_events: Option<usize>,
+        batch_events: Option<usize>,
+    ) {
+        let mut slot = self
+            .inner
+            .history_compaction_limits
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Some(min_live_events) = min_live_events {
+            slot.min_live_events = min_live_events.max(1);
+        }
+        if let Some(batch_events) = batch_events {
+            slot.batch_events = batch_events.max(1);
+        }
+    }
+
+    pub(crate) fn history_compaction_limits(&self) -> HistoryCompactionLimits {
+        *self
+            .inner
+            .history_compaction_limits
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    pub(crate) fn set_max_execution_result_bytes(&self, max_bytes: usize) {
+        self.inner
+            .max_execution_result_bytes
+            .store(max_bytes.max(1), Ordering::Relaxed);
+    }
+
+    pub(crate) fn max_execution_result_bytes(&self) -> Option<usize> {
+        match self
+            .inner
+            .max_execution_result_bytes
+            .load(Ordering::Relaxed)
+        {
+            0 => None,
+            max_bytes => Some(max_bytes),
+        }
+    }
+
+    pub(crate) fn set_session_command_capacity(&self, capacity: usize) {
+        self.inner
+            .session_command_capacity
+            .store(capacity.max(1), Ordering::Relaxed);
+    }
+
+    pub(crate) fn session_command_capacity(&self) -> usize {
+        self.inner.session_command_capacity.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn set_execution_update_grace(&self, grace: Duration) {
+        self.inner
+            .execution_update_grace_ms
+            .store(grace.as_millis() as u64, Ordering::Relaxed);
+    }
+
+    pub(crate) fn execution_update_grace(&self) -> Duration {
+        Duration::from_millis(self.inner.execution_update_grace_ms.load(Ordering::Relaxed))
+    }
+
+    pub(crate) fn set_agent_only_sessions_allowed(&self, allowed: bool) {
+        self.inner
+            .allow_agent_only_sessions
+            .store(allowed, Ordering::Relaxed);
+    }
+
+    pub(crate) fn agent_only_sessions_allowed(&self) -> bool {
+        self.inner.allow_agent_only_sessions.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn set_thinking_events_enabled(&self, enabled: bool) {
+        self.inner
+            .thinking_events_enabled
+            .store(enabled, Ordering::Relaxed);
+    }
+
+    pub(crate) fn thinking_events_enabled(&self) -> bool {
+        self.inner.thinking_events_enabled.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn set_idle_heartbeat_turns_skipped(&self, skipped: bool) {
+        self.inner
+            .skip_idle_heartbeat_turns
+            .store(skipped, Ordering::Relaxed);
+    }
+
+    pub(crate) fn idle_heartbeat_turns_skipped(&self) -> bool {
+        self.inner.skip_idle_heartbeat_turns.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn set_max_assistant_outputs_per_turn(&self, max_outputs: usize) {
+        self.inner
+            .max_assistant_outputs_per_turn
+            .store(max_outputs.max(1), Ordering::Relaxed);
+    }
+
+    pub(crate) fn max_assistant_outputs_per_turn(&self) -> Option<usize> {
+        match self
+            .inner
+            .max_assistant_outputs_per_turn
+            .load(Ordering::Relaxed)
+        {
+            0 => None,
+            max_outputs => Some(max_outputs),
+        }
+    }
+
+    pub(crate) fn set_max_heartbeat_delay_ms(&self, max_delay_ms: u64) {
+        self.inner
+            .max_heartbeat_delay_ms
+            .store(max_delay_ms.max(1), Ordering::Relaxed);
+    }
+
+    pub(crate) fn max_heartbeat_delay_ms(&self) -> u64 {
+        self.inner.max_heartbeat_delay_ms.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn set_queue_depth_warning_threshold(&self, threshold: u64) {
+        self.inner
+            .queue_depth_warning_threshold
+            .store(threshold, Ordering::Relaxed);
+    }
+
+    pub(crate) fn queue_depth_warning_threshold(&self) -> Option<u64> {
+        match self
+            .inner
+            .queue_depth_warning_threshold
+            .load(Ordering::Relaxed)
+        {
+            0 => None,
+            threshold => Some(threshold),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -118,7 +549,7 @@ mod tests {
     async fn creates_session_with_profile_copies() {
         let runtime = Runtime::new(2, 10);
         let session = runtime
-            .create_session("agent-a".to_string(), vec!["user-a".to_string()])
+            .create_session("agent-a".to_string(), vec!["user-a".to_string()], None)
             .await
             .expect("create session");
 
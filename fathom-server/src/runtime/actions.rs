@@ -0,0 +1,168 @@
+use tonic::Status;
+
+use super::Runtime;
+use fathom_protocol::pb;
+
+impl Runtime {
+    pub(crate) fn list_actions(
+        &self,
+        capability_domain_id: &str,
+    ) -> Result<Vec<pb::ActionDescriptor>, Status> {
+        let registry = self.capability_domain_registry();
+        let capability_domain_ids = if capability_domain_id.is_empty() {
+            registry.installed_capability_domain_ids()
+        } else {
+            vec![capability_domain_id.to_string()]
+        };
+
+        let mut actions = Vec::new();
+        for capability_domain_id in capability_domain_ids {
+            let summaries = registry
+                .capability_domain_action_summaries(&capability_domain_id)
+                .ok_or_else(|| {
+                    Status::not_found(format!(
+                        "capability domain `{capability_domain_id}` is not installed"
+                    ))
+                })?;
+            actions.extend(summaries.into_iter().map(|summary| pb::ActionDescriptor {
+                action_id: summary.id,
+                capability_domain_id: capability_domain_id.clone(),
+                name: summary.name,
+                description: summary.description,
+                input_schema_json: summary.input_schema.to_string(),
+            }));
+        }
+        Ok(actions)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fathom_capability_domain::ActionError;
+    use serde_json::{Value, json};
+
+    use crate::runtime::Runtime;
+    use crate::runtime::testing::run_domain_action;
+
+    #[test]
+    fn list_actions_returns_known_actions_with_schemas() {
+        let runtime = Runtime::new(2, 10);
+
+        let actions = runtime.list_actions("").expect("list all actions");
+
+        let read = actions
+            .iter()
+            .find(|action| action.action_id == "filesystem__read")
+            .expect("filesystem__read is listed");
+        assert_eq!(read.capability_domain_id, "filesystem");
+        assert_eq!(read.name, "read");
+        assert!(!read.description.is_empty());
+        let schema = serde_json::from_str::<Value>(&read.input_schema_json).expect("schema json");
+        assert_eq!(schema["type"], "object");
+        assert!(schema["properties"].get("path").is_some());
+        assert!(
+            actions
+                .iter()
+                .any(|action| action.action_id == "shell__run")
+        );
+        assert!(
+            actions
+                .iter()
+                .all(|action| serde_json::from_str::<Value>(&action.input_schema_json).is_ok())
+        );
+    }
+
+    #[test]
+    fn list_actions_filters_by_capability_domain_and_rejects_unknown_domains() {
+        let runtime = Runtime::new(2, 10);
+
+        let actions = runtime
+            .list_actions("filesystem")
+            .expect("list filesystem actions");
+        assert!(!actions.is_empty());
+        assert!(
+            actions
+                .iter()
+                .all(|action| action.capability_domain_id == "filesystem")
+        );
+
+        let error = runtime
+            .list_actions("missing")
+            .expect_err("unknown domain is rejected");
+        assert_eq!(error.code(), tonic::Code::NotFound);
+    }
+
+    #[tokio::test]
+    async fn filesystem_actions_cannot_reach_history_or_protected_paths() {
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .expect("system time")
+            .as_nanos();
+        let workspace_root = std::env::temp_dir().join(format!("fathom-protected-{nanos}"));
+        std::fs::create_dir_all(workspace_root.join("history")).expect("create history dir");
+        std::fs::write(workspace_root.join("history/session-1.jsonl"), "{}").expect("history");
+        std::fs::write(workspace_root.join("audit.log"), "audit").expect("audit log");
+        std::fs::create_dir_all(workspace_root.join(".fathom/diagnostics"))
+            .expect("create diagnostics dir");
+        std::fs::write(
+            workspace_root.join(".fathom/diagnostics/events.jsonl"),
+            "{}",
+        )
+        .expect("diagnostics");
+        std::fs::write(workspace_root.join("notes.txt"), "notes").expect("notes");
+        let runtime =
+            Runtime::new_with_workspace_root(2, 10, workspace_root.clone(), None).expect("runtime");
+        runtime.set_history_dir(Some(workspace_root.join("history")));
+        runtime.set_protected_paths(vec![workspace_root.join("audit.log")]);
+
+        for path in [
+            "history/session-1.jsonl",
+            "audit.log",
+            ".fathom/diagnostics/events.jsonl",
+        ] {
+            let result =
+                run_domain_action(&runtime, "filesystem", "read", json!({ "path": path })).await;
+            assert!(
+                matches!(
+                    &result.outcome,
+                    Err(ActionError::RuntimeError(error)) if error.code == "permission_denied"
+                ),
+                "{path} should be refused"
+            );
+        }
+        let sibling = run_domain_action(
+            &runtime,
+            "filesystem",
+            "read",
+            json!({ "path": "notes.txt" }),
+        )
+        .await;
+        assert!(sibling.outcome.is_ok());
+
+        let _ = std::fs::remove_dir_all(workspace_root);
+    }
+
+    #[tokio::test]
+    async fn configured_list_max_entries_bounds_filesystem_list() {
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .expect("system time")
+            .as_nanos();
+        let workspace_root = std::env::temp_dir().join(format!("fathom-list-limit-{nanos}"));
+        std::fs::create_dir_all(&workspace_root).expect("create workspace root");
+        for index in 0..5 {
+            std::fs::write(workspace_root.join(format!("file-{index}.txt")), "x").expect("file");
+        }
+        let runtime =
+            Runtime::new_with_workspace_root(2, 10, workspace_root.clone(), None).expect("runtime");
+        runtime.set_list_max_entries(3);
+
+        let result =
+            run_domain_action(&runtime, "filesystem", "list", json!({ "path": "." })).await;
+        let payload = result.outcome.expect("list succeeds").payload;
+        assert_eq!(payload["data"]["entries"].as_array().map(Vec::len), Some(3));
+        assert_eq!(payload["data"]["truncated"], json!(true));
+
+        let _ = std::fs::remove_dir_all(workspace_root);
+    }
+}
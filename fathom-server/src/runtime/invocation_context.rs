@@ -1,10 +1,12 @@
+use std::collections::BTreeSet;
+
 use super::Runtime;
 use crate::agent::{
     AgentInvocationContext, CapabilityAction, CapabilityDomain, CapabilityRecipe,
     CapabilitySurface, HarnessContract, IdentityEnvelope, ParticipantEnvelope,
     ResolvedPayloadLookupHint, SessionAnchor, SessionBaseline,
 };
-use crate::profile_material::{agent_identity_material, participant_profile_material};
+use crate::profile_material::{agent_identity_material, bounded_participant_profile_material};
 use crate::session::SessionState;
 use fathom_protocol::pb;
 use serde_json::json;
@@ -47,6 +49,7 @@ impl Runtime {
             triggers: triggers.to_vec(),
             recent_history,
             compaction: state.compaction.clone(),
+            result_preview_limits: state.result_preview_limits,
         }
     }
 
@@ -54,6 +57,7 @@ impl Runtime {
         HarnessContract {
             runtime_version: env!("CARGO_PKG_VERSION").to_string(),
             contract_schema_version: 1,
+            system_preamble: self.load_system_preamble(),
         }
     }
 
@@ -83,23 +87,41 @@ impl Runtime {
 
     fn build_capability_surface(&self, state: &SessionState) -> CapabilitySurface {
         let registry = self.capability_domain_registry();
+        let action_allowlist = self.agent_orchestrator().action_allowlist();
         let mut capability_domains = state
             .engaged_capability_domain_ids
             .iter()
             .filter_map(|capability_domain_id| {
                 let environment = registry.capability_domain_summary(capability_domain_id)?;
-                let mut actions = registry
+                let (mut actions, withheld_action_ids): (Vec<_>, Vec<_>) = registry
                     .capability_domain_action_summaries(capability_domain_id)?
                     .into_iter()
                     .map(|action| CapabilityAction {
                         action_id: action.id,
                         description: action.description,
                     })
-                    .collect::<Vec<_>>();
+                    .partition(|action| {
+                        action_allowlist
+                            .as_deref()
+                            .is_none_or(|allowlist| allowlist.contains(&action.action_id))
+                    });
+                if actions.is_empty() {
+                    return None;
+                }
+                let withheld_action_ids = withheld_action_ids
+                    .into_iter()
+                    .map(|action| action.action_id)
+                    .collect::<BTreeSet<_>>();
                 actions.sort_by(|a, b| a.action_id.cmp(&b.action_id));
                 let mut recipes = environment
                     .recipes
                     .into_iter()
+                    .filter(|recipe| {
+                        !recipe.steps.iter().any(|step| {
+                            step.split(|ch: char| !(ch.is_ascii_alphanumeric() || ch == '_'))
+                                .any(|token| withheld_action_ids.contains(token))
+                        })
+                    })
                     .map(|recipe| CapabilityRecipe {
                         title: recipe.title,
                         steps: recipe.steps,
@@ -120,18 +142,28 @@ impl Runtime {
     }
 
     fn build_participant_envelope(&self, state: &SessionState) -> ParticipantEnvelope {
-        let participants = state
+        let limits = self.participant_prompt_limits();
+        let profiles = state
             .participant_user_ids
             .iter()
             .filter_map(|user_id| state.participant_user_profiles_copy.get(user_id))
-            .map(participant_profile_material)
             .collect::<Vec<_>>();
+        let omitted_participant_count = profiles.len().saturating_sub(limits.max_participants);
+        let participants = profiles
+            .into_iter()
+            .take(limits.max_participants)
+            .map(|profile| bounded_participant_profile_material(profile, limits.max_profile_chars))
+            .collect::<Vec<_>>();
+        let mut material = json!({
+            "participants": participants,
+        });
+        if omitted_participant_count > 0 {
+            material["omitted_participant_count"] = json!(omitted_participant_count);
+        }
         ParticipantEnvelope {
             schema_version: 1,
             source_revision: participant_envelope_source_revision(state),
-            material: json!({
-                "participants": participants,
-            }),
+            material,
         }
     }
 }
@@ -155,6 +187,7 @@ fn participant_envelope_source_revision(state: &SessionState) -> String {
 #[cfg(test)]
 mod tests {
     use std::collections::{BTreeSet, HashMap};
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
     use super::Runtime;
     use crate::agent::{SessionCompaction, SummaryBlockRef};
@@ -308,4 +341,155 @@ mod tests {
             .collect::<Vec<_>>();
         assert_eq!(capability_domain_ids, vec!["filesystem", "shell"]);
     }
+
+    #[test]
+    fn configured_system_preamble_file_replaces_default_preamble_lines() {
+        let runtime = Runtime::new(2, 10);
+        let user_id = "user-a".to_string();
+        let state = SessionState::new(
+            "session-1".to_string(),
+            "agent-a".to_string(),
+            vec![user_id.clone()],
+            default_agent_profile("agent-a"),
+            HashMap::from([(user_id.clone(), default_user_profile(&user_id))]),
+            BTreeSet::new(),
+        );
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_else(|_| Duration::from_secs(0))
+            .as_nanos();
+        let preamble_path = std::env::temp_dir().join(format!("fathom-preamble-{nanos}.md"));
+        std::fs::write(&preamble_path, "## Operator Rules\n- Answer in haiku.\n")
+            .expect("write preamble file");
+
+        let default_prompt = runtime
+            .agent_orchestrator()
+            .assemble_prompt_bundle(&runtime.build_agent_invocation_context(&state, &[]), None);
+        assert!(default_prompt.messages[0].content.contains("## Your Task"));
+
+        runtime.set_system_preamble_path(Some(preamble_path.clone()));
+        let context = runtime.build_agent_invocation_context(&state, &[]);
+        let prompt = runtime
+            .agent_orchestrator()
+            .assemble_prompt_bundle(&context, None);
+        let harness_contract = &prompt.messages[0].content;
+
+        assert!(harness_contract.starts_with("# Harness Contract"));
+        assert!(harness_contract.contains("- Answer in haiku."));
+        assert!(!harness_contract.contains("## Your Task"));
+        assert!(!harness_contract.contains("## Allowed Outputs"));
+
+        runtime.set_system_preamble_path(Some(preamble_path.with_extension("missing")));
+        let fallback_context = runtime.build_agent_invocation_context(&state, &[]);
+        assert!(fallback_context.harness_contract.system_preamble.is_none());
+
+        let _ = std::fs::remove_file(&preamble_path);
+    }
+
+    #[test]
+    fn participant_envelope_caps_inlined_profiles_and_notes_omitted_ones() {
+        let runtime = Runtime::new(2, 10);
+        let user_ids = (0..40)
+            .map(|index| format!("user-{index:02}"))
+            .collect::<Vec<_>>();
+        let profiles = user_ids
+            .iter()
+            .map(|user_id| {
+                let mut profile = default_user_profile(user_id);
+                profile.material_json = json!({
+                    "preferences": { "notes": "x".repeat(20_000) },
+                })
+                .to_string();
+                (user_id.clone(), profile)
+            })
+            .collect::<HashMap<_, _>>();
+        let state = SessionState::new(
+            "session-1".to_string(),
+            "agent-a".to_string(),
+            user_ids.clone(),
+            default_agent_profile("agent-a"),
+            profiles,
+            BTreeSet::new(),
+        );
+
+        let limits = runtime.participant_prompt_limits();
+        let context = runtime.build_agent_invocation_context(&state, &[]);
+        let material = &context.session_baseline.participant_envelope.material;
+        let participants = material["participants"]
+            .as_array()
+            .expect("participants array");
+        assert_eq!(participants.len(), limits.max_participants);
+        assert_eq!(
+            material["omitted_participant_count"],
+            json!(40 - limits.max_participants)
+        );
+        assert!(
+            participants
+                .iter()
+                .all(|participant| participant["material_truncated"] == json!(true))
+        );
+
+        let prompt = runtime
+            .agent_orchestrator()
+            .assemble_prompt_bundle(&context, None)
+            .as_debug_prompt();
+        assert!(prompt.contains(&format!(
+            "_{} more participant(s) omitted from this prompt._",
+            40 - limits.max_participants
+        )));
+        assert!(
+            prompt.len() < limits.max_participants * (limits.max_profile_chars + 1_000) + 40_000
+        );
+        assert!(!prompt.contains("## user-39"));
+
+        runtime.set_participant_prompt_limits(Some(2), Some(100));
+        let context = runtime.build_agent_invocation_context(&state, &[]);
+        let material = &context.session_baseline.participant_envelope.material;
+        assert_eq!(material["participants"].as_array().map(Vec::len), Some(2));
+        assert_eq!(material["omitted_participant_count"], json!(38));
+        assert_eq!(
+            material["participants"][0]["material_excerpt"]
+                .as_str()
+                .map(|excerpt| excerpt.chars().count()),
+            Some(100)
+        );
+    }
+
+    #[test]
+    fn capability_surface_only_renders_allowlisted_actions_and_recipes() {
+        let runtime = Runtime::new(2, 10);
+        runtime
+            .set_action_allowlist(vec![
+                "filesystem__read".to_string(),
+                "system__memory_append".to_string(),
+            ])
+            .expect("set action allowlist");
+        let user_id = "user-a".to_string();
+        let state = SessionState::new(
+            "session-1".to_string(),
+            "agent-a".to_string(),
+            vec![user_id.clone()],
+            default_agent_profile("agent-a"),
+            HashMap::from([(user_id.clone(), default_user_profile(&user_id))]),
+            runtime
+                .capability_domain_registry()
+                .installed_capability_domain_ids()
+                .into_iter()
+                .collect::<BTreeSet<_>>(),
+        );
+
+        let prompt = runtime
+            .agent_orchestrator()
+            .assemble_prompt_bundle(&runtime.build_agent_invocation_context(&state, &[]), None)
+            .as_debug_prompt();
+
+        assert!(prompt.contains("- `filesystem__read`"));
+        assert!(prompt.contains("- `system__memory_append`"));
+        assert!(!prompt.contains("- `filesystem__write`"));
+        assert!(!prompt.contains("- `system__memory_clear`"));
+        assert!(!prompt.contains("(`shell`)"));
+        assert!(prompt.contains("##### Remember a note"));
+        assert!(!prompt.contains("##### Clear profile memory"));
+        assert!(!prompt.contains("##### Wake up later"));
+    }
 }
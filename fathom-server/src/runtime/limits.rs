@@ -0,0 +1,164 @@
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+
+use futures_util::future::join_all;
+use tonic::Status;
+
+use super::Runtime;
+use crate::session::SessionCommand;
+use fathom_protocol::pb;
+
+pub(super) const MAX_EXECUTION_CAPACITY: u64 = 64;
+const MIN_FOREGROUND_WAIT_BUDGET_MS: u64 = 100;
+const MAX_FOREGROUND_WAIT_BUDGET_MS: u64 = 600_000;
+
+impl Runtime {
+    pub(crate) fn execution_capacity(&self) -> usize {
+        self.inner.execution_capacity.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn set_execution_capacity(&self, capacity: usize) -> Result<(), String> {
+        if capacity == 0 || capacity as u64 > MAX_EXECUTION_CAPACITY {
+            return Err(format!(
+                "execution capacity must be between 1 and {MAX_EXECUTION_CAPACITY}, got {capacity}"
+            ));
+        }
+        self.inner
+            .execution_capacity
+            .store(capacity, Ordering::Relaxed);
+        Ok(())
+    }
+
+    pub(crate) fn action_timeout(&self) -> Duration {
+        Duration::from_millis(self.inner.action_timeout_ms.load(Ordering::Relaxed))
+    }
+
+    pub(crate) fn set_action_timeout_ms(&self, timeout_ms: u64) -> Result<(), String> {
+        if timeout_ms == 0 {
+            return Err("action timeout must be at least 1ms".to_string());
+        }
+        self.inner
+            .action_timeout_ms
+            .store(timeout_ms, Ordering::Relaxed);
+        Ok(())
+    }
+
+    pub(crate) fn turn_timeout(&self) -> Option<Duration> {
+        match self.inner.turn_timeout_ms.load(Ordering::Relaxed) {
+            0 => None,
+            timeout_ms => Some(Duration::from_millis(timeout_ms)),
+        }
+    }
+
+    pub(crate) fn set_turn_timeout(&self, timeout: Duration) -> Result<(), String> {
+        let timeout_ms = u64::try_from(timeout.as_millis()).unwrap_or(u64::MAX);
+        if timeout_ms == 0 {
+            return Err("turn timeout must be at least 1ms".to_string());
+        }
+        self.inner
+            .turn_timeout_ms
+            .store(timeout_ms, Ordering::Relaxed);
+        Ok(())
+    }
+
+    pub(crate) fn foreground_wait_budget(&self) -> Duration {
+        Duration::from_millis(self.inner.foreground_wait_budget_ms.load(Ordering::Relaxed))
+    }
+
+    pub(crate) fn runtime_limits(&self) -> pb::RuntimeLimits {
+        pb::RuntimeLimits {
+            execution_capacity: self.execution_capacity() as u64,
+            foreground_wait_budget_ms: self.inner.foreground_wait_budget_ms.load(Ordering::Relaxed),
+        }
+    }
+
+    pub(crate) async fn update_runtime_limits(
+        &self,
+        request: pb::UpdateRuntimeLimitsRequest,
+    ) -> Result<pb::RuntimeLimits, Status> {
+        if request.execution_capacity > MAX_EXECUTION_CAPACITY {
+            return Err(Status::invalid_argument(format!(
+                "execution_capacity must be between 1 and {MAX_EXECUTION_CAPACITY}"
+            )));
+        }
+        if request.foreground_wait_budget_ms != 0
+            && !(MIN_FOREGROUND_WAIT_BUDGET_MS..=MAX_FOREGROUND_WAIT_BUDGET_MS)
+                .contains(&request.foreground_wait_budget_ms)
+        {
+            return Err(Status::invalid_argument(format!(
+                "foreground_wait_budget_ms must be between {MIN_FOREGROUND_WAIT_BUDGET_MS} and {MAX_FOREGROUND_WAIT_BUDGET_MS}"
+            )));
+        }
+
+        if request.foreground_wait_budget_ms != 0 {
+            self.inner
+                .foreground_wait_budget_ms
+                .store(request.foreground_wait_budget_ms, Ordering::Relaxed);
+        }
+        if request.execution_capacity != 0 {
+            let previous_capacity = self
+                .inner
+                .execution_capacity
+                .swap(request.execution_capacity as usize, Ordering::Relaxed);
+            if request.execution_capacity as usize > previous_capacity {
+                self.notify_execution_capacity_raised().await;
+            }
+        }
+
+        Ok(self.runtime_limits())
+    }
+
+    async fn notify_execution_capacity_raised(&self) {
+        let sessions = self
+            .inner
+            .sessions
+            .read()
+            .await
+            .values()
+            .cloned()
+            .collect::<Vec<_>>();
+        join_all(
+            sessions
+                .iter()
+                .map(|session| session.send_command(SessionCommand::StartQueuedExecutions)),
+        )
+        .await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use fathom_protocol::pb;
+
+    use crate::runtime::Runtime;
+
+    #[tokio::test]
+    async fn update_runtime_limits_validates_bounds_and_keeps_unset_values() {
+        let runtime = Runtime::new(2, 10);
+
+        let error = runtime
+            .update_runtime_limits(pb::UpdateRuntimeLimitsRequest {
+                execution_capacity: 1_000,
+                foreground_wait_budget_ms: 0,
+            })
+            .await
+            .expect_err("oversized capacity should be rejected");
+        assert_eq!(error.code(), tonic::Code::InvalidArgument);
+
+        let limits = runtime
+            .update_runtime_limits(pb::UpdateRuntimeLimitsRequest {
+                execution_capacity: 0,
+                foreground_wait_budget_ms: 2_500,
+            })
+            .await
+            .expect("update foreground budget");
+        assert_eq!(limits.execution_capacity, 2);
+        assert_eq!(limits.foreground_wait_budget_ms, 2_500);
+        assert_eq!(
+            runtime.foreground_wait_budget(),
+            Duration::from_millis(2_500)
+        );
+    }
+}
@@ -0,0 +1,200 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use fathom_protocol::pb;
+
+const USER_PROFILES_FILE: &str = "user_profiles.json";
+const AGENT_PROFILES_FILE: &str = "agent_profiles.json";
+
+#[derive(Debug, Serialize, Deserialize)]
+struct StoredUserProfile {
+    user_id: String,
+    name: String,
+    nickname: String,
+    material_json: String,
+    updated_at_unix_ms: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct StoredAgentProfile {
+    agent_id: String,
+    display_name: String,
+    material_json: String,
+    spec_version: u64,
+    updated_at_unix_ms: i64,
+}
+
+impl From<&pb::UserProfile> for StoredUserProfile {
+    fn from(profile: &pb::UserProfile) -> Self {
+        Self {
+            user_id: profile.user_id.clone(),
+            name: profile.name.clone(),
+            nickname: profile.nickname.clone(),
+            material_json: profile.material_json.clone(),
+            updated_at_unix_ms: profile.updated_at_unix_ms,
+        }
+    }
+}
+
+impl From<StoredUserProfile> for pb::UserProfile {
+    fn from(profile: StoredUserProfile) -> Self {
+        Self {
+            user_id: profile.user_id,
+            name: profile.name,
+            nickname: profile.nickname,
+            material_json: profile.material_json,
+            updated_at_unix_ms: profile.updated_at_unix_ms,
+        }
+    }
+}
+
+impl From<&pb::AgentProfile> for StoredAgentProfile {
+    fn from(profile: &pb::AgentProfile) -> Self {
+        Self {
+            agent_id: profile.agent_id.clone(),
+            display_name: profile.display_name.clone(),
+            material_json: profile.material_json.clone(),
+            spec_version: profile.spec_version,
+            updated_at_unix_ms: profile.updated_at_unix_ms,
+        }
+    }
+}
+
+impl From<StoredAgentProfile> for pb::AgentProfile {
+    fn from(profile: StoredAgentProfile) -> Self {
+        Self {
+            agent_id: profile.agent_id,
+            display_name: profile.display_name,
+            material_json: profile.material_json,
+            spec_version: profile.spec_version,
+            updated_at_unix_ms: profile.updated_at_unix_ms,
+        }
+    }
+}
+
+pub(crate) fn load_user_profiles(
+    profile_dir: &Path,
+) -> io::Result<HashMap<String, pb::UserProfile>> {
+    Ok(
+        load_records::<StoredUserProfile>(&profile_dir.join(USER_PROFILES_FILE))?
+            .into_iter()
+            .map(|profile| (profile.user_id.clone(), profile.into()))
+            .collect(),
+    )
+}
+
+pub(crate) fn load_agent_profiles(
+    profile_dir: &Path,
+) -> io::Result<HashMap<String, pb::AgentProfile>> {
+    Ok(
+        load_records::<StoredAgentProfile>(&profile_dir.join(AGENT_PROFILES_FILE))?
+            .into_iter()
+            .map(|profile| (profile.agent_id.clone(), profile.into()))
+            .collect(),
+    )
+}
+
+pub(crate) struct ProfileSnapshot {
+    path: PathBuf,
+    payload: Vec<u8>,
+}
+
+impl ProfileSnapshot {
+    pub(crate) fn write(self) -> io::Result<()> {
+        write_payload(&self.path, &self.payload)
+    }
+}
+
+pub(crate) fn user_profiles_snapshot(
+    profile_dir: &Path,
+    profiles: &HashMap<String, pb::UserProfile>,
+) -> io::Result<ProfileSnapshot> {
+    let mut records = profiles
+        .values()
+        .map(StoredUserProfile::from)
+        .collect::<Vec<_>>();
+    records.sort_by(|a, b| a.user_id.cmp(&b.user_id));
+    snapshot_records(profile_dir.join(USER_PROFILES_FILE), &records)
+}
+
+pub(crate) fn agent_profiles_snapshot(
+    profile_dir: &Path,
+    profiles: &HashMap<String, pb::AgentProfile>,
+) -> io::Result<ProfileSnapshot> {
+    let mut records = profiles
+        .values()
+        .map(StoredAgentProfile::from)
+        .collect::<Vec<_>>();
+    records.sort_by(|a, b| a.agent_id.cmp(&b.agent_id));
+    snapshot_records(profile_dir.join(AGENT_PROFILES_FILE), &records)
+}
+
+fn load_records<T: DeserializeOwned>(path: &Path) -> io::Result<Vec<T>> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let raw = fs::read(path)?;
+    serde_json::from_slice(&raw).map_err(|error| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("failed to parse `{}`: {error}", path.display()),
+        )
+    })
+}
+
+fn snapshot_records<T: Serialize>(path: PathBuf, records: &[T]) -> io::Result<ProfileSnapshot> {
+    let mut payload = serde_json::to_vec_pretty(records)?;
+    payload.push(b'\n');
+    Ok(ProfileSnapshot { path, payload })
+}
+
+fn write_payload(path: &Path, payload: &[u8]) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let temp_path = path.with_extension("json.tmp");
+    let mut file = fs::File::create(&temp_path)?;
+    file.write_all(payload)?;
+    file.sync_all()?;
+    drop(file);
+    fs::rename(&temp_path, path)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::{agent_profiles_snapshot, load_agent_profiles, load_user_profiles};
+    use crate::util::default_agent_profile;
+
+    #[test]
+    fn saved_profiles_round_trip_and_leave_no_temp_file() {
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .expect("system time")
+            .as_nanos();
+        let profile_dir = std::env::temp_dir().join(format!("fathom-profiles-{nanos}"));
+        assert!(load_user_profiles(&profile_dir).expect("load").is_empty());
+
+        let mut profile = default_agent_profile("agent-a");
+        profile.spec_version = 7;
+        let profiles = HashMap::from([(profile.agent_id.clone(), profile.clone())]);
+        agent_profiles_snapshot(&profile_dir, &profiles)
+            .and_then(|snapshot| snapshot.write())
+            .expect("save");
+
+        let loaded = load_agent_profiles(&profile_dir).expect("load");
+        assert_eq!(loaded.get("agent-a"), Some(&profile));
+        assert!(!profile_dir.join("agent_profiles.json.tmp").exists());
+
+        std::fs::write(profile_dir.join("user_profiles.json"), "not json").expect("corrupt");
+        assert!(load_user_profiles(&profile_dir).is_err());
+
+        let _ = std::fs::remove_dir_all(&profile_dir);
+    }
+}
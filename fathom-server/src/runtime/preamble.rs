@@ -0,0 +1,133 @@
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use tracing::warn;
+
+use super::Runtime;
+
+pub(super) struct CachedPreamble {
+    path: PathBuf,
+    modified: SystemTime,
+    preamble: Option<String>,
+}
+
+impl Runtime {
+    pub(crate) fn set_system_preamble_path(&self, path: Option<PathBuf>) {
+        let mut slot = self
+            .inner
+            .system_preamble_path
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        *slot = path;
+    }
+
+    pub(super) fn load_system_preamble(&self) -> Option<String> {
+        let path = self
+            .inner
+            .system_preamble_path
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clone()?;
+
+        let modified = match std::fs::metadata(&path).and_then(|metadata| metadata.modified()) {
+            Ok(modified) => modified,
+            Err(error) => {
+                warn!(
+                    path = %path.display(),
+                    %error,
+                    "failed to read system preamble file; using built-in preamble"
+                );
+                return None;
+            }
+        };
+
+        let mut cache = self
+            .inner
+            .system_preamble_cache
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Some(cached) = cache
+            .as_ref()
+            .filter(|cached| cached.path == path && cached.modified == modified)
+        {
+            return cached.preamble.clone();
+        }
+
+        let preamble = read_system_preamble(&path);
+        *cache = Some(CachedPreamble {
+            path,
+            modified,
+            preamble: preamble.clone(),
+        });
+        preamble
+    }
+}
+
+fn read_system_preamble(path: &Path) -> Option<String> {
+    match std::fs::read_to_string(path) {
+        Ok(preamble) if !preamble.trim().is_empty() => Some(preamble),
+        Ok(_) => {
+            warn!(
+                path = %path.display(),
+                "system preamble file is empty; using built-in preamble"
+            );
+            None
+        }
+        Err(error) => {
+            warn!(
+                path = %path.display(),
+                %error,
+                "failed to read system preamble file; using built-in preamble"
+            );
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+    use super::Runtime;
+
+    #[test]
+    fn system_preamble_is_reread_only_when_the_file_changes() {
+        let runtime = Runtime::new(2, 10);
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_else(|_| Duration::from_secs(0))
+            .as_nanos();
+        let preamble_path = std::env::temp_dir().join(format!("fathom-preamble-cache-{nanos}.md"));
+        std::fs::write(&preamble_path, "first preamble").expect("write preamble file");
+        let modified = std::fs::metadata(&preamble_path)
+            .and_then(|metadata| metadata.modified())
+            .expect("preamble mtime");
+        let set_modified = |modified: SystemTime| {
+            std::fs::File::options()
+                .write(true)
+                .open(&preamble_path)
+                .and_then(|file| file.set_modified(modified))
+                .expect("set preamble mtime");
+        };
+        runtime.set_system_preamble_path(Some(preamble_path.clone()));
+        assert_eq!(
+            runtime.load_system_preamble().as_deref(),
+            Some("first preamble")
+        );
+
+        std::fs::write(&preamble_path, "second preamble").expect("rewrite preamble file");
+        set_modified(modified);
+        assert_eq!(
+            runtime.load_system_preamble().as_deref(),
+            Some("first preamble")
+        );
+
+        set_modified(modified + Duration::from_secs(1));
+        assert_eq!(
+            runtime.load_system_preamble().as_deref(),
+            Some("second preamble")
+        );
+
+        let _ = std::fs::remove_file(&preamble_path);
+    }
+}
@@ -1,25 +1,75 @@
+use std::collections::HashMap;
+
 use tonic::Status;
 
-use super::Runtime;
-use crate::profile_material::validate_material_json_object;
+use super::{Runtime, persistence};
+use crate::profile_material::{
+    ProfileMemoryTarget, append_long_term_memory, clear_long_term_memory,
+    missing_required_agent_fields, validate_material_json_object,
+};
+use crate::session::SessionState;
 use crate::util::{default_agent_profile, default_user_profile, now_unix_ms};
 use fathom_protocol::pb;
 
 impl Runtime {
-    pub(crate) async fn get_or_create_user_profile(&self, user_id: &str) -> pb::UserProfile {
+    pub(crate) async fn get_or_create_user_profile(
+        &self,
+        user_id: &str,
+    ) -> Result<pb::UserProfile, Status> {
         let mut profiles = self.inner.user_profiles.write().await;
-        let profile = profiles
-            .entry(user_id.to_string())
-            .or_insert_with(|| default_user_profile(user_id));
-        profile.clone()
+        if let Some(profile) = profiles.get(user_id) {
+            return Ok(profile.clone());
+        }
+        let profile = default_user_profile(user_id);
+        profiles.insert(user_id.to_string(), profile.clone());
+        if let Err(status) = self.persist_user_profiles(&profiles).await {
+            profiles.remove(user_id);
+            return Err(status);
+        }
+        Ok(profile)
     }
 
-    pub(crate) async fn get_or_create_agent_profile(&self, agent_id: &str) -> pb::AgentProfile {
+    pub(crate) async fn get_or_create_agent_profile(
+        &self,
+        agent_id: &str,
+    ) -> Result<pb::AgentProfile, Status> {
         let mut profiles = self.inner.agent_profiles.write().await;
-        let profile = profiles
-            .entry(agent_id.to_string())
-            .or_insert_with(|| default_agent_profile(agent_id));
-        profile.clone()
+        if let Some(profile) = profiles.get(agent_id) {
+            return Ok(profile.clone());
+        }
+        let profile = default_agent_profile(agent_id);
+        profiles.insert(agent_id.to_string(), profile.clone());
+        if let Err(status) = self.persist_agent_profiles(&profiles).await {
+            profiles.remove(agent_id);
+            return Err(status);
+        }
+        Ok(profile)
+    }
+
+    pub(crate) async fn list_user_profiles(&self) -> Vec<pb::UserProfile> {
+        let mut profiles = self
+            .inner
+            .user_profiles
+            .read()
+            .await
+            .values()
+            .cloned()
+            .collect::<Vec<_>>();
+        profiles.sort_by(|a, b| a.user_id.cmp(&b.user_id));
+        profiles
+    }
+
+    pub(crate) async fn list_agent_profiles(&self) -> Vec<pb::AgentProfile> {
+        let mut profiles = self
+            .inner
+            .agent_profiles
+            .read()
+            .await
+            .values()
+            .cloned()
+            .collect::<Vec<_>>();
+        profiles.sort_by(|a, b| a.agent_id.cmp(&b.agent_id));
+        profiles
     }
 
     pub(crate) async fn upsert_user_profile(
@@ -34,11 +84,12 @@ impl Runtime {
             profile.updated_at_unix_ms = now_unix_ms();
         }
 
-        self.inner
-            .user_profiles
-            .write()
-            .await
-            .insert(profile.user_id.clone(), profile.clone());
+        let mut profiles = self.inner.user_profiles.write().await;
+        let previous = profiles.insert(profile.user_id.clone(), profile.clone());
+        if let Err(status) = self.persist_user_profiles(&profiles).await {
+            restore_entry(&mut profiles, &profile.user_id, previous);
+            return Err(status);
+        }
         Ok(profile)
     }
 
@@ -50,6 +101,7 @@ impl Runtime {
             return Err(Status::invalid_argument("profile.agent_id is required"));
         }
         validate_material_json_object(&profile.material_json).map_err(Status::invalid_argument)?;
+        self.ensure_required_agent_fields(&profile)?;
 
         let mut profiles = self.inner.agent_profiles.write().await;
         let current_version = profiles
@@ -63,10 +115,132 @@ impl Runtime {
             profile.updated_at_unix_ms = now_unix_ms();
         }
 
-        profiles.insert(profile.agent_id.clone(), profile.clone());
+        let previous = profiles.insert(profile.agent_id.clone(), profile.clone());
+        if let Err(status) = self.persist_agent_profiles(&profiles).await {
+            restore_entry(&mut profiles, &profile.agent_id, previous);
+            return Err(status);
+        }
+        Ok(profile)
+    }
+
+    pub(crate) async fn rename_user_profile(
+        &self,
+        user_id: &str,
+        new_user_id: &str,
+    ) -> Result<pb::UserProfile, Status> {
+        validate_rename_ids(user_id, new_user_id)?;
+        let sessions = self.inner.sessions.read().await;
+        if sessions
+            .values()
+            .any(|session| session.participant_user_ids.iter().any(|id| id == user_id))
+        {
+            return Err(Status::failed_precondition(format!(
+                "user profile `{user_id}` is referenced by a live session"
+            )));
+        }
+
+        let mut profiles = self.inner.user_profiles.write().await;
+        if profiles.contains_key(new_user_id) {
+            return Err(Status::already_exists(format!(
+                "user profile `{new_user_id}` already exists"
+            )));
+        }
+        let previous = profiles
+            .remove(user_id)
+            .ok_or_else(|| Status::not_found(format!("user profile `{user_id}` not found")))?;
+        let mut profile = previous.clone();
+        profile.user_id = new_user_id.to_string();
+        profile.updated_at_unix_ms = now_unix_ms();
+        profiles.insert(new_user_id.to_string(), profile.clone());
+        if let Err(status) = self.persist_user_profiles(&profiles).await {
+            profiles.remove(new_user_id);
+            profiles.insert(user_id.to_string(), previous);
+            return Err(status);
+        }
+        Ok(profile)
+    }
+
+    pub(crate) async fn rename_agent_profile(
+        &self,
+        agent_id: &str,
+        new_agent_id: &str,
+    ) -> Result<pb::AgentProfile, Status> {
+        validate_rename_ids(agent_id, new_agent_id)?;
+        let sessions = self.inner.sessions.read().await;
+        if sessions
+            .values()
+            .any(|session| session.agent_id == agent_id)
+        {
+            return Err(Status::failed_precondition(format!(
+                "agent profile `{agent_id}` is referenced by a live session"
+            )));
+        }
+
+        let mut profiles = self.inner.agent_profiles.write().await;
+        if profiles.contains_key(new_agent_id) {
+            return Err(Status::already_exists(format!(
+                "agent profile `{new_agent_id}` already exists"
+            )));
+        }
+        let previous = profiles
+            .remove(agent_id)
+            .ok_or_else(|| Status::not_found(format!("agent profile `{agent_id}` not found")))?;
+        let mut profile = previous.clone();
+        profile.agent_id = new_agent_id.to_string();
+        profile.updated_at_unix_ms = now_unix_ms();
+        profiles.insert(new_agent_id.to_string(), profile.clone());
+        if let Err(status) = self.persist_agent_profiles(&profiles).await {
+            profiles.remove(new_agent_id);
+            profiles.insert(agent_id.to_string(), previous);
+            return Err(status);
+        }
         Ok(profile)
     }
 
+    pub(super) async fn ensure_session_profiles_exist(
+        &self,
+        state: &SessionState,
+    ) -> Result<(), Status> {
+        if !self
+            .inner
+            .agent_profiles
+            .read()
+            .await
+            .contains_key(&state.agent_id)
+        {
+            return Err(Status::aborted(format!(
+                "agent profile `{}` was renamed while the session was starting",
+                state.agent_id
+            )));
+        }
+        let user_profiles = self.inner.user_profiles.read().await;
+        if let Some(user_id) = state
+            .participant_user_ids
+            .iter()
+            .find(|user_id| !user_profiles.contains_key(*user_id))
+        {
+            return Err(Status::aborted(format!(
+                "user profile `{user_id}` was renamed while the session was starting"
+            )));
+        }
+        Ok(())
+    }
+
+    pub(crate) fn ensure_required_agent_fields(
+        &self,
+        profile: &pb::AgentProfile,
+    ) -> Result<(), Status> {
+        let missing = missing_required_agent_fields(profile, &self.required_agent_profile_fields());
+        if missing.is_empty() {
+            return Ok(());
+        }
+        Err(Status::invalid_argument(format!(
+            "agent profile `{}` is missing required fields: {}",
+            profile.agent_id,
+            missing.join(", ")
+        )))
+    }
+
     pub(crate) async fn fetch_agent_profile(&self, agent_id: &str) -> Option<pb::AgentProfile> {
         self.inner
             .agent_profiles
@@ -79,4 +253,408 @@ impl Runtime {
     pub(crate) async fn fetch_user_profile(&self, user_id: &str) -> Option<pb::UserProfile> {
         self.inner.user_profiles.read().await.get(user_id).cloned()
     }
+
+    pub(crate) async fn clear_profile_memory(
+        &self,
+        target: ProfileMemoryTarget,
+        target_id: &str,
+    ) -> Result<usize, Status> {
+        self.update_profile_memory(target, target_id, clear_long_term_memory)
+            .await
+    }
+
+    pub(crate) async fn append_profile_memory(
+        &self,
+        target: ProfileMemoryTarget,
+        target_id: &str,
+        note: &str,
+    ) -> Result<usize, Status> {
+        self.update_profile_memory(target, target_id, |material_json| {
+            append_long_term_memory(material_json, note, now_unix_ms())
+        })
+        .await
+    }
+
+    async fn update_profile_memory<F>(
+        &self,
+        target: ProfileMemoryTarget,
+        target_id: &str,
+        update: F,
+    ) -> Result<usize, Status>
+    where
+        F: FnOnce(&str) -> Result<(String, usize), String>,
+    {
+        match target {
+            ProfileMemoryTarget::Agent => {
+                let mut profiles = self.inner.agent_profiles.write().await;
+                let mut profile = profiles.get(target_id).cloned().ok_or_else(|| {
+                    Status::not_found(format!("agent profile `{target_id}` not found"))
+                })?;
+                let (material_json, bytes_changed) =
+                    update(&profile.material_json).map_err(Status::failed_precondition)?;
+                if bytes_changed > 0 {
+                    profile.material_json = material_json;
+                    profile.spec_version += 1;
+                    profile.updated_at_unix_ms = now_unix_ms();
+                    let previous = profiles.insert(target_id.to_string(), profile);
+                    if let Err(status) = self.persist_agent_profiles(&profiles).await {
+                        restore_entry(&mut profiles, target_id, previous);
+                        return Err(status);
+                    }
+                }
+                Ok(bytes_changed)
+            }
+            ProfileMemoryTarget::User => {
+                let mut profiles = self.inner.user_profiles.write().await;
+                let mut profile = profiles.get(target_id).cloned().ok_or_else(|| {
+                    Status::not_found(format!("user profile `{target_id}` not found"))
+                })?;
+                let (material_json, bytes_changed) =
+                    update(&profile.material_json).map_err(Status::failed_precondition)?;
+                if bytes_changed > 0 {
+                    profile.material_json = material_json;
+                    profile.updated_at_unix_ms = now_unix_ms();
+                    let previous = profiles.insert(target_id.to_string(), profile);
+                    if let Err(status) = self.persist_user_profiles(&profiles).await {
+                        restore_entry(&mut profiles, target_id, previous);
+                        return Err(status);
+                    }
+                }
+                Ok(bytes_changed)
+            }
+        }
+    }
+
+    async fn persist_user_profiles(
+        &self,
+        profiles: &HashMap<String, pb::UserProfile>,
+    ) -> Result<(), Status> {
+        let Some(profile_dir) = self.inner.profile_dir.as_deref() else {
+            return Ok(());
+        };
+        let snapshot =
+            persistence::user_profiles_snapshot(profile_dir, profiles).map_err(|error| {
+                Status::internal(format!("failed to persist user profiles: {error}"))
+            })?;
+        write_profile_snapshot(snapshot, "user").await
+    }
+
+    async fn persist_agent_profiles(
+        &self,
+        profiles: &HashMap<String, pb::AgentProfile>,
+    ) -> Result<(), Status> {
+        let Some(profile_dir) = self.inner.profile_dir.as_deref() else {
+            return Ok(());
+        };
+        let snapshot =
+            persistence::agent_profiles_snapshot(profile_dir, profiles).map_err(|error| {
+                Status::internal(format!("failed to persist agent profiles: {error}"))
+            })?;
+        write_profile_snapshot(snapshot, "agent").await
+    }
+}
+
+async fn write_profile_snapshot(
+    snapshot: persistence::ProfileSnapshot,
+    kind: &str,
+) -> Result<(), Status> {
+    tokio::task::spawn_blocking(move || snapshot.write())
+        .await
+        .map_err(|error| Status::internal(format!("{kind} profile persist task failed: {error}")))?
+        .map_err(|error| Status::internal(format!("failed to persist {kind} profiles: {error}")))
+}
+
+fn validate_rename_ids(id: &str, new_id: &str) -> Result<(), Status> {
+    if id.trim().is_empty() || new_id.trim().is_empty() {
+        return Err(Status::invalid_argument(
+            "both the current and the new profile id are required",
+        ));
+    }
+    if id == new_id {
+        return Err(Status::invalid_argument(
+            "the new profile id must differ from the current one",
+        ));
+    }
+    Ok(())
+}
+
+fn restore_entry<T>(profiles: &mut HashMap<String, T>, id: &str, previous: Option<T>) {
+    match previous {
+        Some(previous) => {
+            profiles.insert(id.to_string(), previous);
+        }
+        None => {
+            profiles.remove(id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use fathom_protocol::pb;
+
+    use crate::profile_material::ProfileMemoryTarget;
+    use crate::runtime::Runtime;
+
+    #[tokio::test]
+    async fn required_agent_profile_fields_reject_blank_profiles() {
+        let runtime = Runtime::new(2, 10);
+        runtime.set_required_agent_profile_fields(vec![
+            "display_name".to_string(),
+            " identity.mission ".to_string(),
+            "identity.soul".to_string(),
+        ]);
+
+        let error = runtime
+            .upsert_agent_profile(pb::AgentProfile {
+                agent_id: "agent-a".to_string(),
+                display_name: "Agent A".to_string(),
+                material_json: json!({"identity": {"mission": "help", "soul": "  "}}).to_string(),
+                ..Default::default()
+            })
+            .await
+            .expect_err("blank soul should be rejected");
+        assert_eq!(error.code(), tonic::Code::InvalidArgument);
+        assert_eq!(
+            error.message(),
+            "agent profile `agent-a` is missing required fields: identity.soul"
+        );
+        assert_eq!(runtime.fetch_agent_profile("agent-a").await, None);
+
+        let error = runtime
+            .create_session("agent-a".to_string(), vec!["user-a".to_string()], None)
+            .await
+            .expect_err("default agent profile lacks required fields");
+        assert_eq!(error.code(), tonic::Code::InvalidArgument);
+        assert!(error.message().contains("identity.soul"));
+
+        runtime
+            .upsert_agent_profile(pb::AgentProfile {
+                agent_id: "agent-a".to_string(),
+                display_name: "Agent A".to_string(),
+                material_json: json!({"identity": {"mission": "help", "soul": "curious"}})
+                    .to_string(),
+                ..Default::default()
+            })
+            .await
+            .expect("complete profile");
+        runtime
+            .create_session("agent-a".to_string(), vec!["user-a".to_string()], None)
+            .await
+            .expect("create session with complete profile");
+    }
+
+    #[tokio::test]
+    async fn profiles_persist_across_runtime_restarts() {
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .expect("system time")
+            .as_nanos();
+        let workspace_root = std::env::temp_dir().join(format!("fathom-profile-store-{nanos}"));
+        std::fs::create_dir_all(&workspace_root).expect("create workspace root");
+        let profile_dir = workspace_root.join("profiles");
+
+        let runtime = Runtime::new_with_workspace_root(
+            2,
+            10,
+            workspace_root.clone(),
+            Some(profile_dir.clone()),
+        )
+        .expect("runtime");
+        let user = runtime
+            .upsert_user_profile(pb::UserProfile {
+                user_id: "user-a".to_string(),
+                name: "Ada".to_string(),
+                nickname: "ada".to_string(),
+                material_json: r#"{"memory":{"long_term":"likes tea"}}"#.to_string(),
+                updated_at_unix_ms: 0,
+            })
+            .await
+            .expect("upsert user");
+        let agent = runtime
+            .upsert_agent_profile(pb::AgentProfile {
+                agent_id: "agent-a".to_string(),
+                display_name: "Agent A".to_string(),
+                material_json: "{}".to_string(),
+                spec_version: 0,
+                updated_at_unix_ms: 0,
+            })
+            .await
+            .expect("upsert agent");
+        drop(runtime);
+
+        let restarted =
+            Runtime::new_with_workspace_root(2, 10, workspace_root.clone(), Some(profile_dir))
+                .expect("restarted runtime");
+        assert_eq!(restarted.fetch_user_profile("user-a").await, Some(user));
+        assert_eq!(restarted.fetch_agent_profile("agent-a").await, Some(agent));
+
+        let _ = std::fs::remove_dir_all(&workspace_root);
+    }
+
+    #[tokio::test]
+    async fn failed_profile_persist_rolls_back_in_memory_state() {
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .expect("system time")
+            .as_nanos();
+        let workspace_root = std::env::temp_dir().join(format!("fathom-profile-rollback-{nanos}"));
+        std::fs::create_dir_all(&workspace_root).expect("create workspace root");
+        let profile_dir = workspace_root.join("profiles");
+
+        let runtime = Runtime::new_with_workspace_root(
+            2,
+            10,
+            workspace_root.clone(),
+            Some(profile_dir.clone()),
+        )
+        .expect("runtime");
+        let agent = runtime
+            .upsert_agent_profile(pb::AgentProfile {
+                agent_id: "agent-a".to_string(),
+                display_name: "Agent A".to_string(),
+                material_json: "{}".to_string(),
+                spec_version: 0,
+                updated_at_unix_ms: 0,
+            })
+            .await
+            .expect("upsert agent");
+
+        std::fs::remove_dir_all(&profile_dir).expect("remove profile dir");
+        std::fs::write(&profile_dir, "not a directory").expect("block profile dir");
+
+        let error = runtime
+            .upsert_agent_profile(pb::AgentProfile {
+                display_name: "Renamed".to_string(),
+                spec_version: 0,
+                updated_at_unix_ms: 0,
+                ..agent.clone()
+            })
+            .await
+            .expect_err("persist should fail");
+        assert_eq!(error.code(), tonic::Code::Internal);
+        assert_eq!(
+            runtime.fetch_agent_profile("agent-a").await,
+            Some(agent.clone())
+        );
+
+        runtime
+            .append_profile_memory(ProfileMemoryTarget::Agent, "agent-a", "remember this")
+            .await
+            .expect_err("memory persist should fail");
+        assert_eq!(runtime.fetch_agent_profile("agent-a").await, Some(agent));
+
+        runtime
+            .upsert_user_profile(pb::UserProfile {
+                user_id: "user-new".to_string(),
+                name: "New".to_string(),
+                nickname: "new".to_string(),
+                material_json: "{}".to_string(),
+                updated_at_unix_ms: 0,
+            })
+            .await
+            .expect_err("new user persist should fail");
+        assert_eq!(runtime.fetch_user_profile("user-new").await, None);
+
+        let _ = std::fs::remove_dir_all(&workspace_root);
+    }
+
+    #[tokio::test]
+    async fn default_profiles_created_on_demand_are_persisted() {
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .expect("system time")
+            .as_nanos();
+        let workspace_root = std::env::temp_dir().join(format!("fathom-profile-defaults-{nanos}"));
+        std::fs::create_dir_all(&workspace_root).expect("create workspace root");
+        let profile_dir = workspace_root.join("profiles");
+        let open_runtime = || {
+            Runtime::new_with_workspace_root(
+                2,
+                10,
+                workspace_root.clone(),
+                Some(profile_dir.clone()),
+            )
+            .expect("runtime")
+        };
+
+        let runtime = open_runtime();
+        let agent = runtime
+            .get_or_create_agent_profile("agent-a")
+            .await
+            .expect("agent default");
+        let user = runtime
+            .get_or_create_user_profile("user-a")
+            .await
+            .expect("user default");
+
+        let reopened = open_runtime();
+        assert_eq!(reopened.fetch_agent_profile("agent-a").await, Some(agent));
+        assert_eq!(reopened.fetch_user_profile("user-a").await, Some(user));
+
+        std::fs::remove_dir_all(&profile_dir).expect("remove profile dir");
+        std::fs::write(&profile_dir, "not a directory").expect("block profile dir");
+        let error = reopened
+            .get_or_create_agent_profile("agent-b")
+            .await
+            .expect_err("persist should fail");
+        assert_eq!(error.code(), tonic::Code::Internal);
+        assert_eq!(reopened.fetch_agent_profile("agent-b").await, None);
+
+        let _ = std::fs::remove_dir_all(&workspace_root);
+    }
+
+    #[tokio::test]
+    async fn renaming_a_profile_moves_it_to_the_new_id() {
+        let runtime = Runtime::new(2, 10);
+        let agent = runtime
+            .get_or_create_agent_profile("agent-old")
+            .await
+            .expect("agent-old");
+        runtime
+            .get_or_create_agent_profile("agent-taken")
+            .await
+            .expect("agent-taken");
+
+        let renamed = runtime
+            .rename_agent_profile("agent-old", "agent-new")
+            .await
+            .expect("rename agent profile");
+        assert_eq!(renamed.agent_id, "agent-new");
+        assert_eq!(renamed.material_json, agent.material_json);
+        assert_eq!(runtime.fetch_agent_profile("agent-old").await, None);
+        assert_eq!(
+            runtime.fetch_agent_profile("agent-new").await,
+            Some(renamed)
+        );
+
+        let taken = runtime
+            .rename_agent_profile("agent-new", "agent-taken")
+            .await
+            .expect_err("target id exists");
+        assert_eq!(taken.code(), tonic::Code::AlreadyExists);
+        let missing = runtime
+            .rename_agent_profile("agent-old", "agent-other")
+            .await
+            .expect_err("source id is gone");
+        assert_eq!(missing.code(), tonic::Code::NotFound);
+
+        runtime
+            .create_session("agent-new".to_string(), vec!["user-a".to_string()], None)
+            .await
+            .expect("create session");
+        let in_use = runtime
+            .rename_agent_profile("agent-new", "agent-other")
+            .await
+            .expect_err("agent is referenced by a session");
+        assert_eq!(in_use.code(), tonic::Code::FailedPrecondition);
+        let user_in_use = runtime
+            .rename_user_profile("user-a", "user-b")
+            .await
+            .expect_err("user is referenced by a session");
+        assert_eq!(user_in_use.code(), tonic::Code::FailedPrecondition);
+        assert!(runtime.fetch_agent_profile("agent-new").await.is_some());
+    }
 }
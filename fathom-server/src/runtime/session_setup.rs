@@ -5,7 +5,7 @@ use tonic::Status;
 use super::Runtime;
 use crate::capability_domain::CapabilityDomainRegistry;
 use crate::session::SessionState;
-use crate::util::dedup_ids;
+use crate::util::{dedup_ids, default_agent_profile, default_user_profile};
 use fathom_protocol::pb;
 
 pub(crate) struct SessionSetupRequest {
@@ -24,8 +24,9 @@ pub(crate) struct SessionSetupResolved {
 
 #[tonic::async_trait]
 pub(crate) trait SessionSetupContext: Send + Sync {
-    async fn get_or_create_agent_profile(&self, agent_id: &str) -> pb::AgentProfile;
-    async fn get_or_create_user_profile(&self, user_id: &str) -> pb::UserProfile;
+    async fn get_or_create_agent_profile(&self, agent_id: &str)
+    -> Result<pb::AgentProfile, Status>;
+    async fn get_or_create_user_profile(&self, user_id: &str) -> Result<pb::UserProfile, Status>;
     fn next_session_id(&self) -> String;
 }
 
@@ -41,11 +42,14 @@ impl<'a> RuntimeSessionSetupContext<'a> {
 
 #[tonic::async_trait]
 impl SessionSetupContext for RuntimeSessionSetupContext<'_> {
-    async fn get_or_create_agent_profile(&self, agent_id: &str) -> pb::AgentProfile {
+    async fn get_or_create_agent_profile(
+        &self,
+        agent_id: &str,
+    ) -> Result<pb::AgentProfile, Status> {
         self.runtime.get_or_create_agent_profile(agent_id).await
     }
 
-    async fn get_or_create_user_profile(&self, user_id: &str) -> pb::UserProfile {
+    async fn get_or_create_user_profile(&self, user_id: &str) -> Result<pb::UserProfile, Status> {
         self.runtime.get_or_create_user_profile(user_id).await
     }
 
@@ -54,14 +58,59 @@ impl SessionSetupContext for RuntimeSessionSetupContext<'_> {
     }
 }
 
+pub(crate) struct ValidateOnlySessionSetupContext<'a> {
+    runtime: &'a Runtime,
+}
+
+impl<'a> ValidateOnlySessionSetupContext<'a> {
+    pub(crate) fn new(runtime: &'a Runtime) -> Self {
+        Self { runtime }
+    }
+}
+
+#[tonic::async_trait]
+impl SessionSetupContext for ValidateOnlySessionSetupContext<'_> {
+    async fn get_or_create_agent_profile(
+        &self,
+        agent_id: &str,
+    ) -> Result<pb::AgentProfile, Status> {
+        Ok(self
+            .runtime
+            .fetch_agent_profile(agent_id)
+            .await
+            .unwrap_or_else(|| default_agent_profile(agent_id)))
+    }
+
+    async fn get_or_create_user_profile(&self, user_id: &str) -> Result<pb::UserProfile, Status> {
+        Ok(self
+            .runtime
+            .fetch_user_profile(user_id)
+            .await
+            .unwrap_or_else(|| default_user_profile(user_id)))
+    }
+
+    fn next_session_id(&self) -> String {
+        String::new()
+    }
+}
+
 #[derive(Clone)]
 pub(crate) struct DefaultSessionSetupPolicy {
     registry: CapabilityDomainRegistry,
+    allow_agent_only_sessions: bool,
 }
 
 impl DefaultSessionSetupPolicy {
     pub(crate) fn new(registry: CapabilityDomainRegistry) -> Self {
-        Self { registry }
+        Self {
+            registry,
+            allow_agent_only_sessions: false,
+        }
+    }
+
+    pub(crate) fn with_agent_only_sessions(mut self, allowed: bool) -> Self {
+        self.allow_agent_only_sessions = allowed;
+        self
     }
 }
 
@@ -86,10 +135,17 @@ impl SessionSetupPolicy for DefaultSessionSetupPolicy {
         }
 
         let participant_user_ids = dedup_ids(request.participant_user_ids);
-        let agent_profile_copy = context.get_or_create_agent_profile(&request.agent_id).await;
+        if participant_user_ids.is_empty() && !self.allow_agent_only_sessions {
+            return Err(Status::invalid_argument(
+                "participant_user_ids must contain at least one non-empty user id",
+            ));
+        }
+        let agent_profile_copy = context
+            .get_or_create_agent_profile(&request.agent_id)
+            .await?;
         let mut participant_user_profiles_copy = HashMap::new();
         for user_id in &participant_user_ids {
-            let profile = context.get_or_create_user_profile(user_id).await;
+            let profile = context.get_or_create_user_profile(user_id).await?;
             participant_user_profiles_copy.insert(user_id.clone(), profile);
         }
 
@@ -126,6 +182,8 @@ mod tests {
     use std::collections::HashMap;
     use std::path::PathBuf;
 
+    use tonic::Status;
+
     use super::{
         DefaultSessionSetupPolicy, SessionSetupContext, SessionSetupPolicy, SessionSetupRequest,
     };
@@ -142,18 +200,26 @@ mod tests {
 
     #[tonic::async_trait]
     impl SessionSetupContext for FakeSetupContext {
-        async fn get_or_create_agent_profile(&self, agent_id: &str) -> pb::AgentProfile {
-            self.agent_profiles
+        async fn get_or_create_agent_profile(
+            &self,
+            agent_id: &str,
+        ) -> Result<pb::AgentProfile, Status> {
+            Ok(self
+                .agent_profiles
                 .get(agent_id)
                 .cloned()
-                .unwrap_or_else(|| default_agent_profile(agent_id))
+                .unwrap_or_else(|| default_agent_profile(agent_id)))
         }
 
-        async fn get_or_create_user_profile(&self, user_id: &str) -> pb::UserProfile {
-            self.user_profiles
+        async fn get_or_create_user_profile(
+            &self,
+            user_id: &str,
+        ) -> Result<pb::UserProfile, Status> {
+            Ok(self
+                .user_profiles
                 .get(user_id)
                 .cloned()
-                .unwrap_or_else(|| default_user_profile(user_id))
+                .unwrap_or_else(|| default_user_profile(user_id)))
         }
 
         fn next_session_id(&self) -> String {
@@ -204,4 +270,61 @@ mod tests {
                 .contains(fathom_capability_domain_shell::SHELL_CAPABILITY_DOMAIN_ID)
         );
     }
+
+    #[tokio::test]
+    async fn default_session_setup_policy_rejects_sessions_without_participants() {
+        let context = FakeSetupContext {
+            workspace_root: PathBuf::from("/tmp/fathom"),
+            agent_profiles: HashMap::new(),
+            user_profiles: HashMap::new(),
+            next_session_id: "session-42".to_string(),
+        };
+        let policy = DefaultSessionSetupPolicy::new(build_default_capability_domain_registry(
+            context.workspace_root.as_path(),
+        ));
+
+        let error = match policy
+            .resolve(
+                &context,
+                SessionSetupRequest {
+                    agent_id: "agent-a".to_string(),
+                    participant_user_ids: vec![" ".to_string(), String::new()],
+                },
+            )
+            .await
+        {
+            Ok(_) => panic!("agent-only session should be rejected"),
+            Err(error) => error,
+        };
+
+        assert_eq!(error.code(), tonic::Code::InvalidArgument);
+    }
+
+    #[tokio::test]
+    async fn session_setup_policy_allows_agent_only_sessions_when_enabled() {
+        let context = FakeSetupContext {
+            workspace_root: PathBuf::from("/tmp/fathom"),
+            agent_profiles: HashMap::new(),
+            user_profiles: HashMap::new(),
+            next_session_id: "session-42".to_string(),
+        };
+        let policy = DefaultSessionSetupPolicy::new(build_default_capability_domain_registry(
+            context.workspace_root.as_path(),
+        ))
+        .with_agent_only_sessions(true);
+
+        let resolved = policy
+            .resolve(
+                &context,
+                SessionSetupRequest {
+                    agent_id: "agent-a".to_string(),
+                    participant_user_ids: Vec::new(),
+                },
+            )
+            .await
+            .expect("agent-only session should resolve");
+
+        assert!(resolved.participant_user_ids.is_empty());
+        assert!(resolved.participant_user_profiles_copy.is_empty());
+    }
 }
@@ -1,12 +1,20 @@
-use tokio::sync::{broadcast, mpsc, oneshot};
+use std::time::Duration;
+
+use futures_util::future::join_all;
+use tokio::sync::{mpsc, oneshot};
 use tonic::Status;
 
+use super::limits::MAX_EXECUTION_CAPACITY;
 use super::session_setup::{
-    DefaultSessionSetupPolicy, RuntimeSessionSetupContext, SessionSetupPolicy, SessionSetupRequest,
-    build_session_state,
+    DefaultSessionSetupPolicy, RuntimeSessionSetupContext, SessionSetupContext, SessionSetupPolicy,
+    SessionSetupRequest, ValidateOnlySessionSetupContext, build_session_state,
 };
-use super::{EVENT_BUFFER_SIZE, Runtime, SESSION_CMD_BUFFER_SIZE};
-use crate::session::{SessionCommand, SessionRuntime, run_session_actor};
+use super::{EVENT_BUFFER_SIZE, Runtime};
+use crate::cron::CronEntry;
+use crate::history;
+use crate::profile_material::ProfileMemoryTarget;
+use crate::session::event_stream::session_event_channel;
+use crate::session::{SessionCommand, SessionRuntime, SessionState, run_session_actor};
 use fathom_protocol::pb;
 
 impl Runtime {
@@ -14,44 +22,182 @@ impl Runtime {
         &self,
         agent_id: String,
         participant_user_ids: Vec<String>,
+        execution_capacity: Option<usize>,
     ) -> Result<pb::SessionSummary, Status> {
-        let setup_policy = DefaultSessionSetupPolicy::new(self.capability_domain_registry());
-        let setup_context = RuntimeSessionSetupContext::new(self);
-        let setup = setup_policy
-            .resolve(
-                &setup_context,
-                SessionSetupRequest {
-                    agent_id,
-                    participant_user_ids,
-                },
+        let mut state = self
+            .resolve_session_state(
+                &RuntimeSessionSetupContext::new(self),
+                agent_id,
+                participant_user_ids,
+                execution_capacity,
             )
             .await?;
-        let session_id = setup.session_id.clone();
-        let state = build_session_state(setup);
-        let session_summary = state.to_summary();
+        if let Some(history_dir) = self.history_dir() {
+            let session_id = state.session_id.clone();
+            let log = history::HistoryLog::for_session(&history_dir, &session_id);
+            let record = history::SessionRecord::from_state(&state);
+            tokio::task::spawn_blocking(move || {
+                history::write_session_record(&history_dir, &session_id, &record)
+            })
+            .await
+            .map_err(|error| Status::internal(format!("session record task failed: {error}")))?
+            .map_err(|error| {
+                Status::internal(format!("failed to write session record: {error}"))
+            })?;
+            state.history_log = Some(log);
+        }
+        self.start_session(state).await
+    }
 
-        let (events_tx, _) = broadcast::channel(EVENT_BUFFER_SIZE);
-        let (command_tx, command_rx) = mpsc::channel(SESSION_CMD_BUFFER_SIZE);
+    pub(crate) async fn resume_session(
+        &self,
+        session_id: String,
+        agent_id: String,
+        participant_user_ids: Vec<String>,
+        execution_capacity: Option<usize>,
+    ) -> Result<pb::SessionSummary, Status> {
+        let Some(history_dir) = self.history_dir() else {
+            return Err(Status::failed_precondition(
+                "resuming a session requires a history directory",
+            ));
+        };
+        if self.inner.sessions.read().await.contains_key(&session_id) {
+            return Err(Status::already_exists(format!(
+                "session `{session_id}` is already running"
+            )));
+        }
+        let mut state = self
+            .resolve_session_state(
+                &RuntimeSessionSetupContext::new(self),
+                agent_id,
+                participant_user_ids,
+                execution_capacity,
+            )
+            .await?;
+
+        let log = history::HistoryLog::for_session(&history_dir, &session_id);
+        let (record, loaded) = {
+            let session_id = session_id.clone();
+            let log = log.clone();
+            tokio::task::spawn_blocking(move || {
+                (
+                    history::read_session_record(&history_dir, &session_id),
+                    log.load(),
+                )
+            })
+            .await
+            .map_err(|error| Status::internal(format!("session history task failed: {error}")))?
+        };
+        let record = record
+            .map_err(|error| Status::internal(format!("failed to read session record: {error}")))?
+            .ok_or_else(|| {
+                Status::not_found(format!("no persisted history for session `{session_id}`"))
+            })?;
+        if record != history::SessionRecord::from_state(&state) {
+            return Err(Status::permission_denied(format!(
+                "session `{session_id}` belongs to a different agent or participant set"
+            )));
+        }
+
+        state.session_id = session_id;
+        history::restore_history(&mut state, log, loaded);
+        self.start_session(state).await
+    }
+
+    async fn start_session(&self, state: SessionState) -> Result<pb::SessionSummary, Status> {
+        let session_summary = self.session_summary(&state);
+        let (events_tx, _) = session_event_channel(EVENT_BUFFER_SIZE);
+        let (command_tx, command_rx) = mpsc::channel(self.session_command_capacity());
+
+        {
+            let mut sessions = self.inner.sessions.write().await;
+            if sessions.contains_key(&state.session_id) {
+                return Err(Status::already_exists(format!(
+                    "session `{}` is already running",
+                    state.session_id
+                )));
+            }
+            self.ensure_session_profiles_exist(&state).await?;
+            sessions.insert(
+                state.session_id.clone(),
+                SessionRuntime {
+                    agent_id: state.agent_id.clone(),
+                    participant_user_ids: state.participant_user_ids.clone(),
+                    command_tx: command_tx.clone(),
+                    events_tx: events_tx.clone(),
+                },
+            );
+        }
 
         tokio::spawn(run_session_actor(
             self.clone(),
             state,
-            command_tx.clone(),
+            command_tx,
             command_rx,
-            events_tx.clone(),
+            events_tx,
         ));
 
-        self.inner.sessions.write().await.insert(
-            session_id,
-            SessionRuntime {
-                command_tx,
-                events_tx,
-            },
-        );
-
         Ok(session_summary)
     }
 
+    pub(crate) async fn validate_session(
+        &self,
+        agent_id: String,
+        participant_user_ids: Vec<String>,
+        execution_capacity: Option<usize>,
+    ) -> Result<pb::SessionSummary, Status> {
+        let state = self
+            .resolve_session_state(
+                &ValidateOnlySessionSetupContext::new(self),
+                agent_id,
+                participant_user_ids,
+                execution_capacity,
+            )
+            .await?;
+        Ok(self.session_summary(&state))
+    }
+
+    async fn resolve_session_state(
+        &self,
+        setup_context: &dyn SessionSetupContext,
+        agent_id: String,
+        participant_user_ids: Vec<String>,
+        execution_capacity: Option<usize>,
+    ) -> Result<SessionState, Status> {
+        if execution_capacity
+            .is_some_and(|capacity| capacity == 0 || capacity as u64 > MAX_EXECUTION_CAPACITY)
+        {
+            return Err(Status::invalid_argument(format!(
+                "execution_capacity must be between 1 and {MAX_EXECUTION_CAPACITY}"
+            )));
+        }
+        let setup_policy = DefaultSessionSetupPolicy::new(self.capability_domain_registry())
+            .with_agent_only_sessions(self.agent_only_sessions_allowed());
+        let setup = setup_policy
+            .resolve(
+                setup_context,
+                SessionSetupRequest {
+                    agent_id,
+                    participant_user_ids,
+                },
+            )
+            .await?;
+        self.ensure_required_agent_fields(&setup.agent_profile_copy)?;
+        let mut state = build_session_state(setup);
+        state.result_preview_limits = self.result_preview_limits();
+        state.history_compaction_limits = self.history_compaction_limits();
+        state.queue_depth_warning_threshold = self.queue_depth_warning_threshold();
+        state.execution_capacity = execution_capacity;
+        Ok(state)
+    }
+
+    fn session_summary(&self, state: &SessionState) -> pb::SessionSummary {
+        let mut session_summary = state.to_summary();
+        session_summary.execution_capacity =
+            state.execution_capacity(self.execution_capacity()) as u64;
+        session_summary
+    }
+
     pub(crate) async fn list_sessions(&self) -> Result<Vec<pb::SessionSummary>, Status> {
         let sessions = self
             .inner
@@ -66,12 +212,10 @@ impl Runtime {
         for session in sessions {
             let (response_tx, response_rx) = oneshot::channel();
             session
-                .command_tx
-                .send(SessionCommand::GetSummary {
+                .send_command(SessionCommand::GetSummary {
                     respond_to: response_tx,
                 })
-                .await
-                .map_err(|_| Status::unavailable("session actor unavailable"))?;
+                .await?;
             let summary = response_rx
                 .await
                 .map_err(|_| Status::unavailable("session summary unavailable"))?;
@@ -92,6 +236,62 @@ impl Runtime {
             .ok_or_else(|| Status::not_found("session not found"))
     }
 
+    pub(crate) async fn ensure_session_profile_target(
+        &self,
+        session_id: &str,
+        target: ProfileMemoryTarget,
+        target_id: &str,
+    ) -> Result<(), Status> {
+        let session = self.get_session(session_id).await?;
+        let (response_tx, response_rx) = oneshot::channel();
+        session
+            .send_command(SessionCommand::GetSummary {
+                respond_to: response_tx,
+            })
+            .await?;
+        let summary = response_rx
+            .await
+            .map_err(|_| Status::unavailable("session summary unavailable"))?;
+        let is_participant = match target {
+            ProfileMemoryTarget::Agent => summary.agent_id == target_id,
+            ProfileMemoryTarget::User => summary
+                .participant_user_ids
+                .iter()
+                .any(|user_id| user_id == target_id),
+        };
+        if !is_participant {
+            return Err(Status::permission_denied(format!(
+                "{} `{target_id}` is not a participant of session `{session_id}`",
+                target.as_str()
+            )));
+        }
+        Ok(())
+    }
+
+    pub(crate) async fn delete_session(
+        &self,
+        session_id: &str,
+    ) -> Result<pb::SessionSummary, Status> {
+        let session = self
+            .inner
+            .sessions
+            .write()
+            .await
+            .remove(session_id)
+            .ok_or_else(|| Status::not_found("session not found"))?;
+        self.inner.cron_scheduler.unschedule_session(session_id);
+        let (response_tx, response_rx) = oneshot::channel();
+        session
+            .send_command(SessionCommand::Shutdown {
+                reason: "session deleted".to_string(),
+                respond_to: Some(response_tx),
+            })
+            .await?;
+        response_rx
+            .await
+            .map_err(|_| Status::unavailable("session actor unavailable"))
+    }
+
     pub(crate) async fn enqueue_trigger(
         &self,
         session_id: &str,
@@ -100,18 +300,96 @@ impl Runtime {
         let session = self.get_session(session_id).await?;
         let (response_tx, response_rx) = oneshot::channel();
         session
-            .command_tx
-            .send(SessionCommand::EnqueueTrigger {
+            .send_command(SessionCommand::EnqueueTrigger {
                 trigger,
                 respond_to: response_tx,
             })
-            .await
-            .map_err(|_| Status::unavailable("session actor unavailable"))?;
+            .await?;
         response_rx
             .await
             .map_err(|_| Status::unavailable("session actor unavailable"))?
     }
 
+    pub(crate) async fn clear_trigger_queue(
+        &self,
+        session_id: &str,
+    ) -> Result<pb::ClearTriggerQueueResponse, Status> {
+        let session = self.get_session(session_id).await?;
+        let (response_tx, response_rx) = oneshot::channel();
+        session
+            .send_command(SessionCommand::ClearTriggerQueue {
+                respond_to: response_tx,
+            })
+            .await?;
+        response_rx
+            .await
+            .map_err(|_| Status::unavailable("session actor unavailable"))
+    }
+
+    pub(crate) async fn compact_session_history(
+        &self,
+        session_id: &str,
+    ) -> Result<pb::CompactSessionHistoryResponse, Status> {
+        let session = self.get_session(session_id).await?;
+        let (response_tx, response_rx) = oneshot::channel();
+        session
+            .send_command(SessionCommand::Compact {
+                respond_to: response_tx,
+            })
+            .await?;
+        response_rx
+            .await
+            .map_err(|_| Status::unavailable("session actor unavailable"))
+    }
+
+    pub(crate) async fn schedule_heartbeat(
+        &self,
+        session_id: &str,
+        delay_ms: u64,
+    ) -> Result<String, Status> {
+        let max_delay_ms = self.max_heartbeat_delay_ms();
+        if delay_ms == 0 || delay_ms > max_delay_ms {
+            return Err(Status::invalid_argument(format!(
+                "delay_ms must be between 1 and {max_delay_ms}"
+            )));
+        }
+        let session = self.get_session(session_id).await?;
+        let (response_tx, response_rx) = oneshot::channel();
+        session
+            .send_command(SessionCommand::ScheduleHeartbeat {
+                delay: Duration::from_millis(delay_ms),
+                respond_to: response_tx,
+            })
+            .await?;
+        response_rx
+            .await
+            .map_err(|_| Status::unavailable("session actor unavailable"))
+    }
+
+    pub(crate) async fn schedule_cron(
+        &self,
+        session_id: &str,
+        key: String,
+        interval: Duration,
+    ) -> Result<bool, Status> {
+        self.get_session(session_id).await?;
+        self.inner
+            .cron_scheduler
+            .schedule(
+                self.clone(),
+                CronEntry {
+                    session_id: session_id.to_string(),
+                    key,
+                    interval,
+                },
+            )
+            .map_err(Status::invalid_argument)
+    }
+
+    pub(crate) fn unschedule_cron(&self, session_id: &str, key: &str) -> bool {
+        self.inner.cron_scheduler.unschedule(session_id, key)
+    }
+
     pub(crate) async fn list_executions(
         &self,
         session_id: &str,
@@ -119,12 +397,49 @@ impl Runtime {
         let session = self.get_session(session_id).await?;
         let (response_tx, response_rx) = oneshot::channel();
         session
-            .command_tx
-            .send(SessionCommand::ListExecutions {
+            .send_command(SessionCommand::ListExecutions {
+                respond_to: response_tx,
+            })
+            .await?;
+        response_rx
+            .await
+            .map_err(|_| Status::unavailable("session actor unavailable"))
+    }
+
+    pub(crate) async fn get_execution(
+        &self,
+        session_id: &str,
+        execution_id: String,
+    ) -> Result<pb::Execution, Status> {
+        let session = self.get_session(session_id).await?;
+        let (response_tx, response_rx) = oneshot::channel();
+        session
+            .send_command(SessionCommand::GetExecution {
+                execution_id,
                 respond_to: response_tx,
             })
+            .await?;
+        response_rx
             .await
-            .map_err(|_| Status::unavailable("session actor unavailable"))?;
+            .map_err(|_| Status::unavailable("session actor unavailable"))?
+            .ok_or_else(|| Status::not_found("execution not found"))
+    }
+
+    pub(crate) async fn get_history(
+        &self,
+        session_id: &str,
+        offset: u64,
+        page_size: u64,
+    ) -> Result<pb::GetHistoryResponse, Status> {
+        let session = self.get_session(session_id).await?;
+        let (response_tx, response_rx) = oneshot::channel();
+        session
+            .send_command(SessionCommand::GetHistory {
+                offset,
+                page_size,
+                respond_to: response_tx,
+            })
+            .await?;
         response_rx
             .await
             .map_err(|_| Status::unavailable("session actor unavailable"))
@@ -138,15 +453,538 @@ impl Runtime {
         let session = self.get_session(session_id).await?;
         let (response_tx, response_rx) = oneshot::channel();
         session
-            .command_tx
-            .send(SessionCommand::CancelExecution {
+            .send_command(SessionCommand::CancelExecution {
                 execution_id,
                 respond_to: response_tx,
             })
+            .await?;
+        response_rx
             .await
-            .map_err(|_| Status::unavailable("session actor unavailable"))?;
+            .map_err(|_| Status::unavailable("session actor unavailable"))?
+    }
+
+    pub(crate) async fn run_action(
+        &self,
+        session_id: &str,
+        action_id: String,
+        args_json: String,
+    ) -> Result<pb::Execution, Status> {
+        let session = self.get_session(session_id).await?;
+        let (response_tx, response_rx) = oneshot::channel();
+        session
+            .send_command(SessionCommand::RunAction {
+                action_id,
+                args_json,
+                respond_to: response_tx,
+            })
+            .await?;
         response_rx
             .await
             .map_err(|_| Status::unavailable("session actor unavailable"))?
     }
+
+    pub(crate) async fn batch_cancel_sessions(&self, reason: &str) -> u64 {
+        let sessions = self
+            .inner
+            .sessions
+            .write()
+            .await
+            .drain()
+            .map(|(_, session)| session)
+            .collect::<Vec<_>>();
+        self.inner.cron_scheduler.unschedule_all();
+
+        join_all(sessions.into_iter().map(|session| async move {
+            session
+                .send_command(SessionCommand::Shutdown {
+                    reason: reason.to_string(),
+                    respond_to: None,
+                })
+                .await
+                .is_ok()
+        }))
+        .await
+        .into_iter()
+        .filter(|notified| *notified)
+        .count() as u64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+    use tokio::sync::{mpsc, oneshot};
+
+    use fathom_protocol::pb;
+
+    use super::RuntimeSessionSetupContext;
+    use crate::runtime::{Runtime, SESSION_CMD_BUFFER_SIZE};
+    use crate::session::event_stream::session_event_channel;
+    use crate::session::{SessionCommand, SessionRuntime};
+
+    #[tokio::test]
+    async fn sessions_cannot_start_on_a_profile_renamed_mid_creation() {
+        let runtime = Runtime::new(2, 10);
+        let state = runtime
+            .resolve_session_state(
+                &RuntimeSessionSetupContext::new(&runtime),
+                "agent-old".to_string(),
+                vec!["user-a".to_string()],
+                None,
+            )
+            .await
+            .expect("resolve session state");
+
+        runtime
+            .rename_agent_profile("agent-old", "agent-new")
+            .await
+            .expect("rename before the session is live");
+        let error = runtime
+            .start_session(state)
+            .await
+            .expect_err("renamed profile should abort the start");
+        assert_eq!(error.code(), tonic::Code::Aborted);
+        assert!(runtime.list_sessions().await.expect("list").is_empty());
+
+        runtime
+            .create_session("agent-new".to_string(), vec!["user-a".to_string()], None)
+            .await
+            .expect("create session");
+        let error = runtime
+            .rename_agent_profile("agent-new", "agent-other")
+            .await
+            .expect_err("live session blocks rename");
+        assert_eq!(error.code(), tonic::Code::FailedPrecondition);
+        let error = runtime
+            .rename_user_profile("user-a", "user-b")
+            .await
+            .expect_err("live session blocks user rename");
+        assert_eq!(error.code(), tonic::Code::FailedPrecondition);
+    }
+
+    #[tokio::test]
+    async fn batch_cancel_sessions_expires_every_session() {
+        let runtime = Runtime::new(2, 10);
+        let mut events_rxs = Vec::new();
+        for user_id in ["user-a", "user-b"] {
+            let session = runtime
+                .create_session("agent-a".to_string(), vec![user_id.to_string()], None)
+                .await
+                .expect("create session");
+            let session_runtime = runtime
+                .get_session(&session.session_id)
+                .await
+                .expect("session runtime");
+            events_rxs.push(session_runtime.events_tx.subscribe());
+        }
+
+        let notified = runtime.batch_cancel_sessions("maintenance").await;
+
+        assert_eq!(notified, 2);
+        assert!(
+            runtime
+                .list_sessions()
+                .await
+                .expect("list sessions")
+                .is_empty()
+        );
+        for mut events_rx in events_rxs {
+            let event = events_rx.recv().await.expect("session expired event");
+            let Some(pb::session_event::Kind::SessionExpired(expired)) = event.kind else {
+                panic!("expected session expired event");
+            };
+            assert_eq!(expired.reason, "maintenance");
+        }
+    }
+
+    #[tokio::test]
+    async fn enqueue_trigger_fails_fast_when_command_queue_is_full() {
+        let runtime = Runtime::new(2, 10);
+        let (command_tx, _command_rx) = mpsc::channel(1);
+        let (events_tx, _) = session_event_channel(8);
+        let (summary_tx, _summary_rx) = oneshot::channel();
+        command_tx
+            .try_send(SessionCommand::GetSummary {
+                respond_to: summary_tx,
+            })
+            .expect("fill command queue");
+        runtime.inner.sessions.write().await.insert(
+            "session-full".to_string(),
+            SessionRuntime {
+                agent_id: "agent-a".to_string(),
+                participant_user_ids: Vec::new(),
+                command_tx,
+                events_tx,
+            },
+        );
+
+        let started = Instant::now();
+        let error = runtime
+            .enqueue_trigger(
+                "session-full",
+                pb::Trigger {
+                    trigger_id: String::new(),
+                    created_at_unix_ms: 0,
+                    kind: Some(pb::trigger::Kind::Heartbeat(pb::HeartbeatTrigger {})),
+                },
+            )
+            .await
+            .expect_err("full command queue should be rejected");
+
+        assert_eq!(error.code(), tonic::Code::ResourceExhausted);
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+
+    #[tokio::test]
+    async fn create_session_uses_configured_command_capacity() {
+        let runtime = Runtime::new(2, 10);
+        let default_session = runtime
+            .create_session("agent-a".to_string(), vec!["user-a".to_string()], None)
+            .await
+            .expect("create default session");
+        runtime.set_session_command_capacity(512);
+        let tuned_session = runtime
+            .create_session("agent-a".to_string(), vec!["user-a".to_string()], None)
+            .await
+            .expect("create tuned session");
+
+        let default_runtime = runtime
+            .get_session(&default_session.session_id)
+            .await
+            .expect("default session runtime");
+        let tuned_runtime = runtime
+            .get_session(&tuned_session.session_id)
+            .await
+            .expect("tuned session runtime");
+        assert_eq!(
+            default_runtime.command_tx.max_capacity(),
+            SESSION_CMD_BUFFER_SIZE
+        );
+        assert_eq!(tuned_runtime.command_tx.max_capacity(), 512);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn send_command_waits_for_room_in_a_full_command_queue() {
+        let runtime = Runtime::new(2, 10);
+        runtime.set_session_command_capacity(2);
+        let (command_tx, mut command_rx) = mpsc::channel(runtime.session_command_capacity());
+        let (events_tx, _) = session_event_channel(8);
+        let session_runtime = SessionRuntime {
+            agent_id: "agent-a".to_string(),
+            participant_user_ids: Vec::new(),
+            command_tx,
+            events_tx,
+        };
+        for _ in 0..2 {
+            let (summary_tx, _summary_rx) = oneshot::channel();
+            session_runtime
+                .command_tx
+                .try_send(SessionCommand::GetSummary {
+                    respond_to: summary_tx,
+                })
+                .expect("fill command queue");
+        }
+
+        let (summary_tx, _summary_rx) = oneshot::channel();
+        let pending_send = tokio::spawn({
+            let session_runtime = session_runtime.clone();
+            async move {
+                session_runtime
+                    .send_command(SessionCommand::GetSummary {
+                        respond_to: summary_tx,
+                    })
+                    .await
+            }
+        });
+        tokio::time::advance(Duration::from_millis(500)).await;
+        assert!(!pending_send.is_finished());
+
+        command_rx.recv().await.expect("drain one command");
+        pending_send
+            .await
+            .expect("send task")
+            .expect("send succeeds once the queue has room");
+        assert_eq!(session_runtime.command_tx.capacity(), 0);
+    }
+
+    #[tokio::test]
+    async fn create_session_applies_per_session_execution_capacity() {
+        let runtime = Runtime::new(2, 10);
+        let default_session = runtime
+            .create_session("agent-a".to_string(), vec!["user-a".to_string()], None)
+            .await
+            .expect("create default session");
+        let tuned_session = runtime
+            .create_session("agent-a".to_string(), vec!["user-a".to_string()], Some(5))
+            .await
+            .expect("create tuned session");
+        assert_eq!(default_session.execution_capacity, 2);
+        assert_eq!(tuned_session.execution_capacity, 5);
+
+        runtime
+            .update_runtime_limits(pb::UpdateRuntimeLimitsRequest {
+                execution_capacity: 3,
+                foreground_wait_budget_ms: 0,
+            })
+            .await
+            .expect("update runtime limits");
+        let summaries = runtime.list_sessions().await.expect("list sessions");
+        let capacity_of = |session_id: &str| {
+            summaries
+                .iter()
+                .find(|summary| summary.session_id == session_id)
+                .expect("session summary")
+                .execution_capacity
+        };
+        assert_eq!(capacity_of(&default_session.session_id), 3);
+        assert_eq!(capacity_of(&tuned_session.session_id), 5);
+
+        let error = runtime
+            .create_session("agent-a".to_string(), vec!["user-a".to_string()], Some(65))
+            .await
+            .expect_err("oversized session capacity should be rejected");
+        assert_eq!(error.code(), tonic::Code::InvalidArgument);
+    }
+
+    #[tokio::test]
+    async fn deleted_session_stops_its_actor_and_pending_heartbeats() {
+        let runtime = Runtime::new(2, 10);
+        let session = runtime
+            .create_session("agent-a".to_string(), vec!["user-a".to_string()], None)
+            .await
+            .expect("create session");
+        let mut events_rx = runtime
+            .get_session(&session.session_id)
+            .await
+            .expect("session runtime")
+            .events_tx
+            .subscribe();
+        let heartbeat_trigger_id = runtime
+            .schedule_heartbeat(&session.session_id, 50)
+            .await
+            .expect("schedule heartbeat");
+
+        let summary = runtime
+            .delete_session(&session.session_id)
+            .await
+            .expect("delete session");
+        assert_eq!(summary.session_id, session.session_id);
+        assert!(runtime.list_sessions().await.expect("list").is_empty());
+        assert_eq!(
+            runtime
+                .get_session(&session.session_id)
+                .await
+                .err()
+                .map(|status| status.code()),
+            Some(tonic::Code::NotFound)
+        );
+
+        let mut expired = false;
+        let closed = tokio::time::timeout(Duration::from_secs(5), async {
+            loop {
+                match events_rx.recv().await {
+                    Ok(event) => match event.kind {
+                        Some(pb::session_event::Kind::SessionExpired(_)) => expired = true,
+                        Some(pb::session_event::Kind::TriggerAccepted(accepted)) => {
+                            assert_ne!(
+                                accepted.trigger.map(|trigger| trigger.trigger_id),
+                                Some(heartbeat_trigger_id.clone())
+                            );
+                        }
+                        _ => {}
+                    },
+                    Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => {}
+                    Err(tokio::sync::broadcast::error::RecvError::Closed) => return,
+                }
+            }
+        })
+        .await;
+        assert!(closed.is_ok(), "session event stream should close");
+        assert!(expired);
+    }
+
+    #[tokio::test]
+    async fn user_message_trigger_is_exported_in_session_history() {
+        let runtime = Runtime::new(2, 10);
+        let session = runtime
+            .create_session("agent-a".to_string(), vec!["user-a".to_string()], None)
+            .await
+            .expect("create session");
+
+        let empty = runtime
+            .get_history(&session.session_id, 0, 10)
+            .await
+            .expect("history");
+        assert!(
+            runtime
+                .get_history(&session.session_id, empty.total_count, 10)
+                .await
+                .expect("history at end")
+                .entries
+                .is_empty()
+        );
+
+        runtime
+            .enqueue_trigger(
+                &session.session_id,
+                pb::Trigger {
+                    trigger_id: runtime.next_trigger_id(),
+                    created_at_unix_ms: 1,
+                    kind: Some(pb::trigger::Kind::UserMessage(pb::UserMessageTrigger {
+                        user_id: "user-a".to_string(),
+                        text: "hello history".to_string(),
+                    })),
+                },
+            )
+            .await
+            .expect("enqueue user message");
+
+        let recorded = tokio::time::timeout(Duration::from_secs(5), async {
+            loop {
+                let history = runtime
+                    .get_history(&session.session_id, 0, 100)
+                    .await
+                    .expect("history");
+                if let Some(entry) = history
+                    .entries
+                    .iter()
+                    .find(|entry| entry.event_json.contains("hello history"))
+                {
+                    return entry.clone();
+                }
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("user message should reach session history");
+        let event: serde_json::Value =
+            serde_json::from_str(&recorded.event_json).expect("history entry json");
+        assert_eq!(event["actor_kind"], "user");
+        assert_eq!(event["actor_id"], "user-a");
+
+        let missing = runtime
+            .get_history("missing-session", 0, 10)
+            .await
+            .expect_err("unknown session");
+        assert_eq!(missing.code(), tonic::Code::NotFound);
+    }
+
+    #[tokio::test]
+    async fn queued_execution_can_be_fetched_by_id() {
+        let runtime = Runtime::new(2, 10);
+        let session = runtime
+            .create_session("agent-a".to_string(), vec!["user-a".to_string()], None)
+            .await
+            .expect("create session");
+
+        let queued = runtime
+            .run_action(
+                &session.session_id,
+                "system__list_executions".to_string(),
+                "{}".to_string(),
+            )
+            .await
+            .expect("run action");
+        let fetched = runtime
+            .get_execution(&session.session_id, queued.execution_id.clone())
+            .await
+            .expect("get execution");
+        assert_eq!(fetched.execution_id, queued.execution_id);
+        assert_eq!(fetched.action_id, "system__list_executions");
+
+        let error = runtime
+            .get_execution(&session.session_id, "execution-missing".to_string())
+            .await
+            .expect_err("unknown execution");
+        assert_eq!(error.code(), tonic::Code::NotFound);
+    }
+
+    #[tokio::test]
+    async fn persisted_history_is_only_restored_by_an_explicit_matching_resume() {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("system time")
+            .as_nanos();
+        let history_dir = std::env::temp_dir().join(format!("fathom-session-resume-{nanos}"));
+
+        let first = Runtime::new(2, 10);
+        first.set_history_dir(Some(history_dir.clone()));
+        let session = first
+            .create_session("agent-a".to_string(), vec!["user-a".to_string()], None)
+            .await
+            .expect("create session");
+        assert_eq!(session.session_id, "session-1");
+        std::fs::write(
+            history_dir.join("session-1.jsonl"),
+            format!(
+                "{}\n",
+                serde_json::json!({
+                    "ts_unix_ms": 1,
+                    "actor_kind": "user",
+                    "actor_id": "user-a",
+                    "profile_ref": "",
+                    "event": "trigger_user_message",
+                    "payload": {"text": "private transcript"},
+                })
+            ),
+        )
+        .expect("write history");
+
+        let restarted = Runtime::new(2, 10);
+        restarted.set_history_dir(Some(history_dir.clone()));
+        let fresh = restarted
+            .create_session("agent-a".to_string(), vec!["user-b".to_string()], None)
+            .await
+            .expect("create fresh session");
+        assert_eq!(fresh.session_id, "session-2");
+        assert_eq!(
+            restarted
+                .get_history(&fresh.session_id, 0, 10)
+                .await
+                .expect("fresh history")
+                .total_count,
+            0
+        );
+
+        let foreign = restarted
+            .resume_session(
+                "session-1".to_string(),
+                "agent-a".to_string(),
+                vec!["user-b".to_string()],
+                None,
+            )
+            .await
+            .expect_err("resume with other participants should be rejected");
+        assert_eq!(foreign.code(), tonic::Code::PermissionDenied);
+        let unknown = restarted
+            .resume_session(
+                "session-9".to_string(),
+                "agent-a".to_string(),
+                vec!["user-a".to_string()],
+                None,
+            )
+            .await
+            .expect_err("resume without a record should be rejected");
+        assert_eq!(unknown.code(), tonic::Code::NotFound);
+
+        let resumed = restarted
+            .resume_session(
+                "session-1".to_string(),
+                "agent-a".to_string(),
+                vec!["user-a".to_string()],
+                None,
+            )
+            .await
+            .expect("resume session");
+        assert_eq!(resumed.session_id, "session-1");
+        let history = restarted
+            .get_history("session-1", 0, 10)
+            .await
+            .expect("resumed history");
+        assert_eq!(history.total_count, 1);
+        assert!(history.entries[0].event_json.contains("private transcript"));
+
+        let _ = std::fs::remove_dir_all(history_dir);
+    }
 }
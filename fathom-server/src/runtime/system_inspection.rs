@@ -5,11 +5,14 @@ use tokio::sync::oneshot;
 use crate::capability_domain::{
     SystemInspectionError, SystemInspectionFuture, SystemInspectionService,
 };
-use crate::runtime::RuntimeInner;
+use crate::profile_material::ProfileMemoryTarget;
+use crate::runtime::{Runtime, RuntimeInner};
 use crate::session::inspection::{
     ExecutionInspection, ExecutionListPage, ExecutionListQuery, PayloadSlice,
 };
 use crate::session::{SessionCommand, SessionRuntime};
+use crate::util::now_unix_ms;
+use fathom_protocol::pb;
 
 pub(crate) struct RuntimeSystemInspectionService {
     inner: Weak<RuntimeInner>,
@@ -47,15 +50,12 @@ impl SystemInspectionService for RuntimeSystemInspectionService {
             let session = self.session(session_id).await?;
             let (response_tx, response_rx) = oneshot::channel();
             session
-                .command_tx
-                .send(SessionCommand::InspectListExecutions {
+                .send_command(SessionCommand::InspectListExecutions {
                     query,
                     respond_to: response_tx,
                 })
                 .await
-                .map_err(|_| {
-                    SystemInspectionError::Runtime("session actor unavailable".to_string())
-                })?;
+                .map_err(|status| SystemInspectionError::Runtime(status.message().to_string()))?;
             response_rx
                 .await
                 .map_err(|_| {
@@ -74,15 +74,12 @@ impl SystemInspectionService for RuntimeSystemInspectionService {
             let session = self.session(session_id).await?;
             let (response_tx, response_rx) = oneshot::channel();
             session
-                .command_tx
-                .send(SessionCommand::InspectGetExecution {
+                .send_command(SessionCommand::InspectGetExecution {
                     execution_id: execution_id.to_string(),
                     respond_to: response_tx,
                 })
                 .await
-                .map_err(|_| {
-                    SystemInspectionError::Runtime("session actor unavailable".to_string())
-                })?;
+                .map_err(|status| SystemInspectionError::Runtime(status.message().to_string()))?;
             response_rx
                 .await
                 .map_err(|_| {
@@ -103,17 +100,14 @@ impl SystemInspectionService for RuntimeSystemInspectionService {
             let session = self.session(session_id).await?;
             let (response_tx, response_rx) = oneshot::channel();
             session
-                .command_tx
-                .send(SessionCommand::InspectReadExecutionInput {
+                .send_command(SessionCommand::InspectReadExecutionInput {
                     execution_id: execution_id.to_string(),
                     offset,
                     limit,
                     respond_to: response_tx,
                 })
                 .await
-                .map_err(|_| {
-                    SystemInspectionError::Runtime("session actor unavailable".to_string())
-                })?;
+                .map_err(|status| SystemInspectionError::Runtime(status.message().to_string()))?;
             response_rx
                 .await
                 .map_err(|_| {
@@ -134,17 +128,14 @@ impl SystemInspectionService for RuntimeSystemInspectionService {
             let session = self.session(session_id).await?;
             let (response_tx, response_rx) = oneshot::channel();
             session
-                .command_tx
-                .send(SessionCommand::InspectReadExecutionResult {
+                .send_command(SessionCommand::InspectReadExecutionResult {
                     execution_id: execution_id.to_string(),
                     offset,
                     limit,
                     respond_to: response_tx,
                 })
                 .await
-                .map_err(|_| {
-                    SystemInspectionError::Runtime("session actor unavailable".to_string())
-                })?;
+                .map_err(|status| SystemInspectionError::Runtime(status.message().to_string()))?;
             response_rx
                 .await
                 .map_err(|_| {
@@ -153,4 +144,99 @@ impl SystemInspectionService for RuntimeSystemInspectionService {
                 .map_err(SystemInspectionError::Input)
         })
     }
+
+    fn clear_profile_memory<'a>(
+        &'a self,
+        session_id: &'a str,
+        target: ProfileMemoryTarget,
+        target_id: &'a str,
+    ) -> SystemInspectionFuture<'a, usize> {
+        Box::pin(async move {
+            let inner = self.inner.upgrade().ok_or_else(|| {
+                SystemInspectionError::Runtime("runtime is unavailable".to_string())
+            })?;
+            let runtime = Runtime { inner };
+            runtime
+                .ensure_session_profile_target(session_id, target, target_id)
+                .await
+                .map_err(runtime_status_error)?;
+            runtime
+                .clear_profile_memory(target, target_id)
+                .await
+                .map_err(runtime_status_error)
+        })
+    }
+
+    fn append_profile_memory<'a>(
+        &'a self,
+        session_id: &'a str,
+        target: ProfileMemoryTarget,
+        target_id: &'a str,
+        note: &'a str,
+    ) -> SystemInspectionFuture<'a, usize> {
+        Box::pin(async move {
+            let inner = self.inner.upgrade().ok_or_else(|| {
+                SystemInspectionError::Runtime("runtime is unavailable".to_string())
+            })?;
+            let runtime = Runtime { inner };
+            runtime
+                .ensure_session_profile_target(session_id, target, target_id)
+                .await
+                .map_err(runtime_status_error)?;
+            runtime
+                .append_profile_memory(target, target_id, note)
+                .await
+                .map_err(runtime_status_error)
+        })
+    }
+
+    fn request_profile_refresh<'a>(
+        &'a self,
+        session_id: &'a str,
+        refresh: pb::RefreshProfileTrigger,
+    ) -> SystemInspectionFuture<'a, pb::EnqueueTriggerResponse> {
+        Box::pin(async move {
+            let inner = self.inner.upgrade().ok_or_else(|| {
+                SystemInspectionError::Runtime("runtime is unavailable".to_string())
+            })?;
+            let runtime = Runtime { inner };
+            let trigger = pb::Trigger {
+                trigger_id: runtime.next_trigger_id(),
+                created_at_unix_ms: now_unix_ms(),
+                kind: Some(pb::trigger::Kind::RefreshProfile(refresh)),
+            };
+            runtime
+                .enqueue_trigger(session_id, trigger)
+                .await
+                .map_err(runtime_status_error)
+        })
+    }
+
+    fn schedule_heartbeat<'a>(
+        &'a self,
+        session_id: &'a str,
+        delay_ms: u64,
+    ) -> SystemInspectionFuture<'a, String> {
+        Box::pin(async move {
+            let inner = self.inner.upgrade().ok_or_else(|| {
+                SystemInspectionError::Runtime("runtime is unavailable".to_string())
+            })?;
+            Runtime { inner }
+                .schedule_heartbeat(session_id, delay_ms)
+                .await
+                .map_err(runtime_status_error)
+        })
+    }
+}
+
+fn runtime_status_error(status: tonic::Status) -> SystemInspectionError {
+    match status.code() {
+        tonic::Code::NotFound | tonic::Code::InvalidArgument => {
+            SystemInspectionError::Input(status.message().to_string())
+        }
+        tonic::Code::PermissionDenied => {
+            SystemInspectionError::PermissionDenied(status.message().to_string())
+        }
+        _ => SystemInspectionError::Runtime(status.message().to_string()),
+    }
 }
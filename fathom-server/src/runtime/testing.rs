@@ -0,0 +1,47 @@
+use fathom_capability_domain::{
+    CapabilityActionResult, CapabilityActionSubmission, CapabilityDomainSessionContext,
+};
+
+use super::Runtime;
+
+pub(crate) async fn run_domain_action(
+    runtime: &Runtime,
+    capability_domain_id: &str,
+    action_name: &str,
+    args: serde_json::Value,
+) -> CapabilityActionResult {
+    run_session_domain_action(
+        runtime,
+        "session-test",
+        capability_domain_id,
+        action_name,
+        args,
+    )
+    .await
+}
+
+pub(crate) async fn run_session_domain_action(
+    runtime: &Runtime,
+    session_id: &str,
+    capability_domain_id: &str,
+    action_name: &str,
+    args: serde_json::Value,
+) -> CapabilityActionResult {
+    let factory = runtime
+        .capability_domain_registry()
+        .domain_factory(capability_domain_id)
+        .expect("capability domain factory");
+    let action_key = factory
+        .actions()
+        .into_iter()
+        .find(|action| action.action_name == action_name)
+        .expect("capability domain action")
+        .key;
+    let mut instance = factory.create_instance(CapabilityDomainSessionContext {
+        session_id: session_id.to_string(),
+    });
+    instance
+        .execute_actions(vec![CapabilityActionSubmission { action_key, args }])
+        .await
+        .remove(0)
+}
@@ -1,6 +1,31 @@
+use std::ffi::OsString;
 use std::path::PathBuf;
 
 use anyhow::{Context, bail};
+use tracing::warn;
+
+const WORKSPACE_ROOT_ENV: &str = "FATHOM_WORKSPACE_ROOT";
+
+pub(crate) fn default_workspace_root() -> PathBuf {
+    resolve_default_workspace_root(std::env::var_os(WORKSPACE_ROOT_ENV))
+}
+
+fn resolve_default_workspace_root(configured: Option<OsString>) -> PathBuf {
+    let current_dir = || std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+    let Some(configured) = configured.filter(|value| !value.is_empty()) else {
+        return current_dir();
+    };
+    match canonicalize_workspace_root(PathBuf::from(configured)) {
+        Ok(workspace_root) => workspace_root,
+        Err(error) => {
+            warn!(
+                error = %format!("{error:#}"),
+                "ignoring {WORKSPACE_ROOT_ENV}; using the current directory"
+            );
+            current_dir()
+        }
+    }
+}
 
 pub(super) fn canonicalize_workspace_root(workspace_root: PathBuf) -> anyhow::Result<PathBuf> {
     let workspace_root = if workspace_root.is_absolute() {
@@ -31,3 +56,37 @@ pub(super) fn canonicalize_workspace_root(workspace_root: PathBuf) -> anyhow::Re
     }
     Ok(canonical)
 }
+
+#[cfg(test)]
+mod tests {
+    use std::ffi::OsString;
+
+    use super::resolve_default_workspace_root;
+
+    #[test]
+    fn default_workspace_root_honors_the_env_value_and_falls_back_to_cwd() {
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .expect("system time")
+            .as_nanos();
+        let workspace_root = std::env::temp_dir().join(format!("fathom-env-root-{nanos}"));
+        std::fs::create_dir_all(&workspace_root).expect("create workspace root");
+        let current_dir = std::env::current_dir().expect("current dir");
+
+        assert_eq!(
+            resolve_default_workspace_root(Some(workspace_root.clone().into_os_string())),
+            std::fs::canonicalize(&workspace_root).expect("canonical root")
+        );
+        assert_eq!(resolve_default_workspace_root(None), current_dir);
+        assert_eq!(
+            resolve_default_workspace_root(Some(OsString::new())),
+            current_dir
+        );
+        assert_eq!(
+            resolve_default_workspace_root(Some(workspace_root.join("missing").into_os_string())),
+            current_dir
+        );
+
+        let _ = std::fs::remove_dir_all(&workspace_root);
+    }
+}
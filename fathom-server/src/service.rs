@@ -1,40 +1,224 @@
 use std::path::PathBuf;
 use std::pin::Pin;
+use std::time::Duration;
 
 use anyhow::Result;
-use tokio_stream::wrappers::BroadcastStream;
-use tokio_stream::wrappers::errors::BroadcastStreamRecvError;
-use tokio_stream::{Stream, StreamExt};
+use tokio_stream::Stream;
 use tonic::{Request, Response, Status};
+use tracing::warn;
 
+use crate::cron::MAX_CRON_INTERVAL_SECS;
 use crate::runtime::{DEFAULT_EXECUTION_CAPACITY, Runtime};
+use crate::session::event_stream::SessionEventStream;
 use crate::util::now_unix_ms;
-use fathom_protocol::pb;
 use fathom_protocol::pb::runtime_service_server::RuntimeService;
+use fathom_protocol::{PROTOCOL_VERSION, pb};
 
 #[derive(Clone)]
 pub struct FathomRuntimeService {
     runtime: Runtime,
+    admin_rpc_enabled: bool,
 }
 
 impl Default for FathomRuntimeService {
     fn default() -> Self {
         Self {
             runtime: Runtime::new(DEFAULT_EXECUTION_CAPACITY, 0),
+            admin_rpc_enabled: false,
         }
     }
 }
 
 impl FathomRuntimeService {
-    pub fn with_workspace_root(workspace_root: PathBuf) -> Result<Self> {
+    pub fn with_workspace_root(
+        workspace_root: PathBuf,
+        profile_dir: Option<PathBuf>,
+    ) -> Result<Self> {
         Ok(Self {
             runtime: Runtime::new_with_workspace_root(
                 DEFAULT_EXECUTION_CAPACITY,
                 0,
                 workspace_root,
+                profile_dir,
             )?,
+            admin_rpc_enabled: false,
         })
     }
+
+    pub fn with_system_preamble_path(self, system_preamble_path: Option<PathBuf>) -> Self {
+        self.runtime.set_system_preamble_path(system_preamble_path);
+        self
+    }
+
+    pub fn with_history_dir(self, history_dir: Option<PathBuf>) -> Self {
+        self.runtime.set_history_dir(history_dir);
+        self
+    }
+
+    pub fn with_protected_paths(self, paths: Vec<PathBuf>) -> Self {
+        self.runtime.set_protected_paths(paths);
+        self
+    }
+
+    pub fn with_blocked_write_extensions(self, extensions: Vec<String>) -> Self {
+        self.runtime.set_blocked_write_extensions(extensions);
+        self
+    }
+
+    pub fn with_list_max_entries(self, max_entries: Option<usize>) -> Self {
+        if let Some(max_entries) = max_entries {
+            self.runtime.set_list_max_entries(max_entries);
+        }
+        self
+    }
+
+    pub fn with_queued_action_message_template(self, template: Option<String>) -> Self {
+        self.runtime.set_queued_action_message_template(template);
+        self
+    }
+
+    pub fn with_session_command_capacity(self, capacity: Option<usize>) -> Self {
+        if let Some(capacity) = capacity {
+            self.runtime.set_session_command_capacity(capacity);
+        }
+        self
+    }
+
+    pub fn with_execution_capacity(self, capacity: Option<usize>) -> Result<Self> {
+        if let Some(capacity) = capacity {
+            self.runtime
+                .set_execution_capacity(capacity)
+                .map_err(anyhow::Error::msg)?;
+        }
+        Ok(self)
+    }
+
+    pub fn with_action_timeout_ms(self, timeout_ms: Option<u64>) -> Result<Self> {
+        if let Some(timeout_ms) = timeout_ms {
+            self.runtime
+                .set_action_timeout_ms(timeout_ms)
+                .map_err(anyhow::Error::msg)?;
+        }
+        Ok(self)
+    }
+
+    pub fn with_turn_timeout_secs(self, timeout_secs: Option<u64>) -> Result<Self> {
+        if let Some(timeout_secs) = timeout_secs {
+            self.runtime
+                .set_turn_timeout(Duration::from_secs(timeout_secs))
+                .map_err(anyhow::Error::msg)?;
+        }
+        Ok(self)
+    }
+
+    pub fn with_required_agent_profile_fields(self, fields: Vec<String>) -> Self {
+        self.runtime.set_required_agent_profile_fields(fields);
+        self
+    }
+
+    pub fn with_participant_prompt_limits(
+        self,
+        max_participants: Option<usize>,
+        max_profile_chars: Option<usize>,
+    ) -> Self {
+        self.runtime
+            .set_participant_prompt_limits(max_participants, max_profile_chars);
+        self
+    }
+
+    pub fn with_result_preview_limits(
+        self,
+        max_bytes: Option<usize>,
+        max_lines: Option<usize>,
+    ) -> Self {
+        self.runtime.set_result_preview_limits(max_bytes, max_lines);
+        self
+    }
+
+    pub fn with_history_compaction_limits(
+        self,
+        min_live_events: Option<usize>,
+        batch_events: Option<usize>,
+    ) -> Self {
+        self.runtime
+            .set_history_compaction_limits(min_live_events, batch_events);
+        self
+    }
+
+    pub fn with_max_concurrent_model_requests(self, limit: Option<usize>) -> Result<Self> {
+        if let Some(limit) = limit {
+            self.runtime
+                .set_max_concurrent_model_requests(limit)
+                .map_err(anyhow::Error::msg)?;
+        }
+        Ok(self)
+    }
+
+    pub fn with_max_execution_result_bytes(self, max_bytes: Option<usize>) -> Self {
+        if let Some(max_bytes) = max_bytes {
+            self.runtime.set_max_execution_result_bytes(max_bytes);
+        }
+        self
+    }
+
+    pub fn with_execution_update_grace(self, grace: Duration) -> Self {
+        self.runtime.set_execution_update_grace(grace);
+        self
+    }
+
+    pub fn with_agent_only_sessions_allowed(self, allowed: bool) -> Self {
+        self.runtime.set_agent_only_sessions_allowed(allowed);
+        self
+    }
+
+    pub fn with_thinking_events_enabled(self, enabled: bool) -> Self {
+        self.runtime.set_thinking_events_enabled(enabled);
+        self
+    }
+
+    pub fn with_idle_heartbeat_turns_skipped(self, skipped: bool) -> Self {
+        self.runtime.set_idle_heartbeat_turns_skipped(skipped);
+        self
+    }
+
+    pub fn with_max_assistant_outputs_per_turn(self, max_outputs: Option<usize>) -> Self {
+        if let Some(max_outputs) = max_outputs {
+            self.runtime.set_max_assistant_outputs_per_turn(max_outputs);
+        }
+        self
+    }
+
+    pub fn with_max_heartbeat_delay_ms(self, max_delay_ms: Option<u64>) -> Self {
+        if let Some(max_delay_ms) = max_delay_ms {
+            self.runtime.set_max_heartbeat_delay_ms(max_delay_ms);
+        }
+        self
+    }
+
+    pub fn with_queue_depth_warning_threshold(self, threshold: Option<u64>) -> Self {
+        if let Some(threshold) = threshold {
+            self.runtime.set_queue_depth_warning_threshold(threshold);
+        }
+        self
+    }
+
+    pub fn with_action_allowlist(self, action_ids: Vec<String>) -> Result<Self> {
+        if !action_ids.is_empty() {
+            self.runtime
+                .set_action_allowlist(action_ids)
+                .map_err(anyhow::Error::msg)?;
+        }
+        Ok(self)
+    }
+
+    pub fn with_admin_rpc_enabled(mut self, enabled: bool) -> Self {
+        self.admin_rpc_enabled = enabled;
+        self
+    }
+
+    pub async fn cancel_all_sessions(&self, reason: &str) -> u64 {
+        self.runtime.batch_cancel_sessions(reason).await
+    }
 }
 
 #[tonic::async_trait]
@@ -42,15 +226,57 @@ impl RuntimeService for FathomRuntimeService {
     type AttachSessionEventsStream =
         Pin<Box<dyn Stream<Item = Result<pb::SessionEvent, Status>> + Send + 'static>>;
 
+    async fn ping(
+        &self,
+        request: Request<pb::PingRequest>,
+    ) -> Result<Response<pb::PingResponse>, Status> {
+        let client_protocol_version = request.into_inner().client_protocol_version;
+        if client_protocol_version != 0 && client_protocol_version != PROTOCOL_VERSION {
+            warn!(
+                client_protocol_version,
+                server_protocol_version = PROTOCOL_VERSION,
+                "client protocol version does not match the server"
+            );
+        }
+        Ok(Response::new(pb::PingResponse {
+            protocol_version: PROTOCOL_VERSION,
+            server_version: env!("CARGO_PKG_VERSION").to_string(),
+        }))
+    }
+
     async fn create_session(
         &self,
         request: Request<pb::CreateSessionRequest>,
     ) -> Result<Response<pb::CreateSessionResponse>, Status> {
         let request = request.into_inner();
-        let session = self
-            .runtime
-            .create_session(request.agent_id, request.participant_user_ids)
-            .await?;
+        let execution_capacity =
+            (request.execution_capacity != 0).then_some(request.execution_capacity as usize);
+        let session = if request.validate_only {
+            self.runtime
+                .validate_session(
+                    request.agent_id,
+                    request.participant_user_ids,
+                    execution_capacity,
+                )
+                .await?
+        } else if !request.resume_session_id.is_empty() {
+            self.runtime
+                .resume_session(
+                    request.resume_session_id,
+                    request.agent_id,
+                    request.participant_user_ids,
+                    execution_capacity,
+                )
+                .await?
+        } else {
+            self.runtime
+                .create_session(
+                    request.agent_id,
+                    request.participant_user_ids,
+                    execution_capacity,
+                )
+                .await?
+        };
         Ok(Response::new(pb::CreateSessionResponse {
             session: Some(session),
         }))
@@ -64,6 +290,31 @@ impl RuntimeService for FathomRuntimeService {
         Ok(Response::new(pb::ListSessionsResponse { sessions }))
     }
 
+    async fn delete_session(
+        &self,
+        request: Request<pb::DeleteSessionRequest>,
+    ) -> Result<Response<pb::DeleteSessionResponse>, Status> {
+        let request = request.into_inner();
+        if request.session_id.trim().is_empty() {
+            return Err(Status::invalid_argument("session_id is required"));
+        }
+        let session = self.runtime.delete_session(&request.session_id).await?;
+        Ok(Response::new(pb::DeleteSessionResponse {
+            session: Some(session),
+        }))
+    }
+
+    async fn list_actions(
+        &self,
+        request: Request<pb::ListActionsRequest>,
+    ) -> Result<Response<pb::ListActionsResponse>, Status> {
+        let request = request.into_inner();
+        let actions = self
+            .runtime
+            .list_actions(request.capability_domain_id.trim())?;
+        Ok(Response::new(pb::ListActionsResponse { actions }))
+    }
+
     async fn enqueue_trigger(
         &self,
         request: Request<pb::EnqueueTriggerRequest>,
@@ -85,6 +336,76 @@ impl RuntimeService for FathomRuntimeService {
         Ok(Response::new(response))
     }
 
+    async fn schedule_cron(
+        &self,
+        request: Request<pb::ScheduleCronRequest>,
+    ) -> Result<Response<pb::ScheduleCronResponse>, Status> {
+        let request = request.into_inner();
+        if request.session_id.trim().is_empty() {
+            return Err(Status::invalid_argument("session_id is required"));
+        }
+        if !(1..=MAX_CRON_INTERVAL_SECS).contains(&request.interval_secs) {
+            return Err(Status::invalid_argument(format!(
+                "interval_secs must be between 1 and {MAX_CRON_INTERVAL_SECS}"
+            )));
+        }
+
+        let replaced = self
+            .runtime
+            .schedule_cron(
+                &request.session_id,
+                request.key,
+                Duration::from_secs(request.interval_secs),
+            )
+            .await?;
+        Ok(Response::new(pb::ScheduleCronResponse { replaced }))
+    }
+
+    async fn unschedule_cron(
+        &self,
+        request: Request<pb::UnscheduleCronRequest>,
+    ) -> Result<Response<pb::UnscheduleCronResponse>, Status> {
+        let request = request.into_inner();
+        if request.session_id.trim().is_empty() {
+            return Err(Status::invalid_argument("session_id is required"));
+        }
+
+        let removed = self
+            .runtime
+            .unschedule_cron(&request.session_id, &request.key);
+        Ok(Response::new(pb::UnscheduleCronResponse { removed }))
+    }
+
+    async fn clear_trigger_queue(
+        &self,
+        request: Request<pb::ClearTriggerQueueRequest>,
+    ) -> Result<Response<pb::ClearTriggerQueueResponse>, Status> {
+        let request = request.into_inner();
+        if request.session_id.trim().is_empty() {
+            return Err(Status::invalid_argument("session_id is required"));
+        }
+        let response = self
+            .runtime
+            .clear_trigger_queue(&request.session_id)
+            .await?;
+        Ok(Response::new(response))
+    }
+
+    async fn compact_session_history(
+        &self,
+        request: Request<pb::CompactSessionHistoryRequest>,
+    ) -> Result<Response<pb::CompactSessionHistoryResponse>, Status> {
+        let request = request.into_inner();
+        if request.session_id.trim().is_empty() {
+            return Err(Status::invalid_argument("session_id is required"));
+        }
+        let response = self
+            .runtime
+            .compact_session_history(&request.session_id)
+            .await?;
+        Ok(Response::new(response))
+    }
+
     async fn attach_session_events(
         &self,
         request: Request<pb::AttachSessionEventsRequest>,
@@ -95,12 +416,7 @@ impl RuntimeService for FathomRuntimeService {
         }
 
         let session = self.runtime.get_session(&request.session_id).await?;
-        let stream = BroadcastStream::new(session.events_tx.subscribe()).map(|event| match event {
-            Ok(event) => Ok(event),
-            Err(BroadcastStreamRecvError::Lagged(skipped)) => Err(Status::resource_exhausted(
-                format!("event stream lagged by {skipped} event(s)"),
-            )),
-        });
+        let stream = SessionEventStream::attach(request.session_id, session.events_tx.subscribe());
         Ok(Response::new(Box::pin(stream)))
     }
 
@@ -116,6 +432,57 @@ impl RuntimeService for FathomRuntimeService {
         Ok(Response::new(pb::ListExecutionsResponse { executions }))
     }
 
+    async fn get_execution(
+        &self,
+        request: Request<pb::GetExecutionRequest>,
+    ) -> Result<Response<pb::GetExecutionResponse>, Status> {
+        let request = request.into_inner();
+        if request.session_id.trim().is_empty() {
+            return Err(Status::invalid_argument("session_id is required"));
+        }
+        if request.execution_id.trim().is_empty() {
+            return Err(Status::invalid_argument("execution_id is required"));
+        }
+        let execution = self
+            .runtime
+            .get_execution(&request.session_id, request.execution_id)
+            .await?;
+        Ok(Response::new(pb::GetExecutionResponse {
+            execution: Some(execution),
+        }))
+    }
+
+    async fn get_history(
+        &self,
+        request: Request<pb::GetHistoryRequest>,
+    ) -> Result<Response<pb::GetHistoryResponse>, Status> {
+        let request = request.into_inner();
+        if request.session_id.trim().is_empty() {
+            return Err(Status::invalid_argument("session_id is required"));
+        }
+        let response = self
+            .runtime
+            .get_history(&request.session_id, request.offset, request.page_size)
+            .await?;
+        Ok(Response::new(response))
+    }
+
+    async fn update_runtime_limits(
+        &self,
+        request: Request<pb::UpdateRuntimeLimitsRequest>,
+    ) -> Result<Response<pb::UpdateRuntimeLimitsResponse>, Status> {
+        if !self.admin_rpc_enabled {
+            return Err(Status::permission_denied("admin rpc is disabled"));
+        }
+        let limits = self
+            .runtime
+            .update_runtime_limits(request.into_inner())
+            .await?;
+        Ok(Response::new(pb::UpdateRuntimeLimitsResponse {
+            limits: Some(limits),
+        }))
+    }
+
     async fn cancel_execution(
         &self,
         request: Request<pb::CancelExecutionRequest>,
@@ -134,6 +501,29 @@ impl RuntimeService for FathomRuntimeService {
         Ok(Response::new(response))
     }
 
+    async fn run_action(
+        &self,
+        request: Request<pb::RunActionRequest>,
+    ) -> Result<Response<pb::RunActionResponse>, Status> {
+        if !self.admin_rpc_enabled {
+            return Err(Status::permission_denied("admin rpc is disabled"));
+        }
+        let request = request.into_inner();
+        if request.session_id.trim().is_empty() {
+            return Err(Status::invalid_argument("session_id is required"));
+        }
+        if request.action_id.trim().is_empty() {
+            return Err(Status::invalid_argument("action_id is required"));
+        }
+        let execution = self
+            .runtime
+            .run_action(&request.session_id, request.action_id, request.args_json)
+            .await?;
+        Ok(Response::new(pb::RunActionResponse {
+            execution: Some(execution),
+        }))
+    }
+
     async fn get_user_profile(
         &self,
         request: Request<pb::GetUserProfileRequest>,
@@ -145,7 +535,7 @@ impl RuntimeService for FathomRuntimeService {
         let profile = self
             .runtime
             .get_or_create_user_profile(&request.user_id)
-            .await;
+            .await?;
         Ok(Response::new(pb::GetUserProfileResponse {
             profile: Some(profile),
         }))
@@ -176,7 +566,7 @@ impl RuntimeService for FathomRuntimeService {
         let profile = self
             .runtime
             .get_or_create_agent_profile(&request.agent_id)
-            .await;
+            .await?;
         Ok(Response::new(pb::GetAgentProfileResponse {
             profile: Some(profile),
         }))
@@ -195,11 +585,68 @@ impl RuntimeService for FathomRuntimeService {
             profile: Some(profile),
         }))
     }
+
+    async fn rename_user_profile(
+        &self,
+        request: Request<pb::RenameUserProfileRequest>,
+    ) -> Result<Response<pb::RenameUserProfileResponse>, Status> {
+        let request = request.into_inner();
+        let profile = self
+            .runtime
+            .rename_user_profile(&request.user_id, &request.new_user_id)
+            .await?;
+        Ok(Response::new(pb::RenameUserProfileResponse {
+            profile: Some(profile),
+        }))
+    }
+
+    async fn rename_agent_profile(
+        &self,
+        request: Request<pb::RenameAgentProfileRequest>,
+    ) -> Result<Response<pb::RenameAgentProfileResponse>, Status> {
+        let request = request.into_inner();
+        let profile = self
+            .runtime
+            .rename_agent_profile(&request.agent_id, &request.new_agent_id)
+            .await?;
+        Ok(Response::new(pb::RenameAgentProfileResponse {
+            profile: Some(profile),
+        }))
+    }
+
+    async fn list_user_profiles(
+        &self,
+        _request: Request<pb::ListUserProfilesRequest>,
+    ) -> Result<Response<pb::ListUserProfilesResponse>, Status> {
+        let profiles = self.runtime.list_user_profiles().await;
+        Ok(Response::new(pb::ListUserProfilesResponse { profiles }))
+    }
+
+    async fn list_agent_profiles(
+        &self,
+        _request: Request<pb::ListAgentProfilesRequest>,
+    ) -> Result<Response<pb::ListAgentProfilesResponse>, Status> {
+        let profiles = self.runtime.list_agent_profiles().await;
+        Ok(Response::new(pb::ListAgentProfilesResponse { profiles }))
+    }
 }
 
 fn normalize_trigger(trigger: pb::Trigger, runtime: &Runtime) -> Result<pb::Trigger, Status> {
-    if trigger.kind.is_none() {
-        return Err(Status::invalid_argument("trigger.kind is required"));
+    match &trigger.kind {
+        None => return Err(Status::invalid_argument("trigger.kind is required")),
+        Some(pb::trigger::Kind::ExternalEvent(external)) => {
+            if external.source.trim().is_empty() {
+                return Err(Status::invalid_argument(
+                    "external_event.source is required",
+                ));
+            }
+            if let Err(error) = serde_json::from_str::<serde_json::Value>(&external.payload_json) {
+                return Err(Status::invalid_argument(format!(
+                    "external_event.payload_json must be valid JSON: {error}"
+                )));
+            }
+        }
+        Some(_) => {}
     }
     let mut trigger = trigger;
     if trigger.trigger_id.trim().is_empty() {
@@ -210,3 +657,325 @@ fn normalize_trigger(trigger: pb::Trigger, runtime: &Runtime) -> Result<pb::Trig
     }
     Ok(trigger)
 }
+
+#[cfg(test)]
+mod tests {
+    use fathom_protocol::pb::runtime_service_server::RuntimeService;
+    use fathom_protocol::{PROTOCOL_VERSION, pb};
+    use tonic::Request;
+
+    use super::FathomRuntimeService;
+    use crate::cron::MAX_CRON_INTERVAL_SECS;
+    use crate::util::{default_agent_profile, default_user_profile};
+
+    #[tokio::test]
+    async fn ping_returns_server_protocol_version() {
+        let service = FathomRuntimeService::default();
+
+        let response = service
+            .ping(Request::new(pb::PingRequest {
+                client_protocol_version: PROTOCOL_VERSION + 1,
+            }))
+            .await
+            .expect("ping")
+            .into_inner();
+
+        assert_eq!(response.protocol_version, PROTOCOL_VERSION);
+        assert_eq!(response.server_version, env!("CARGO_PKG_VERSION"));
+    }
+
+    #[tokio::test]
+    async fn external_event_triggers_require_a_source_and_json_payload() {
+        let service = FathomRuntimeService::default();
+        let session = service
+            .create_session(Request::new(pb::CreateSessionRequest {
+                agent_id: "agent-a".to_string(),
+                participant_user_ids: vec!["user-a".to_string()],
+                ..Default::default()
+            }))
+            .await
+            .expect("create session")
+            .into_inner()
+            .session
+            .expect("session");
+
+        let enqueue = |source: &str, payload_json: &str| {
+            Request::new(pb::EnqueueTriggerRequest {
+                session_id: session.session_id.clone(),
+                trigger: Some(pb::Trigger {
+                    kind: Some(pb::trigger::Kind::ExternalEvent(pb::ExternalEventTrigger {
+                        source: source.to_string(),
+                        payload_json: payload_json.to_string(),
+                    })),
+                    ..Default::default()
+                }),
+            })
+        };
+
+        let error = service
+            .enqueue_trigger(enqueue("scheduler", "not json"))
+            .await
+            .expect_err("invalid payload should be rejected");
+        assert_eq!(error.code(), tonic::Code::InvalidArgument);
+        assert!(error.message().contains("payload_json must be valid JSON"));
+
+        let error = service
+            .enqueue_trigger(enqueue(" ", "{}"))
+            .await
+            .expect_err("missing source should be rejected");
+        assert_eq!(error.code(), tonic::Code::InvalidArgument);
+
+        service
+            .enqueue_trigger(enqueue("scheduler", "{\"job\":\"nightly\"}"))
+            .await
+            .expect("valid external event");
+    }
+
+    #[tokio::test]
+    async fn schedule_cron_rejects_intervals_outside_the_supported_range() {
+        let service = FathomRuntimeService::default();
+        let session = service
+            .create_session(Request::new(pb::CreateSessionRequest {
+                agent_id: "agent-a".to_string(),
+                participant_user_ids: vec!["user-a".to_string()],
+                ..Default::default()
+            }))
+            .await
+            .expect("create session")
+            .into_inner()
+            .session
+            .expect("session");
+        let schedule = |interval_secs: u64| {
+            Request::new(pb::ScheduleCronRequest {
+                session_id: session.session_id.clone(),
+                key: "digest".to_string(),
+                interval_secs,
+            })
+        };
+
+        for interval_secs in [0, MAX_CRON_INTERVAL_SECS + 1, u64::MAX] {
+            let error = service
+                .schedule_cron(schedule(interval_secs))
+                .await
+                .expect_err("out-of-range interval should be rejected");
+            assert_eq!(error.code(), tonic::Code::InvalidArgument);
+        }
+        service
+            .schedule_cron(schedule(MAX_CRON_INTERVAL_SECS))
+            .await
+            .expect("maximum interval is accepted");
+    }
+
+    #[tokio::test]
+    async fn configured_execution_capacity_is_validated_and_applied() {
+        let error = FathomRuntimeService::default()
+            .with_execution_capacity(Some(0))
+            .err()
+            .expect("zero capacity should be rejected");
+        assert!(
+            error
+                .to_string()
+                .contains("execution capacity must be between 1 and 64")
+        );
+
+        let service = FathomRuntimeService::default()
+            .with_execution_capacity(Some(1))
+            .expect("capacity of one");
+        assert_eq!(service.runtime.runtime_limits().execution_capacity, 1);
+        let session = service
+            .create_session(Request::new(pb::CreateSessionRequest {
+                agent_id: "agent-a".to_string(),
+                participant_user_ids: vec!["user-a".to_string()],
+                execution_capacity: 0,
+                validate_only: false,
+                resume_session_id: String::new(),
+            }))
+            .await
+            .expect("create session")
+            .into_inner()
+            .session
+            .expect("session summary");
+        assert_eq!(session.execution_capacity, 1);
+    }
+
+    #[tokio::test]
+    async fn validate_only_create_session_reports_errors_without_creating_a_session() {
+        let service = FathomRuntimeService::default();
+        let validate = |agent_id: &str, participant_user_ids: Vec<String>, capacity: u64| {
+            Request::new(pb::CreateSessionRequest {
+                agent_id: agent_id.to_string(),
+                participant_user_ids,
+                execution_capacity: capacity,
+                validate_only: true,
+                ..Default::default()
+            })
+        };
+
+        let error = service
+            .create_session(validate(" ", vec!["user-a".to_string()], 0))
+            .await
+            .expect_err("blank agent id should be rejected");
+        assert_eq!(error.code(), tonic::Code::InvalidArgument);
+        let error = service
+            .create_session(validate("agent-a", vec![" ".to_string()], 0))
+            .await
+            .expect_err("blank participant ids should be rejected");
+        assert_eq!(error.code(), tonic::Code::InvalidArgument);
+        let error = service
+            .create_session(validate("agent-a", vec!["user-a".to_string()], 65))
+            .await
+            .expect_err("oversized capacity should be rejected");
+        assert_eq!(error.code(), tonic::Code::InvalidArgument);
+
+        let summary = service
+            .create_session(validate("agent-a", vec!["user-a".to_string()], 5))
+            .await
+            .expect("valid request")
+            .into_inner()
+            .session
+            .expect("would-be summary");
+        assert!(summary.session_id.is_empty());
+        assert_eq!(summary.agent_id, "agent-a");
+        assert_eq!(summary.participant_user_ids, vec!["user-a".to_string()]);
+        assert_eq!(summary.execution_capacity, 5);
+
+        let listed = service
+            .list_sessions(Request::new(pb::ListSessionsRequest {}))
+            .await
+            .expect("list sessions")
+            .into_inner();
+        assert!(listed.sessions.is_empty());
+        assert!(
+            service
+                .runtime
+                .fetch_agent_profile("agent-a")
+                .await
+                .is_none()
+        );
+        assert!(service.runtime.fetch_user_profile("user-a").await.is_none());
+
+        let created = service
+            .create_session(Request::new(pb::CreateSessionRequest {
+                agent_id: "agent-a".to_string(),
+                participant_user_ids: vec!["user-a".to_string()],
+                ..Default::default()
+            }))
+            .await
+            .expect("create session")
+            .into_inner()
+            .session
+            .expect("session summary");
+        assert_eq!(created.session_id, "session-1");
+    }
+
+    #[tokio::test]
+    async fn list_profiles_returns_upserted_profiles_sorted_by_id() {
+        let service = FathomRuntimeService::default();
+        for agent_id in ["agent-b", "agent-a"] {
+            let mut profile = default_agent_profile(agent_id);
+            profile.display_name = format!("Display {agent_id}");
+            service
+                .upsert_agent_profile(Request::new(pb::UpsertAgentProfileRequest {
+                    profile: Some(profile),
+                }))
+                .await
+                .expect("upsert agent profile");
+        }
+        for user_id in ["user-z", "user-m"] {
+            service
+                .upsert_user_profile(Request::new(pb::UpsertUserProfileRequest {
+                    profile: Some(default_user_profile(user_id)),
+                }))
+                .await
+                .expect("upsert user profile");
+        }
+
+        let agents = service
+            .list_agent_profiles(Request::new(pb::ListAgentProfilesRequest {}))
+            .await
+            .expect("list agent profiles")
+            .into_inner()
+            .profiles;
+        assert_eq!(
+            agents
+                .iter()
+                .map(|profile| (profile.agent_id.as_str(), profile.display_name.as_str()))
+                .collect::<Vec<_>>(),
+            vec![
+                ("agent-a", "Display agent-a"),
+                ("agent-b", "Display agent-b")
+            ]
+        );
+
+        let users = service
+            .list_user_profiles(Request::new(pb::ListUserProfilesRequest {}))
+            .await
+            .expect("list user profiles")
+            .into_inner()
+            .profiles;
+        assert_eq!(
+            users
+                .iter()
+                .map(|profile| profile.user_id.as_str())
+                .collect::<Vec<_>>(),
+            vec!["user-m", "user-z"]
+        );
+    }
+
+    #[tokio::test]
+    async fn delete_session_returns_final_summary_and_forgets_the_session() {
+        let service = FathomRuntimeService::default();
+        let session = service
+            .create_session(Request::new(pb::CreateSessionRequest {
+                agent_id: "agent-a".to_string(),
+                participant_user_ids: vec!["user-a".to_string()],
+                execution_capacity: 0,
+                validate_only: false,
+                resume_session_id: String::new(),
+            }))
+            .await
+            .expect("create session")
+            .into_inner()
+            .session
+            .expect("session summary");
+
+        let deleted = service
+            .delete_session(Request::new(pb::DeleteSessionRequest {
+                session_id: session.session_id.clone(),
+            }))
+            .await
+            .expect("delete session")
+            .into_inner()
+            .session
+            .expect("final summary");
+        assert_eq!(deleted.session_id, session.session_id);
+
+        let listed = service
+            .list_sessions(Request::new(pb::ListSessionsRequest {}))
+            .await
+            .expect("list sessions")
+            .into_inner();
+        assert!(listed.sessions.is_empty());
+
+        let enqueue = service
+            .enqueue_trigger(Request::new(pb::EnqueueTriggerRequest {
+                session_id: session.session_id.clone(),
+                trigger: Some(pb::Trigger {
+                    trigger_id: String::new(),
+                    created_at_unix_ms: 0,
+                    kind: Some(pb::trigger::Kind::Heartbeat(pb::HeartbeatTrigger {})),
+                }),
+            }))
+            .await
+            .expect_err("deleted session rejects triggers");
+        assert_eq!(enqueue.code(), tonic::Code::NotFound);
+
+        let again = service
+            .delete_session(Request::new(pb::DeleteSessionRequest {
+                session_id: session.session_id,
+            }))
+            .await
+            .expect_err("session is already gone");
+        assert_eq!(again.code(), tonic::Code::NotFound);
+    }
+}
@@ -1,8 +1,12 @@
+pub(crate) mod action_messages;
 pub(crate) mod diagnostics;
 pub(crate) mod engine;
+pub(crate) mod event_stream;
 pub(crate) mod inspection;
 pub(crate) mod payload_lookup;
 pub(crate) mod state;
+#[cfg(test)]
+pub(crate) mod testing;
 
 pub(crate) use engine::run_session_actor;
 pub(crate) use state::{SessionCommand, SessionRuntime, SessionState};
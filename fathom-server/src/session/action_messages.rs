@@ -0,0 +1,108 @@
+use fathom_protocol::execution_status_label;
+use fathom_protocol::pb;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) enum QueuedActionMessageFormat {
+    #[default]
+    Default,
+    Suppressed,
+    Template(String),
+}
+
+impl QueuedActionMessageFormat {
+    pub(crate) fn from_template(template: Option<String>) -> Self {
+        match template {
+            None => Self::Default,
+            Some(template) if template.trim().is_empty() => Self::Suppressed,
+            Some(template) => Self::Template(template),
+        }
+    }
+
+    pub(crate) fn render(
+        &self,
+        execution: &pb::Execution,
+        call_id: Option<&str>,
+        background: bool,
+    ) -> String {
+        let status = pb::ExecutionStatus::try_from(execution.status)
+            .map(execution_status_label)
+            .unwrap_or("unknown");
+
+        match self {
+            Self::Suppressed => String::new(),
+            Self::Default => {
+                let call_suffix = call_id
+                    .map(|value| format!(" call_id={value}"))
+                    .unwrap_or_default();
+                let mode_suffix = if background { " background=true" } else { "" };
+                format!(
+                    "submitted action `{}` as {} ({status}){}{}",
+                    execution.action_id, execution.execution_id, call_suffix, mode_suffix
+                )
+            }
+            Self::Template(template) => template
+                .replace("{action_id}", &execution.action_id)
+                .replace("{execution_id}", &execution.execution_id)
+                .replace("{status}", status)
+                .replace("{call_id}", call_id.unwrap_or_default())
+                .replace("{background}", if background { "true" } else { "false" }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::QueuedActionMessageFormat;
+    use fathom_protocol::pb;
+
+    fn test_execution() -> pb::Execution {
+        pb::Execution {
+            execution_id: "execution-1".to_string(),
+            session_id: "session-1".to_string(),
+            action_id: "shell__run".to_string(),
+            args_json: "{}".to_string(),
+            status: pb::ExecutionStatus::Running as i32,
+            result_message: String::new(),
+            created_at_unix_ms: 0,
+            updated_at_unix_ms: 0,
+            call_id: String::new(),
+            turn_id: 0,
+        }
+    }
+
+    #[test]
+    fn default_format_renders_submitted_action_line() {
+        let line = QueuedActionMessageFormat::from_template(None).render(
+            &test_execution(),
+            Some("call-1"),
+            true,
+        );
+        assert_eq!(
+            line,
+            "submitted action `shell__run` as execution-1 (running) call_id=call-1 background=true"
+        );
+    }
+
+    #[test]
+    fn template_format_substitutes_placeholders() {
+        let line = QueuedActionMessageFormat::from_template(Some(
+            "{action_id} queued ({execution_id}, {status}, background={background})".to_string(),
+        ))
+        .render(&test_execution(), None, false);
+        assert_eq!(
+            line,
+            "shell__run queued (execution-1, running, background=false)"
+        );
+    }
+
+    #[test]
+    fn blank_template_suppresses_line() {
+        let format = QueuedActionMessageFormat::from_template(Some(String::new()));
+        assert_eq!(format, QueuedActionMessageFormat::Suppressed);
+        assert!(
+            format
+                .render(&test_execution(), Some("call-1"), true)
+                .is_empty()
+        );
+    }
+}
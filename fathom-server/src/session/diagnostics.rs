@@ -44,6 +44,11 @@ pub(crate) fn trigger_to_json(trigger: &pb::Trigger) -> Value {
             "type": "cron",
             "key": cron.key,
         }),
+        Some(pb::trigger::Kind::ExternalEvent(external)) => json!({
+            "type": "external_event",
+            "source": external.source,
+            "payload_json": external.payload_json,
+        }),
         Some(pb::trigger::Kind::RefreshProfile(refresh)) => json!({
             "type": "refresh_profile",
             "scope": refresh.scope,
@@ -3,9 +3,12 @@ mod actor;
 mod assistant_stream;
 mod delta_transport;
 mod events;
+mod heartbeat;
 mod history_flush;
 mod profiles;
 mod tasks;
+#[cfg(test)]
+mod testing;
 mod turn;
 
 pub(crate) use actor::run_session_actor;
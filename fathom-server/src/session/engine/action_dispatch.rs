@@ -1,23 +1,20 @@
 use std::collections::HashMap;
 
-use tokio::sync::broadcast;
-
 use crate::agent::ActionInvocation;
 use crate::capability_domain::CapabilityDomainActorHandle;
 use crate::runtime::Runtime;
 use crate::session::diagnostics::execution_to_json;
+use crate::session::event_stream::SessionEventSender;
 use crate::session::state::SessionState;
 use fathom_protocol::pb;
 
 use super::events::emit_execution_update_event;
-use super::tasks::{
-    QueuedExecutionOutcome, queue_executions, queued_action_output, settled_execution_output,
-};
+use super::tasks::{QueuedExecutionOutcome, queue_executions, settled_execution_output};
 
 pub(super) struct TurnActionDispatcher<'a> {
     runtime: &'a Runtime,
     state: &'a mut SessionState,
-    events_tx: &'a broadcast::Sender<pb::SessionEvent>,
+    events_tx: &'a SessionEventSender,
     capability_domain_handles: &'a HashMap<String, CapabilityDomainActorHandle>,
     pending_action_invocations: Vec<ActionInvocation>,
     dispatched_actions: Vec<serde_json::Value>,
@@ -27,7 +24,7 @@ impl<'a> TurnActionDispatcher<'a> {
     pub(super) fn new(
         runtime: &'a Runtime,
         state: &'a mut SessionState,
-        events_tx: &'a broadcast::Sender<pb::SessionEvent>,
+        events_tx: &'a SessionEventSender,
         capability_domain_handles: &'a HashMap<String, CapabilityDomainActorHandle>,
     ) -> Self {
         Self {
@@ -71,9 +68,10 @@ impl<'a> TurnActionDispatcher<'a> {
                 }
             };
             let detail = match phase {
-                Some(pb::ExecutionUpdatePhase::ExecutionBackgrounded) => {
-                    queued_action_output(&queued.execution, call_id.as_deref(), true)
-                }
+                Some(pb::ExecutionUpdatePhase::ExecutionBackgrounded) => self
+                    .runtime
+                    .queued_action_message_format()
+                    .render(&queued.execution, call_id.as_deref(), true),
                 Some(pb::ExecutionUpdatePhase::ExecutionRejected) => settled_execution_output(
                     &queued.execution,
                     pb::ExecutionUpdatePhase::ExecutionRejected,
@@ -110,6 +108,10 @@ impl<'a> TurnActionDispatcher<'a> {
         }
     }
 
+    pub(super) fn discard_action_invocations(&mut self) -> usize {
+        std::mem::take(&mut self.pending_action_invocations).len()
+    }
+
     pub(super) fn action_dispatches(&self) -> &[serde_json::Value] {
         &self.dispatched_actions
     }
@@ -119,7 +121,7 @@ impl<'a> TurnActionDispatcher<'a> {
 mod tests {
     use std::collections::{BTreeSet, HashMap};
 
-    use tokio::sync::{broadcast, mpsc};
+    use tokio::sync::mpsc;
 
     use super::TurnActionDispatcher;
     use crate::agent::ActionInvocation;
@@ -127,6 +129,7 @@ mod tests {
         build_default_capability_domain_registry, spawn_capability_domain_actor,
     };
     use crate::runtime::Runtime;
+    use crate::session::event_stream::session_event_channel;
     use crate::session::{SessionCommand, SessionState};
     use crate::util::{default_agent_profile, default_user_profile};
     use fathom_capability_domain::CapabilityDomainSessionContext;
@@ -154,7 +157,7 @@ mod tests {
     fn dispatch_action_invocation_records_dispatch_and_emits_rejected_execution_update_without_runtime()
      {
         let runtime = Runtime::new(2, 10);
-        let (events_tx, mut events_rx) = broadcast::channel(16);
+        let (events_tx, mut events_rx) = session_event_channel(16);
         let mut state = test_state();
         let capability_domain_handles = HashMap::new();
 
@@ -193,9 +196,9 @@ mod tests {
     #[tokio::test]
     async fn dispatch_action_invocation_emits_execution_backgrounded_for_background_action() {
         let runtime = Runtime::new(2, 10);
-        let (events_tx, mut events_rx) = broadcast::channel(16);
+        let (events_tx, mut events_rx) = session_event_channel(16);
         let mut state = test_state();
-        let (session_command_tx, _session_command_rx) = mpsc::channel::<SessionCommand>(16);
+        let (session_command_tx, _session_command_rx) = mpsc::unbounded_channel::<SessionCommand>();
         let shell_instance = runtime
             .capability_domain_registry()
             .domain_factory("shell")
@@ -203,8 +206,12 @@ mod tests {
             .create_instance(CapabilityDomainSessionContext {
                 session_id: state.session_id.clone(),
             });
-        let shell_handle =
-            spawn_capability_domain_actor("shell".to_string(), shell_instance, session_command_tx);
+        let shell_handle = spawn_capability_domain_actor(
+            "shell".to_string(),
+            shell_instance,
+            session_command_tx,
+            runtime.action_timeout(),
+        );
         let capability_domain_handles = HashMap::from([("shell".to_string(), shell_handle)]);
 
         let mut dispatcher =
@@ -236,4 +243,29 @@ mod tests {
         assert!(!execution_update.execution_id.is_empty());
         assert!(execution_update.detail.contains("background=true"));
     }
+
+    #[test]
+    fn dispatched_execution_records_originating_call_id_and_turn() {
+        let runtime = Runtime::new(2, 10);
+        let (events_tx, _events_rx) = session_event_channel(16);
+        let mut state = test_state();
+        state.turn_seq = 7;
+        let capability_domain_handles = HashMap::new();
+
+        let mut dispatcher =
+            TurnActionDispatcher::new(&runtime, &mut state, &events_tx, &capability_domain_handles);
+        dispatcher.dispatch_action_invocation(ActionInvocation {
+            action_id: "filesystem__list".to_string(),
+            args_json: "{\"path\":\".\"}".to_string(),
+            call_key: "call-key-1".to_string(),
+            call_id: Some("call-id-1".to_string()),
+        });
+        dispatcher.flush_action_invocations();
+
+        let executions = state.executions.values().collect::<Vec<_>>();
+        assert_eq!(executions.len(), 1);
+        assert_eq!(executions[0].action_id, "filesystem__list");
+        assert_eq!(executions[0].call_id, "call-id-1");
+        assert_eq!(executions[0].turn_id, 7);
+    }
 }
@@ -2,17 +2,25 @@ use std::future::pending;
 use std::time::Duration;
 
 use fathom_capability_domain::CapabilityDomainSessionContext;
-use tokio::sync::{broadcast, mpsc};
+use tokio::sync::mpsc;
 
 use crate::capability_domain::{CapabilityDomainActorHandle, spawn_capability_domain_actor};
+use crate::history::history_page;
 use crate::runtime::Runtime;
+use crate::session::event_stream::SessionEventSender;
 use crate::session::inspection;
 use crate::session::state::{SessionCommand, SessionState};
+use crate::util::now_unix_ms;
 use fathom_protocol::pb;
 
-use super::events::{enqueue_automatic_heartbeat, enqueue_trigger};
+use super::events::{
+    clear_trigger_queue, emit_event, enqueue_automatic_heartbeat, enqueue_trigger,
+};
+use super::heartbeat::spawn_heartbeat_timer;
+use super::history_flush::{apply_history_summary, compact_session_history};
 use super::tasks::{
     background_expired_submissions, cancel_execution, handle_capability_domain_action_committed,
+    run_action, start_queued_submissions,
 };
 use super::turn::process_turns;
 
@@ -23,8 +31,9 @@ pub(crate) async fn run_session_actor(
     mut state: SessionState,
     command_tx: mpsc::Sender<SessionCommand>,
     mut command_rx: mpsc::Receiver<SessionCommand>,
-    events_tx: broadcast::Sender<pb::SessionEvent>,
+    events_tx: SessionEventSender,
 ) {
+    let (internal_command_tx, mut internal_command_rx) = mpsc::unbounded_channel();
     let registry = runtime.capability_domain_registry();
     let capability_domain_handles = state
         .engaged_capability_domain_ids
@@ -40,7 +49,8 @@ pub(crate) async fn run_session_actor(
                             domain_factory.create_instance(CapabilityDomainSessionContext {
                                 session_id: state.session_id.clone(),
                             }),
-                            command_tx.clone(),
+                            internal_command_tx.clone(),
+                            runtime.action_timeout(),
                         ),
                     )
                 })
@@ -51,10 +61,13 @@ pub(crate) async fn run_session_actor(
     heartbeat_interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
     let _ = heartbeat_interval.tick().await;
 
+    let mut execution_update_turn_deadline = None::<tokio::time::Instant>;
+    let mut heartbeat_timers = Vec::<tokio::task::AbortHandle>::new();
+
     loop {
         let foreground_wait_deadline = state.next_foreground_wait_deadline();
         tokio::select! {
-            command = command_rx.recv() => {
+            command = next_command(&mut internal_command_rx, &mut command_rx) => {
                 let Some(command) = command else {
                     break;
                 };
@@ -82,8 +95,36 @@ pub(crate) async fn run_session_actor(
                         )
                         .await;
                     }
+                    SessionCommand::ClearTriggerQueue { respond_to } => {
+                        let cleared_count = clear_trigger_queue(&mut state, &events_tx);
+                        let _ = respond_to.send(pb::ClearTriggerQueueResponse { cleared_count });
+                    }
+                    SessionCommand::Compact { respond_to } => {
+                        let response =
+                            compact_session_history(&runtime, &mut state, &events_tx).await;
+                        let _ = respond_to.send(response);
+                    }
+                    SessionCommand::ScheduleHeartbeat { delay, respond_to } => {
+                        let trigger = pb::Trigger {
+                            trigger_id: runtime.next_trigger_id(),
+                            created_at_unix_ms: now_unix_ms(),
+                            kind: Some(pb::trigger::Kind::Heartbeat(pb::HeartbeatTrigger {})),
+                        };
+                        let trigger_id = trigger.trigger_id.clone();
+                        heartbeat_timers.retain(|timer| !timer.is_finished());
+                        heartbeat_timers.push(spawn_heartbeat_timer(
+                            command_tx.clone(),
+                            trigger,
+                            delay,
+                        ));
+                        let _ = respond_to.send(trigger_id);
+                    }
                     SessionCommand::GetSummary { respond_to } => {
-                        let _ = respond_to.send(state.to_summary());
+                        let mut summary = state.to_summary();
+                        summary.dropped_event_count = events_tx.dropped_count();
+                        summary.execution_capacity =
+                            state.execution_capacity(runtime.execution_capacity()) as u64;
+                        let _ = respond_to.send(summary);
                     }
                     SessionCommand::ListExecutions { respond_to } => {
                         let mut executions =
@@ -91,6 +132,19 @@ pub(crate) async fn run_session_actor(
                         executions.sort_by(|a, b| a.execution_id.cmp(&b.execution_id));
                         let _ = respond_to.send(executions);
                     }
+                    SessionCommand::GetExecution {
+                        execution_id,
+                        respond_to,
+                    } => {
+                        let _ = respond_to.send(state.executions.get(&execution_id).cloned());
+                    }
+                    SessionCommand::GetHistory {
+                        offset,
+                        page_size,
+                        respond_to,
+                    } => {
+                        let _ = respond_to.send(history_page(&state, offset, page_size));
+                    }
                     SessionCommand::InspectListExecutions { query, respond_to } => {
                         let _ = respond_to.send(inspection::list_executions(&state, &query));
                     }
@@ -140,6 +194,21 @@ pub(crate) async fn run_session_actor(
                             );
                         let _ = respond_to.send(response);
                     }
+                    SessionCommand::RunAction {
+                        action_id,
+                        args_json,
+                        respond_to,
+                    } => {
+                        let execution = run_action(
+                            &runtime,
+                            &mut state,
+                            &events_tx,
+                            &capability_domain_handles,
+                            action_id,
+                            args_json,
+                        );
+                        let _ = respond_to.send(execution);
+                    }
                     SessionCommand::CapabilityDomainActionCommitted { committed } => {
                         handle_capability_domain_action_committed(
                             &runtime,
@@ -148,14 +217,50 @@ pub(crate) async fn run_session_actor(
                             &capability_domain_handles,
                             committed,
                         );
-                        maybe_process_turns(
+                        let grace = runtime.execution_update_grace();
+                        if grace.is_zero() {
+                            maybe_process_turns(
+                                &runtime,
+                                &mut state,
+                                &command_tx,
+                                &events_tx,
+                                &capability_domain_handles,
+                            )
+                            .await;
+                        } else {
+                            execution_update_turn_deadline
+                                .get_or_insert_with(|| tokio::time::Instant::now() + grace);
+                        }
+                    }
+                    SessionCommand::StartQueuedExecutions => {
+                        start_queued_submissions(
                             &runtime,
                             &mut state,
-                            &command_tx,
                             &events_tx,
                             &capability_domain_handles,
-                        )
-                        .await;
+                        );
+                    }
+                    SessionCommand::HistorySummaryReady {
+                        block_id,
+                        summary_text,
+                    } => {
+                        apply_history_summary(&mut state, &block_id, summary_text);
+                    }
+                    SessionCommand::Shutdown { reason, respond_to } => {
+                        emit_event(
+                            &events_tx,
+                            &state.session_id,
+                            pb::session_event::Kind::SessionExpired(pb::SessionExpiredEvent {
+                                reason,
+                            }),
+                        );
+                        if let Some(respond_to) = respond_to {
+                            let mut summary = state.to_summary();
+                            summary.execution_capacity =
+                                state.execution_capacity(runtime.execution_capacity()) as u64;
+                            let _ = respond_to.send(summary);
+                        }
+                        break;
                     }
                 }
             }
@@ -177,6 +282,23 @@ pub(crate) async fn run_session_actor(
                     .await;
                 }
             }
+            _ = async {
+                if let Some(deadline) = execution_update_turn_deadline {
+                    tokio::time::sleep_until(deadline).await;
+                } else {
+                    pending::<()>().await;
+                }
+            } => {
+                execution_update_turn_deadline = None;
+                maybe_process_turns(
+                    &runtime,
+                    &mut state,
+                    &command_tx,
+                    &events_tx,
+                    &capability_domain_handles,
+                )
+                .await;
+            }
             _ = heartbeat_interval.tick() => {
                 enqueue_automatic_heartbeat(&runtime, &mut state, &events_tx);
                 maybe_process_turns(
@@ -190,13 +312,31 @@ pub(crate) async fn run_session_actor(
             }
         }
     }
+
+    for timer in heartbeat_timers {
+        timer.abort();
+    }
+    for handle in capability_domain_handles.values() {
+        handle.shutdown();
+    }
+}
+
+async fn next_command(
+    internal_command_rx: &mut mpsc::UnboundedReceiver<SessionCommand>,
+    command_rx: &mut mpsc::Receiver<SessionCommand>,
+) -> Option<SessionCommand> {
+    tokio::select! {
+        biased;
+        Some(command) = internal_command_rx.recv() => Some(command),
+        command = command_rx.recv() => command,
+    }
 }
 
 async fn maybe_process_turns(
     runtime: &Runtime,
     state: &mut SessionState,
     command_tx: &mpsc::Sender<SessionCommand>,
-    events_tx: &broadcast::Sender<pb::SessionEvent>,
+    events_tx: &SessionEventSender,
     capability_domain_handles: &std::collections::HashMap<String, CapabilityDomainActorHandle>,
 ) {
     if state.has_blocking_submissions() {
@@ -212,3 +352,233 @@ async fn maybe_process_turns(
     )
     .await;
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use fathom_capability_domain::{CapabilityActionKey, CapabilityActionResult};
+    use serde_json::json;
+    use tokio::sync::{broadcast, mpsc, oneshot};
+
+    use super::run_session_actor;
+    use crate::agent::AgentOrchestrator;
+    use crate::capability_domain::{
+        CapabilityDomainCommittedAction, CapabilityDomainCommittedExecution,
+    };
+    use crate::history::append_assistant_output_history;
+    use crate::runtime::Runtime;
+    use crate::session::SessionState;
+    use crate::session::event_stream::session_event_channel;
+    use crate::session::state::{
+        ExecutionRuntimeState, ExecutionSubmissionExecution, ExecutionSubmissionState,
+        ExecutionSubmissionStatus, SessionCommand,
+    };
+    use crate::session::testing::{test_state, test_state_with_capability_domains};
+    use fathom_protocol::pb;
+
+    fn insert_background_execution(state: &mut SessionState, index: usize) {
+        let execution_id = format!("execution-{index}");
+        let submission_id = format!("execution-submission-{index}");
+        state.executions.insert(
+            execution_id.clone(),
+            pb::Execution {
+                execution_id: execution_id.clone(),
+                session_id: state.session_id.clone(),
+                action_id: "filesystem__list".to_string(),
+                args_json: r#"{"path":"."}"#.to_string(),
+                status: pb::ExecutionStatus::Running as i32,
+                result_message: String::new(),
+                created_at_unix_ms: 100,
+                updated_at_unix_ms: 100,
+                call_id: String::new(),
+                turn_id: 0,
+            },
+        );
+        state.execution_runtimes.insert(
+            execution_id.clone(),
+            ExecutionRuntimeState {
+                submission_id: submission_id.clone(),
+                background_requested: true,
+                wakes_agent: true,
+                call_key: format!("call-key-{index}"),
+                call_id: None,
+            },
+        );
+        state.execution_submissions.insert(
+            submission_id,
+            ExecutionSubmissionState {
+                capability_domain_id: "filesystem".to_string(),
+                executions: vec![ExecutionSubmissionExecution {
+                    execution_id,
+                    action_key: CapabilityActionKey(1),
+                }],
+                status: ExecutionSubmissionStatus::RunningBackground,
+                foreground_wait_deadline: None,
+            },
+        );
+    }
+
+    async fn next_event_kind(
+        events_rx: &mut broadcast::Receiver<pb::SessionEvent>,
+    ) -> pb::session_event::Kind {
+        events_rx
+            .recv()
+            .await
+            .expect("session event")
+            .kind
+            .expect("session event kind")
+    }
+
+    async fn turns_started_around_grace_window(grace: Duration) -> usize {
+        let runtime = Runtime::new(4, 10);
+        runtime.set_execution_update_grace(grace);
+        let mut state = test_state_with_capability_domains(["filesystem"]);
+        for index in 0..3 {
+            insert_background_execution(&mut state, index);
+        }
+        let (command_tx, command_rx) = mpsc::channel(16);
+        let (events_tx, mut events_rx) = session_event_channel(256);
+        let actor = tokio::spawn(run_session_actor(
+            runtime,
+            state,
+            command_tx.clone(),
+            command_rx,
+            events_tx,
+        ));
+
+        for index in 0..3 {
+            command_tx
+                .send(SessionCommand::CapabilityDomainActionCommitted {
+                    committed: CapabilityDomainCommittedAction {
+                        submission_id: format!("execution-submission-{index}"),
+                        capability_domain_id: "filesystem".to_string(),
+                        executions: vec![CapabilityDomainCommittedExecution {
+                            execution_id: format!("execution-{index}"),
+                            result: CapabilityActionResult::success(json!({ "index": index }), 1),
+                        }],
+                    },
+                })
+                .await
+                .expect("send commit");
+        }
+
+        let mut turns_started = 0;
+        let mut settled = 0;
+        while settled < 3 {
+            match next_event_kind(&mut events_rx).await {
+                pb::session_event::Kind::TurnStarted(_) => turns_started += 1,
+                pb::session_event::Kind::ExecutionUpdate(update)
+                    if update.phase == pb::ExecutionUpdatePhase::ExecutionSucceeded as i32 =>
+                {
+                    settled += 1;
+                }
+                _ => {}
+            }
+        }
+
+        if !grace.is_zero() {
+            assert_eq!(turns_started, 0);
+            tokio::time::advance(grace).await;
+            while !matches!(
+                next_event_kind(&mut events_rx).await,
+                pb::session_event::Kind::TurnStarted(_)
+            ) {}
+            turns_started += 1;
+        }
+
+        command_tx
+            .send(SessionCommand::Shutdown {
+                reason: "test".to_string(),
+                respond_to: None,
+            })
+            .await
+            .expect("send shutdown");
+        actor.await.expect("actor exits");
+        while let Ok(event) = events_rx.try_recv() {
+            if matches!(event.kind, Some(pb::session_event::Kind::TurnStarted(_))) {
+                turns_started += 1;
+            }
+        }
+        turns_started
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn rapid_execution_updates_fold_into_one_turn_within_grace_window() {
+        assert_eq!(
+            turns_started_around_grace_window(Duration::from_millis(150)).await,
+            1
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn execution_updates_start_a_turn_each_without_grace_window() {
+        assert_eq!(turns_started_around_grace_window(Duration::ZERO).await, 3);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn turn_end_history_compaction_does_not_wait_for_the_model_summary() {
+        let runtime = Runtime::new(2, 10);
+        runtime.set_history_compaction_limits(Some(2), Some(2));
+        runtime.set_agent_orchestrator(AgentOrchestrator::stalled(
+            runtime.capability_domain_registry(),
+            Duration::from_secs(30 * 60),
+        ));
+        let mut state = test_state();
+        state.history_compaction_limits = runtime.history_compaction_limits();
+        for index in 0..8 {
+            append_assistant_output_history(&mut state, &format!("output-{index}"));
+        }
+        let (command_tx, command_rx) = mpsc::channel(16);
+        let (events_tx, _events_rx) = session_event_channel(64);
+        let actor = tokio::spawn(run_session_actor(
+            runtime,
+            state,
+            command_tx.clone(),
+            command_rx,
+            events_tx,
+        ));
+
+        let (respond_to, response) = oneshot::channel();
+        command_tx
+            .send(SessionCommand::EnqueueTrigger {
+                trigger: pb::Trigger {
+                    trigger_id: "trigger-0".to_string(),
+                    created_at_unix_ms: 0,
+                    kind: Some(pb::trigger::Kind::RefreshProfile(
+                        pb::RefreshProfileTrigger {
+                            scope: pb::RefreshScope::All as i32,
+                            user_id: String::new(),
+                        },
+                    )),
+                },
+                respond_to,
+            })
+            .await
+            .expect("send trigger");
+        response
+            .await
+            .expect("enqueue response")
+            .expect("trigger enqueued");
+        let (respond_to, summary) = oneshot::channel();
+        command_tx
+            .send(SessionCommand::GetSummary { respond_to })
+            .await
+            .expect("send summary request");
+        let summary = tokio::time::timeout(Duration::from_secs(1), summary)
+            .await
+            .expect("actor should answer while the model summary is stalled")
+            .expect("summary response");
+        assert_eq!(summary.total_turn_count, 1);
+        assert!(summary.history_entry_count >= 8);
+
+        command_tx
+            .send(SessionCommand::Shutdown {
+                reason: "test".to_string(),
+                respond_to: None,
+            })
+            .await
+            .expect("send shutdown");
+        actor.await.expect("actor exits");
+    }
+}
@@ -1,10 +1,9 @@
 use std::collections::HashMap;
 
-use tokio::sync::broadcast;
-
 use crate::agent::{ModelDeltaEvent, StreamNote};
 use crate::capability_domain::CapabilityDomainActorHandle;
 use crate::runtime::Runtime;
+use crate::session::event_stream::SessionEventSender;
 use crate::session::state::SessionState;
 use crate::util::now_unix_ms;
 use fathom_protocol::pb;
@@ -15,7 +14,10 @@ use super::events::{emit_event, emit_execution_update_event};
 
 pub(super) struct TurnDeltaTransport<'a> {
     session_id: String,
-    events_tx: &'a broadcast::Sender<pb::SessionEvent>,
+    turn_id: u64,
+    events_tx: &'a SessionEventSender,
+    thinking_events_enabled: bool,
+    thinking: bool,
     stream_emitter: TurnAssistantStreamEmitter,
     invocation_stream_notes: Vec<serde_json::Value>,
     streamed_assistant_outputs: Vec<(String, String)>,
@@ -26,14 +28,17 @@ impl<'a> TurnDeltaTransport<'a> {
     pub(super) fn new(
         runtime: &'a Runtime,
         state: &'a mut SessionState,
-        events_tx: &'a broadcast::Sender<pb::SessionEvent>,
+        events_tx: &'a SessionEventSender,
         capability_domain_handles: &'a HashMap<String, CapabilityDomainActorHandle>,
         turn_id: u64,
     ) -> Self {
         let session_id = state.session_id.clone();
         Self {
             session_id,
+            turn_id,
             events_tx,
+            thinking_events_enabled: runtime.thinking_events_enabled(),
+            thinking: false,
             stream_emitter: TurnAssistantStreamEmitter::new(turn_id),
             invocation_stream_notes: Vec::new(),
             streamed_assistant_outputs: Vec::new(),
@@ -47,6 +52,9 @@ impl<'a> TurnDeltaTransport<'a> {
     }
 
     pub(super) fn handle_model_event(&mut self, event: ModelDeltaEvent) {
+        if !matches!(event, ModelDeltaEvent::StreamNote(_)) {
+            self.set_thinking(false);
+        }
         match event {
             ModelDeltaEvent::StreamNote(note) => self.on_stream_note(note),
             ModelDeltaEvent::ActionInvocation(action_invocation) => {
@@ -98,6 +106,10 @@ impl<'a> TurnDeltaTransport<'a> {
         }
     }
 
+    pub(super) fn finish_thinking(&mut self) {
+        self.set_thinking(false);
+    }
+
     pub(super) fn invocation_stream_notes(&self) -> &[serde_json::Value] {
         &self.invocation_stream_notes
     }
@@ -110,12 +122,17 @@ impl<'a> TurnDeltaTransport<'a> {
         self.action_dispatcher.flush_action_invocations();
     }
 
+    pub(super) fn discard_action_invocations(&mut self) -> usize {
+        self.action_dispatcher.discard_action_invocations()
+    }
+
     pub(super) fn drain_streamed_assistant_outputs(&mut self) -> Vec<(String, String)> {
         std::mem::take(&mut self.streamed_assistant_outputs)
     }
 
     fn on_stream_note(&mut self, note: StreamNote) {
-        if note.phase != "openai.stream.event" {
+        let request_started = note.phase.ends_with(".request.start");
+        if !note.phase.ends_with(".stream.event") {
             self.invocation_stream_notes.push(serde_json::json!({
                 "phase": note.phase.clone(),
                 "detail": note.detail.clone(),
@@ -130,6 +147,25 @@ impl<'a> TurnDeltaTransport<'a> {
                 created_at_unix_ms: now_unix_ms(),
             }),
         );
+        if request_started {
+            self.set_thinking(true);
+        }
+    }
+
+    fn set_thinking(&mut self, thinking: bool) {
+        if !self.thinking_events_enabled || self.thinking == thinking {
+            return;
+        }
+        self.thinking = thinking;
+        emit_event(
+            self.events_tx,
+            &self.session_id,
+            pb::session_event::Kind::AgentThinking(pb::AgentThinkingEvent {
+                turn_id: self.turn_id,
+                thinking,
+                created_at_unix_ms: now_unix_ms(),
+            }),
+        );
     }
 }
 
@@ -137,14 +173,13 @@ impl<'a> TurnDeltaTransport<'a> {
 mod tests {
     use std::collections::{BTreeSet, HashMap};
 
-    use tokio::sync::broadcast;
-
     use super::TurnDeltaTransport;
     use crate::agent::{ActionArgDeltaNote, ActionArgDoneNote, ModelDeltaEvent, StreamNote};
     use crate::capability_domain::CapabilityDomainActorHandle;
     use crate::capability_domain::build_default_capability_domain_registry;
     use crate::runtime::Runtime;
     use crate::session::SessionState;
+    use crate::session::event_stream::session_event_channel;
     use crate::util::{default_agent_profile, default_user_profile};
     use fathom_protocol::pb;
 
@@ -169,7 +204,7 @@ mod tests {
     #[test]
     fn delta_transport_preserves_event_order_for_stream_notes_argument_updates_and_text_streams() {
         let runtime = Runtime::new(2, 10);
-        let (events_tx, mut events_rx) = broadcast::channel(32);
+        let (events_tx, mut events_rx) = session_event_channel(32);
         let mut state = test_state();
         let capability_domain_handles = HashMap::<String, CapabilityDomainActorHandle>::new();
         let mut transport = TurnDeltaTransport::new(
@@ -250,4 +285,76 @@ mod tests {
             vec![("7:assistant".to_string(), "hello".to_string())]
         );
     }
+
+    fn thinking_states(
+        events_rx: &mut tokio::sync::broadcast::Receiver<pb::SessionEvent>,
+    ) -> Vec<(u64, bool)> {
+        let mut states = Vec::new();
+        while let Ok(event) = events_rx.try_recv() {
+            if let Some(pb::session_event::Kind::AgentThinking(data)) = event.kind {
+                states.push((data.turn_id, data.thinking));
+            }
+        }
+        states
+    }
+
+    #[test]
+    fn thinking_events_bracket_the_wait_for_first_model_output() {
+        let runtime = Runtime::new(2, 10);
+        let (events_tx, mut events_rx) = session_event_channel(32);
+        let mut state = test_state();
+        let capability_domain_handles = HashMap::<String, CapabilityDomainActorHandle>::new();
+        let mut transport = TurnDeltaTransport::new(
+            &runtime,
+            &mut state,
+            &events_tx,
+            &capability_domain_handles,
+            3,
+        );
+
+        transport.handle_model_event(ModelDeltaEvent::StreamNote(StreamNote {
+            phase: "openai.request.start".to_string(),
+            detail: "attempt=1".to_string(),
+        }));
+        transport.handle_model_event(ModelDeltaEvent::StreamNote(StreamNote {
+            phase: "openai.stream.event".to_string(),
+            detail: "response.created".to_string(),
+        }));
+        transport.handle_model_event(ModelDeltaEvent::AssistantTextDelta("hel".to_string()));
+        transport.handle_model_event(ModelDeltaEvent::AssistantTextDone("hello".to_string()));
+        transport.finish_thinking();
+        assert_eq!(thinking_states(&mut events_rx), vec![(3, true), (3, false)]);
+
+        transport.handle_model_event(ModelDeltaEvent::StreamNote(StreamNote {
+            phase: "openai.request.start".to_string(),
+            detail: "attempt=1".to_string(),
+        }));
+        transport.finish_thinking();
+        transport.finish_thinking();
+        assert_eq!(thinking_states(&mut events_rx), vec![(3, true), (3, false)]);
+    }
+
+    #[test]
+    fn thinking_events_are_not_emitted_when_disabled() {
+        let runtime = Runtime::new(2, 10);
+        runtime.set_thinking_events_enabled(false);
+        let (events_tx, mut events_rx) = session_event_channel(32);
+        let mut state = test_state();
+        let capability_domain_handles = HashMap::<String, CapabilityDomainActorHandle>::new();
+        let mut transport = TurnDeltaTransport::new(
+            &runtime,
+            &mut state,
+            &events_tx,
+            &capability_domain_handles,
+            3,
+        );
+
+        transport.handle_model_event(ModelDeltaEvent::StreamNote(StreamNote {
+            phase: "openai.request.start".to_string(),
+            detail: "attempt=1".to_string(),
+        }));
+        transport.handle_model_event(ModelDeltaEvent::AssistantTextDone("hello".to_string()));
+        transport.finish_thinking();
+        assert!(thinking_states(&mut events_rx).is_empty());
+    }
 }
@@ -1,7 +1,7 @@
-use tokio::sync::broadcast;
-use tracing::warn;
+use tracing::{debug, warn};
 
 use crate::runtime::Runtime;
+use crate::session::event_stream::{EventDelivery, SessionEventSender};
 use crate::session::state::SessionState;
 use crate::util::now_unix_ms;
 use fathom_protocol::pb;
@@ -9,7 +9,7 @@ use fathom_protocol::pb;
 pub(super) fn enqueue_automatic_heartbeat(
     runtime: &Runtime,
     state: &mut SessionState,
-    events_tx: &broadcast::Sender<pb::SessionEvent>,
+    events_tx: &SessionEventSender,
 ) {
     let trigger = pb::Trigger {
         trigger_id: runtime.next_trigger_id(),
@@ -21,7 +21,7 @@ pub(super) fn enqueue_automatic_heartbeat(
 
 pub(super) fn enqueue_trigger(
     state: &mut SessionState,
-    events_tx: &broadcast::Sender<pb::SessionEvent>,
+    events_tx: &SessionEventSender,
     trigger: pb::Trigger,
 ) -> u64 {
     state.trigger_queue.push_back(trigger.clone());
@@ -34,27 +34,80 @@ pub(super) fn enqueue_trigger(
             queue_depth,
         }),
     );
+    check_queue_depth_warning(state, events_tx, queue_depth);
     queue_depth
 }
 
+fn check_queue_depth_warning(
+    state: &mut SessionState,
+    events_tx: &SessionEventSender,
+    queue_depth: u64,
+) {
+    let Some(threshold) = state.queue_depth_warning_threshold else {
+        return;
+    };
+    if queue_depth <= threshold / 2 {
+        state.queue_depth_warning_armed = true;
+    } else if queue_depth >= threshold && state.queue_depth_warning_armed {
+        state.queue_depth_warning_armed = false;
+        emit_event(
+            events_tx,
+            &state.session_id,
+            pb::session_event::Kind::QueueDepthWarning(pb::QueueDepthWarningEvent {
+                queue_depth,
+                threshold,
+            }),
+        );
+    }
+}
+
+pub(super) fn clear_trigger_queue(state: &mut SessionState, events_tx: &SessionEventSender) -> u64 {
+    let trigger_ids = state
+        .trigger_queue
+        .drain(..)
+        .map(|trigger| trigger.trigger_id)
+        .collect::<Vec<_>>();
+    let cleared_count = trigger_ids.len() as u64;
+    emit_event(
+        events_tx,
+        &state.session_id,
+        pb::session_event::Kind::TriggerQueueCleared(pb::TriggerQueueClearedEvent {
+            trigger_ids,
+            cleared_count,
+        }),
+    );
+    cleared_count
+}
+
 pub(super) fn emit_event(
-    events_tx: &broadcast::Sender<pb::SessionEvent>,
+    events_tx: &SessionEventSender,
     session_id: &str,
     kind: pb::session_event::Kind,
 ) {
     let event = pb::SessionEvent {
         session_id: session_id.to_string(),
         created_at_unix_ms: now_unix_ms(),
+        seq: 0,
         kind: Some(kind),
     };
-    if events_tx.send(event).is_err() {
-        warn!(%session_id, "dropping event because no subscribers are attached");
+    match events_tx.send(event) {
+        EventDelivery::Delivered => {}
+        EventDelivery::FirstDrop => {
+            warn!(%session_id, "dropping events because no subscribers are attached");
+        }
+        EventDelivery::Dropped => {
+            debug!(
+                %session_id,
+                dropped_event_count = events_tx.dropped_count(),
+                "dropping event because no subscribers are attached"
+            );
+        }
     }
 }
 
 #[allow(clippy::too_many_arguments)]
 pub(super) fn emit_execution_update_event(
-    events_tx: &broadcast::Sender<pb::SessionEvent>,
+    events_tx: &SessionEventSender,
     session_id: &str,
     phase: pb::ExecutionUpdatePhase,
     call_key: String,
@@ -80,3 +133,197 @@ pub(super) fn emit_execution_update_event(
         }),
     );
 }
+
+#[cfg(test)]
+mod tests {
+
+    use tokio::sync::broadcast;
+
+    use super::{clear_trigger_queue, emit_event, enqueue_trigger};
+    use crate::session::event_stream::{EventDelivery, session_event_channel};
+    use crate::session::testing::test_state;
+    use fathom_protocol::pb;
+
+    fn heartbeat_trigger(trigger_id: &str) -> pb::Trigger {
+        pb::Trigger {
+            trigger_id: trigger_id.to_string(),
+            created_at_unix_ms: 0,
+            kind: Some(pb::trigger::Kind::Heartbeat(pb::HeartbeatTrigger {})),
+        }
+    }
+
+    #[test]
+    fn clear_trigger_queue_drops_queued_triggers_and_emits_event() {
+        let mut state = test_state();
+        let (events_tx, mut events_rx) = session_event_channel(64);
+
+        for index in 0..3 {
+            enqueue_trigger(
+                &mut state,
+                &events_tx,
+                heartbeat_trigger(&format!("trigger-{index}")),
+            );
+        }
+        assert_eq!(state.to_summary().queued_trigger_count, 3);
+
+        let cleared_count = clear_trigger_queue(&mut state, &events_tx);
+
+        assert_eq!(cleared_count, 3);
+        assert!(state.trigger_queue.is_empty());
+        assert_eq!(state.to_summary().queued_trigger_count, 0);
+
+        let mut cleared = None;
+        while let Ok(event) = events_rx.try_recv() {
+            if let Some(pb::session_event::Kind::TriggerQueueCleared(data)) = event.kind {
+                cleared = Some(data);
+            }
+        }
+        let cleared = cleared.expect("trigger queue cleared event");
+        assert_eq!(cleared.cleared_count, 3);
+        assert_eq!(
+            cleared.trigger_ids,
+            vec!["trigger-0", "trigger-1", "trigger-2"]
+        );
+
+        assert_eq!(clear_trigger_queue(&mut state, &events_tx), 0);
+    }
+
+    fn queue_depth_warnings(
+        events_rx: &mut broadcast::Receiver<pb::SessionEvent>,
+    ) -> Vec<pb::QueueDepthWarningEvent> {
+        let mut warnings = Vec::new();
+        while let Ok(event) = events_rx.try_recv() {
+            if let Some(pb::session_event::Kind::QueueDepthWarning(data)) = event.kind {
+                warnings.push(data);
+            }
+        }
+        warnings
+    }
+
+    #[test]
+    fn crossing_the_queue_depth_threshold_emits_a_single_warning() {
+        let mut state = test_state();
+        state.queue_depth_warning_threshold = Some(4);
+        let (events_tx, mut events_rx) = session_event_channel(64);
+
+        for index in 0..8 {
+            enqueue_trigger(
+                &mut state,
+                &events_tx,
+                heartbeat_trigger(&format!("trigger-{index}")),
+            );
+        }
+        assert_eq!(
+            queue_depth_warnings(&mut events_rx),
+            vec![pb::QueueDepthWarningEvent {
+                queue_depth: 4,
+                threshold: 4,
+            }]
+        );
+
+        state.trigger_queue.truncate(3);
+        enqueue_trigger(&mut state, &events_tx, heartbeat_trigger("trigger-8"));
+        assert!(queue_depth_warnings(&mut events_rx).is_empty());
+
+        clear_trigger_queue(&mut state, &events_tx);
+        for index in 9..13 {
+            enqueue_trigger(
+                &mut state,
+                &events_tx,
+                heartbeat_trigger(&format!("trigger-{index}")),
+            );
+        }
+        assert_eq!(queue_depth_warnings(&mut events_rx).len(), 1);
+    }
+
+    #[test]
+    fn event_seq_orders_events_that_share_a_millisecond() {
+        let (events_tx, mut events_rx) = session_event_channel(512);
+
+        for index in 0..256 {
+            emit_event(
+                &events_tx,
+                "session-1",
+                pb::session_event::Kind::Diagnostic(pb::DiagnosticEvent {
+                    detail: format!("diagnostic-{index}"),
+                    created_at_unix_ms: 0,
+                }),
+            );
+        }
+
+        let mut events = Vec::new();
+        while let Ok(event) = events_rx.try_recv() {
+            events.push(event);
+        }
+        assert_eq!(events.len(), 256);
+        assert!(
+            events
+                .windows(2)
+                .any(|pair| pair[0].created_at_unix_ms == pair[1].created_at_unix_ms)
+        );
+        for pair in events.windows(2) {
+            assert!(pair[0].seq < pair[1].seq);
+        }
+
+        let mut shuffled = events.clone();
+        shuffled.reverse();
+        shuffled.sort_by_key(|event| event.seq);
+        let details = shuffled
+            .iter()
+            .map(|event| match &event.kind {
+                Some(pb::session_event::Kind::Diagnostic(data)) => data.detail.clone(),
+                _ => String::new(),
+            })
+            .collect::<Vec<_>>();
+        let expected = (0..256)
+            .map(|index| format!("diagnostic-{index}"))
+            .collect::<Vec<_>>();
+        assert_eq!(details, expected);
+    }
+
+    #[test]
+    fn repeated_drops_without_subscribers_warn_once_and_count_every_event() {
+        let (events_tx, events_rx) = session_event_channel(8);
+        drop(events_rx);
+        let diagnostic = |detail: &str| pb::SessionEvent {
+            session_id: "session-1".to_string(),
+            created_at_unix_ms: 0,
+            seq: 0,
+            kind: Some(pb::session_event::Kind::Diagnostic(pb::DiagnosticEvent {
+                detail: detail.to_string(),
+                created_at_unix_ms: 0,
+            })),
+        };
+
+        let deliveries = (0..3)
+            .map(|index| events_tx.send(diagnostic(&format!("diagnostic-{index}"))))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            deliveries,
+            vec![
+                EventDelivery::FirstDrop,
+                EventDelivery::Dropped,
+                EventDelivery::Dropped
+            ]
+        );
+        assert_eq!(events_tx.dropped_count(), 3);
+
+        emit_event(
+            &events_tx,
+            "session-1",
+            pb::session_event::Kind::Diagnostic(pb::DiagnosticEvent {
+                detail: "late diagnostic".to_string(),
+                created_at_unix_ms: 0,
+            }),
+        );
+        assert_eq!(events_tx.dropped_count(), 4);
+
+        let mut events_rx = events_tx.subscribe();
+        assert_eq!(
+            events_tx.send(diagnostic("watched")),
+            EventDelivery::Delivered
+        );
+        assert!(events_rx.try_recv().is_ok());
+        assert_eq!(events_tx.dropped_count(), 4);
+    }
+}
@@ -0,0 +1,78 @@
+use std::time::Duration;
+
+use tokio::sync::{mpsc, oneshot};
+use tokio::task::AbortHandle;
+
+use crate::session::state::SessionCommand;
+use fathom_protocol::pb;
+
+pub(super) fn spawn_heartbeat_timer(
+    command_tx: mpsc::Sender<SessionCommand>,
+    trigger: pb::Trigger,
+    delay: Duration,
+) -> AbortHandle {
+    tokio::spawn(async move {
+        tokio::time::sleep(delay).await;
+        let (respond_to, _) = oneshot::channel();
+        let _ = command_tx
+            .send(SessionCommand::EnqueueTrigger {
+                trigger,
+                respond_to,
+            })
+            .await;
+    })
+    .abort_handle()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use tokio::sync::mpsc;
+
+    use super::spawn_heartbeat_timer;
+    use crate::session::state::SessionCommand;
+    use fathom_protocol::pb;
+
+    fn heartbeat(trigger_id: &str) -> pb::Trigger {
+        pb::Trigger {
+            trigger_id: trigger_id.to_string(),
+            created_at_unix_ms: 1,
+            kind: Some(pb::trigger::Kind::Heartbeat(pb::HeartbeatTrigger {})),
+        }
+    }
+
+    #[tokio::test]
+    async fn timer_enqueues_the_heartbeat_after_the_delay() {
+        let (command_tx, mut command_rx) = mpsc::channel(4);
+        spawn_heartbeat_timer(
+            command_tx,
+            heartbeat("trigger-1"),
+            Duration::from_millis(30),
+        );
+
+        assert!(command_rx.try_recv().is_err());
+        let command = tokio::time::timeout(Duration::from_secs(5), command_rx.recv())
+            .await
+            .expect("heartbeat should fire")
+            .expect("command channel open");
+        let SessionCommand::EnqueueTrigger { trigger, .. } = command else {
+            panic!("expected enqueue trigger command");
+        };
+        assert_eq!(trigger.trigger_id, "trigger-1");
+    }
+
+    #[tokio::test]
+    async fn aborted_timer_never_fires() {
+        let (command_tx, mut command_rx) = mpsc::channel(4);
+        let timer = spawn_heartbeat_timer(
+            command_tx,
+            heartbeat("trigger-1"),
+            Duration::from_millis(30),
+        );
+        timer.abort();
+
+        let received = tokio::time::timeout(Duration::from_millis(200), command_rx.recv()).await;
+        assert!(matches!(received, Ok(None)));
+    }
+}
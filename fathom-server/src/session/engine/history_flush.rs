@@ -1,7 +1,15 @@
-use crate::history;
-use crate::session::state::SessionState;
+use tokio::sync::mpsc;
+use tracing::warn;
+
+use crate::agent::SummaryBlockRef;
+use crate::history::{self, HistoryBatch};
+use crate::runtime::Runtime;
+use crate::session::event_stream::SessionEventSender;
+use crate::session::state::{SessionCommand, SessionState};
 use fathom_protocol::pb;
 
+use super::events::emit_event;
+
 pub(super) fn flush_history(
     state: &mut SessionState,
     turn_triggers: &[pb::Trigger],
@@ -14,4 +22,246 @@ pub(super) fn flush_history(
     for output in assistant_outputs {
         history::append_assistant_output_history(state, output);
     }
+
+    history::persist_history(state);
+}
+
+pub(super) fn compact_due_history(
+    runtime: &Runtime,
+    state: &mut SessionState,
+    command_tx: &mpsc::Sender<SessionCommand>,
+) {
+    while state.history.len() > state.history_compaction_limits.threshold() {
+        let Some(batch) = history::take_history_batch(state) else {
+            break;
+        };
+        let fallback = batch.fallback_summary();
+        history::push_summary_block(state, &batch, fallback);
+        spawn_model_summary(runtime, state, batch, command_tx.clone());
+    }
+}
+
+fn spawn_model_summary(
+    runtime: &Runtime,
+    state: &SessionState,
+    batch: HistoryBatch,
+    command_tx: mpsc::Sender<SessionCommand>,
+) {
+    let orchestrator = runtime.agent_orchestrator();
+    let session_id = state.session_id.clone();
+    tokio::spawn(async move {
+        match orchestrator.summarize_history(&batch.transcript()).await {
+            Ok(summary) => {
+                let _ = command_tx
+                    .send(SessionCommand::HistorySummaryReady {
+                        block_id: batch.block_id().to_string(),
+                        summary_text: batch.model_summary(&summary),
+                    })
+                    .await;
+            }
+            Err(error) => warn!(
+                session_id = %session_id,
+                %error,
+                "model history summary failed; keeping the event count summary"
+            ),
+        }
+    });
+}
+
+pub(super) fn apply_history_summary(
+    state: &mut SessionState,
+    block_id: &str,
+    summary_text: String,
+) {
+    if let Some(block) = state
+        .compaction
+        .summary_blocks
+        .iter_mut()
+        .find(|block| block.id == block_id)
+    {
+        block.summary_text = summary_text;
+    }
+}
+
+pub(super) async fn compact_session_history(
+    runtime: &Runtime,
+    state: &mut SessionState,
+    events_tx: &SessionEventSender,
+) -> pb::CompactSessionHistoryResponse {
+    history::persist_history(state);
+
+    let min_live_events = state.history_compaction_limits.min_live_events;
+    let blocks = summarize_history_batches(runtime, state, min_live_events)
+        .await
+        .into_iter()
+        .map(|block| pb::CompactionDoneEvent {
+            block_id: block.id,
+            source_range_start: block.source_range_start,
+            source_range_end: block.source_range_end,
+            summary_bytes: block.summary_text.len() as u64,
+        })
+        .collect::<Vec<_>>();
+    for block in &blocks {
+        emit_event(
+            events_tx,
+            &state.session_id,
+            pb::session_event::Kind::CompactionDone(block.clone()),
+        );
+    }
+
+    pb::CompactSessionHistoryResponse {
+        blocks,
+        retained_history_count: state.history.len() as u64,
+    }
+}
+
+async fn summarize_history_batches(
+    runtime: &Runtime,
+    state: &mut SessionState,
+    retained_history_len: usize,
+) -> Vec<SummaryBlockRef> {
+    let orchestrator = runtime.agent_orchestrator();
+    let mut blocks = Vec::new();
+    while state.history.len() > retained_history_len {
+        let Some(batch) = history::take_history_batch(state) else {
+            break;
+        };
+        let summary_text = match orchestrator.summarize_history(&batch.transcript()).await {
+            Ok(summary) => batch.model_summary(&summary),
+            Err(error) => {
+                warn!(
+                    session_id = %state.session_id,
+                    %error,
+                    "model history summary failed; using the event count summary"
+                );
+                batch.fallback_summary()
+            }
+        };
+        blocks.push(history::push_summary_block(state, &batch, summary_text));
+    }
+    blocks
+}
+
+#[cfg(test)]
+mod tests {
+
+    use tokio::sync::mpsc;
+
+    use super::{apply_history_summary, compact_due_history, compact_session_history};
+    use crate::agent::AgentOrchestrator;
+    use crate::history::append_assistant_output_history;
+    use crate::runtime::Runtime;
+    use crate::session::event_stream::session_event_channel;
+    use crate::session::state::SessionCommand;
+    use crate::session::testing::test_state;
+    use fathom_protocol::pb;
+
+    #[tokio::test]
+    async fn compact_command_summarizes_old_history_into_prompt_blocks() {
+        let runtime = Runtime::new(2, 10);
+        let mut state = test_state();
+        for index in 0..60 {
+            append_assistant_output_history(&mut state, &format!("output-{index}"));
+        }
+        assert_eq!(state.history.len(), 60);
+        assert!(state.compaction.summary_blocks.is_empty());
+        let (events_tx, mut events_rx) = session_event_channel(16);
+
+        let response = compact_session_history(&runtime, &mut state, &events_tx).await;
+
+        assert_eq!(response.blocks.len(), 1);
+        let block = &response.blocks[0];
+        assert_eq!(block.source_range_start, 0);
+        assert_eq!(block.source_range_end, 12);
+        assert!(block.summary_bytes > 0);
+        assert_eq!(response.retained_history_count, 48);
+        assert_eq!(state.history.len(), 48);
+        assert_eq!(state.compaction.last_compacted_history_index, 12);
+
+        let event = events_rx.try_recv().expect("compaction done event");
+        let Some(pb::session_event::Kind::CompactionDone(done)) = event.kind else {
+            panic!("expected compaction done event");
+        };
+        assert_eq!(&done, block);
+
+        let context = runtime.build_agent_invocation_context(&state, &[]);
+        assert_eq!(context.compaction.summary_blocks.len(), 1);
+        let prompt = runtime
+            .agent_orchestrator()
+            .assemble_prompt_bundle(&context, None)
+            .as_debug_prompt();
+        assert!(prompt.contains(&block.block_id));
+        assert!(!prompt.contains("output-11"));
+        assert!(prompt.contains("output-12"));
+
+        let again = compact_session_history(&runtime, &mut state, &events_tx).await;
+        assert!(again.blocks.is_empty());
+        assert_eq!(again.retained_history_count, 48);
+    }
+
+    #[tokio::test]
+    async fn compact_command_stores_the_model_summary_when_available() {
+        let runtime = Runtime::new(2, 10);
+        let (orchestrator, prompts) = AgentOrchestrator::replying(
+            runtime.capability_domain_registry(),
+            "the user asked for twelve outputs",
+        );
+        runtime.set_agent_orchestrator(orchestrator);
+        let mut state = test_state();
+        for index in 0..60 {
+            append_assistant_output_history(&mut state, &format!("output-{index}"));
+        }
+        let (events_tx, _events_rx) = session_event_channel(16);
+
+        let response = compact_session_history(&runtime, &mut state, &events_tx).await;
+
+        assert_eq!(response.blocks.len(), 1);
+        let block = &state.compaction.summary_blocks[0];
+        assert_eq!(
+            block.summary_text,
+            "history-summary-000012 source=[0,12) events=12 summary=the user asked for twelve outputs"
+        );
+        let prompts = prompts.lock().expect("recorded prompts lock");
+        assert_eq!(prompts.len(), 1);
+        let prompt = prompts[0].as_debug_prompt();
+        assert!(prompt.contains("output-0\""));
+        assert!(prompt.contains("output-11\""));
+        assert!(!prompt.contains("output-12\""));
+    }
+
+    #[tokio::test]
+    async fn due_compaction_keeps_the_fallback_until_the_model_summary_arrives() {
+        let runtime = Runtime::new(2, 10);
+        runtime.set_history_compaction_limits(Some(2), Some(2));
+        let (orchestrator, _prompts) =
+            AgentOrchestrator::replying(runtime.capability_domain_registry(), "two outputs");
+        runtime.set_agent_orchestrator(orchestrator);
+        let mut state = test_state();
+        state.history_compaction_limits = runtime.history_compaction_limits();
+        for index in 0..5 {
+            append_assistant_output_history(&mut state, &format!("output-{index}"));
+        }
+        let (command_tx, mut command_rx) = mpsc::channel(8);
+
+        compact_due_history(&runtime, &mut state, &command_tx);
+
+        assert_eq!(state.history.len(), 3);
+        assert_eq!(state.compaction.summary_blocks.len(), 1);
+        let fallback = state.compaction.summary_blocks[0].summary_text.clone();
+        assert!(!fallback.contains("summary=two outputs"));
+
+        let Some(SessionCommand::HistorySummaryReady {
+            block_id,
+            summary_text,
+        }) = command_rx.recv().await
+        else {
+            panic!("expected a history summary command");
+        };
+        assert_eq!(block_id, "history-summary-000002");
+        apply_history_summary(&mut state, &block_id, summary_text);
+        assert_eq!(
+            state.compaction.summary_blocks[0].summary_text,
+            "history-summary-000002 source=[0,2) events=2 summary=two outputs"
+        );
+    }
 }
@@ -1,7 +1,5 @@
 use std::collections::HashMap;
-use std::time::Duration;
 
-use tokio::sync::broadcast;
 use tokio::time::Instant;
 use tonic::Status;
 
@@ -13,6 +11,7 @@ use crate::capability_domain::{
 use crate::history;
 use crate::runtime::Runtime;
 use crate::session::diagnostics::execution_to_json;
+use crate::session::event_stream::SessionEventSender;
 use crate::session::payload_lookup::resolve_from_execution;
 use crate::session::state::{
     ExecutionRuntimeState, ExecutionSubmissionExecution, ExecutionSubmissionState,
@@ -26,6 +25,9 @@ use serde_json::json;
 
 use super::events::{emit_event, emit_execution_update_event, enqueue_trigger};
 
+const TRUNCATED_STRING_MIN_BYTES: usize = 64;
+const TRUNCATED_MARKER_BYTES: usize = r#","truncated":true"#.len();
+
 pub(super) struct QueuedExecution {
     pub(super) execution: pb::Execution,
     pub(super) outcome: QueuedExecutionOutcome,
@@ -44,8 +46,6 @@ pub(super) enum CommitTurnPolicy {
     DeferUntilFutureTrigger,
 }
 
-const FOREGROUND_WAIT_BUDGET: Duration = Duration::from_secs(10);
-
 #[derive(Clone)]
 struct AcceptedExecution {
     execution_id: String,
@@ -64,9 +64,27 @@ struct AcceptedExecutionGroup {
 pub(super) fn queue_executions(
     runtime: &Runtime,
     state: &mut SessionState,
-    events_tx: &broadcast::Sender<pb::SessionEvent>,
+    events_tx: &SessionEventSender,
+    capability_domain_handles: &HashMap<String, CapabilityDomainActorHandle>,
+    action_invocations: Vec<ActionInvocation>,
+) -> Vec<QueuedExecution> {
+    queue_executions_with_wakeup(
+        runtime,
+        state,
+        events_tx,
+        capability_domain_handles,
+        action_invocations,
+        true,
+    )
+}
+
+fn queue_executions_with_wakeup(
+    runtime: &Runtime,
+    state: &mut SessionState,
+    events_tx: &SessionEventSender,
     capability_domain_handles: &HashMap<String, CapabilityDomainActorHandle>,
     action_invocations: Vec<ActionInvocation>,
+    wakes_agent: bool,
 ) -> Vec<QueuedExecution> {
     let mut queued_executions = Vec::with_capacity(action_invocations.len());
     let mut grouped = Vec::<AcceptedExecutionGroup>::new();
@@ -90,6 +108,8 @@ pub(super) fn queue_executions(
             result_message: String::new(),
             created_at_unix_ms: now,
             updated_at_unix_ms: now,
+            call_id: call_id.clone().unwrap_or_default(),
+            turn_id: state.turn_seq,
         };
         let mut outcome = QueuedExecutionOutcome::Rejected;
 
@@ -165,22 +185,21 @@ pub(super) fn queue_executions(
         history::append_execution_requested_history(state, &execution);
         append_execution_started_record(runtime, state, &execution);
 
-        if matches!(outcome, QueuedExecutionOutcome::Rejected) {
-            append_execution_rejected_record(runtime, state, &execution);
-            enqueue_execution_update_trigger(
-                runtime,
-                state,
-                events_tx,
-                build_execution_update_trigger(
-                    runtime,
-                    &execution.execution_id,
-                    &execution.action_id,
+        let wakeup_kind = match outcome {
+            QueuedExecutionOutcome::Rejected => {
+                append_execution_rejected_record(runtime, state, &execution);
+                Some((
                     pb::ExecutionUpdateKind::ExecutionRejected,
                     execution.result_message.clone(),
-                    String::new(),
-                ),
-            );
-        } else if matches!(outcome, QueuedExecutionOutcome::BackgroundAccepted) {
+                ))
+            }
+            QueuedExecutionOutcome::BackgroundAccepted => Some((
+                pb::ExecutionUpdateKind::ExecutionBackgrounded,
+                String::new(),
+            )),
+            QueuedExecutionOutcome::ForegroundAccepted => None,
+        };
+        if let Some((kind, message)) = wakeup_kind.filter(|_| wakes_agent) {
             enqueue_execution_update_trigger(
                 runtime,
                 state,
@@ -189,8 +208,8 @@ pub(super) fn queue_executions(
                     runtime,
                     &execution.execution_id,
                     &execution.action_id,
-                    pb::ExecutionUpdateKind::ExecutionBackgrounded,
-                    String::new(),
+                    kind,
+                    message,
                     String::new(),
                 ),
             );
@@ -209,7 +228,9 @@ pub(super) fn queue_executions(
         let submission_background = group.all_background_requested;
         let running_now = !state
             .active_submission_ids_by_domain
-            .contains_key(&group.capability_domain_id);
+            .contains_key(&group.capability_domain_id)
+            && state.active_submission_ids_by_domain.len()
+                < state.execution_capacity(runtime.execution_capacity());
         let submission_status = match (running_now, submission_background) {
             (true, true) => ExecutionSubmissionStatus::RunningBackground,
             (true, false) => ExecutionSubmissionStatus::RunningForeground,
@@ -230,7 +251,7 @@ pub(super) fn queue_executions(
                     .collect(),
                 status: submission_status,
                 foreground_wait_deadline: (!submission_background)
-                    .then(|| Instant::now() + FOREGROUND_WAIT_BUDGET),
+                    .then(|| Instant::now() + runtime.foreground_wait_budget()),
             },
         );
         if !submission_background {
@@ -244,6 +265,7 @@ pub(super) fn queue_executions(
                 ExecutionRuntimeState {
                     submission_id: submission_id.clone(),
                     background_requested: accepted.background_requested,
+                    wakes_agent,
                     call_key: accepted.call_key.clone(),
                     call_id: accepted.call_id.clone(),
                 },
@@ -273,10 +295,37 @@ pub(super) fn queue_executions(
     queued_executions
 }
 
+pub(super) fn run_action(
+    runtime: &Runtime,
+    state: &mut SessionState,
+    events_tx: &SessionEventSender,
+    capability_domain_handles: &HashMap<String, CapabilityDomainActorHandle>,
+    action_id: String,
+    args_json: String,
+) -> Result<pb::Execution, Status> {
+    let call_key = format!("run_action:{}", state.executions.len() + 1);
+    queue_executions_with_wakeup(
+        runtime,
+        state,
+        events_tx,
+        capability_domain_handles,
+        vec![ActionInvocation {
+            action_id,
+            args_json,
+            call_key,
+            call_id: None,
+        }],
+        false,
+    )
+    .pop()
+    .map(|queued| queued.execution)
+    .ok_or_else(|| Status::internal("action execution was not queued"))
+}
+
 pub(super) fn cancel_execution(
     runtime: &Runtime,
     state: &mut SessionState,
-    events_tx: &broadcast::Sender<pb::SessionEvent>,
+    events_tx: &SessionEventSender,
     capability_domain_handles: &HashMap<String, CapabilityDomainActorHandle>,
     execution_id: &str,
 ) -> Result<pb::CancelExecutionResponse, Status> {
@@ -325,13 +374,7 @@ pub(super) fn cancel_execution(
         state
             .active_submission_ids_by_domain
             .remove(&submission.capability_domain_id);
-        start_next_queued_submission(
-            runtime,
-            state,
-            events_tx,
-            capability_domain_handles,
-            &submission.capability_domain_id,
-        );
+        start_queued_submissions(runtime, state, events_tx, capability_domain_handles);
     } else if let Some(queue) = state
         .queued_submission_ids_by_domain
         .get_mut(&submission.capability_domain_id)
@@ -369,7 +412,7 @@ pub(super) fn cancel_execution(
 pub(super) fn handle_capability_domain_action_committed(
     runtime: &Runtime,
     state: &mut SessionState,
-    events_tx: &broadcast::Sender<pb::SessionEvent>,
+    events_tx: &SessionEventSender,
     capability_domain_handles: &HashMap<String, CapabilityDomainActorHandle>,
     committed: CapabilityDomainCommittedAction,
 ) -> CommitTurnPolicy {
@@ -405,13 +448,7 @@ pub(super) fn handle_capability_domain_action_committed(
         settle_committed_execution(runtime, state, events_tx, committed_execution);
     }
 
-    start_next_queued_submission(
-        runtime,
-        state,
-        events_tx,
-        capability_domain_handles,
-        &submission.capability_domain_id,
-    );
+    start_queued_submissions(runtime, state, events_tx, capability_domain_handles);
 
     if submission_is_foreground {
         CommitTurnPolicy::ResumeNow
@@ -423,7 +460,7 @@ pub(super) fn handle_capability_domain_action_committed(
 pub(super) fn background_expired_submissions(
     runtime: &Runtime,
     state: &mut SessionState,
-    events_tx: &broadcast::Sender<pb::SessionEvent>,
+    events_tx: &SessionEventSender,
 ) -> bool {
     let now = Instant::now();
     let expired_submission_ids = state
@@ -451,7 +488,7 @@ pub(super) fn background_expired_submissions(
 
 fn emit_execution_state_changed(
     state: &SessionState,
-    events_tx: &broadcast::Sender<pb::SessionEvent>,
+    events_tx: &SessionEventSender,
     execution: &pb::Execution,
 ) {
     emit_event(
@@ -497,7 +534,7 @@ fn append_execution_rejected_record(
 
 fn start_execution_submission(
     state: &mut SessionState,
-    events_tx: &broadcast::Sender<pb::SessionEvent>,
+    events_tx: &SessionEventSender,
     capability_domain_handles: &HashMap<String, CapabilityDomainActorHandle>,
     capability_domain_id: &str,
     submission_id: &str,
@@ -560,7 +597,7 @@ fn start_execution_submission(
 fn background_submission(
     runtime: &Runtime,
     state: &mut SessionState,
-    events_tx: &broadcast::Sender<pb::SessionEvent>,
+    events_tx: &SessionEventSender,
     submission_id: &str,
 ) {
     let Some(submission) = state.execution_submissions.get_mut(submission_id) else {
@@ -603,6 +640,9 @@ fn background_submission(
             String::new(),
             detail,
         );
+        if !execution_runtime.wakes_agent {
+            continue;
+        }
         enqueue_execution_update_trigger(
             runtime,
             state,
@@ -619,10 +659,43 @@ fn background_submission(
     }
 }
 
+pub(super) fn start_queued_submissions(
+    runtime: &Runtime,
+    state: &mut SessionState,
+    events_tx: &SessionEventSender,
+    capability_domain_handles: &HashMap<String, CapabilityDomainActorHandle>,
+) {
+    let mut queued_capability_domain_ids = state
+        .queued_submission_ids_by_domain
+        .keys()
+        .cloned()
+        .collect::<Vec<_>>();
+    queued_capability_domain_ids.sort();
+
+    for capability_domain_id in queued_capability_domain_ids {
+        if state.active_submission_ids_by_domain.len()
+            >= state.execution_capacity(runtime.execution_capacity())
+        {
+            break;
+        }
+        if state
+            .active_submission_ids_by_domain
+            .contains_key(&capability_domain_id)
+        {
+            continue;
+        }
+        start_next_queued_submission(
+            state,
+            events_tx,
+            capability_domain_handles,
+            &capability_domain_id,
+        );
+    }
+}
+
 fn start_next_queued_submission(
-    _runtime: &Runtime,
     state: &mut SessionState,
-    events_tx: &broadcast::Sender<pb::SessionEvent>,
+    events_tx: &SessionEventSender,
     capability_domain_handles: &HashMap<String, CapabilityDomainActorHandle>,
     capability_domain_id: &str,
 ) {
@@ -657,7 +730,7 @@ fn start_next_queued_submission(
 fn settle_committed_execution(
     runtime: &Runtime,
     state: &mut SessionState,
-    events_tx: &broadcast::Sender<pb::SessionEvent>,
+    events_tx: &SessionEventSender,
     committed_execution: crate::capability_domain::CapabilityDomainCommittedExecution,
 ) {
     let Some(execution_runtime) = state
@@ -687,7 +760,10 @@ fn settle_committed_execution(
     } else {
         pb::ExecutionStatus::Failed as i32
     };
-    execution.result_message = serialize_action_result_message(&committed_execution.result);
+    execution.result_message = bound_execution_result_message(
+        action_result_message_payload(&committed_execution.result),
+        runtime.max_execution_result_bytes(),
+    );
     execution.updated_at_unix_ms = now_unix_ms();
     let execution_snapshot = execution.clone();
 
@@ -721,6 +797,9 @@ fn settle_committed_execution(
         String::new(),
         detail,
     );
+    if !execution_runtime.wakes_agent {
+        return;
+    }
     enqueue_execution_update_trigger(
         runtime,
         state,
@@ -743,7 +822,7 @@ fn settle_committed_execution(
 fn enqueue_execution_update_trigger(
     _runtime: &Runtime,
     state: &mut SessionState,
-    events_tx: &broadcast::Sender<pb::SessionEvent>,
+    events_tx: &SessionEventSender,
     trigger: pb::Trigger,
 ) {
     enqueue_trigger(state, events_tx, trigger);
@@ -815,25 +894,6 @@ pub(super) fn settled_execution_output(
     }
 }
 
-pub(super) fn queued_action_output(
-    execution: &pb::Execution,
-    call_id: Option<&str>,
-    background: bool,
-) -> String {
-    let status = pb::ExecutionStatus::try_from(execution.status)
-        .map(execution_status_label)
-        .unwrap_or("unknown");
-    let call_suffix = call_id
-        .map(|value| format!(" call_id={value}"))
-        .unwrap_or_default();
-    let mode_suffix = if background { " background=true" } else { "" };
-
-    format!(
-        "submitted action `{}` as {} ({status}){}{}",
-        execution.action_id, execution.execution_id, call_suffix, mode_suffix
-    )
-}
-
 fn outcome_phase_for_commit(
     background: bool,
     succeeded: bool,
@@ -878,8 +938,8 @@ fn action_result_succeeded(result: &CapabilityActionResult) -> bool {
     result.outcome.is_ok()
 }
 
-fn serialize_action_result_message(result: &CapabilityActionResult) -> String {
-    let payload = match &result.outcome {
+fn action_result_message_payload(result: &CapabilityActionResult) -> serde_json::Value {
+    match &result.outcome {
         Ok(success) => json!({
             "ok": true,
             "data": success.payload,
@@ -905,8 +965,75 @@ fn serialize_action_result_message(result: &CapabilityActionResult) -> String {
             },
             "execution_time_ms": result.execution_time_ms,
         }),
+    }
+}
+
+fn bound_execution_result_message(
+    mut payload: serde_json::Value,
+    max_bytes: Option<usize>,
+) -> String {
+    let message = payload.to_string();
+    let Some(max_bytes) = max_bytes.filter(|max_bytes| message.len() > *max_bytes) else {
+        return message;
     };
-    payload.to_string()
+    let original_bytes = message.len();
+    let budget = max_bytes.saturating_sub(TRUNCATED_MARKER_BYTES);
+    let mut current_bytes = original_bytes;
+    while current_bytes > budget {
+        let Some(largest) = largest_string_mut(&mut payload)
+            .filter(|value| value.len() > TRUNCATED_STRING_MIN_BYTES)
+        else {
+            break;
+        };
+        let mut end = largest
+            .len()
+            .saturating_sub(current_bytes - budget + TRUNCATED_STRING_MIN_BYTES);
+        while !largest.is_char_boundary(end) {
+            end -= 1;
+        }
+        *largest = format!(
+            "{}... ({} bytes omitted)",
+            &largest[..end],
+            largest.len() - end
+        );
+        current_bytes = payload.to_string().len();
+    }
+    if current_bytes > budget {
+        let omitted = json!({ "truncated": true, "original_bytes": original_bytes });
+        if let Some(data) = payload.get_mut("data") {
+            *data = omitted;
+        } else if let Some(details) = payload.pointer_mut("/error/details") {
+            *details = omitted;
+        }
+    }
+    if let Some(envelope) = payload.as_object_mut() {
+        envelope.insert("truncated".to_string(), json!(true));
+    }
+    let message = payload.to_string();
+    if message.len() <= max_bytes {
+        return message;
+    }
+    json!({
+        "ok": payload.get("ok").cloned().unwrap_or(serde_json::Value::Null),
+        "truncated": true,
+        "original_bytes": original_bytes,
+    })
+    .to_string()
+}
+
+fn largest_string_mut(value: &mut serde_json::Value) -> Option<&mut String> {
+    match value {
+        serde_json::Value::String(value) => Some(value),
+        serde_json::Value::Array(items) => items
+            .iter_mut()
+            .filter_map(largest_string_mut)
+            .max_by_key(|value| value.len()),
+        serde_json::Value::Object(fields) => fields
+            .values_mut()
+            .filter_map(largest_string_mut)
+            .max_by_key(|value| value.len()),
+        _ => None,
+    }
 }
 
 fn truncate_inline(value: &str, max_chars: usize) -> String {
@@ -922,13 +1049,15 @@ fn truncate_inline(value: &str, max_chars: usize) -> String {
 #[cfg(test)]
 mod tests {
     use std::collections::{BTreeSet, HashMap};
+    use std::time::Duration;
 
     use tokio::sync::{broadcast, mpsc};
     use tokio::time::Instant;
 
     use super::{
         CommitTurnPolicy, QueuedExecutionOutcome, background_expired_submissions,
-        handle_capability_domain_action_committed, queue_executions,
+        bound_execution_result_message, handle_capability_domain_action_committed,
+        queue_executions, run_action, start_queued_submissions,
     };
     use crate::agent::ActionInvocation;
     use crate::capability_domain::{
@@ -937,6 +1066,7 @@ mod tests {
         spawn_capability_domain_actor,
     };
     use crate::runtime::Runtime;
+    use crate::session::event_stream::session_event_channel;
     use crate::session::state::{
         ExecutionRuntimeState, ExecutionSubmissionExecution, ExecutionSubmissionState,
         ExecutionSubmissionStatus,
@@ -972,20 +1102,35 @@ mod tests {
         state: &SessionState,
     ) -> (
         HashMap<String, CapabilityDomainActorHandle>,
-        mpsc::Receiver<SessionCommand>,
+        mpsc::UnboundedReceiver<SessionCommand>,
+    ) {
+        domain_handle(runtime, state, "shell")
+    }
+
+    fn domain_handle(
+        runtime: &Runtime,
+        state: &SessionState,
+        capability_domain_id: &str,
+    ) -> (
+        HashMap<String, CapabilityDomainActorHandle>,
+        mpsc::UnboundedReceiver<SessionCommand>,
     ) {
-        let (session_command_tx, session_command_rx) = mpsc::channel::<SessionCommand>(16);
-        let shell_instance = runtime
+        let (session_command_tx, session_command_rx) = mpsc::unbounded_channel::<SessionCommand>();
+        let domain_instance = runtime
             .capability_domain_registry()
-            .domain_factory("shell")
-            .expect("shell factory")
+            .domain_factory(capability_domain_id)
+            .expect("capability domain factory")
             .create_instance(CapabilityDomainSessionContext {
                 session_id: state.session_id.clone(),
             });
-        let shell_handle =
-            spawn_capability_domain_actor("shell".to_string(), shell_instance, session_command_tx);
+        let handle = spawn_capability_domain_actor(
+            capability_domain_id.to_string(),
+            domain_instance,
+            session_command_tx,
+            runtime.action_timeout(),
+        );
         (
-            HashMap::from([("shell".to_string(), shell_handle)]),
+            HashMap::from([(capability_domain_id.to_string(), handle)]),
             session_command_rx,
         )
     }
@@ -993,7 +1138,7 @@ mod tests {
     #[test]
     fn queue_executions_reject_invalid_background_hint_and_enqueue_execution_rejected_trigger() {
         let runtime = Runtime::new(2, 10);
-        let (events_tx, _) = broadcast::channel(16);
+        let (events_tx, _) = session_event_channel(16);
         let mut state = test_state();
         let capability_domain_handles = HashMap::new();
 
@@ -1034,7 +1179,7 @@ mod tests {
     #[tokio::test]
     async fn queue_executions_background_acceptance_backgrounds_without_blocking() {
         let runtime = Runtime::new(2, 10);
-        let (events_tx, _) = broadcast::channel(16);
+        let (events_tx, _) = session_event_channel(16);
         let mut state = test_state();
         let (capability_domain_handles, _session_command_rx) = shell_handle(&runtime, &state);
 
@@ -1077,7 +1222,7 @@ mod tests {
     #[test]
     fn background_expired_submissions_moves_running_foreground_submission_to_background() {
         let runtime = Runtime::new(2, 10);
-        let (events_tx, mut events_rx) = broadcast::channel(16);
+        let (events_tx, mut events_rx) = session_event_channel(16);
         let mut state = test_state();
         let execution_id = "execution-1".to_string();
         let submission_id = "execution-submission-1".to_string();
@@ -1093,6 +1238,8 @@ mod tests {
                 result_message: String::new(),
                 created_at_unix_ms: 100,
                 updated_at_unix_ms: 110,
+                call_id: String::new(),
+                turn_id: 0,
             },
         );
         state
@@ -1103,6 +1250,7 @@ mod tests {
             ExecutionRuntimeState {
                 submission_id: submission_id.clone(),
                 background_requested: false,
+                wakes_agent: true,
                 call_key: "call-key-1".to_string(),
                 call_id: Some("call-id-1".to_string()),
             },
@@ -1163,7 +1311,7 @@ mod tests {
     #[test]
     fn background_expired_submissions_keeps_queued_submission_state_queued() {
         let runtime = Runtime::new(2, 10);
-        let (events_tx, _) = broadcast::channel(16);
+        let (events_tx, _) = session_event_channel(16);
         let mut state = test_state();
         let execution_id = "execution-1".to_string();
         let submission_id = "execution-submission-1".to_string();
@@ -1179,6 +1327,8 @@ mod tests {
                 result_message: String::new(),
                 created_at_unix_ms: 100,
                 updated_at_unix_ms: 110,
+                call_id: String::new(),
+                turn_id: 0,
             },
         );
         state
@@ -1189,6 +1339,7 @@ mod tests {
             ExecutionRuntimeState {
                 submission_id: submission_id.clone(),
                 background_requested: false,
+                wakes_agent: true,
                 call_key: "call-key-1".to_string(),
                 call_id: Some("call-id-1".to_string()),
             },
@@ -1227,10 +1378,58 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn run_action_settles_without_enqueueing_agent_triggers() {
+        let runtime = Runtime::new(2, 10);
+        let (events_tx, _) = session_event_channel(16);
+        let mut state = test_state();
+        let (capability_domain_handles, mut session_command_rx) =
+            domain_handle(&runtime, &state, "filesystem");
+
+        let rejected = run_action(
+            &runtime,
+            &mut state,
+            &events_tx,
+            &capability_domain_handles,
+            "filesystem__missing".to_string(),
+            "{}".to_string(),
+        )
+        .expect("rejected execution");
+        assert_eq!(rejected.status, pb::ExecutionStatus::Failed as i32);
+
+        let execution = run_action(
+            &runtime,
+            &mut state,
+            &events_tx,
+            &capability_domain_handles,
+            "filesystem__list".to_string(),
+            r#"{"path":"."}"#.to_string(),
+        )
+        .expect("queued execution");
+        let Some(SessionCommand::CapabilityDomainActionCommitted { committed }) =
+            session_command_rx.recv().await
+        else {
+            panic!("expected committed action");
+        };
+        handle_capability_domain_action_committed(
+            &runtime,
+            &mut state,
+            &events_tx,
+            &capability_domain_handles,
+            committed,
+        );
+
+        assert_eq!(
+            state.executions[&execution.execution_id].status,
+            pb::ExecutionStatus::Succeeded as i32
+        );
+        assert!(state.trigger_queue.is_empty());
+    }
+
     #[tokio::test]
     async fn queued_foreground_submission_blocks_until_committed() {
         let runtime = Runtime::new(2, 10);
-        let (events_tx, _) = broadcast::channel(16);
+        let (events_tx, _) = session_event_channel(16);
         let mut state = test_state();
         let (capability_domain_handles, _session_command_rx) = shell_handle(&runtime, &state);
 
@@ -1270,10 +1469,115 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn session_execution_capacity_overrides_runtime_capacity() {
+        let runtime = Runtime::new(1, 10);
+        let (events_tx, _) = session_event_channel(16);
+        let mut tuned_state = test_state();
+        tuned_state.execution_capacity = Some(2);
+        let mut default_state = test_state();
+        let (capability_domain_handles, _session_command_rx) = shell_handle(&runtime, &tuned_state);
+
+        let queue_shell_run = |state: &mut SessionState| {
+            state.active_submission_ids_by_domain.insert(
+                "filesystem".to_string(),
+                "execution-submission-active".to_string(),
+            );
+            let queued = queue_executions(
+                &runtime,
+                state,
+                &events_tx,
+                &capability_domain_handles,
+                vec![ActionInvocation {
+                    action_id: "shell__run".to_string(),
+                    args_json: r#"{"command":"pwd"}"#.to_string(),
+                    call_key: "call-key-1".to_string(),
+                    call_id: Some("call-id-1".to_string()),
+                }],
+            )
+            .pop()
+            .expect("queued execution");
+            let submission_id = state.execution_runtimes[&queued.execution.execution_id]
+                .submission_id
+                .clone();
+            state.execution_submissions[&submission_id].status
+        };
+
+        assert_eq!(
+            queue_shell_run(&mut tuned_state),
+            ExecutionSubmissionStatus::RunningForeground
+        );
+        assert_eq!(
+            queue_shell_run(&mut default_state),
+            ExecutionSubmissionStatus::Queued
+        );
+    }
+
+    #[tokio::test]
+    async fn raising_execution_capacity_starts_pending_submissions() {
+        let runtime = Runtime::new(1, 10);
+        let (events_tx, _) = session_event_channel(16);
+        let mut state = test_state();
+        let (capability_domain_handles, _session_command_rx) = shell_handle(&runtime, &state);
+
+        state.active_submission_ids_by_domain.insert(
+            "filesystem".to_string(),
+            "execution-submission-active".to_string(),
+        );
+
+        let queued = queue_executions(
+            &runtime,
+            &mut state,
+            &events_tx,
+            &capability_domain_handles,
+            vec![ActionInvocation {
+                action_id: "shell__run".to_string(),
+                args_json: r#"{"command":"pwd"}"#.to_string(),
+                call_key: "call-key-1".to_string(),
+                call_id: Some("call-id-1".to_string()),
+            }],
+        )
+        .pop()
+        .expect("queued execution");
+        let submission_id = state
+            .execution_runtimes
+            .get(&queued.execution.execution_id)
+            .expect("execution runtime")
+            .submission_id
+            .clone();
+        assert_eq!(
+            state.execution_submissions[&submission_id].status,
+            ExecutionSubmissionStatus::Queued
+        );
+
+        let limits = runtime
+            .update_runtime_limits(pb::UpdateRuntimeLimitsRequest {
+                execution_capacity: 2,
+                foreground_wait_budget_ms: 0,
+            })
+            .await
+            .expect("update runtime limits");
+        assert_eq!(limits.execution_capacity, 2);
+        start_queued_submissions(&runtime, &mut state, &events_tx, &capability_domain_handles);
+
+        assert_eq!(
+            state.execution_submissions[&submission_id].status,
+            ExecutionSubmissionStatus::RunningForeground
+        );
+        assert_eq!(
+            state.active_submission_ids_by_domain.get("shell"),
+            Some(&submission_id)
+        );
+        assert_eq!(
+            state.executions[&queued.execution.execution_id].status,
+            pb::ExecutionStatus::Running as i32
+        );
+    }
+
     #[test]
     fn foreground_submission_commit_resumes_agent_and_emits_execution_succeeded_trigger() {
         let runtime = Runtime::new(2, 10);
-        let (events_tx, mut events_rx) = broadcast::channel(16);
+        let (events_tx, mut events_rx) = session_event_channel(16);
         let mut state = test_state();
         let capability_domain_handles = HashMap::new();
         let execution_id = "execution-1".to_string();
@@ -1290,6 +1594,8 @@ mod tests {
                 result_message: String::new(),
                 created_at_unix_ms: 100,
                 updated_at_unix_ms: 110,
+                call_id: String::new(),
+                turn_id: 0,
             },
         );
         state
@@ -1300,6 +1606,7 @@ mod tests {
             ExecutionRuntimeState {
                 submission_id: submission_id.clone(),
                 background_requested: false,
+                wakes_agent: true,
                 call_key: "call-key-1".to_string(),
                 call_id: Some("call-id-1".to_string()),
             },
@@ -1360,7 +1667,7 @@ mod tests {
     #[test]
     fn background_submission_commit_defers_agent_wakeup_and_emits_execution_succeeded_trigger() {
         let runtime = Runtime::new(2, 10);
-        let (events_tx, mut events_rx) = broadcast::channel(16);
+        let (events_tx, mut events_rx) = session_event_channel(16);
         let mut state = test_state();
         let capability_domain_handles = HashMap::new();
         let execution_id = "execution-2".to_string();
@@ -1377,6 +1684,8 @@ mod tests {
                 result_message: String::new(),
                 created_at_unix_ms: 100,
                 updated_at_unix_ms: 110,
+                call_id: String::new(),
+                turn_id: 0,
             },
         );
         state.execution_runtimes.insert(
@@ -1384,6 +1693,7 @@ mod tests {
             ExecutionRuntimeState {
                 submission_id: submission_id.clone(),
                 background_requested: true,
+                wakes_agent: true,
                 call_key: "call-key-2".to_string(),
                 call_id: Some("call-id-2".to_string()),
             },
@@ -1441,6 +1751,119 @@ mod tests {
         );
     }
 
+    #[test]
+    fn failed_filesystem_write_commit_marks_execution_failed() {
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .expect("system time")
+            .as_nanos();
+        let workspace_root = std::env::temp_dir().join(format!("fathom-fs-write-failed-{nanos}"));
+        std::fs::create_dir_all(&workspace_root).expect("create workspace root");
+        std::fs::write(workspace_root.join("existing.txt"), "original").expect("seed file");
+        let runtime = Runtime::new(2, 10);
+        let (events_tx, mut events_rx) = session_event_channel(16);
+        let mut state = test_state();
+        let capability_domain_handles = HashMap::new();
+        let execution_id = "execution-3".to_string();
+        let submission_id = "execution-submission-3".to_string();
+        let args_json = r#"{"path":"existing.txt","content":"x","allow_override":false}"#;
+
+        state.executions.insert(
+            execution_id.clone(),
+            pb::Execution {
+                execution_id: execution_id.clone(),
+                session_id: state.session_id.clone(),
+                action_id: "filesystem__write".to_string(),
+                args_json: args_json.to_string(),
+                status: pb::ExecutionStatus::Running as i32,
+                result_message: String::new(),
+                created_at_unix_ms: 100,
+                updated_at_unix_ms: 110,
+                call_id: String::new(),
+                turn_id: 0,
+            },
+        );
+        state
+            .foreground_submission_ids
+            .insert(submission_id.clone());
+        state.execution_runtimes.insert(
+            execution_id.clone(),
+            ExecutionRuntimeState {
+                submission_id: submission_id.clone(),
+                background_requested: false,
+                wakes_agent: true,
+                call_key: "call-key-3".to_string(),
+                call_id: Some("call-id-3".to_string()),
+            },
+        );
+        state.execution_submissions.insert(
+            submission_id.clone(),
+            ExecutionSubmissionState {
+                capability_domain_id: "filesystem".to_string(),
+                executions: vec![ExecutionSubmissionExecution {
+                    execution_id: execution_id.clone(),
+                    action_key: CapabilityActionKey(0),
+                }],
+                status: ExecutionSubmissionStatus::RunningForeground,
+                foreground_wait_deadline: None,
+            },
+        );
+        state
+            .active_submission_ids_by_domain
+            .insert("filesystem".to_string(), submission_id.clone());
+
+        let result = fathom_capability_domain_fs::execute_action(
+            "write",
+            args_json,
+            &json!({ "base_path": workspace_root.display().to_string() }),
+        )
+        .expect("filesystem write should dispatch");
+        assert!(result.outcome.is_err());
+
+        handle_capability_domain_action_committed(
+            &runtime,
+            &mut state,
+            &events_tx,
+            &capability_domain_handles,
+            CapabilityDomainCommittedAction {
+                submission_id,
+                capability_domain_id: "filesystem".to_string(),
+                executions: vec![CapabilityDomainCommittedExecution {
+                    execution_id: execution_id.clone(),
+                    result,
+                }],
+            },
+        );
+
+        let execution = state.executions.get(&execution_id).expect("execution");
+        assert_eq!(execution.status, pb::ExecutionStatus::Failed as i32);
+        assert!(execution.result_message.contains("already_exists"));
+        let trigger = state
+            .trigger_queue
+            .back()
+            .expect("execution_failed trigger");
+        let pb::trigger::Kind::ExecutionUpdate(update) =
+            trigger.kind.as_ref().expect("trigger kind")
+        else {
+            panic!("expected execution update trigger");
+        };
+        assert_eq!(
+            pb::ExecutionUpdateKind::try_from(update.kind).expect("execution update kind"),
+            pb::ExecutionUpdateKind::ExecutionFailed
+        );
+        let execution_update =
+            collect_execution_update_event(&mut events_rx).expect("execution update event");
+        assert_eq!(
+            execution_update.phase,
+            pb::ExecutionUpdatePhase::ExecutionFailed as i32
+        );
+        assert_eq!(
+            std::fs::read_to_string(workspace_root.join("existing.txt")).expect("read seed file"),
+            "original"
+        );
+        let _ = std::fs::remove_dir_all(workspace_root);
+    }
+
     fn collect_execution_update_event(
         events_rx: &mut broadcast::Receiver<pb::SessionEvent>,
     ) -> Option<pb::ExecutionUpdateEvent> {
@@ -1451,4 +1874,251 @@ mod tests {
         }
         None
     }
+
+    fn state_with_running_foreground_execution(
+        execution_id: &str,
+        action_id: &str,
+    ) -> SessionState {
+        let mut state = test_state();
+        let submission_id = format!("{execution_id}-submission");
+
+        state.executions.insert(
+            execution_id.to_string(),
+            pb::Execution {
+                execution_id: execution_id.to_string(),
+                session_id: state.session_id.clone(),
+                action_id: action_id.to_string(),
+                args_json: r#"{"path":"."}"#.to_string(),
+                status: pb::ExecutionStatus::Running as i32,
+                result_message: String::new(),
+                created_at_unix_ms: 100,
+                updated_at_unix_ms: 110,
+                call_id: format!("call-id-{execution_id}"),
+                turn_id: 1,
+            },
+        );
+        state
+            .foreground_submission_ids
+            .insert(submission_id.clone());
+        state.execution_runtimes.insert(
+            execution_id.to_string(),
+            ExecutionRuntimeState {
+                submission_id: submission_id.clone(),
+                background_requested: false,
+                wakes_agent: true,
+                call_key: format!("call-key-{execution_id}"),
+                call_id: Some(format!("call-id-{execution_id}")),
+            },
+        );
+        state.execution_submissions.insert(
+            submission_id,
+            ExecutionSubmissionState {
+                capability_domain_id: "filesystem".to_string(),
+                executions: vec![ExecutionSubmissionExecution {
+                    execution_id: execution_id.to_string(),
+                    action_key: CapabilityActionKey(1),
+                }],
+                status: ExecutionSubmissionStatus::RunningForeground,
+                foreground_wait_deadline: None,
+            },
+        );
+        state.turn_seq = 1;
+        state
+    }
+
+    #[tokio::test]
+    async fn committed_execution_result_reaches_the_next_turn_prompt() {
+        let runtime = Runtime::new(2, 10);
+        let (events_tx, _) = session_event_channel(64);
+        let execution_id = "execution-7".to_string();
+        let mut state = state_with_running_foreground_execution(&execution_id, "filesystem__list");
+
+        let prompt = runtime
+            .commit_execution_and_run_next_turn(
+                &mut state,
+                &events_tx,
+                &execution_id,
+                CapabilityActionResult::success(json!({ "entries": ["marker-entry-42.txt"] }), 3),
+            )
+            .await
+            .expect("follow-up turn prompt")
+            .as_debug_prompt();
+
+        assert_eq!(state.turn_seq, 2);
+        assert!(state.trigger_queue.is_empty());
+        assert!(prompt.contains(&execution_id));
+        assert!(prompt.contains("marker-entry-42.txt"));
+    }
+
+    #[tokio::test]
+    async fn large_read_result_is_truncated_in_the_prompt_but_kept_in_the_execution() {
+        let runtime = Runtime::new(2, 10);
+        runtime.set_result_preview_limits(Some(256), Some(4));
+        let (events_tx, _) = session_event_channel(64);
+        let execution_id = "execution-8".to_string();
+        let mut state = state_with_running_foreground_execution(&execution_id, "filesystem__read");
+        state.result_preview_limits = runtime.result_preview_limits();
+        let content = format!("{}tail-marker", "line of file content\n".repeat(400));
+
+        let prompt = runtime
+            .commit_execution_and_run_next_turn(
+                &mut state,
+                &events_tx,
+                &execution_id,
+                CapabilityActionResult::success(json!({ "content": content }), 3),
+            )
+            .await
+            .expect("follow-up turn prompt")
+            .as_debug_prompt();
+
+        let result_message = &state.executions[&execution_id].result_message;
+        assert!(result_message.contains("tail-marker"));
+        assert_eq!(result_message.matches("line of file content").count(), 400);
+        assert!(prompt.contains("execution://execution-8/result"));
+        assert!(prompt.matches("line of file content").count() < 20);
+    }
+
+    #[test]
+    fn execution_result_message_cap_truncates_strings_inside_valid_json() {
+        let payload = json!({
+            "ok": true,
+            "data": { "content": format!("{}끝", "a".repeat(400)), "path": "notes.txt" },
+            "execution_time_ms": 3,
+        });
+
+        assert_eq!(
+            bound_execution_result_message(payload.clone(), None),
+            payload.to_string()
+        );
+        let bounded = bound_execution_result_message(payload, Some(200));
+        assert!(bounded.len() <= 200);
+        let bounded = serde_json::from_str::<serde_json::Value>(&bounded)
+            .expect("bounded result stays valid JSON");
+        assert_eq!(bounded["ok"], json!(true));
+        assert_eq!(bounded["truncated"], json!(true));
+        assert_eq!(bounded["execution_time_ms"], json!(3));
+        assert_eq!(bounded["data"]["path"], json!("notes.txt"));
+        let content = bounded["data"]["content"].as_str().expect("content");
+        assert!(content.starts_with("aaaa"));
+        assert!(content.ends_with("bytes omitted)"));
+    }
+
+    #[test]
+    fn execution_result_message_cap_replaces_oversized_structures() {
+        let payload = json!({
+            "ok": true,
+            "data": { "entries": (0..200).map(|index| json!({ "index": index })).collect::<Vec<_>>() },
+            "execution_time_ms": 3,
+        });
+
+        let bounded = bound_execution_result_message(payload, Some(200));
+        let bounded = serde_json::from_str::<serde_json::Value>(&bounded)
+            .expect("bounded result stays valid JSON");
+        assert_eq!(bounded["data"]["truncated"], json!(true));
+        assert!(
+            bounded["data"]["original_bytes"]
+                .as_u64()
+                .expect("original bytes")
+                > 200
+        );
+        assert_eq!(bounded["truncated"], json!(true));
+    }
+
+    #[test]
+    fn execution_result_message_cap_holds_for_many_short_strings() {
+        let payload = json!({
+            "ok": false,
+            "error": {
+                "code": "invalid_args",
+                "message": "rejected",
+                "hints": (0..200).map(|index| format!("hint-{index}")).collect::<Vec<_>>(),
+            },
+            "execution_time_ms": 3,
+        });
+
+        for max_bytes in [60, 200, 1000] {
+            let bounded = bound_execution_result_message(payload.clone(), Some(max_bytes));
+            assert!(bounded.len() <= max_bytes, "{max_bytes}: {bounded}");
+            let bounded = serde_json::from_str::<serde_json::Value>(&bounded)
+                .expect("bounded result stays valid JSON");
+            assert_eq!(bounded["ok"], json!(false));
+            assert_eq!(bounded["truncated"], json!(true));
+        }
+        let bounded = bound_execution_result_message(payload.clone(), Some(60));
+        let bounded = serde_json::from_str::<serde_json::Value>(&bounded)
+            .expect("bounded result stays valid JSON");
+        assert_eq!(bounded["original_bytes"], json!(payload.to_string().len()));
+    }
+
+    #[tokio::test]
+    async fn queued_filesystem_write_runs_for_real_and_reports_its_payload() {
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .expect("system time")
+            .as_nanos();
+        let workspace_root = std::env::temp_dir().join(format!("fathom-fs-write-commit-{nanos}"));
+        std::fs::create_dir_all(&workspace_root).expect("create workspace root");
+        let runtime =
+            Runtime::new_with_workspace_root(2, 10, workspace_root.clone(), None).expect("runtime");
+        let (events_tx, _) = session_event_channel(64);
+        let mut state = test_state();
+        let (capability_domain_handles, mut session_command_rx) =
+            domain_handle(&runtime, &state, "filesystem");
+
+        let queued = queue_executions(
+            &runtime,
+            &mut state,
+            &events_tx,
+            &capability_domain_handles,
+            vec![ActionInvocation {
+                action_id: "filesystem__write".to_string(),
+                args_json: r#"{"path":"notes.txt","content":"hello","allow_override":false}"#
+                    .to_string(),
+                call_key: "call-key-1".to_string(),
+                call_id: Some("call-id-1".to_string()),
+            }],
+        )
+        .pop()
+        .expect("queued execution");
+        start_queued_submissions(&runtime, &mut state, &events_tx, &capability_domain_handles);
+
+        let command = tokio::time::timeout(Duration::from_secs(5), session_command_rx.recv())
+            .await
+            .expect("filesystem write should complete")
+            .expect("session command channel open");
+        let SessionCommand::CapabilityDomainActionCommitted { committed } = command else {
+            panic!("expected committed capability domain action");
+        };
+        handle_capability_domain_action_committed(
+            &runtime,
+            &mut state,
+            &events_tx,
+            &capability_domain_handles,
+            committed,
+        );
+
+        let execution = &state.executions[&queued.execution.execution_id];
+        assert_eq!(execution.status, pb::ExecutionStatus::Succeeded as i32);
+        assert!(execution.result_message.contains(r#""ok":true"#));
+        let trigger = state
+            .trigger_queue
+            .back()
+            .expect("execution update trigger");
+        let pb::trigger::Kind::ExecutionUpdate(update) =
+            trigger.kind.as_ref().expect("trigger kind")
+        else {
+            panic!("expected execution update trigger");
+        };
+        assert_eq!(
+            pb::ExecutionUpdateKind::try_from(update.kind).expect("execution update kind"),
+            pb::ExecutionUpdateKind::ExecutionSucceeded
+        );
+        assert!(update.payload_message.contains(r#""ok":true"#));
+        assert_eq!(
+            std::fs::read_to_string(workspace_root.join("notes.txt")).expect("written file"),
+            "hello"
+        );
+
+        let _ = std::fs::remove_dir_all(workspace_root);
+    }
 }
@@ -0,0 +1,69 @@
+use std::collections::HashMap;
+
+use fathom_capability_domain::CapabilityActionResult;
+use tokio::sync::mpsc;
+
+use crate::agent::{AgentOrchestrator, CompiledPrompt};
+use crate::capability_domain::{
+    CapabilityDomainCommittedAction, CapabilityDomainCommittedExecution,
+};
+use crate::runtime::Runtime;
+use crate::session::SessionState;
+use crate::session::event_stream::SessionEventSender;
+
+use super::tasks::handle_capability_domain_action_committed;
+use super::turn::process_turns;
+
+impl Runtime {
+    pub(crate) async fn commit_execution_and_run_next_turn(
+        &self,
+        state: &mut SessionState,
+        events_tx: &SessionEventSender,
+        execution_id: &str,
+        result: CapabilityActionResult,
+    ) -> Option<CompiledPrompt> {
+        let submission_id = state
+            .execution_runtimes
+            .get(execution_id)?
+            .submission_id
+            .clone();
+        let capability_domain_id = state
+            .execution_submissions
+            .get(&submission_id)?
+            .capability_domain_id
+            .clone();
+        let (orchestrator, prompts) =
+            AgentOrchestrator::recording(self.capability_domain_registry());
+        self.set_agent_orchestrator(orchestrator);
+        let capability_domain_handles = HashMap::new();
+
+        handle_capability_domain_action_committed(
+            self,
+            state,
+            events_tx,
+            &capability_domain_handles,
+            CapabilityDomainCommittedAction {
+                submission_id,
+                capability_domain_id,
+                executions: vec![CapabilityDomainCommittedExecution {
+                    execution_id: execution_id.to_string(),
+                    result,
+                }],
+            },
+        );
+        if state.has_blocking_submissions() {
+            return None;
+        }
+
+        let (command_tx, _command_rx) = mpsc::channel(1);
+        process_turns(
+            self,
+            state,
+            &command_tx,
+            events_tx,
+            &capability_domain_handles,
+        )
+        .await;
+        prompts.lock().expect("recorded prompts lock").pop()
+    }
+}
@@ -5,23 +5,194 @@ mod types;
 
 use std::collections::HashMap;
 
-use tokio::sync::{broadcast, mpsc};
+use tokio::sync::mpsc;
 
 use crate::capability_domain::CapabilityDomainActorHandle;
 use crate::runtime::Runtime;
+use crate::session::event_stream::SessionEventSender;
 use crate::session::state::{SessionCommand, SessionState};
-use fathom_protocol::pb;
 
 use self::coordinator::TurnCoordinator;
 
 pub(super) async fn process_turns(
     runtime: &Runtime,
     state: &mut SessionState,
-    _command_tx: &mpsc::Sender<SessionCommand>,
-    events_tx: &broadcast::Sender<pb::SessionEvent>,
+    command_tx: &mpsc::Sender<SessionCommand>,
+    events_tx: &SessionEventSender,
     capability_domain_handles: &HashMap<String, CapabilityDomainActorHandle>,
 ) {
-    TurnCoordinator::new(runtime, state, events_tx, capability_domain_handles)
-        .process()
+    TurnCoordinator::new(
+        runtime,
+        state,
+        command_tx,
+        events_tx,
+        capability_domain_handles,
+    )
+    .process()
+    .await;
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::time::Duration;
+
+    use tokio::sync::mpsc;
+
+    use super::process_turns;
+    use crate::agent::AgentOrchestrator;
+    use crate::runtime::Runtime;
+    use crate::session::event_stream::session_event_channel;
+    use crate::session::testing::test_state;
+    use fathom_protocol::pb;
+
+    fn refresh_trigger(trigger_id: &str) -> pb::Trigger {
+        pb::Trigger {
+            trigger_id: trigger_id.to_string(),
+            created_at_unix_ms: 0,
+            kind: Some(pb::trigger::Kind::RefreshProfile(
+                pb::RefreshProfileTrigger {
+                    scope: pb::RefreshScope::All as i32,
+                    user_id: String::new(),
+                },
+            )),
+        }
+    }
+
+    #[tokio::test]
+    async fn process_turns_clears_turn_in_progress_after_each_turn() {
+        let runtime = Runtime::new(2, 10);
+        let mut state = test_state();
+        let (command_tx, _command_rx) = mpsc::channel(8);
+        let (events_tx, _events_rx) = session_event_channel(64);
+        let capability_domain_handles = HashMap::new();
+
+        for index in 0..3 {
+            state
+                .trigger_queue
+                .push_back(refresh_trigger(&format!("trigger-{index}")));
+            process_turns(
+                &runtime,
+                &mut state,
+                &command_tx,
+                &events_tx,
+                &capability_domain_handles,
+            )
+            .await;
+
+            assert!(!state.turn_in_progress);
+            assert!(state.trigger_queue.is_empty());
+        }
+        assert_eq!(state.turn_seq, 3);
+    }
+
+    #[tokio::test]
+    async fn process_turns_leaves_queue_untouched_while_turn_in_progress() {
+        let runtime = Runtime::new(2, 10);
+        let mut state = test_state();
+        let (command_tx, _command_rx) = mpsc::channel(8);
+        let (events_tx, _events_rx) = session_event_channel(64);
+        let capability_domain_handles = HashMap::new();
+
+        state.turn_in_progress = true;
+        state.trigger_queue.push_back(refresh_trigger("trigger-0"));
+        process_turns(
+            &runtime,
+            &mut state,
+            &command_tx,
+            &events_tx,
+            &capability_domain_handles,
+        )
         .await;
+
+        assert!(state.turn_in_progress);
+        assert_eq!(state.trigger_queue.len(), 1);
+        assert_eq!(state.turn_seq, 0);
+    }
+
+    #[tokio::test]
+    async fn process_turns_compacts_history_past_the_configured_threshold() {
+        let runtime = Runtime::new(2, 10);
+        runtime.set_history_compaction_limits(Some(2), Some(2));
+        let mut state = test_state();
+        state.history_compaction_limits = runtime.history_compaction_limits();
+        let (command_tx, _command_rx) = mpsc::channel(8);
+        let (events_tx, _events_rx) = session_event_channel(64);
+        let capability_domain_handles = HashMap::new();
+
+        for index in 0..6 {
+            state
+                .trigger_queue
+                .push_back(refresh_trigger(&format!("trigger-{index}")));
+            process_turns(
+                &runtime,
+                &mut state,
+                &command_tx,
+                &events_tx,
+                &capability_domain_handles,
+            )
+            .await;
+        }
+
+        assert!(!state.compaction.summary_blocks.is_empty());
+        assert!(state.compaction.last_compacted_history_index > 0);
+        assert!(state.history.len() <= 4);
+        assert!(state.compaction.last_compacted_history_index + state.history.len() as u64 >= 6);
+
+        let context = runtime.build_agent_invocation_context(&state, &[]);
+        assert_eq!(
+            context.compaction.summary_blocks.len(),
+            state.compaction.summary_blocks.len()
+        );
+        assert_eq!(context.recent_history.len(), state.history.len());
+    }
+
+    #[tokio::test]
+    async fn process_turns_fails_turns_that_exceed_the_turn_timeout() {
+        let runtime = Runtime::new(2, 10);
+        runtime
+            .set_turn_timeout(Duration::from_millis(50))
+            .expect("turn timeout");
+        runtime.set_agent_orchestrator(AgentOrchestrator::stalled(
+            runtime.capability_domain_registry(),
+            Duration::from_secs(30),
+        ));
+        let mut state = test_state();
+        let (command_tx, _command_rx) = mpsc::channel(8);
+        let (events_tx, mut events_rx) = session_event_channel(64);
+        let capability_domain_handles = HashMap::new();
+
+        state.trigger_queue.push_back(pb::Trigger {
+            trigger_id: "trigger-0".to_string(),
+            created_at_unix_ms: 0,
+            kind: Some(pb::trigger::Kind::UserMessage(pb::UserMessageTrigger {
+                user_id: "user-a".to_string(),
+                text: "are you there?".to_string(),
+            })),
+        });
+        tokio::time::timeout(
+            Duration::from_secs(5),
+            process_turns(
+                &runtime,
+                &mut state,
+                &command_tx,
+                &events_tx,
+                &capability_domain_handles,
+            ),
+        )
+        .await
+        .expect("timed-out turn should not block the session");
+
+        assert!(!state.turn_in_progress);
+        assert!(state.executions.is_empty());
+        let mut failure = None;
+        while let Ok(event) = events_rx.try_recv() {
+            if let Some(pb::session_event::Kind::TurnFailure(turn_failure)) = event.kind {
+                failure = Some(turn_failure);
+            }
+        }
+        let failure = failure.expect("turn failure event");
+        assert_eq!(failure.reason_code, "turn_timeout");
+        assert_eq!(failure.turn_id, 1);
+    }
 }
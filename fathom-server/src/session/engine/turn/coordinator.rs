@@ -1,14 +1,16 @@
 use std::collections::HashMap;
 
-use tokio::sync::broadcast;
+use tokio::sync::mpsc;
+use tracing::debug;
 
 use crate::capability_domain::CapabilityDomainActorHandle;
 use crate::runtime::Runtime;
-use crate::session::state::SessionState;
+use crate::session::event_stream::SessionEventSender;
+use crate::session::state::{SessionCommand, SessionState};
 use fathom_protocol::pb;
 
 use super::super::events::emit_event;
-use super::super::history_flush::flush_history;
+use super::super::history_flush::{compact_due_history, flush_history};
 use super::super::profiles::apply_profile_refresh;
 use super::invocation::run_agent_invocation;
 use super::journal::{append_turn_ended_record, append_turn_started_record};
@@ -17,7 +19,8 @@ use super::types::{AgentTurnSummary, PreparedTurn};
 pub(super) struct TurnCoordinator<'a> {
     runtime: &'a Runtime,
     state: &'a mut SessionState,
-    events_tx: &'a broadcast::Sender<pb::SessionEvent>,
+    command_tx: &'a mpsc::Sender<SessionCommand>,
+    events_tx: &'a SessionEventSender,
     capability_domain_handles: &'a HashMap<String, CapabilityDomainActorHandle>,
 }
 
@@ -25,18 +28,28 @@ impl<'a> TurnCoordinator<'a> {
     pub(super) fn new(
         runtime: &'a Runtime,
         state: &'a mut SessionState,
-        events_tx: &'a broadcast::Sender<pb::SessionEvent>,
+        command_tx: &'a mpsc::Sender<SessionCommand>,
+        events_tx: &'a SessionEventSender,
         capability_domain_handles: &'a HashMap<String, CapabilityDomainActorHandle>,
     ) -> Self {
         Self {
             runtime,
             state,
+            command_tx,
             events_tx,
             capability_domain_handles,
         }
     }
 
     pub(super) async fn process(&mut self) {
+        if self.state.turn_in_progress {
+            debug!(
+                session_id = %self.state.session_id,
+                queued_triggers = self.state.trigger_queue.len(),
+                "turn loop already active; queued triggers are drained by the active loop"
+            );
+            return;
+        }
         if self.is_blocked() {
             return;
         }
@@ -51,6 +64,7 @@ impl<'a> TurnCoordinator<'a> {
 
             let mut prepared = PreparedTurn::new(turn_triggers);
             self.preprocess_triggers(&mut prepared).await;
+            self.skip_idle_heartbeat_turn(&mut prepared);
 
             let agent_summary = if prepared.agent_triggers.is_empty() {
                 None
@@ -70,15 +84,13 @@ impl<'a> TurnCoordinator<'a> {
                 )
             };
 
-            self.finalize_turn(turn_id, prepared, agent_summary);
+            self.finalize_turn(turn_id, prepared, agent_summary).await;
         }
         self.state.turn_in_progress = false;
     }
 
     fn is_blocked(&self) -> bool {
-        self.state.turn_in_progress
-            || self.state.trigger_queue.is_empty()
-            || self.state.has_blocking_submissions()
+        self.state.trigger_queue.is_empty() || self.state.has_blocking_submissions()
     }
 
     fn allocate_turn_id(&mut self) -> u64 {
@@ -124,34 +136,48 @@ impl<'a> TurnCoordinator<'a> {
         }
     }
 
-    fn finalize_turn(
+    fn skip_idle_heartbeat_turn(&mut self, prepared: &mut PreparedTurn) {
+        let heartbeat_only = !prepared.agent_triggers.is_empty()
+            && prepared
+                .agent_triggers
+                .iter()
+                .all(|trigger| matches!(trigger.kind, Some(pb::trigger::Kind::Heartbeat(_))));
+        if !heartbeat_only || !self.runtime.idle_heartbeat_turns_skipped() {
+            return;
+        }
+
+        prepared.agent_triggers.clear();
+        emit_event(
+            self.events_tx,
+            &self.state.session_id,
+            pb::session_event::Kind::SystemNotice(pb::SystemNoticeEvent {
+                level: pb::SystemNoticeLevel::Info as i32,
+                code: "heartbeat_turn_skipped".to_string(),
+                message: "heartbeat-only turn completed without invoking the agent".to_string(),
+            }),
+        );
+    }
+
+    async fn finalize_turn(
         &mut self,
         turn_id: u64,
         prepared: PreparedTurn,
         agent_summary: Option<AgentTurnSummary>,
     ) {
-        for (index, output) in prepared.assistant_outputs.iter().enumerate() {
-            let stream_id = prepared
-                .assistant_stream_ids
-                .get(index)
-                .cloned()
-                .unwrap_or_default();
-            emit_event(
-                self.events_tx,
-                &self.state.session_id,
-                pb::session_event::Kind::AssistantOutput(pb::AssistantOutputEvent {
-                    content: output.clone(),
-                    stream_id,
-                }),
-            );
-        }
+        self.record_turn_metrics(agent_summary.as_ref());
+        self.emit_assistant_outputs(&prepared);
 
         flush_history(
             self.state,
             &prepared.turn_triggers,
             &prepared.assistant_outputs,
         );
+        compact_due_history(self.runtime, self.state, self.command_tx);
         let reason = format!("processed {} trigger(s)", prepared.turn_triggers.len());
+        let retry_summary = agent_summary
+            .as_ref()
+            .map(|summary| summary.retry_summary.clone())
+            .unwrap_or_default();
         emit_event(
             self.events_tx,
             &self.state.session_id,
@@ -159,10 +185,13 @@ impl<'a> TurnCoordinator<'a> {
                 turn_id,
                 reason,
                 history_size: self.state.history.len() as u64,
+                agent_attempts: retry_summary.attempts as u64,
+                model_request_retries: retry_summary.model_request_retries as u64,
+                fallback_effort: retry_summary.fallback_effort.unwrap_or_default(),
             }),
         );
 
-        let is_quiescent = agent_summary.is_some_and(|summary| {
+        let is_quiescent = agent_summary.as_ref().is_some_and(|summary| {
             summary.assistant_output_count > 0
                 && summary.action_call_count == 0
                 && !self.state.has_blocking_submissions()
@@ -175,11 +204,63 @@ impl<'a> TurnCoordinator<'a> {
             self.runtime,
             self.state,
             turn_id,
-            agent_summary,
+            agent_summary.as_ref(),
             is_quiescent,
         );
     }
 
+    fn emit_assistant_outputs(&self, prepared: &PreparedTurn) {
+        let output_count = prepared.assistant_outputs.len();
+        let emitted_count = match self.runtime.max_assistant_outputs_per_turn() {
+            Some(max_outputs) if output_count > max_outputs => max_outputs.saturating_sub(1),
+            _ => output_count,
+        };
+        for (index, output) in prepared
+            .assistant_outputs
+            .iter()
+            .take(emitted_count)
+            .enumerate()
+        {
+            let stream_id = prepared
+                .assistant_stream_ids
+                .get(index)
+                .cloned()
+                .unwrap_or_default();
+            emit_event(
+                self.events_tx,
+                &self.state.session_id,
+                pb::session_event::Kind::AssistantOutput(pb::AssistantOutputEvent {
+                    content: output.clone(),
+                    stream_id,
+                }),
+            );
+        }
+        if emitted_count < output_count {
+            emit_event(
+                self.events_tx,
+                &self.state.session_id,
+                pb::session_event::Kind::AssistantOutput(pb::AssistantOutputEvent {
+                    content: format!(
+                        "{} more assistant output(s) collapsed for this turn",
+                        output_count - emitted_count
+                    ),
+                    stream_id: String::new(),
+                }),
+            );
+        }
+    }
+
+    fn record_turn_metrics(&mut self, agent_summary: Option<&AgentTurnSummary>) {
+        let metrics = &mut self.state.metrics;
+        metrics.turn_count += 1;
+        if let Some(summary) = agent_summary {
+            metrics.action_call_count += summary.action_call_count as u64;
+            if summary.failed {
+                metrics.failure_count += 1;
+            }
+        }
+    }
+
     fn emit_turn_started(&self, turn_id: u64, trigger_count: usize) {
         emit_event(
             self.events_tx,
@@ -191,3 +272,223 @@ impl<'a> TurnCoordinator<'a> {
         );
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use tokio::sync::mpsc;
+
+    use super::super::invocation::emit_model_diagnostics;
+    use super::super::types::{AgentTurnSummary, PreparedTurn};
+    use super::TurnCoordinator;
+    use crate::agent::AgentTurnRetrySummary;
+    use crate::runtime::Runtime;
+    use crate::session::event_stream::session_event_channel;
+    use crate::session::testing::test_state;
+    use fathom_protocol::pb;
+
+    #[tokio::test]
+    async fn finalize_turn_records_turn_and_action_call_metrics() {
+        let runtime = Runtime::new(2, 10);
+        let mut state = test_state();
+        let (events_tx, _events_rx) = session_event_channel(64);
+        let (command_tx, _command_rx) = mpsc::channel(8);
+        let capability_domain_handles = HashMap::new();
+
+        TurnCoordinator::new(
+            &runtime,
+            &mut state,
+            &command_tx,
+            &events_tx,
+            &capability_domain_handles,
+        )
+        .finalize_turn(
+            1,
+            PreparedTurn::new(Vec::new()),
+            Some(AgentTurnSummary {
+                action_call_count: 1,
+                assistant_output_count: 0,
+                failed: false,
+                retry_summary: AgentTurnRetrySummary::default(),
+            }),
+        )
+        .await;
+
+        let summary = state.to_summary();
+        assert_eq!(summary.total_turn_count, 1);
+        assert_eq!(summary.total_action_call_count, 1);
+        assert_eq!(summary.total_failure_count, 0);
+    }
+
+    #[tokio::test]
+    async fn diagnostics_and_assistant_text_are_distinct_event_kinds() {
+        let runtime = Runtime::new(2, 10);
+        let mut state = test_state();
+        let (events_tx, mut events_rx) = session_event_channel(64);
+        let (command_tx, _command_rx) = mpsc::channel(8);
+        let capability_domain_handles = HashMap::new();
+        let mut prepared = PreparedTurn::new(Vec::new());
+        prepared.assistant_outputs.push("hello there".to_string());
+        prepared.assistant_stream_ids.push(String::new());
+
+        emit_model_diagnostics(
+            &events_tx,
+            &state.session_id,
+            &["dispatched action_call=call-1 name=filesystem__list".to_string()],
+        );
+        TurnCoordinator::new(
+            &runtime,
+            &mut state,
+            &command_tx,
+            &events_tx,
+            &capability_domain_handles,
+        )
+        .finalize_turn(1, prepared, None)
+        .await;
+
+        let mut diagnostics = Vec::new();
+        let mut assistant_outputs = Vec::new();
+        while let Ok(event) = events_rx.try_recv() {
+            match event.kind {
+                Some(pb::session_event::Kind::Diagnostic(diagnostic)) => {
+                    diagnostics.push(diagnostic.detail)
+                }
+                Some(pb::session_event::Kind::AssistantOutput(output)) => {
+                    assistant_outputs.push(output.content)
+                }
+                _ => {}
+            }
+        }
+        assert_eq!(
+            diagnostics,
+            vec!["dispatched action_call=call-1 name=filesystem__list".to_string()]
+        );
+        assert_eq!(assistant_outputs, vec!["hello there".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn heartbeat_only_turn_is_short_circuited_without_invoking_the_agent() {
+        let runtime = Runtime::new(2, 10);
+        runtime.set_idle_heartbeat_turns_skipped(true);
+        let mut state = test_state();
+        state.trigger_queue.push_back(pb::Trigger {
+            trigger_id: "trigger-1".to_string(),
+            created_at_unix_ms: 0,
+            kind: Some(pb::trigger::Kind::Heartbeat(pb::HeartbeatTrigger {})),
+        });
+        let (events_tx, mut events_rx) = session_event_channel(64);
+        let (command_tx, _command_rx) = mpsc::channel(8);
+        let capability_domain_handles = HashMap::new();
+
+        TurnCoordinator::new(
+            &runtime,
+            &mut state,
+            &command_tx,
+            &events_tx,
+            &capability_domain_handles,
+        )
+        .process()
+        .await;
+
+        let mut kinds = Vec::new();
+        while let Ok(event) = events_rx.try_recv() {
+            kinds.push(event.kind.expect("event kind"));
+        }
+        assert!(matches!(
+            kinds.first(),
+            Some(pb::session_event::Kind::TurnStarted(_))
+        ));
+        assert!(kinds.iter().any(|kind| matches!(
+            kind,
+            pb::session_event::Kind::SystemNotice(notice) if notice.code == "heartbeat_turn_skipped"
+        )));
+        assert!(matches!(
+            kinds.last(),
+            Some(pb::session_event::Kind::TurnEnded(_))
+        ));
+        assert!(!kinds.iter().any(|kind| matches!(
+            kind,
+            pb::session_event::Kind::AgentStream(_)
+                | pb::session_event::Kind::AgentThinking(_)
+                | pb::session_event::Kind::TurnFailure(_)
+        )));
+        assert!(state.trigger_queue.is_empty());
+        assert_eq!(state.to_summary().total_turn_count, 1);
+    }
+
+    #[tokio::test]
+    async fn finalize_turn_collapses_assistant_outputs_beyond_the_cap() {
+        let runtime = Runtime::new(2, 10);
+        runtime.set_max_assistant_outputs_per_turn(5);
+        let mut state = test_state();
+        let (events_tx, mut events_rx) = session_event_channel(256);
+        let (command_tx, _command_rx) = mpsc::channel(8);
+        let capability_domain_handles = HashMap::new();
+        let mut prepared = PreparedTurn::new(Vec::new());
+        for index in 0..40 {
+            prepared
+                .assistant_outputs
+                .push(format!("dispatched action call {index}"));
+            prepared.assistant_stream_ids.push(String::new());
+        }
+
+        TurnCoordinator::new(
+            &runtime,
+            &mut state,
+            &command_tx,
+            &events_tx,
+            &capability_domain_handles,
+        )
+        .finalize_turn(1, prepared, None)
+        .await;
+
+        let mut assistant_outputs = Vec::new();
+        while let Ok(event) = events_rx.try_recv() {
+            if let Some(pb::session_event::Kind::AssistantOutput(output)) = event.kind {
+                assistant_outputs.push(output.content);
+            }
+        }
+        assert_eq!(assistant_outputs.len(), 5);
+        assert_eq!(assistant_outputs[3], "dispatched action call 3");
+        assert_eq!(
+            assistant_outputs[4],
+            "36 more assistant output(s) collapsed for this turn"
+        );
+    }
+
+    #[tokio::test]
+    async fn finalize_turn_emits_every_assistant_output_without_a_cap() {
+        let runtime = Runtime::new(2, 10);
+        let mut state = test_state();
+        let (events_tx, mut events_rx) = session_event_channel(256);
+        let (command_tx, _command_rx) = mpsc::channel(8);
+        let capability_domain_handles = HashMap::new();
+        let mut prepared = PreparedTurn::new(Vec::new());
+        for index in 0..40 {
+            prepared
+                .assistant_outputs
+                .push(format!("dispatched action call {index}"));
+            prepared.assistant_stream_ids.push(String::new());
+        }
+
+        TurnCoordinator::new(
+            &runtime,
+            &mut state,
+            &command_tx,
+            &events_tx,
+            &capability_domain_handles,
+        )
+        .finalize_turn(1, prepared, None)
+        .await;
+
+        let mut assistant_outputs = Vec::new();
+        while let Ok(event) = events_rx.try_recv() {
+            if let Some(pb::session_event::Kind::AssistantOutput(output)) = event.kind {
+                assistant_outputs.push(output.content);
+            }
+        }
+        assert_eq!(assistant_outputs.len(), 40);
+        assert_eq!(assistant_outputs[39], "dispatched action call 39");
+    }
+}
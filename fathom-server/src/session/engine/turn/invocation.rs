@@ -1,10 +1,9 @@
 use std::collections::HashMap;
 
-use tokio::sync::broadcast;
-
-use crate::agent::ModelDeltaEvent;
+use crate::agent::{AgentTurnOutcome, ModelDeltaEvent};
 use crate::capability_domain::CapabilityDomainActorHandle;
 use crate::runtime::Runtime;
+use crate::session::event_stream::SessionEventSender;
 use crate::session::state::SessionState;
 use crate::util::now_unix_ms;
 use fathom_protocol::pb;
@@ -19,7 +18,7 @@ use super::types::{AgentTurnSummary, PreparedTurn};
 pub(super) async fn run_agent_invocation(
     runtime: &Runtime,
     state: &mut SessionState,
-    events_tx: &broadcast::Sender<pb::SessionEvent>,
+    events_tx: &SessionEventSender,
     capability_domain_handles: &HashMap<String, CapabilityDomainActorHandle>,
     turn_id: u64,
     invocation_seq: u64,
@@ -48,12 +47,25 @@ pub(super) async fn run_agent_invocation(
             capability_domain_handles,
             turn_id,
         );
-        let outcome = orchestrator
-            .run_turn(&context, prompt_bundle.clone(), |event: ModelDeltaEvent| {
+        let turn =
+            orchestrator.run_turn(&context, prompt_bundle.clone(), |event: ModelDeltaEvent| {
                 delta_transport.handle_model_event(event);
-            })
-            .await;
-        delta_transport.flush_action_invocations();
+            });
+        let outcome = match runtime.turn_timeout() {
+            Some(turn_timeout) => tokio::time::timeout(turn_timeout, turn).await.ok(),
+            None => Some(turn.await),
+        };
+        delta_transport.finish_thinking();
+        let outcome = match outcome {
+            Some(outcome) => {
+                delta_transport.flush_action_invocations();
+                outcome
+            }
+            None => {
+                let discarded = delta_transport.discard_action_invocations();
+                turn_timeout_outcome(runtime, discarded)
+            }
+        };
         let stream_notes = delta_transport.invocation_stream_notes().to_vec();
         let action_dispatches = delta_transport.action_dispatches().to_vec();
         let streamed_outputs = delta_transport.drain_streamed_assistant_outputs();
@@ -66,6 +78,7 @@ pub(super) async fn run_agent_invocation(
     let failed = outcome.failed;
     let failure_code = outcome.failure_code;
     let failure_message = outcome.failure_message;
+    let retry_summary = outcome.retry_summary;
 
     for (stream_id, output) in streamed_outputs {
         prepared.assistant_outputs.push(output);
@@ -87,17 +100,7 @@ pub(super) async fn run_agent_invocation(
         prepared.assistant_stream_ids.push(String::new());
     }
 
-    for diagnostic in &model_diagnostics {
-        emit_event(
-            events_tx,
-            &state.session_id,
-            pb::session_event::Kind::AgentStream(pb::AgentStreamEvent {
-                phase: "agent.diagnostic".to_string(),
-                detail: diagnostic.clone(),
-                created_at_unix_ms: now_unix_ms(),
-            }),
-        );
-    }
+    emit_model_diagnostics(events_tx, &state.session_id, &model_diagnostics);
 
     if failed {
         emit_event(
@@ -138,5 +141,38 @@ pub(super) async fn run_agent_invocation(
             .assistant_outputs
             .len()
             .saturating_sub(assistant_output_start_len),
+        failed,
+        retry_summary,
+    }
+}
+
+fn turn_timeout_outcome(runtime: &Runtime, discarded_action_calls: usize) -> AgentTurnOutcome {
+    let timeout_secs = runtime
+        .turn_timeout()
+        .map(|timeout| timeout.as_secs_f64())
+        .unwrap_or_default();
+    AgentTurnOutcome::failure(
+        "turn_timeout",
+        format!("agent turn did not finish within {timeout_secs}s"),
+        vec![format!(
+            "discarded {discarded_action_calls} action call(s) from the timed-out turn"
+        )],
+    )
+}
+
+pub(super) fn emit_model_diagnostics(
+    events_tx: &SessionEventSender,
+    session_id: &str,
+    diagnostics: &[String],
+) {
+    for diagnostic in diagnostics {
+        emit_event(
+            events_tx,
+            session_id,
+            pb::session_event::Kind::Diagnostic(pb::DiagnosticEvent {
+                detail: diagnostic.clone(),
+                created_at_unix_ms: now_unix_ms(),
+            }),
+        );
     }
 }
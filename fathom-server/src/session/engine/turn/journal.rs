@@ -108,7 +108,7 @@ pub(super) fn append_turn_ended_record(
     runtime: &Runtime,
     state: &SessionState,
     turn_id: u64,
-    agent_summary: Option<AgentTurnSummary>,
+    agent_summary: Option<&AgentTurnSummary>,
     is_quiescent: bool,
 ) {
     runtime.diagnostics().append_session_record(
@@ -124,6 +124,9 @@ pub(super) fn append_turn_ended_record(
             "agent_summary": agent_summary.map(|summary| serde_json::json!({
                 "action_call_count": summary.action_call_count,
                 "assistant_output_count": summary.assistant_output_count,
+                "attempts": summary.retry_summary.attempts,
+                "model_request_retries": summary.retry_summary.model_request_retries,
+                "fallback_effort": summary.retry_summary.fallback_effort,
             })),
             "quiescent": is_quiescent,
         }),
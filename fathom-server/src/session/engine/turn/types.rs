@@ -1,9 +1,12 @@
+use crate::agent::AgentTurnRetrySummary;
 use fathom_protocol::pb;
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 pub(super) struct AgentTurnSummary {
     pub(super) action_call_count: usize,
     pub(super) assistant_output_count: usize,
+    pub(super) failed: bool,
+    pub(super) retry_summary: AgentTurnRetrySummary,
 }
 
 #[derive(Debug)]
@@ -0,0 +1,209 @@
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+
+use tokio::sync::broadcast;
+use tokio_stream::Stream;
+use tokio_stream::wrappers::BroadcastStream;
+use tokio_stream::wrappers::errors::BroadcastStreamRecvError;
+use tonic::Status;
+use tracing::info;
+
+use fathom_protocol::pb;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum EventDelivery {
+    Delivered,
+    FirstDrop,
+    Dropped,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct SessionEventSender {
+    inner: broadcast::Sender<pb::SessionEvent>,
+    next_seq: Arc<Mutex<u64>>,
+    dropped_count: Arc<AtomicU64>,
+}
+
+pub(crate) fn session_event_channel(
+    capacity: usize,
+) -> (SessionEventSender, broadcast::Receiver<pb::SessionEvent>) {
+    let (inner, receiver) = broadcast::channel(capacity);
+    (
+        SessionEventSender {
+            inner,
+            next_seq: Arc::new(Mutex::new(1)),
+            dropped_count: Arc::new(AtomicU64::new(0)),
+        },
+        receiver,
+    )
+}
+
+impl SessionEventSender {
+    pub(crate) fn subscribe(&self) -> broadcast::Receiver<pb::SessionEvent> {
+        self.inner.subscribe()
+    }
+
+    pub(crate) fn send(&self, mut event: pb::SessionEvent) -> EventDelivery {
+        let mut next_seq = self
+            .next_seq
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        event.seq = *next_seq;
+        *next_seq += 1;
+        let delivered = self.inner.send(event).is_ok();
+        drop(next_seq);
+        if delivered {
+            return EventDelivery::Delivered;
+        }
+        if self.dropped_count.fetch_add(1, Ordering::Relaxed) == 0 {
+            EventDelivery::FirstDrop
+        } else {
+            EventDelivery::Dropped
+        }
+    }
+
+    pub(crate) fn dropped_count(&self) -> u64 {
+        self.dropped_count.load(Ordering::Relaxed)
+    }
+}
+
+pub(crate) struct SessionEventStream {
+    session_id: String,
+    delivered_count: u64,
+    inner: BroadcastStream<pb::SessionEvent>,
+}
+
+impl SessionEventStream {
+    pub(crate) fn attach(
+        session_id: String,
+        receiver: broadcast::Receiver<pb::SessionEvent>,
+    ) -> Self {
+        info!(%session_id, "session event stream attached");
+        Self {
+            session_id,
+            delivered_count: 0,
+            inner: BroadcastStream::new(receiver),
+        }
+    }
+}
+
+impl Stream for SessionEventStream {
+    type Item = Result<pb::SessionEvent, Status>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        match Pin::new(&mut self.inner).poll_next(cx) {
+            Poll::Ready(Some(Ok(event))) => {
+                self.delivered_count += 1;
+                Poll::Ready(Some(Ok(event)))
+            }
+            Poll::Ready(Some(Err(BroadcastStreamRecvError::Lagged(skipped)))) => {
+                Poll::Ready(Some(Err(Status::resource_exhausted(format!(
+                    "event stream lagged by {skipped} event(s)"
+                )))))
+            }
+            Poll::Ready(None) => Poll::Ready(None),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+impl Drop for SessionEventStream {
+    fn drop(&mut self) {
+        info!(
+            session_id = %self.session_id,
+            delivered_count = self.delivered_count,
+            "session event stream detached"
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::sync::broadcast;
+    use tokio_stream::StreamExt;
+
+    use super::{SessionEventStream, session_event_channel};
+    use fathom_protocol::pb;
+
+    fn diagnostic(detail: &str) -> pb::SessionEvent {
+        pb::SessionEvent {
+            session_id: "session-1".to_string(),
+            created_at_unix_ms: 0,
+            seq: 0,
+            kind: Some(pb::session_event::Kind::Diagnostic(pb::DiagnosticEvent {
+                detail: detail.to_string(),
+                created_at_unix_ms: 0,
+            })),
+        }
+    }
+
+    #[test]
+    fn event_seq_is_per_session_and_follows_send_order_across_clones() {
+        let (events_tx, mut events_rx) = session_event_channel(1024);
+        let (other_tx, mut other_rx) = session_event_channel(8);
+
+        std::thread::scope(|scope| {
+            for _ in 0..4 {
+                let events_tx = events_tx.clone();
+                scope.spawn(move || {
+                    for _ in 0..128 {
+                        events_tx.send(diagnostic("concurrent"));
+                    }
+                });
+            }
+        });
+        other_tx.send(diagnostic("other"));
+
+        let seqs = std::iter::from_fn(|| events_rx.try_recv().ok())
+            .map(|event| event.seq)
+            .collect::<Vec<_>>();
+        assert_eq!(seqs, (1..=512).collect::<Vec<_>>());
+        assert_eq!(other_rx.try_recv().expect("other session event").seq, 1);
+    }
+
+    #[tokio::test]
+    async fn dropping_event_stream_releases_broadcast_subscriber() {
+        let (events_tx, _) = broadcast::channel(8);
+        let mut stream = SessionEventStream::attach("session-1".to_string(), events_tx.subscribe());
+        assert_eq!(events_tx.receiver_count(), 1);
+
+        events_tx
+            .send(diagnostic("first"))
+            .expect("subscriber attached");
+        let delivered = stream
+            .next()
+            .await
+            .expect("stream item")
+            .expect("delivered event");
+        assert!(matches!(
+            delivered.kind,
+            Some(pb::session_event::Kind::Diagnostic(ref diagnostic)) if diagnostic.detail == "first"
+        ));
+
+        drop(stream);
+
+        assert_eq!(events_tx.receiver_count(), 0);
+        assert!(events_tx.send(diagnostic("second")).is_err());
+    }
+
+    #[tokio::test]
+    async fn lagged_event_stream_reports_resource_exhausted() {
+        let (events_tx, _) = broadcast::channel(1);
+        let mut stream = SessionEventStream::attach("session-1".to_string(), events_tx.subscribe());
+        events_tx
+            .send(diagnostic("first"))
+            .expect("subscriber attached");
+        events_tx
+            .send(diagnostic("second"))
+            .expect("subscriber attached");
+
+        let status = stream
+            .next()
+            .await
+            .expect("stream item")
+            .expect_err("lagged error");
+        assert_eq!(status.code(), tonic::Code::ResourceExhausted);
+    }
+}
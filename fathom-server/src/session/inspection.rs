@@ -363,6 +363,7 @@ mod tests {
         read_execution_input, read_execution_result,
     };
     use crate::agent::SessionCompaction;
+    use crate::history::{HistoryCompactionLimits, PreviewLimits};
     use crate::session::state::{
         ExecutionRuntimeState, ExecutionSubmissionExecution, ExecutionSubmissionState,
         ExecutionSubmissionStatus, SessionState,
@@ -383,7 +384,13 @@ mod tests {
                 default_user_profile(&user_id),
             )]),
             trigger_queue: Default::default(),
+            queue_depth_warning_threshold: None,
+            queue_depth_warning_armed: true,
             history: Vec::new(),
+            history_log: None,
+            result_preview_limits: PreviewLimits::default(),
+            history_compaction_limits: HistoryCompactionLimits::default(),
+            execution_capacity: None,
             executions: HashMap::new(),
             engaged_capability_domain_ids: BTreeSet::new(),
             foreground_submission_ids: Default::default(),
@@ -396,6 +403,7 @@ mod tests {
             turn_seq: 0,
             turn_in_progress: false,
             compaction: SessionCompaction::default(),
+            metrics: Default::default(),
         };
         state.executions.insert(
             "execution-1".to_string(),
@@ -413,6 +421,8 @@ mod tests {
                 .to_string(),
                 created_at_unix_ms: 0,
                 updated_at_unix_ms: 0,
+                call_id: String::new(),
+                turn_id: 0,
             },
         );
         state.executions.insert(
@@ -426,6 +436,8 @@ mod tests {
                 result_message: String::new(),
                 created_at_unix_ms: 0,
                 updated_at_unix_ms: 0,
+                call_id: String::new(),
+                turn_id: 0,
             },
         );
         state.execution_runtimes.insert(
@@ -433,6 +445,7 @@ mod tests {
             ExecutionRuntimeState {
                 submission_id: "execution-submission-1".to_string(),
                 background_requested: true,
+                wakes_agent: true,
                 call_key: "call-key".to_string(),
                 call_id: None,
             },
@@ -95,6 +95,8 @@ mod tests {
             .to_string(),
             created_at_unix_ms: 0,
             updated_at_unix_ms: 0,
+            call_id: String::new(),
+            turn_id: 0,
         };
 
         let resolved = resolve_from_execution(&execution).expect("lookup should resolve");
@@ -127,6 +129,8 @@ mod tests {
             .to_string(),
             created_at_unix_ms: 0,
             updated_at_unix_ms: 0,
+            call_id: String::new(),
+            turn_id: 0,
         };
 
         let resolved = resolve_from_execution(&execution).expect("lookup should resolve");
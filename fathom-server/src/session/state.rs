@@ -1,12 +1,15 @@
 use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+use std::time::Duration;
 use tokio::time::Instant;
 
-use tokio::sync::{broadcast, mpsc, oneshot};
+use tokio::sync::mpsc::error::SendTimeoutError;
+use tokio::sync::{mpsc, oneshot};
 use tonic::Status;
 
 use crate::agent::SessionCompaction;
 use crate::capability_domain::CapabilityDomainCommittedAction;
-use crate::history::HistoryEvent;
+use crate::history::{HistoryCompactionLimits, HistoryEvent, HistoryLog, PreviewLimits};
+use crate::session::event_stream::SessionEventSender;
 use crate::session::inspection::{
     ExecutionInspection, ExecutionListPage, ExecutionListQuery, PayloadSlice,
 };
@@ -17,8 +20,26 @@ use fathom_protocol::pb;
 
 #[derive(Clone)]
 pub(crate) struct SessionRuntime {
+    pub(crate) agent_id: String,
+    pub(crate) participant_user_ids: Vec<String>,
     pub(crate) command_tx: mpsc::Sender<SessionCommand>,
-    pub(crate) events_tx: broadcast::Sender<pb::SessionEvent>,
+    pub(crate) events_tx: SessionEventSender,
+}
+
+const SESSION_CMD_SEND_TIMEOUT: Duration = Duration::from_secs(1);
+
+impl SessionRuntime {
+    pub(crate) async fn send_command(&self, command: SessionCommand) -> Result<(), Status> {
+        self.command_tx
+            .send_timeout(command, SESSION_CMD_SEND_TIMEOUT)
+            .await
+            .map_err(|error| match error {
+                SendTimeoutError::Timeout(_) => {
+                    Status::resource_exhausted("session command queue is full")
+                }
+                SendTimeoutError::Closed(_) => Status::unavailable("session actor unavailable"),
+            })
+    }
 }
 
 pub(crate) enum SessionCommand {
@@ -26,12 +47,26 @@ pub(crate) enum SessionCommand {
         trigger: pb::Trigger,
         respond_to: oneshot::Sender<Result<pb::EnqueueTriggerResponse, Status>>,
     },
+    ClearTriggerQueue {
+        respond_to: oneshot::Sender<pb::ClearTriggerQueueResponse>,
+    },
+    Compact {
+        respond_to: oneshot::Sender<pb::CompactSessionHistoryResponse>,
+    },
+    ScheduleHeartbeat {
+        delay: Duration,
+        respond_to: oneshot::Sender<String>,
+    },
     GetSummary {
         respond_to: oneshot::Sender<pb::SessionSummary>,
     },
     ListExecutions {
         respond_to: oneshot::Sender<Vec<pb::Execution>>,
     },
+    GetExecution {
+        execution_id: String,
+        respond_to: oneshot::Sender<Option<pb::Execution>>,
+    },
     InspectListExecutions {
         query: ExecutionListQuery,
         respond_to: oneshot::Sender<Result<ExecutionListPage, String>>,
@@ -52,19 +87,39 @@ pub(crate) enum SessionCommand {
         limit: usize,
         respond_to: oneshot::Sender<Result<PayloadSlice, String>>,
     },
+    GetHistory {
+        offset: u64,
+        page_size: u64,
+        respond_to: oneshot::Sender<pb::GetHistoryResponse>,
+    },
     CancelExecution {
         execution_id: String,
         respond_to: oneshot::Sender<Result<pb::CancelExecutionResponse, Status>>,
     },
+    RunAction {
+        action_id: String,
+        args_json: String,
+        respond_to: oneshot::Sender<Result<pb::Execution, Status>>,
+    },
     CapabilityDomainActionCommitted {
         committed: CapabilityDomainCommittedAction,
     },
+    StartQueuedExecutions,
+    HistorySummaryReady {
+        block_id: String,
+        summary_text: String,
+    },
+    Shutdown {
+        reason: String,
+        respond_to: Option<oneshot::Sender<pb::SessionSummary>>,
+    },
 }
 
 #[derive(Debug, Clone)]
 pub(crate) struct ExecutionRuntimeState {
     pub(crate) submission_id: String,
     pub(crate) background_requested: bool,
+    pub(crate) wakes_agent: bool,
     pub(crate) call_key: String,
     pub(crate) call_id: Option<String>,
 }
@@ -90,6 +145,13 @@ pub(crate) struct ExecutionSubmissionState {
     pub(crate) foreground_wait_deadline: Option<Instant>,
 }
 
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct SessionMetrics {
+    pub(crate) turn_count: u64,
+    pub(crate) action_call_count: u64,
+    pub(crate) failure_count: u64,
+}
+
 pub(crate) struct SessionState {
     pub(crate) session_id: String,
     pub(crate) created_at_unix_ms: i64,
@@ -98,7 +160,13 @@ pub(crate) struct SessionState {
     pub(crate) agent_profile_copy: pb::AgentProfile,
     pub(crate) participant_user_profiles_copy: HashMap<String, pb::UserProfile>,
     pub(crate) trigger_queue: VecDeque<pb::Trigger>,
+    pub(crate) queue_depth_warning_threshold: Option<u64>,
+    pub(crate) queue_depth_warning_armed: bool,
     pub(crate) history: Vec<HistoryEvent>,
+    pub(crate) history_log: Option<HistoryLog>,
+    pub(crate) result_preview_limits: PreviewLimits,
+    pub(crate) history_compaction_limits: HistoryCompactionLimits,
+    pub(crate) execution_capacity: Option<usize>,
     pub(crate) executions: HashMap<String, pb::Execution>,
     pub(crate) engaged_capability_domain_ids: BTreeSet<String>,
     pub(crate) foreground_submission_ids: HashSet<String>,
@@ -111,6 +179,7 @@ pub(crate) struct SessionState {
     pub(crate) turn_seq: u64,
     pub(crate) turn_in_progress: bool,
     pub(crate) compaction: SessionCompaction,
+    pub(crate) metrics: SessionMetrics,
 }
 
 impl SessionState {
@@ -130,7 +199,13 @@ impl SessionState {
             agent_profile_copy,
             participant_user_profiles_copy,
             trigger_queue: VecDeque::new(),
+            queue_depth_warning_threshold: None,
+            queue_depth_warning_armed: true,
             history: Vec::new(),
+            history_log: None,
+            result_preview_limits: PreviewLimits::default(),
+            history_compaction_limits: HistoryCompactionLimits::default(),
+            execution_capacity: None,
             executions: HashMap::new(),
             engaged_capability_domain_ids,
             foreground_submission_ids: HashSet::new(),
@@ -143,9 +218,14 @@ impl SessionState {
             turn_seq: 0,
             turn_in_progress: false,
             compaction: SessionCompaction::default(),
+            metrics: SessionMetrics::default(),
         }
     }
 
+    pub(crate) fn execution_capacity(&self, runtime_capacity: usize) -> usize {
+        self.execution_capacity.unwrap_or(runtime_capacity)
+    }
+
     pub(crate) fn to_summary(&self) -> pb::SessionSummary {
         let participant_user_profiles_copy = self
             .participant_user_ids
@@ -176,6 +256,11 @@ impl SessionState {
                 + self.history.len() as u64,
             pending_execution_count,
             running_execution_count,
+            total_turn_count: self.metrics.turn_count,
+            total_action_call_count: self.metrics.action_call_count,
+            total_failure_count: self.metrics.failure_count,
+            dropped_event_count: 0,
+            execution_capacity: 0,
         }
     }
 
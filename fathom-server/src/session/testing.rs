@@ -0,0 +1,25 @@
+use std::collections::{BTreeSet, HashMap};
+
+use crate::session::SessionState;
+use crate::util::{default_agent_profile, default_user_profile};
+
+pub(crate) fn test_state() -> SessionState {
+    test_state_with_capability_domains([])
+}
+
+pub(crate) fn test_state_with_capability_domains<'a>(
+    capability_domain_ids: impl IntoIterator<Item = &'a str>,
+) -> SessionState {
+    let user_id = "user-a".to_string();
+    SessionState::new(
+        "session-1".to_string(),
+        "agent-a".to_string(),
+        vec![user_id.clone()],
+        default_agent_profile("agent-a"),
+        HashMap::from([(user_id.clone(), default_user_profile(&user_id))]),
+        capability_domain_ids
+            .into_iter()
+            .map(str::to_string)
+            .collect::<BTreeSet<_>>(),
+    )
+}
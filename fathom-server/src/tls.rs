@@ -0,0 +1,69 @@
+use std::path::Path;
+
+use anyhow::{Context, Result, bail};
+use tonic::transport::{Identity, ServerTlsConfig};
+
+pub(crate) fn load_server_tls_config(
+    cert_path: Option<&Path>,
+    key_path: Option<&Path>,
+) -> Result<Option<ServerTlsConfig>> {
+    let (cert_path, key_path) = match (cert_path, key_path) {
+        (None, None) => return Ok(None),
+        (Some(cert_path), Some(key_path)) => (cert_path, key_path),
+        _ => bail!("--tls-cert and --tls-key must be provided together"),
+    };
+    let cert = std::fs::read(cert_path)
+        .with_context(|| format!("failed to read TLS certificate `{}`", cert_path.display()))?;
+    let key = std::fs::read(key_path)
+        .with_context(|| format!("failed to read TLS private key `{}`", key_path.display()))?;
+    Ok(Some(
+        ServerTlsConfig::new().identity(Identity::from_pem(cert, key)),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::{Path, PathBuf};
+
+    use tonic::transport::Server;
+
+    use super::load_server_tls_config;
+
+    #[test]
+    fn tls_config_requires_both_files_and_reports_unreadable_paths() {
+        assert!(
+            load_server_tls_config(None, None)
+                .expect("plaintext")
+                .is_none()
+        );
+
+        let error =
+            load_server_tls_config(Some(Path::new("cert.pem")), None).expect_err("key is missing");
+        assert!(error.to_string().contains("--tls-key"));
+
+        let missing = std::env::temp_dir().join("fathom-missing-tls-cert.pem");
+        let error =
+            load_server_tls_config(Some(&missing), Some(&missing)).expect_err("cert is unreadable");
+        assert!(error.to_string().contains(&missing.display().to_string()));
+    }
+
+    #[test]
+    fn server_accepts_tls_files_from_the_environment() {
+        let (Some(cert_path), Some(key_path)) = (
+            std::env::var_os("FATHOM_TEST_TLS_CERT").map(PathBuf::from),
+            std::env::var_os("FATHOM_TEST_TLS_KEY").map(PathBuf::from),
+        ) else {
+            return;
+        };
+        if !cert_path.is_file() || !key_path.is_file() {
+            return;
+        }
+
+        let tls_config = load_server_tls_config(Some(&cert_path), Some(&key_path))
+            .expect("load tls files")
+            .expect("tls config");
+        Server::builder()
+            .tls_config(tls_config)
+            .expect("server accepts the certificate and key");
+    }
+}
@@ -4,6 +4,8 @@ use std::time::Duration;
 
 use anyhow::Result;
 use clap::{Parser, Subcommand};
+use fathom_client::{ClientConfig, ProfileKind};
+use fathom_server::ServerOptions;
 use tracing_subscriber::EnvFilter;
 
 #[derive(Debug, Parser)]
@@ -22,6 +24,115 @@ struct Cli {
     #[arg(long, global = true)]
     workspace_root: Option<PathBuf>,
 
+    #[arg(long, global = true, requires = "tls_key")]
+    tls_cert: Option<PathBuf>,
+
+    #[arg(long, global = true, requires = "tls_cert")]
+    tls_key: Option<PathBuf>,
+
+    #[arg(long, global = true)]
+    tls_ca: Option<PathBuf>,
+
+    #[arg(long, global = true)]
+    system_preamble_file: Option<PathBuf>,
+
+    #[arg(long, global = true)]
+    history_dir: Option<PathBuf>,
+
+    #[arg(long, global = true)]
+    profile_dir: Option<PathBuf>,
+
+    #[arg(long = "blocked-write-extension", global = true, value_delimiter = ',')]
+    blocked_write_extensions: Vec<String>,
+
+    #[arg(long = "protected-path", global = true, value_delimiter = ',')]
+    protected_paths: Vec<PathBuf>,
+
+    #[arg(long, global = true)]
+    list_max_entries: Option<usize>,
+
+    #[arg(long, global = true)]
+    session_command_capacity: Option<usize>,
+
+    #[arg(long, global = true)]
+    execution_capacity: Option<usize>,
+
+    #[arg(long, global = true)]
+    action_timeout_ms: Option<u64>,
+
+    #[arg(long, global = true)]
+    turn_timeout_secs: Option<u64>,
+
+    #[arg(long, global = true)]
+    enable_admin_rpc: bool,
+
+    #[arg(long, global = true)]
+    queued_action_message_template: Option<String>,
+
+    #[arg(long, global = true)]
+    client_rpc_timeout_ms: Option<u64>,
+
+    #[arg(long, global = true, default_value_t = 50)]
+    client_connect_backoff_base_ms: u64,
+
+    #[arg(long, global = true, default_value_t = 1000)]
+    client_connect_backoff_cap_ms: u64,
+
+    #[arg(
+        long = "required-agent-profile-field",
+        global = true,
+        value_delimiter = ','
+    )]
+    required_agent_profile_fields: Vec<String>,
+
+    #[arg(long, global = true)]
+    max_prompt_participants: Option<usize>,
+
+    #[arg(long, global = true)]
+    max_participant_profile_chars: Option<usize>,
+
+    #[arg(long, global = true)]
+    prompt_result_preview_bytes: Option<usize>,
+
+    #[arg(long, global = true)]
+    prompt_result_preview_lines: Option<usize>,
+
+    #[arg(long, global = true)]
+    history_compaction_live_events: Option<usize>,
+
+    #[arg(long, global = true)]
+    history_compaction_batch_events: Option<usize>,
+
+    #[arg(long, global = true)]
+    max_execution_result_bytes: Option<usize>,
+
+    #[arg(long, global = true)]
+    max_concurrent_model_requests: Option<usize>,
+
+    #[arg(long, global = true, default_value_t = 0)]
+    execution_update_grace_ms: u64,
+
+    #[arg(long, global = true)]
+    allow_agent_only_sessions: bool,
+
+    #[arg(long, global = true)]
+    disable_thinking_events: bool,
+
+    #[arg(long, global = true)]
+    skip_idle_heartbeat_turns: bool,
+
+    #[arg(long, global = true)]
+    max_assistant_outputs_per_turn: Option<usize>,
+
+    #[arg(long, global = true)]
+    max_heartbeat_delay_ms: Option<u64>,
+
+    #[arg(long, global = true)]
+    queue_depth_warning_threshold: Option<u64>,
+
+    #[arg(long = "allow-action", global = true, value_delimiter = ',')]
+    action_allowlist: Vec<String>,
+
     #[command(subcommand)]
     command: Option<Command>,
 }
@@ -31,6 +142,20 @@ enum Command {
     Server,
     Client,
     Both,
+    Selftest {
+        #[arg(long)]
+        spawn_server: bool,
+    },
+    Profiles {
+        #[command(subcommand)]
+        kind: ProfilesCommand,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+enum ProfilesCommand {
+    Agents,
+    Users,
 }
 
 #[tokio::main]
@@ -42,33 +167,105 @@ async fn main() -> Result<()> {
         .init();
 
     let cli = Cli::parse();
+    let mut client_config = ClientConfig::new(cli.server.clone()).with_server_wait_backoff(
+        Duration::from_millis(cli.client_connect_backoff_base_ms),
+        Duration::from_millis(cli.client_connect_backoff_cap_ms),
+    );
+    if let Some(timeout_ms) = cli.client_rpc_timeout_ms {
+        client_config = client_config.with_rpc_timeout(Duration::from_millis(timeout_ms));
+    }
+    if let Some(tls_ca) = cli.tls_ca.as_deref() {
+        client_config = client_config.with_tls_ca(tls_ca)?;
+    }
+    let server_options = ServerOptions {
+        workspace_root: cli.workspace_root,
+        tls_cert: cli.tls_cert,
+        tls_key: cli.tls_key,
+        system_preamble_path: cli.system_preamble_file,
+        history_dir: cli.history_dir,
+        profile_dir: cli.profile_dir,
+        blocked_write_extensions: cli.blocked_write_extensions,
+        protected_paths: cli.protected_paths,
+        list_max_entries: cli.list_max_entries,
+        session_command_capacity: cli.session_command_capacity,
+        execution_capacity: cli.execution_capacity,
+        action_timeout_ms: cli.action_timeout_ms,
+        turn_timeout_secs: cli.turn_timeout_secs,
+        admin_rpc_enabled: cli.enable_admin_rpc,
+        queued_action_message_template: cli.queued_action_message_template,
+        required_agent_profile_fields: cli.required_agent_profile_fields,
+        max_prompt_participants: cli.max_prompt_participants,
+        max_participant_profile_chars: cli.max_participant_profile_chars,
+        prompt_result_preview_bytes: cli.prompt_result_preview_bytes,
+        prompt_result_preview_lines: cli.prompt_result_preview_lines,
+        history_compaction_live_events: cli.history_compaction_live_events,
+        history_compaction_batch_events: cli.history_compaction_batch_events,
+        max_execution_result_bytes: cli.max_execution_result_bytes,
+        max_concurrent_model_requests: cli.max_concurrent_model_requests,
+        execution_update_grace_ms: cli.execution_update_grace_ms,
+        allow_agent_only_sessions: cli.allow_agent_only_sessions,
+        disable_thinking_events: cli.disable_thinking_events,
+        skip_idle_heartbeat_turns: cli.skip_idle_heartbeat_turns,
+        max_assistant_outputs_per_turn: cli.max_assistant_outputs_per_turn,
+        max_heartbeat_delay_ms: cli.max_heartbeat_delay_ms,
+        queue_depth_warning_threshold: cli.queue_depth_warning_threshold,
+        action_allowlist: cli.action_allowlist,
+    };
 
     match cli.command {
-        Some(Command::Server) => {
-            fathom_server::serve_with_workspace_root(cli.addr, cli.workspace_root).await
-        }
-        Some(Command::Client) => fathom_client::run_tui(&cli.server).await,
+        Some(Command::Server) => fathom_server::serve_with_options(cli.addr, server_options).await,
+        Some(Command::Client) => fathom_client::run_tui(&client_config).await,
         Some(Command::Both) | None => {
             run_server_and_client(
                 cli.addr,
-                &cli.server,
+                &client_config,
                 cli.startup_delay_ms,
-                cli.workspace_root,
+                server_options,
             )
             .await
         }
+        Some(Command::Selftest { spawn_server }) => {
+            let server_task = spawn_server.then(|| {
+                let server_options = ServerOptions {
+                    admin_rpc_enabled: true,
+                    ..server_options
+                };
+                tokio::spawn(fathom_server::serve_with_options(cli.addr, server_options))
+            });
+            let report = fathom_client::run_selftest(&client_config).await;
+            if let Some(server_task) = server_task {
+                server_task.abort();
+                let _ = server_task.await;
+            }
+            println!("{report}");
+            if report.passed() {
+                Ok(())
+            } else {
+                Err(anyhow::anyhow!("selftest failed"))
+            }
+        }
+        Some(Command::Profiles { kind }) => {
+            let kind = match kind {
+                ProfilesCommand::Agents => ProfileKind::Agents,
+                ProfilesCommand::Users => ProfileKind::Users,
+            };
+            println!(
+                "{}",
+                fathom_client::list_profiles(&client_config, kind).await?
+            );
+            Ok(())
+        }
     }
 }
 
 async fn run_server_and_client(
     addr: SocketAddr,
-    server: &str,
+    client_config: &ClientConfig,
     startup_delay_ms: u64,
-    workspace_root: Option<PathBuf>,
+    server_options: ServerOptions,
 ) -> Result<()> {
-    let server_task = tokio::spawn(async move {
-        fathom_server::serve_with_workspace_root(addr, workspace_root).await
-    });
+    let server_task =
+        tokio::spawn(async move { fathom_server::serve_with_options(addr, server_options).await });
     tokio::pin!(server_task);
 
     tokio::select! {
@@ -82,7 +279,7 @@ async fn run_server_and_client(
     }
 
     let readiness = tokio::select! {
-        result = fathom_client::wait_for_server(server, Duration::from_secs(15)) => result,
+        result = fathom_client::wait_for_server(client_config, Duration::from_secs(15)) => result,
         server_result = &mut server_task => {
             return match server_result {
                 Ok(result) => result,
@@ -97,7 +294,7 @@ async fn run_server_and_client(
         return Err(error);
     }
 
-    let client_result = fathom_client::run_tui(server).await;
+    let client_result = fathom_client::run_tui(client_config).await;
     server_task.as_mut().abort();
     let _ = server_task.await;
     client_result